@@ -1,4 +1,12 @@
-use crate::{interfaces::AnchorViewer, validator_set::ValidatorSetViewer, *};
+use crate::{
+    appchain_messages::PAYLOAD_TYPE_VARIANTS, compression::PAYLOAD_COMPRESSION_VARIANTS,
+    interfaces::AnchorViewer, validator_set::ValidatorSetViewer, *,
+};
+
+/// The maximum number of appchain notification histories scanned in a query of anchor events.
+const MAXIMUM_SCANNED_HISTORIES_OF_ANCHOR_EVENTS: u64 = 100;
+/// The maximum quantity of activities of an account returned in a single query.
+const MAXIMUM_QUANTITY_OF_ACCOUNT_ACTIVITIES: u64 = 50;
 
 #[near_bindgen]
 impl AnchorViewer for AppchainAnchor {
@@ -7,6 +15,47 @@ impl AnchorViewer for AppchainAnchor {
         ANCHOR_VERSION.to_string()
     }
     //
+    fn get_view_api_version(&self) -> u32 {
+        ANCHOR_VIEW_API_VERSION
+    }
+    //
+    fn contract_metadata(&self) -> AnchorContractMetadata {
+        let to_strings = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<String>>()
+        };
+        AnchorContractMetadata {
+            version: ANCHOR_VERSION.to_string(),
+            view_api_version: ANCHOR_VIEW_API_VERSION,
+            ft_deposit_messages: to_strings(&FT_DEPOSIT_MESSAGE_VARIANTS),
+            nft_transfer_messages: to_strings(&NFT_TRANSFER_MESSAGE_VARIANTS),
+            payload_types: to_strings(&PAYLOAD_TYPE_VARIANTS),
+            payload_compressions: to_strings(&PAYLOAD_COMPRESSION_VARIANTS),
+            feature_flags: AnchorFeatureFlags {
+                beefy_light_client_witness_mode: self
+                    .anchor_settings
+                    .get()
+                    .unwrap()
+                    .beefy_light_client_witness_mode,
+                secondary_stake: self
+                    .protocol_settings
+                    .get()
+                    .unwrap()
+                    .secondary_stake_token
+                    .is_some(),
+                nft_bridge: !self
+                    .wrapped_appchain_nfts
+                    .get()
+                    .unwrap()
+                    .to_vec()
+                    .is_empty(),
+                maintenance_mode: self.maintenance_mode_is_on,
+            },
+        }
+    }
+    //
     fn get_appchain_template_type(&self) -> AppchainTemplateType {
         self.appchain_template_type.clone()
     }
@@ -27,6 +76,12 @@ impl AnchorViewer for AppchainAnchor {
         self.protocol_settings.get().unwrap()
     }
     //
+    fn get_protocol_settings_of_era(&self, era_number: U64) -> Option<ProtocolSettings> {
+        self.protocol_settings_of_eras
+            .get(&era_number.0)
+            .map(|protocol_settings| protocol_settings.into_current())
+    }
+    //
     fn get_oct_token(&self) -> OctToken {
         self.oct_token.get().unwrap()
     }
@@ -35,10 +90,41 @@ impl AnchorViewer for AppchainAnchor {
         self.wrapped_appchain_token.get().unwrap()
     }
     //
+    fn get_market_value_of_staked_oct_token(&self) -> U128 {
+        U128::from(
+            self.next_validator_set.get().unwrap().total_stake() / OCT_DECIMALS_VALUE
+                * self.oct_token.get().unwrap().price_in_usd.0,
+        )
+    }
+    //
     fn get_near_fungible_tokens(&self) -> Vec<NearFungibleToken> {
         self.near_fungible_tokens.get().unwrap().to_vec()
     }
     //
+    fn get_metadata_mirror_of_near_fungible_token(
+        &self,
+        symbol: String,
+    ) -> Option<FungibleTokenMetadataMirror> {
+        self.near_fungible_tokens
+            .get()
+            .unwrap()
+            .get(&symbol)
+            .and_then(|near_fungible_token| {
+                self.near_fungible_token_metadata_mirrors
+                    .get(&near_fungible_token.contract_account)
+            })
+    }
+    //
+    fn get_latest_wrapped_asset_balance_attestation(
+        &self,
+    ) -> Option<WrappedAssetBalanceAttestation> {
+        self.latest_wrapped_asset_balance_attestation.get()
+    }
+    //
+    fn get_wrapped_appchain_token_issuance(&self) -> WrappedAppchainTokenIssuance {
+        self.wrapped_appchain_token_issuance.get().unwrap()
+    }
+    //
     fn get_wrapped_appchain_nfts(&self) -> Vec<WrappedAppchainNFT> {
         self.wrapped_appchain_nfts.get().unwrap().to_vec()
     }
@@ -47,49 +133,115 @@ impl AnchorViewer for AppchainAnchor {
         self.appchain_state.clone()
     }
     //
+    fn get_appchain_termination(&self) -> Option<AppchainTermination> {
+        self.appchain_termination.get()
+    }
+    //
     fn get_anchor_status(&self) -> AnchorStatus {
         let next_validator_set = self.next_validator_set.get().unwrap();
-        let appchain_messages = self.appchain_messages.get().unwrap();
         AnchorStatus {
             total_stake_in_next_era: next_validator_set.total_stake().into(),
             validator_count_in_next_era: next_validator_set.validator_count().into(),
             delegator_count_in_next_era: next_validator_set.delegator_count().into(),
             index_range_of_appchain_notification_history: self
-                .appchain_notification_histories
-                .get()
-                .unwrap()
-                .index_range(),
+                .get_index_range_of(HistoryKind::AppchainNotificationHistory),
             index_range_of_validator_set_history: self
-                .validator_set_histories
-                .get()
-                .unwrap()
-                .index_range(),
-            index_range_of_staking_history: self.staking_histories.get().unwrap().index_range(),
-            nonce_range_of_appchain_messages: IndexRange {
-                start_index: U64::from(u64::from(appchain_messages.min_nonce())),
-                end_index: U64::from(u64::from(appchain_messages.max_nonce())),
-            },
+                .get_index_range_of(HistoryKind::ValidatorSetHistory),
+            index_range_of_staking_history: self.get_index_range_of(HistoryKind::StakingHistory),
+            nonce_range_of_appchain_messages: self
+                .get_index_range_of(HistoryKind::AppchainMessageQueue),
             index_range_of_appchain_challenges: self
-                .appchain_challenges
-                .get()
-                .unwrap()
-                .index_range(),
+                .get_index_range_of(HistoryKind::AppchainChallenge),
             permissionless_actions_status: self.permissionless_actions_status.get().unwrap(),
             asset_transfer_is_paused: self.asset_transfer_is_paused,
             rewards_withdrawal_is_paused: self.rewards_withdrawal_is_paused,
+            maintenance_mode_is_on: self.maintenance_mode_is_on,
+            staking_status: self.staking_status.clone(),
         }
     }
     //
+    fn get_state_checksum(&self) -> AnchorStateChecksum {
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        let stake_aggregates_hash = env::keccak256(
+            &(
+                next_validator_set.total_stake(),
+                next_validator_set.validator_count(),
+                next_validator_set.delegator_count(),
+            )
+                .try_to_vec()
+                .unwrap(),
+        );
+        let mut history_end_indices: Vec<u64> = [
+            HistoryKind::StakingHistory,
+            HistoryKind::ValidatorSetHistory,
+            HistoryKind::AppchainNotificationHistory,
+            HistoryKind::AppchainMessageQueue,
+            HistoryKind::AppchainChallenge,
+        ]
+        .into_iter()
+        .map(|history_kind| self.get_index_range_of(history_kind).end_index.0)
+        .collect();
+        history_end_indices.push(u64::from(
+            self.permissionless_actions_status
+                .get()
+                .unwrap()
+                .latest_applied_appchain_message_nonce,
+        ));
+        let history_end_indices_hash = env::keccak256(&history_end_indices.try_to_vec().unwrap());
+        let checksum = env::keccak256(
+            &[
+                stake_aggregates_hash.as_slice(),
+                history_end_indices_hash.as_slice(),
+            ]
+            .concat(),
+        );
+        AnchorStateChecksum {
+            stake_aggregates_hash: format!("0x{}", hex::encode(stake_aggregates_hash)),
+            history_end_indices_hash: format!("0x{}", hex::encode(history_end_indices_hash)),
+            checksum: format!("0x{}", hex::encode(checksum)),
+        }
+    }
+    //
+    fn get_initialization_status(&self) -> AnchorInitializationStatus {
+        self.internal_get_initialization_status()
+    }
+    //
+    fn get_configuration_digest(&self) -> AnchorConfigurationDigest {
+        self.internal_get_configuration_digest()
+    }
+    //
     fn get_validator_set_info_of(&self, era_number: U64) -> Option<ValidatorSetInfo> {
         let validator_set_histories = self.validator_set_histories.get().unwrap();
         if validator_set_histories.contains(&era_number.0) {
             let validator_set = validator_set_histories.get(&era_number.0).unwrap();
-            Some(validator_set.to_validator_set_info())
+            let mut validator_set_info = validator_set.to_validator_set_info();
+            validator_set_info.validator_list = self
+                .apply_bonded_era_guard_to_weights(validator_set_info.validator_list, era_number.0);
+            Some(validator_set_info)
         } else {
             None
         }
     }
     //
+    fn verify_validator_of_era(
+        &self,
+        era_number: U64,
+        account_id: AccountId,
+    ) -> Option<ValidatorMembershipProof> {
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        validator_set_histories
+            .get(&era_number.0)?
+            .validator_membership_proof_of(&account_id)
+    }
+    //
+    fn get_bootstrap_allocation_of(&self, validator_id: AccountId) -> U128 {
+        U128::from(self.bootstrap_allocations.get(&validator_id).unwrap_or(0))
+    }
+    //
+    fn get_total_bootstrap_allocation(&self) -> U128 {
+        U128::from(self.total_bootstrap_allocation)
+    }
+    //
     fn get_processing_status_of(&self, era_number: U64) -> Option<ValidatorSetProcessingStatus> {
         let validator_set_histories = self.validator_set_histories.get().unwrap();
         if validator_set_histories.contains(&era_number.0) {
@@ -100,6 +252,88 @@ impl AnchorViewer for AppchainAnchor {
         }
     }
     //
+    fn get_era_summaries(&self, start_era: U64, quantity: Option<U64>) -> Vec<EraSummary> {
+        let end_index = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .index_range()
+            .end_index
+            .0;
+        let quantity = quantity.map_or(50, |q| q.0.min(50));
+        let mut results = Vec::<EraSummary>::new();
+        let mut era_number = start_era.0;
+        while era_number <= end_index && era_number < start_era.0.saturating_add(quantity) {
+            if let Some(era_summary) = self.era_summaries.get(&era_number) {
+                results.push(era_summary);
+            }
+            era_number += 1;
+        }
+        results
+    }
+    //
+    fn get_era_duration_drift(&self, era_number: U64) -> Option<EraDurationDrift> {
+        self.get_era_duration_drift_of(era_number.0)
+    }
+    //
+    fn preview_reward_distribution(
+        &self,
+        era_number: U64,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<ValidatorRewardPreview> {
+        self.internal_preview_reward_distribution(
+            era_number.0,
+            start_index.0,
+            quantity.map_or(10, |q| q.0.min(10)),
+        )
+    }
+    //
+    fn get_delegator_exposure(&self, delegator_id: AccountId) -> Option<DelegatorExposure> {
+        self.internal_get_delegator_exposure(&delegator_id)
+    }
+    //
+    fn audit_era_distribution(
+        &self,
+        era_number: U64,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Option<EraDistributionAudit> {
+        self.internal_audit_era_distribution(
+            era_number.0,
+            start_index.0,
+            quantity.map_or(10, |q| q.0.min(10)),
+        )
+    }
+    //
+    fn get_index_range_of(&self, history_kind: HistoryKind) -> IndexRange {
+        match history_kind {
+            HistoryKind::StakingHistory => self.staking_histories.get().unwrap().index_range(),
+            HistoryKind::ValidatorSetHistory => {
+                self.validator_set_histories.get().unwrap().index_range()
+            }
+            HistoryKind::AppchainNotificationHistory => self
+                .appchain_notification_histories
+                .get()
+                .unwrap()
+                .index_range(),
+            HistoryKind::AppchainMessageQueue => {
+                let appchain_messages = self.appchain_messages.get().unwrap();
+                IndexRange {
+                    start_index: U64::from(u64::from(appchain_messages.min_nonce())),
+                    end_index: U64::from(u64::from(appchain_messages.max_nonce())),
+                }
+            }
+            HistoryKind::AppchainChallenge => self.appchain_challenges.get().unwrap().index_range(),
+            HistoryKind::AppchainReferendum => self.appchain_referenda.get().unwrap().index_range(),
+            HistoryKind::AppchainRuntimeUpgradeNotice => self
+                .appchain_runtime_upgrade_notices
+                .get()
+                .unwrap()
+                .index_range(),
+        }
+    }
+    //
     fn get_index_range_of_staking_history(&self) -> IndexRange {
         self.staking_histories.get().unwrap().index_range()
     }
@@ -113,6 +347,48 @@ impl AnchorViewer for AppchainAnchor {
         staking_histories.get_slice_of(&start_index.0, quantity.map(|q| q.0))
     }
     //
+    fn get_staking_histories_in_order(
+        &self,
+        direction: SortDirection,
+        start_index: Option<U64>,
+        quantity: Option<U64>,
+    ) -> Vec<StakingHistory> {
+        let staking_histories = self.staking_histories.get().unwrap();
+        let index_range = staking_histories.index_range();
+        let quantity = quantity.map_or(50, |q| q.0.min(50));
+        let mut results = Vec::<StakingHistory>::new();
+        match direction {
+            SortDirection::Ascending => {
+                let start_index = start_index.map_or(index_range.start_index.0, |index| {
+                    index.0.max(index_range.start_index.0)
+                });
+                let mut index = start_index;
+                while index <= index_range.end_index.0 && index < start_index + quantity {
+                    if let Some(staking_history) = staking_histories.get(&index) {
+                        results.push(staking_history);
+                    }
+                    index += 1;
+                }
+            }
+            SortDirection::Descending => {
+                let start_index = start_index.map_or(index_range.end_index.0, |index| {
+                    index.0.min(index_range.end_index.0)
+                });
+                let mut index = start_index;
+                while index >= index_range.start_index.0 && index + quantity > start_index {
+                    if let Some(staking_history) = staking_histories.get(&index) {
+                        results.push(staking_history);
+                    }
+                    if index == 0 {
+                        break;
+                    }
+                    index -= 1;
+                }
+            }
+        }
+        results
+    }
+    //
     fn get_staking_history(&self, index: Option<U64>) -> Option<StakingHistory> {
         let index = match index {
             Some(index) => index,
@@ -127,6 +403,10 @@ impl AnchorViewer for AppchainAnchor {
         self.staking_histories.get().unwrap().get(&index.0)
     }
     //
+    fn get_compacted_staking_histories(&self, index: U64) -> Option<CompactedStakingHistories> {
+        self.compacted_staking_histories.get(&index.0)
+    }
+    //
     fn get_index_range_of_appchain_notification_history(&self) -> IndexRange {
         self.appchain_notification_histories
             .get()
@@ -134,6 +414,27 @@ impl AnchorViewer for AppchainAnchor {
             .index_range()
     }
     //
+    fn get_appchain_notification_acknowledgment(&self) -> AppchainNotificationAcknowledgment {
+        let next_index = self
+            .appchain_notification_histories
+            .get()
+            .unwrap()
+            .next_index();
+        let acknowledged_count = self
+            .latest_acknowledged_appchain_notification_index
+            .map_or(0, |acknowledged_index| acknowledged_index + 1);
+        AppchainNotificationAcknowledgment {
+            latest_acknowledged_index: self
+                .latest_acknowledged_appchain_notification_index
+                .map(U64::from),
+            latest_notification_index: match next_index {
+                0 => None,
+                _ => Some(U64::from(next_index - 1)),
+            },
+            lag: U64::from(next_index - acknowledged_count),
+        }
+    }
+    //
     fn get_appchain_notification_history(
         &self,
         index: Option<U64>,
@@ -163,6 +464,38 @@ impl AnchorViewer for AppchainAnchor {
         appchain_notification_histories.get_slice_of(&start_index.0, quantity.map(|q| q.0))
     }
     //
+    fn get_anchor_events_since(
+        &self,
+        index: U64,
+        filters: Option<Vec<AnchorEventClass>>,
+    ) -> AnchorEventsPage {
+        let appchain_notification_histories = self.appchain_notification_histories.get().unwrap();
+        let start_index = std::cmp::max(
+            index.0,
+            appchain_notification_histories.index_range().start_index.0,
+        );
+        let end_index = std::cmp::min(
+            start_index + MAXIMUM_SCANNED_HISTORIES_OF_ANCHOR_EVENTS,
+            appchain_notification_histories.next_index(),
+        );
+        let mut events = Vec::<AppchainNotificationHistory>::new();
+        for index in start_index..end_index {
+            if let Some(history) = appchain_notification_histories.get(&index) {
+                let is_matched = match &filters {
+                    Some(filters) => filters.contains(&history.appchain_notification.event_class()),
+                    None => true,
+                };
+                if is_matched {
+                    events.push(history);
+                }
+            }
+        }
+        AnchorEventsPage {
+            events,
+            next_index: U64::from(std::cmp::max(start_index, end_index)),
+        }
+    }
+    //
     fn get_validator_list_of(&self, era_number: Option<U64>) -> Vec<AppchainValidator> {
         if let Some(era_number) = era_number {
             if let Some(validator_set_of_era) = self
@@ -171,15 +504,180 @@ impl AnchorViewer for AppchainAnchor {
                 .unwrap()
                 .get(&era_number.0)
             {
-                validator_set_of_era.get_validator_list()
+                self.apply_standby_to_validator_list(
+                    self.apply_bonded_era_guard_to_weights(
+                        validator_set_of_era.get_validator_list(),
+                        era_number.0,
+                    ),
+                    &self
+                        .standby_validator_ids_of_eras
+                        .get(&era_number.0)
+                        .unwrap_or_default(),
+                )
             } else {
                 Vec::new()
             }
         } else {
-            self.next_validator_set.get().unwrap().get_validator_list()
+            let next_validator_set = self.next_validator_set.get().unwrap();
+            self.apply_standby_to_validator_list(
+                self.apply_bonded_era_guard_to_weights(
+                    next_validator_set.get_validator_list(),
+                    next_validator_set.era_number(),
+                ),
+                &self.select_standby_validators(
+                    &next_validator_set,
+                    self.protocol_settings
+                        .get()
+                        .unwrap()
+                        .maximum_validator_count_per_operator
+                        .0,
+                ),
+            )
+        }
+    }
+    //
+    fn get_duty_schedule_of(&self, era_number: U64) -> Option<DutyScheduleOfEra> {
+        let duty_weighting = self.appchain_template_type.template().duty_weighting()?;
+        let validator_list = self
+            .get_validator_list_of(Some(era_number))
+            .into_iter()
+            .filter(|validator| !validator.is_standby)
+            .collect::<Vec<AppchainValidator>>();
+        let weight_of = |validator: &AppchainValidator| match duty_weighting {
+            DutyWeighting::ByWeight => validator.weight.0,
+            DutyWeighting::Equal => 1,
+        };
+        let total_weight: u128 = validator_list.iter().map(weight_of).sum();
+        if total_weight == 0 {
+            return None;
+        }
+        let expected_era_duration_in_secs = self
+            .appchain_settings
+            .get()
+            .unwrap()
+            .expected_era_duration_in_secs
+            .0;
+        let expected_block_time_in_secs = self
+            .appchain_template_type
+            .template()
+            .expected_block_time_in_secs();
+        let expected_block_count_of_era =
+            u128::from(expected_era_duration_in_secs / expected_block_time_in_secs);
+        let validator_duties = validator_list
+            .iter()
+            .map(|validator| ValidatorDutyHint {
+                validator_id: validator.validator_id.clone(),
+                validator_id_in_appchain: validator.validator_id_in_appchain.clone(),
+                block_production_share_bps: U64::from(
+                    (weight_of(validator) * 10_000 / total_weight) as u64,
+                ),
+                expected_block_count: match expected_era_duration_in_secs > 0 {
+                    true => Some(U64::from(
+                        (expected_block_count_of_era * weight_of(validator) / total_weight) as u64,
+                    )),
+                    false => None,
+                },
+            })
+            .collect();
+        Some(DutyScheduleOfEra {
+            era_number,
+            duty_weighting,
+            validator_duties,
+        })
+    }
+    //
+    fn get_sorted_validators(
+        &self,
+        sort_key: ListSortKey,
+        direction: SortDirection,
+        cursor: Option<ListCursor>,
+        quantity: Option<U64>,
+    ) -> ListPage<AppchainValidator> {
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        let (validator_ids, next_cursor) = self
+            .staking_sorted_indices
+            .get()
+            .unwrap()
+            .get_validator_ids(&sort_key, &direction, cursor, quantity.map(|q| q.0));
+        let validators = validator_ids
+            .iter()
+            .filter_map(|validator_id| {
+                next_validator_set
+                    .get_validator(validator_id)
+                    .map(|validator| {
+                        AppchainValidator::from_validator(
+                            validator,
+                            next_validator_set.get_delegator_count_of(validator_id),
+                            false,
+                        )
+                    })
+            })
+            .collect();
+        ListPage {
+            items: self
+                .apply_bonded_era_guard_to_weights(validators, next_validator_set.era_number()),
+            next_cursor,
+        }
+    }
+    //
+    fn get_sorted_delegators_of(
+        &self,
+        validator_id: AccountId,
+        sort_key: ListSortKey,
+        direction: SortDirection,
+        cursor: Option<ListCursor>,
+        quantity: Option<U64>,
+    ) -> ListPage<AppchainDelegator> {
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        if !next_validator_set.contains_validator(&validator_id) {
+            return ListPage {
+                items: Vec::new(),
+                next_cursor: None,
+            };
+        }
+        let (delegator_ids, next_cursor) = self
+            .staking_sorted_indices
+            .get()
+            .unwrap()
+            .get_delegator_ids(
+                &validator_id,
+                &sort_key,
+                &direction,
+                cursor,
+                quantity.map(|q| q.0),
+            );
+        let delegators = delegator_ids
+            .iter()
+            .filter_map(|delegator_id| {
+                next_validator_set
+                    .get_delegator(delegator_id, &validator_id)
+                    .map(|delegator| AppchainDelegator {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: validator_id.clone(),
+                        delegation_amount: U128::from(delegator.deposit_amount),
+                        label: self
+                            .delegation_labels
+                            .get(&(delegator_id.clone(), validator_id.clone())),
+                    })
+            })
+            .collect();
+        ListPage {
+            items: delegators,
+            next_cursor,
         }
     }
     //
+    fn get_next_validator_list(&self) -> Vec<AppchainValidator> {
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.apply_bonded_era_guard_to_weights(
+            next_validator_set.get_validator_list(),
+            next_validator_set.era_number(),
+        )
+        .into_iter()
+        .filter(|validator| !validator.is_unbonding)
+        .collect()
+    }
+    //
     fn get_delegators_of_validator_in_era(
         &self,
         era_number: Option<U64>,
@@ -202,6 +700,9 @@ impl AnchorViewer for AppchainAnchor {
                                     delegator_id: delegator_id.clone(),
                                     validator_id: validator_id.clone(),
                                     delegation_amount: U128::from(delegator.deposit_amount),
+                                    label: self
+                                        .delegation_labels
+                                        .get(&(delegator_id.clone(), validator_id.clone())),
                                 });
                             });
                     }
@@ -221,6 +722,9 @@ impl AnchorViewer for AppchainAnchor {
                             delegator_id: delegator_id.clone(),
                             validator_id: validator_id.clone(),
                             delegation_amount: U128::from(delegator.deposit_amount),
+                            label: self
+                                .delegation_labels
+                                .get(&(delegator_id.clone(), validator_id.clone())),
                         });
                     });
             }
@@ -229,75 +733,313 @@ impl AnchorViewer for AppchainAnchor {
     }
     //
     fn get_unbonded_stakes_of(&self, account_id: AccountId) -> Vec<UnbondedStake> {
-        let protocol_settings = self.protocol_settings.get().unwrap();
-        let mut results = Vec::<UnbondedStake>::new();
-        if let Some(unbonded_stake_references) = self.unbonded_stakes.get(&account_id) {
-            unbonded_stake_references.iter().for_each(|reference| {
-                let validator_set = self
-                    .validator_set_histories
-                    .get()
-                    .unwrap()
-                    .get(&reference.era_number)
-                    .unwrap();
-                let staking_history = self
-                    .staking_histories
-                    .get()
-                    .unwrap()
-                    .get(&reference.staking_history_index)
-                    .unwrap();
-                match staking_history.staking_fact {
-                    StakingFact::StakeDecreased {
-                        validator_id,
-                        amount,
-                    }
-                    | StakingFact::ValidatorUnbonded {
-                        validator_id,
-                        amount,
-                    }
-                    | StakingFact::ValidatorAutoUnbonded {
-                        validator_id,
-                        amount,
-                    } => results.push(UnbondedStake {
-                        era_number: U64::from(reference.era_number),
-                        account_id: validator_id,
-                        amount,
-                        unlock_time: U64::from(
-                            validator_set.start_timestamp()
-                                + protocol_settings.unlock_period_of_validator_deposit.0
-                                    * SECONDS_OF_A_DAY
-                                    * NANO_SECONDS_MULTIPLE,
-                        ),
-                    }),
-                    StakingFact::DelegationDecreased {
-                        delegator_id,
-                        validator_id: _,
-                        amount,
-                    }
-                    | StakingFact::DelegatorUnbonded {
-                        delegator_id,
-                        validator_id: _,
-                        amount,
-                    }
-                    | StakingFact::DelegatorAutoUnbonded {
-                        delegator_id,
-                        validator_id: _,
-                        amount,
-                    } => results.push(UnbondedStake {
-                        era_number: U64::from(reference.era_number),
-                        account_id: delegator_id,
-                        amount,
-                        unlock_time: U64::from(
-                            validator_set.start_timestamp()
-                                + protocol_settings.unlock_period_of_delegator_deposit.0
-                                    * SECONDS_OF_A_DAY
-                                    * NANO_SECONDS_MULTIPLE,
-                        ),
-                    }),
-                    _ => (),
-                };
-            });
+        self.evaluate_unbonded_stakes_of(&account_id)
+            .into_unbonded_stakes()
+    }
+    //
+    fn get_unbonded_secondary_stakes_of(&self, account_id: AccountId) -> Vec<UnbondedStake> {
+        self.evaluate_unbonded_secondary_stakes_of(&account_id)
+            .into_unbonded_stakes()
+    }
+    //
+    fn get_commission_payout_account_of(&self, validator_id: AccountId) -> Option<AccountId> {
+        self.commission_payout_accounts.get(&validator_id)
+    }
+    //
+    fn get_retiring_era_number_of(&self, validator_id: AccountId) -> Option<U64> {
+        self.validator_retirements.get(&validator_id)
+    }
+    //
+    fn get_fallback_validator_of(&self, delegator_id: AccountId) -> Option<AccountId> {
+        self.delegator_fallback_validators.get(&delegator_id)
+    }
+    //
+    fn get_validator_set_commitment(
+        &self,
+        era_number: Option<U64>,
+    ) -> Option<ValidatorSetCommitment> {
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let era_number = era_number.unwrap_or(validator_set_histories.index_range().end_index);
+        validator_set_histories
+            .get(&era_number.0)
+            .map(|validator_set| self.internal_get_validator_set_commitment(&validator_set))
+    }
+    //
+    fn get_peer_anchors(&self) -> Vec<PeerAnchor> {
+        self.peer_anchors.values().collect()
+    }
+    //
+    fn get_peer_anchor(&self, anchor_account: AccountId) -> Option<PeerAnchor> {
+        self.peer_anchors.get(&anchor_account)
+    }
+    //
+    fn get_insurance_pool_balance(&self) -> U128 {
+        U128::from(self.insurance_pool_balance)
+    }
+    //
+    fn get_withdrawal_allowlist_of(&self, account_id: AccountId) -> Option<WithdrawalAllowlist> {
+        self.withdrawal_allowlists
+            .get(&account_id)
+            .map(|mut allowlist| {
+                allowlist.settle_pending_change();
+                allowlist
+            })
+    }
+    //
+    fn get_held_era_reward_distribution(
+        &self,
+        era_number: U64,
+    ) -> Option<HeldEraRewardDistribution> {
+        self.held_era_reward_distributions.get(&era_number.0)
+    }
+    //
+    fn get_validator_set_override_proposals(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<ValidatorSetOverrideProposal> {
+        self.validator_set_override_proposals
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_appchain_setting_change_proposals(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<AppchainSettingChangeProposal> {
+        self.appchain_setting_change_proposals
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_pending_appchain_setting_change_proposals(&self) -> Vec<AppchainSettingChangeProposal> {
+        let proposals = self.appchain_setting_change_proposals.get().unwrap();
+        let index_range = proposals.index_range();
+        (index_range.start_index.0..index_range.end_index.0 + 1)
+            .filter_map(|index| proposals.get(&index))
+            .filter(|proposal| proposal.status == AppchainSettingChangeProposalStatus::Pending)
+            .collect()
+    }
+    //
+    fn get_penalty_escrows(&self, start_index: U64, quantity: Option<U64>) -> Vec<PenaltyEscrow> {
+        self.penalty_escrows
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_penalty_escrow_balance(&self) -> U128 {
+        U128::from(self.penalty_escrow_balance)
+    }
+    //
+    fn get_stake_migration_exports(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<StakeMigrationExport> {
+        self.stake_migration_exports
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_stake_migration_allocation(&self) -> U128 {
+        U128::from(self.stake_migration_allocation)
+    }
+    //
+    fn get_queued_validator_registrations(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<QueuedValidatorRegistration> {
+        self.queued_validator_registrations
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_staking_status(&self) -> StakingStatus {
+        self.staking_status.clone()
+    }
+    //
+    fn get_staking_status_changes(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<StakingStatusChange> {
+        self.staking_status_changes
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_oct_obligations(&self) -> OctObligations {
+        self.calculate_oct_obligations()
+    }
+    //
+    fn get_oct_balance_reconciliations(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<OctBalanceReconciliation> {
+        self.oct_balance_reconciliations
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_latest_oct_balance_reconciliation(&self) -> Option<OctBalanceReconciliation> {
+        self.last_oct_balance_reconciliation()
+    }
+    //
+    fn get_relayer_bond_of(&self, relayer_id: AccountId) -> Option<RelayerBond> {
+        self.relayer_bonds.get(&relayer_id)
+    }
+    //
+    fn get_relayer_bonds(&self) -> Vec<RelayerBond> {
+        self.relayer_bonds.values().collect()
+    }
+    //
+    fn get_whitelisted_relayers(&self) -> Vec<AccountId> {
+        self.relayer_bonds
+            .keys()
+            .filter(|relayer_id| self.is_whitelisted_relayer(relayer_id))
+            .collect()
+    }
+    //
+    fn get_relayer_bond_slashes(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<RelayerBondSlash> {
+        self.relayer_bond_slashes
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_reward_claim_tip_percent_of(&self, delegator_id: AccountId) -> Option<u16> {
+        self.reward_claim_tip_percents.get(&delegator_id)
+    }
+    //
+    fn get_delegation_lock_of(
+        &self,
+        delegator_id: AccountId,
+        validator_id: AccountId,
+    ) -> Option<DelegationLock> {
+        self.delegation_locks.get(&(delegator_id, validator_id))
+    }
+    //
+    fn get_reward_rounding_of_era(&self, era_number: U64) -> Option<RewardRoundingOfEra> {
+        self.reward_roundings_of_eras.get(&era_number.0)
+    }
+    //
+    fn get_validator_endpoints_of(&self, validator_id: AccountId) -> Option<ValidatorEndpoints> {
+        self.validator_endpoints.get(&validator_id)
+    }
+    //
+    fn get_appchain_peers(&self) -> AppchainPeers {
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        AppchainPeers {
+            boot_nodes: self.appchain_settings.get().unwrap().boot_nodes,
+            validator_endpoints: next_validator_set
+                .get_validator_ids()
+                .into_iter()
+                .filter_map(|validator_id| {
+                    self.validator_endpoints
+                        .get(&validator_id)
+                        .filter(|endpoints| {
+                            !endpoints.is_hidden && !endpoints.p2p_multiaddrs.is_empty()
+                        })
+                        .map(|endpoints| (validator_id, endpoints))
+                })
+                .collect(),
         }
-        results
+    }
+    //
+    fn get_stake_change_intents(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<StakeChangeIntent> {
+        let intents = self.stake_change_intents.values_as_vector();
+        let end_index = std::cmp::min(
+            start_index.0 + quantity.map_or(50, |q| q.0.min(50)),
+            intents.len(),
+        );
+        (start_index.0..end_index)
+            .filter_map(|index| intents.get(index))
+            .collect()
+    }
+    //
+    fn get_stake_change_intents_of(&self, delegator_id: AccountId) -> Vec<StakeChangeIntent> {
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        next_validator_set
+            .get_validator_ids_of(&delegator_id)
+            .iter()
+            .filter_map(|validator_id| {
+                self.stake_change_intents
+                    .get(&(delegator_id.clone(), validator_id.clone()))
+            })
+            .collect()
+    }
+    //
+    fn get_accepted_payload_types_of_state(
+        &self,
+        appchain_state: AppchainState,
+    ) -> Option<Vec<PayloadType>> {
+        self.accepted_payload_types_of_states.get(&appchain_state)
+    }
+    //
+    fn get_appchain_message_handler_config(
+        &self,
+        payload_type: PayloadType,
+    ) -> AppchainMessageHandlerConfig {
+        self.get_appchain_message_handler_config_of(&payload_type)
+    }
+    //
+    fn get_price_update_violations(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<PriceUpdateViolation> {
+        let price_update_violations = self.price_update_violations.get().unwrap();
+        price_update_violations.get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_insurance_claim_event(&self, index: U64) -> Option<InsuranceClaimEvent> {
+        self.insurance_claim_events.get().unwrap().get(&index.0)
+    }
+    //
+    fn get_insurance_compensation_of(&self, event_index: U64, delegator_id: AccountId) -> U128 {
+        U128::from(
+            self.insurance_compensations
+                .get(&(event_index.0, delegator_id))
+                .unwrap_or(0),
+        )
+    }
+    //
+    fn get_relayer_reimbursement_pool_balance(&self) -> U128 {
+        U128::from(self.relayer_reimbursement_pool_balance)
+    }
+    //
+    fn get_relayer_gas_usage_of(&self, era_number: U64, relayer_id: AccountId) -> U64 {
+        U64::from(
+            self.relayer_gas_usages
+                .get(&(era_number.0, relayer_id))
+                .unwrap_or(0),
+        )
+    }
+    //
+    fn get_relayer_reimbursement_of(&self, relayer_id: AccountId) -> U128 {
+        U128::from(
+            self.get_claimable_eras_of_relayer_reimbursement()
+                .map(|era_number| self.get_relayer_reimbursement_of_era(&relayer_id, era_number))
+                .sum::<u128>(),
+        )
     }
     //
     fn get_validator_rewards_of(
@@ -317,7 +1059,10 @@ impl AnchorViewer for AppchainAnchor {
                     {
                         Some(reward) => reward,
                         None => 0,
-                    };
+                    } + self
+                        .unwithdrawn_commission_rewards
+                        .get(&(era_number, validator_id.clone()))
+                        .unwrap_or(0);
                     reward_histories.push(RewardHistory {
                         era_number: U64::from(era_number),
                         total_reward: U128::from(reward),
@@ -329,6 +1074,19 @@ impl AnchorViewer for AppchainAnchor {
         reward_histories
     }
     //
+    fn get_validator_reward_vestings_of(
+        &self,
+        validator_id: AccountId,
+    ) -> Vec<ValidatorRewardVesting> {
+        self.validator_reward_vestings
+            .get(&validator_id)
+            .unwrap_or_default()
+    }
+    //
+    fn get_releasable_vested_reward_of(&self, validator_id: AccountId) -> U128 {
+        U128::from(self.calculate_releasable_vested_reward_of(&validator_id))
+    }
+    //
     fn get_delegator_rewards_of(
         &self,
         start_era: U64,
@@ -362,6 +1120,10 @@ impl AnchorViewer for AppchainAnchor {
         reward_histories
     }
     //
+    fn get_unwithdrawn_rewards_of(&self, account_id: AccountId) -> Vec<UnwithdrawnReward> {
+        self.calculate_unwithdrawn_rewards_of(&account_id)
+    }
+    //
     fn get_storage_balance(&self) -> U128 {
         U128::from(u128::from(env::storage_usage()) * env::storage_byte_cost())
     }
@@ -440,6 +1202,9 @@ impl AnchorViewer for AppchainAnchor {
                                     delegator_id: delegator_id.clone(),
                                     validator_id: validator_id.clone(),
                                     delegation_amount: U128::from(delegator.deposit_amount),
+                                    label: self
+                                        .delegation_labels
+                                        .get(&(delegator_id.clone(), validator_id.clone())),
                                 });
                             }
                         })
@@ -458,6 +1223,9 @@ impl AnchorViewer for AppchainAnchor {
                                 delegator_id: delegator_id.clone(),
                                 validator_id: validator_id.clone(),
                                 delegation_amount: U128::from(delegator.deposit_amount),
+                                label: self
+                                    .delegation_labels
+                                    .get(&(delegator_id.clone(), validator_id.clone())),
                             });
                         }
                     })
@@ -547,6 +1315,67 @@ impl AnchorViewer for AppchainAnchor {
         results
     }
     //
+    fn get_account_activity(
+        &self,
+        account_id: AccountId,
+        start: U64,
+        limit: Option<U64>,
+    ) -> Vec<AccountActivityRecord> {
+        let mut records: Vec<AccountActivityRecord> = self
+            .get_user_staking_histories_of(account_id.clone())
+            .into_iter()
+            .map(|history| AccountActivityRecord {
+                activity: AccountActivity::Staking {
+                    staking_fact: history.staking_fact,
+                    has_taken_effect: history.has_taken_effect,
+                },
+                block_height: history.block_height,
+                timestamp: history.timestamp,
+            })
+            .collect();
+        records.extend(
+            self.reward_withdrawal_histories
+                .get(&account_id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|history| AccountActivityRecord {
+                    activity: AccountActivity::RewardWithdrawn {
+                        validator_id: history.validator_id,
+                        amount: history.amount,
+                        destination: history.destination,
+                    },
+                    block_height: history.block_height,
+                    timestamp: history.timestamp,
+                }),
+        );
+        let appchain_notification_histories = self.appchain_notification_histories.get().unwrap();
+        records.extend(
+            self.user_appchain_notification_indexes
+                .get(&account_id)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|index| appchain_notification_histories.get(index))
+                .map(|history| AccountActivityRecord {
+                    activity: AccountActivity::Bridging {
+                        appchain_notification: history.appchain_notification,
+                        notification_index: history.index,
+                    },
+                    block_height: history.block_height,
+                    timestamp: history.timestamp,
+                }),
+        );
+        // The sorting is stable, so the activities at a same time keep the order of kinds.
+        records.sort_by_key(|record| record.timestamp.0);
+        let quantity = limit.map_or(MAXIMUM_QUANTITY_OF_ACCOUNT_ACTIVITIES, |limit| {
+            limit.0.min(MAXIMUM_QUANTITY_OF_ACCOUNT_ACTIVITIES)
+        });
+        records
+            .into_iter()
+            .skip(start.0 as usize)
+            .take(quantity as usize)
+            .collect()
+    }
+    //
     fn get_appchain_message_of(&self, nonce: u32) -> Option<AppchainMessage> {
         let appchain_messages = self.appchain_messages.get().unwrap();
         appchain_messages.get_message(&nonce)
@@ -578,6 +1407,10 @@ impl AnchorViewer for AppchainAnchor {
         appchain_messages.get_processing_results(&start_nonce, quantity)
     }
     //
+    fn get_messages_applied_in_era(&self, era_number: U64) -> Option<AppliedMessagesOfEra> {
+        self.applied_messages_of_eras.get(&era_number.0)
+    }
+    //
     fn get_appchain_challenge(&self, index: Option<U64>) -> Option<AppchainChallenge> {
         let index = match index {
             Some(index) => index,
@@ -602,4 +1435,151 @@ impl AnchorViewer for AppchainAnchor {
             .unwrap()
             .get_slice_of(&start_index.0, quantity.map(|q| q.0))
     }
+    //
+    fn get_freezing_of_account(&self, account_id: AccountId) -> Option<AccountFreezing> {
+        self.frozen_accounts
+            .get(&account_id)
+            .filter(|account_freezing| env::block_timestamp() < account_freezing.expiry_timestamp.0)
+    }
+    //
+    fn get_index_range_of_appchain_referenda(&self) -> IndexRange {
+        self.appchain_referenda.get().unwrap().index_range()
+    }
+    //
+    fn get_appchain_referendum(&self, index: Option<U64>) -> Option<AppchainReferendumInfo> {
+        let appchain_referenda = self.appchain_referenda.get().unwrap();
+        let index = match index {
+            Some(index) => index,
+            None => appchain_referenda.index_range().end_index,
+        };
+        appchain_referenda
+            .get(&index.0)
+            .map(|referendum| referendum.to_referendum_info())
+    }
+    //
+    fn get_appchain_referenda(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<AppchainReferendumInfo> {
+        self.appchain_referenda
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+            .iter()
+            .map(|referendum| referendum.to_referendum_info())
+            .collect()
+    }
+    //
+    fn get_appchain_runtime_upgrade_notice(
+        &self,
+        index: Option<U64>,
+    ) -> Option<AppchainRuntimeUpgradeNotice> {
+        let appchain_runtime_upgrade_notices = self.appchain_runtime_upgrade_notices.get().unwrap();
+        let index = match index {
+            Some(index) => index,
+            None => appchain_runtime_upgrade_notices.index_range().end_index,
+        };
+        appchain_runtime_upgrade_notices.get(&index.0)
+    }
+    //
+    fn get_appchain_runtime_upgrade_notices(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<AppchainRuntimeUpgradeNotice> {
+        self.appchain_runtime_upgrade_notices
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_vote_on_appchain_referendum(
+        &self,
+        referendum_index: U64,
+        voter_id: AccountId,
+    ) -> Option<ReferendumVote> {
+        self.appchain_referenda
+            .get()
+            .unwrap()
+            .get(&referendum_index.0)
+            .and_then(|referendum| referendum.get_vote_of(&voter_id))
+    }
+    //
+    fn get_delegator_vote_on_appchain_referendum(
+        &self,
+        referendum_index: U64,
+        delegator_id: AccountId,
+        validator_id: AccountId,
+    ) -> Option<ReferendumVote> {
+        self.appchain_referenda
+            .get()
+            .unwrap()
+            .get(&referendum_index.0)
+            .and_then(|referendum| referendum.get_delegator_vote_of(&delegator_id, &validator_id))
+    }
+    //
+    fn dump_validators(&self, cursor: Option<U64>) -> StateDumpChunk<AppchainValidator, U64> {
+        self.internal_dump_validators(cursor.map_or(0, |cursor| cursor.0))
+    }
+    //
+    fn dump_delegations(
+        &self,
+        cursor: Option<DelegationDumpCursor>,
+    ) -> StateDumpChunk<AppchainDelegator, DelegationDumpCursor> {
+        self.internal_dump_delegations(cursor.unwrap_or(DelegationDumpCursor {
+            validator_index: U64::from(0),
+            delegator_index: U64::from(0),
+        }))
+    }
+    //
+    fn dump_unbonded_stakes(&self, cursor: Option<U64>) -> StateDumpChunk<UnbondedStake, U64> {
+        self.internal_dump_unbonded_stakes(cursor.map_or(0, |cursor| cursor.0))
+    }
+    //
+    fn get_withdrawal_receipt(&self, receipt_id: U64) -> Option<WithdrawalReceipt> {
+        self.withdrawal_receipts.get().unwrap().get(&receipt_id.0)
+    }
+    //
+    fn get_withdrawal_receipts(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<WithdrawalReceipt> {
+        self.withdrawal_receipts
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_slash_histories(&self, start_index: U64, quantity: Option<U64>) -> Vec<SlashHistory> {
+        self.slash_histories
+            .get()
+            .unwrap()
+            .get_slice_of(&start_index.0, quantity.map(|q| q.0))
+    }
+    //
+    fn get_slash_histories_of_validator(&self, validator_id: AccountId) -> Vec<SlashHistory> {
+        let slash_histories = self.slash_histories.get().unwrap();
+        self.slash_history_indexes_of_validators
+            .get(&validator_id)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|index| slash_histories.get(index))
+            .collect()
+    }
+    //
+    fn get_treasury_routes_of(&self, source: TreasurySource) -> Vec<TreasuryRoute> {
+        self.treasury_routes.get(&source).unwrap_or_default()
+    }
+    //
+    fn get_treasury_flows_of_era(&self, era_number: U64) -> Vec<TreasuryFlow> {
+        self.treasury_flows_of_eras
+            .get(&era_number.0)
+            .unwrap_or_default()
+    }
+    //
+    fn get_projected_stake_of(&self, validator_id: AccountId) -> Option<ProjectedStakeOfValidator> {
+        self.internal_get_projected_stake_of(&validator_id)
+    }
 }
@@ -1,7 +1,17 @@
 use crate::*;
 use codec::{Decode, Encode};
 
-#[derive(Encode, Decode, Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Serialize,
+    Deserialize,
+    BorshDeserialize,
+    BorshSerialize,
+    Debug,
+    PartialEq,
+)]
 #[serde(crate = "near_sdk::serde")]
 pub enum PayloadType {
     Lock,
@@ -9,8 +19,25 @@ pub enum PayloadType {
     PlanNewEra,
     EraPayout,
     LockNft,
+    BalanceAttestation,
+    SettingChangeRequest,
+    NotificationAck,
+    Slash,
 }
 
+/// The names of variants of `PayloadType`, which should be updated along with the enum.
+pub const PAYLOAD_TYPE_VARIANTS: [&str; 9] = [
+    "Lock",
+    "BurnAsset",
+    "PlanNewEra",
+    "EraPayout",
+    "LockNft",
+    "BalanceAttestation",
+    "SettingChangeRequest",
+    "NotificationAck",
+    "Slash",
+];
+
 #[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct BurnAssetPayload {
@@ -69,6 +96,45 @@ pub struct AppchainMessage {
     pub nonce: u32,
 }
 
+#[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AttestedBalance {
+    /// The contract account of the NEP-141 token in NEAR protocol.
+    pub token_id: String,
+    /// The total balance of the wrapped token issued in appchain.
+    pub amount: u128,
+}
+
+#[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct BalanceAttestationPayload {
+    pub balances: Vec<AttestedBalance>,
+}
+
+#[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SettingChangeRequestPayload {
+    pub change: AnchorSettingChange,
+}
+
+#[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NotificationAckPayload {
+    /// The index of the latest appchain notification processed in appchain.
+    pub processed_index: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SlashPayload {
+    /// The id of the offending validator in appchain.
+    pub who: String,
+    /// The kind of the offence, e.g. `equivocation`.
+    pub kind: String,
+    /// The era in which the offence is committed.
+    pub era: u32,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub enum MessagePayload {
@@ -77,6 +143,10 @@ pub enum MessagePayload {
     PlanNewEra(PlanNewEraPayload),
     EraPayout(EraPayoutPayload),
     LockNft(LockNftPayload),
+    BalanceAttestation(BalanceAttestationPayload),
+    SettingChangeRequest(SettingChangeRequestPayload),
+    NotificationAck(NotificationAckPayload),
+    Slash(SlashPayload),
 }
 
 #[derive(Encode, Decode, Clone)]
@@ -355,14 +425,40 @@ impl AppchainAnchor {
                 }
             }
             PayloadType::EraPayout => {
+                let mut payload_data = &raw_message.payload[..];
                 let payload_result: Result<EraPayoutPayload, std::io::Error> =
-                    BorshDeserialize::deserialize(&mut &raw_message.payload[..]);
+                    BorshDeserialize::deserialize(&mut payload_data);
                 match payload_result {
                     Ok(payload) => {
                         log!(
                             "Origin appchain message: '{}'",
                             serde_json::to_string(&payload).unwrap()
                         );
+                        // The hash of validator set is optional, which is appended
+                        // to the payload as a borsh-encoded `[u8; 32]`.
+                        let validator_set_hash = match payload_data.is_empty() {
+                            true => None,
+                            false => {
+                                let hash_result: Result<[u8; 32], std::io::Error> =
+                                    BorshDeserialize::deserialize(&mut payload_data);
+                                match hash_result {
+                                    Ok(hash) => Some(format!("0x{}", hex::encode(hash))),
+                                    Err(err) => {
+                                        appchain_messages.insert_processing_result(
+                                            raw_message.nonce as u32,
+                                            &AppchainMessageProcessingResult::Error {
+                                                nonce: raw_message.nonce as u32,
+                                                message: format!(
+                                                    "Failed to deserialize hash of validator set: {}",
+                                                    err
+                                                ),
+                                            },
+                                        );
+                                        return;
+                                    }
+                                }
+                            }
+                        };
                         let protocol_settings = self.protocol_settings.get().unwrap();
                         if self.era_number_is_too_old(
                             u64::from(payload.end_era),
@@ -387,6 +483,7 @@ impl AppchainAnchor {
                                 era_number: payload.end_era,
                                 unprofitable_validator_ids: payload.excluded_validators,
                                 offenders: payload.offenders,
+                                validator_set_hash,
                             },
                         });
                     }
@@ -428,6 +525,130 @@ impl AppchainAnchor {
                     ),
                 }
             }
+            PayloadType::BalanceAttestation => {
+                let payload_result: Result<BalanceAttestationPayload, std::io::Error> =
+                    BorshDeserialize::deserialize(&mut &raw_message.payload[..]);
+                match payload_result {
+                    Ok(payload) => {
+                        log!(
+                            "Origin appchain message: '{}'",
+                            serde_json::to_string(&payload).unwrap()
+                        );
+                        appchain_messages.insert_message(&AppchainMessage {
+                            nonce: raw_message.nonce as u32,
+                            appchain_event: AppchainEvent::WrappedAssetBalancesAttested {
+                                balances: payload
+                                    .balances
+                                    .iter()
+                                    .map(|balance| {
+                                        (balance.token_id.clone(), U128::from(balance.amount))
+                                    })
+                                    .collect(),
+                            },
+                        });
+                    }
+                    Err(err) => appchain_messages.insert_processing_result(
+                        raw_message.nonce as u32,
+                        &AppchainMessageProcessingResult::Error {
+                            nonce: raw_message.nonce as u32,
+                            message: format!("Failed to deserialize raw message payload: {}", err),
+                        },
+                    ),
+                }
+            }
+            PayloadType::SettingChangeRequest => {
+                let payload_result: Result<SettingChangeRequestPayload, std::io::Error> =
+                    BorshDeserialize::deserialize(&mut &raw_message.payload[..]);
+                match payload_result {
+                    Ok(payload) => {
+                        log!(
+                            "Origin appchain message: '{}'",
+                            serde_json::to_string(&payload).unwrap()
+                        );
+                        appchain_messages.insert_message(&AppchainMessage {
+                            nonce: raw_message.nonce as u32,
+                            appchain_event: AppchainEvent::AnchorSettingChangeRequested {
+                                change: payload.change,
+                            },
+                        });
+                    }
+                    Err(err) => appchain_messages.insert_processing_result(
+                        raw_message.nonce as u32,
+                        &AppchainMessageProcessingResult::Error {
+                            nonce: raw_message.nonce as u32,
+                            message: format!("Failed to deserialize raw message payload: {}", err),
+                        },
+                    ),
+                }
+            }
+            PayloadType::NotificationAck => {
+                let payload_result: Result<NotificationAckPayload, std::io::Error> =
+                    BorshDeserialize::deserialize(&mut &raw_message.payload[..]);
+                match payload_result {
+                    Ok(payload) => {
+                        log!(
+                            "Origin appchain message: '{}'",
+                            serde_json::to_string(&payload).unwrap()
+                        );
+                        appchain_messages.insert_message(&AppchainMessage {
+                            nonce: raw_message.nonce as u32,
+                            appchain_event: AppchainEvent::AppchainNotificationsAcknowledged {
+                                processed_index: U64::from(payload.processed_index),
+                            },
+                        });
+                    }
+                    Err(err) => appchain_messages.insert_processing_result(
+                        raw_message.nonce as u32,
+                        &AppchainMessageProcessingResult::Error {
+                            nonce: raw_message.nonce as u32,
+                            message: format!("Failed to deserialize raw message payload: {}", err),
+                        },
+                    ),
+                }
+            }
+            PayloadType::Slash => {
+                let payload_result: Result<SlashPayload, std::io::Error> =
+                    BorshDeserialize::deserialize(&mut &raw_message.payload[..]);
+                match payload_result {
+                    Ok(payload) => {
+                        log!(
+                            "Origin appchain message: '{}'",
+                            serde_json::to_string(&payload).unwrap()
+                        );
+                        let protocol_settings = self.protocol_settings.get().unwrap();
+                        if self.era_number_is_too_old(
+                            u64::from(payload.era),
+                            protocol_settings
+                                .maximum_era_count_of_valid_appchain_message
+                                .0,
+                        ) {
+                            appchain_messages.insert_processing_result(
+                                raw_message.nonce as u32,
+                                &AppchainMessageProcessingResult::Error {
+                                    nonce: raw_message.nonce as u32,
+                                    message: format!("Era number of message 'Slash' is too old."),
+                                },
+                            );
+                            return;
+                        }
+                        appchain_messages.insert_message(&AppchainMessage {
+                            nonce: raw_message.nonce as u32,
+                            appchain_event: AppchainEvent::ValidatorOffenceReported {
+                                validator_id_in_appchain: payload.who,
+                                offence_kind: payload.kind,
+                                era_number: payload.era,
+                            },
+                        });
+                    }
+                    Err(err) => appchain_messages.insert_processing_result(
+                        raw_message.nonce as u32,
+                        &AppchainMessageProcessingResult::Error {
+                            nonce: raw_message.nonce as u32,
+                            message: format!("Failed to deserialize raw message payload: {}", err),
+                        },
+                    ),
+                }
+            }
         }
     }
     //
@@ -0,0 +1,360 @@
+use crate::interfaces::AppchainReferendaManager;
+use crate::*;
+use near_sdk::collections::{LookupMap, UnorderedMap};
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct AppchainReferendum {
+    /// The index of the referendum.
+    index: u64,
+    /// The title of the referendum.
+    title: String,
+    /// The description of the referendum.
+    description: String,
+    /// The era number of the validator set which is used for voting.
+    era_number: u64,
+    /// The timestamp when the voting starts.
+    start_timestamp: Timestamp,
+    /// The timestamp when the voting ends.
+    end_timestamp: Timestamp,
+    /// Total stake of the validator set which is used for voting, including the boosts
+    /// of voting weight of the delegators who have voted.
+    total_stake: Balance,
+    /// Total stake of voters who vote `Aye`.
+    aye_stake: Balance,
+    /// Total stake of voters who vote `Nay`.
+    nay_stake: Balance,
+    /// The votes of validators.
+    votes: UnorderedMap<AccountId, ReferendumVote>,
+    /// The votes of delegators which override the votes of their validators,
+    /// keyed by `(delegator_id, validator_id)`.
+    delegator_votes: UnorderedMap<(AccountId, AccountId), ReferendumVote>,
+    /// The stake of a validator which is overridden by the votes of its delegators.
+    overridden_stakes: LookupMap<AccountId, Balance>,
+    /// The status of the referendum.
+    status: AppchainReferendumStatus,
+}
+
+impl AppchainReferendum {
+    ///
+    pub fn new(
+        index: u64,
+        title: String,
+        description: String,
+        era_number: u64,
+        voting_period: u64,
+        total_stake: Balance,
+    ) -> Self {
+        Self {
+            index,
+            title,
+            description,
+            era_number,
+            start_timestamp: env::block_timestamp(),
+            end_timestamp: env::block_timestamp() + voting_period,
+            total_stake,
+            aye_stake: 0,
+            nay_stake: 0,
+            votes: UnorderedMap::new(StorageKey::AppchainReferendumVotes(index).into_bytes()),
+            delegator_votes: UnorderedMap::new(
+                StorageKey::AppchainReferendumDelegatorVotes(index).into_bytes(),
+            ),
+            overridden_stakes: LookupMap::new(
+                StorageKey::AppchainReferendumOverriddenStakes(index).into_bytes(),
+            ),
+            status: AppchainReferendumStatus::Voting,
+        }
+    }
+    ///
+    pub fn era_number(&self) -> u64 {
+        self.era_number
+    }
+    ///
+    pub fn get_vote_of(&self, voter_id: &AccountId) -> Option<ReferendumVote> {
+        self.votes.get(voter_id)
+    }
+    ///
+    pub fn get_delegator_vote_of(
+        &self,
+        delegator_id: &AccountId,
+        validator_id: &AccountId,
+    ) -> Option<ReferendumVote> {
+        self.delegator_votes
+            .get(&(delegator_id.clone(), validator_id.clone()))
+    }
+    ///
+    pub fn assert_voting(&self) {
+        assert!(
+            self.status.eq(&AppchainReferendumStatus::Voting)
+                && env::block_timestamp() < self.end_timestamp,
+            "Referendum '{}' is not in voting period.",
+            self.index
+        );
+    }
+    /// Record the vote of a validator, and replace the former vote of the validator (if any).
+    ///
+    /// The stake of the vote is the total stake of the validator, excluding the delegations
+    /// of delegators who vote by themselves.
+    pub fn record_validator_vote(
+        &mut self,
+        validator_id: &AccountId,
+        option: ReferendumVoteOption,
+        total_stake: Balance,
+    ) -> ReferendumVote {
+        if let Some(old_vote) = self.votes.get(validator_id) {
+            self.remove_stake_of_vote(&old_vote);
+        }
+        let vote = ReferendumVote {
+            option,
+            stake: U128::from(total_stake - self.overridden_stakes.get(validator_id).unwrap_or(0)),
+        };
+        self.add_stake_of_vote(&vote);
+        self.votes.insert(validator_id, &vote);
+        vote
+    }
+    /// Record the vote of a delegator with its delegation to a validator, which overrides
+    /// the vote of the validator for the portion of the delegation.
+    /// The former vote of the delegator (if any) will be replaced.
+    ///
+    /// The stake of the vote is the `voting_weight`, which may be boosted from
+    /// the `deposit_amount` by the lock of the delegation. The boost is also added to
+    /// the total stake of the referendum, so that the stake of all votes never exceeds it.
+    pub fn record_delegator_vote(
+        &mut self,
+        delegator_id: &AccountId,
+        validator_id: &AccountId,
+        option: ReferendumVoteOption,
+        deposit_amount: Balance,
+        voting_weight: Balance,
+    ) -> ReferendumVote {
+        let key = (delegator_id.clone(), validator_id.clone());
+        if let Some(old_vote) = self.delegator_votes.get(&key) {
+            self.remove_stake_of_vote(&old_vote);
+            self.total_stake -= old_vote.stake.0 - deposit_amount;
+        } else {
+            let overridden_stake = self.overridden_stakes.get(validator_id).unwrap_or(0);
+            self.overridden_stakes
+                .insert(validator_id, &(overridden_stake + deposit_amount));
+            if let Some(mut validator_vote) = self.votes.get(validator_id) {
+                self.remove_stake_of_vote(&validator_vote);
+                validator_vote.stake = U128::from(validator_vote.stake.0 - deposit_amount);
+                self.add_stake_of_vote(&validator_vote);
+                self.votes.insert(validator_id, &validator_vote);
+            }
+        }
+        self.total_stake += voting_weight - deposit_amount;
+        let vote = ReferendumVote {
+            option,
+            stake: U128::from(voting_weight),
+        };
+        self.add_stake_of_vote(&vote);
+        self.delegator_votes.insert(&key, &vote);
+        vote
+    }
+    //
+    fn add_stake_of_vote(&mut self, vote: &ReferendumVote) {
+        match vote.option {
+            ReferendumVoteOption::Aye => self.aye_stake += vote.stake.0,
+            ReferendumVoteOption::Nay => self.nay_stake += vote.stake.0,
+        }
+    }
+    //
+    fn remove_stake_of_vote(&mut self, vote: &ReferendumVote) {
+        match vote.option {
+            ReferendumVoteOption::Aye => self.aye_stake -= vote.stake.0,
+            ReferendumVoteOption::Nay => self.nay_stake -= vote.stake.0,
+        }
+    }
+    /// Conclude the referendum by the stake-weighted votes.
+    pub fn conclude(&mut self) {
+        assert!(
+            self.status.eq(&AppchainReferendumStatus::Voting),
+            "Referendum '{}' is already concluded.",
+            self.index
+        );
+        assert!(
+            env::block_timestamp() >= self.end_timestamp,
+            "Referendum '{}' is still in voting period.",
+            self.index
+        );
+        self.status = match self.aye_stake > self.nay_stake {
+            true => AppchainReferendumStatus::Approved,
+            false => AppchainReferendumStatus::Rejected,
+        };
+    }
+    ///
+    pub fn to_referendum_info(&self) -> AppchainReferendumInfo {
+        AppchainReferendumInfo {
+            index: U64::from(self.index),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            era_number: U64::from(self.era_number),
+            start_timestamp: U64::from(self.start_timestamp),
+            end_timestamp: U64::from(self.end_timestamp),
+            total_stake: U128::from(self.total_stake),
+            aye_stake: U128::from(self.aye_stake),
+            nay_stake: U128::from(self.nay_stake),
+            voter_count: U64::from(self.votes.len()),
+            delegator_voter_count: U64::from(self.delegator_votes.len()),
+            status: self.status.clone(),
+        }
+    }
+}
+
+impl IndexedAndClearable for AppchainReferendum {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = *index;
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        let keys = self
+            .delegator_votes
+            .keys()
+            .collect::<Vec<(AccountId, AccountId)>>();
+        for key in keys {
+            self.overridden_stakes.remove(&key.1);
+            self.delegator_votes.remove(&key);
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                return MultiTxsOperationProcessingResult::NeedMoreGas;
+            }
+        }
+        let voter_ids = self.votes.keys().collect::<Vec<AccountId>>();
+        for voter_id in voter_ids {
+            self.votes.remove(&voter_id);
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                return MultiTxsOperationProcessingResult::NeedMoreGas;
+            }
+        }
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
+#[near_bindgen]
+impl AppchainReferendaManager for AppchainAnchor {
+    //
+    fn create_appchain_referendum(
+        &mut self,
+        title: String,
+        description: String,
+        voting_period_in_seconds: U64,
+    ) -> U64 {
+        self.assert_owner();
+        self.assert_not_in_maintenance_mode();
+        assert!(
+            self.appchain_state.eq(&AppchainState::Active),
+            "Referendum can only be created while appchain state is 'active'."
+        );
+        assert!(
+            voting_period_in_seconds.0 > 0,
+            "The voting period should not be zero."
+        );
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let era_number = validator_set_histories.index_range().end_index.0;
+        let validator_set = validator_set_histories.get(&era_number).unwrap();
+        assert!(
+            validator_set.total_stake() > 0,
+            "The validator set of era '{}' is not ready for voting.",
+            era_number
+        );
+        let mut appchain_referenda = self.appchain_referenda.get().unwrap();
+        let referendum = appchain_referenda.append(&mut AppchainReferendum::new(
+            appchain_referenda.next_index(),
+            title,
+            description,
+            era_number,
+            voting_period_in_seconds.0 * NANO_SECONDS_MULTIPLE,
+            validator_set.total_stake(),
+        ));
+        self.appchain_referenda.set(&appchain_referenda);
+        U64::from(referendum.index)
+    }
+    //
+    fn vote_on_appchain_referendum(&mut self, referendum_index: U64, option: ReferendumVoteOption) {
+        self.assert_not_in_maintenance_mode();
+        let mut appchain_referenda = self.appchain_referenda.get().unwrap();
+        let mut referendum = appchain_referenda
+            .get(&referendum_index.0)
+            .expect("Referendum is not existed.");
+        referendum.assert_voting();
+        let validator_id = env::predecessor_account_id();
+        let validator_set = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .get(&referendum.era_number())
+            .unwrap();
+        let validator = validator_set.get_validator(&validator_id).expect(
+            "Only validators in the validator set of the referendum can vote on the referendum.",
+        );
+        referendum.record_validator_vote(&validator_id, option, validator.total_stake);
+        appchain_referenda.insert(&referendum_index.0, &referendum);
+        self.appchain_referenda.set(&appchain_referenda);
+    }
+    //
+    fn vote_on_appchain_referendum_as_delegator(
+        &mut self,
+        referendum_index: U64,
+        validator_id: AccountId,
+        option: ReferendumVoteOption,
+    ) {
+        self.assert_not_in_maintenance_mode();
+        let mut appchain_referenda = self.appchain_referenda.get().unwrap();
+        let mut referendum = appchain_referenda
+            .get(&referendum_index.0)
+            .expect("Referendum is not existed.");
+        referendum.assert_voting();
+        let delegator_id = env::predecessor_account_id();
+        let validator_set = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .get(&referendum.era_number())
+            .unwrap();
+        let delegator = validator_set
+            .get_delegator(&delegator_id, &validator_id)
+            .expect(
+            "Only delegators in the validator set of the referendum can vote on the referendum.",
+        );
+        let voting_weight = match self
+            .delegation_locks
+            .get(&(delegator_id.clone(), validator_id.clone()))
+        {
+            Some(lock) if lock.is_locked_in(referendum.era_number()) => {
+                delegator.deposit_amount * (100 + u128::from(lock.voting_boost_percent)) / 100
+            }
+            _ => delegator.deposit_amount,
+        };
+        referendum.record_delegator_vote(
+            &delegator_id,
+            &validator_id,
+            option,
+            delegator.deposit_amount,
+            voting_weight,
+        );
+        appchain_referenda.insert(&referendum_index.0, &referendum);
+        self.appchain_referenda.set(&appchain_referenda);
+    }
+    //
+    fn conclude_appchain_referendum(&mut self, referendum_index: U64) {
+        self.assert_not_in_maintenance_mode();
+        let mut appchain_referenda = self.appchain_referenda.get().unwrap();
+        let mut referendum = appchain_referenda
+            .get(&referendum_index.0)
+            .expect("Referendum is not existed.");
+        referendum.conclude();
+        appchain_referenda.insert(&referendum_index.0, &referendum);
+        self.appchain_referenda.set(&appchain_referenda);
+        //
+        let referendum_info = referendum.to_referendum_info();
+        self.internal_append_appchain_notification(AppchainNotification::ReferendumConcluded {
+            referendum_index,
+            title: referendum_info.title,
+            approved: referendum_info
+                .status
+                .eq(&AppchainReferendumStatus::Approved),
+            aye_stake: referendum_info.aye_stake,
+            nay_stake: referendum_info.nay_stake,
+            total_stake: referendum_info.total_stake,
+        });
+    }
+}
@@ -0,0 +1,103 @@
+use crate::*;
+
+impl AppchainAnchor {
+    // Stage a request of appchain governance to change an anchor-side setting, the earlier
+    // pending proposal which changes the same setting is superseded. Return the index
+    // of the staged proposal.
+    pub fn internal_stage_appchain_setting_change(
+        &mut self,
+        appchain_message_nonce: u32,
+        change: &AnchorSettingChange,
+    ) -> u64 {
+        let mut proposals = self.appchain_setting_change_proposals.get().unwrap();
+        let proposal = proposals.append(&mut AppchainSettingChangeProposal {
+            change: change.clone(),
+            appchain_message_nonce,
+            status: AppchainSettingChangeProposalStatus::Pending,
+            staged_timestamp: U64::from(env::block_timestamp()),
+            index: U64::from(0),
+        });
+        let index_range = proposals.index_range();
+        for index in index_range.start_index.0..proposal.index.0 {
+            if let Some(mut earlier_proposal) = proposals.get(&index) {
+                if earlier_proposal.status == AppchainSettingChangeProposalStatus::Pending
+                    && std::mem::discriminant(&earlier_proposal.change)
+                        == std::mem::discriminant(change)
+                {
+                    earlier_proposal.status = AppchainSettingChangeProposalStatus::Superseded {
+                        by_index: proposal.index,
+                    };
+                    proposals.insert(&index, &earlier_proposal);
+                }
+            }
+        }
+        self.appchain_setting_change_proposals.set(&proposals);
+        log!(
+            "Appchain setting change '{}' is staged as proposal '{}'.",
+            serde_json::to_string(change).unwrap(),
+            proposal.index.0
+        );
+        proposal.index.0
+    }
+    //
+    pub fn get_pending_appchain_setting_change_proposal(
+        &self,
+        proposal_index: u64,
+    ) -> AppchainSettingChangeProposal {
+        self.appchain_setting_change_proposals
+            .get()
+            .unwrap()
+            .get(&proposal_index)
+            .filter(|proposal| proposal.status == AppchainSettingChangeProposalStatus::Pending)
+            .expect("The proposal is not pending.")
+    }
+    // Conclude a pending proposal with the decision of the owner,
+    // the change is applied if it is approved.
+    pub fn internal_conclude_appchain_setting_change(
+        &mut self,
+        proposal_index: u64,
+        is_approved: bool,
+    ) {
+        let mut proposal = self.get_pending_appchain_setting_change_proposal(proposal_index);
+        proposal.status = match is_approved {
+            true => {
+                self.apply_anchor_setting_change(&proposal.change);
+                AppchainSettingChangeProposalStatus::Approved
+            }
+            false => AppchainSettingChangeProposalStatus::Rejected,
+        };
+        let mut proposals = self.appchain_setting_change_proposals.get().unwrap();
+        proposals.insert(&proposal_index, &proposal);
+        self.appchain_setting_change_proposals.set(&proposals);
+    }
+    // Apply a change of setting with the same checks as the corresponding setter of the owner.
+    fn apply_anchor_setting_change(&mut self, change: &AnchorSettingChange) {
+        match change {
+            AnchorSettingChange::EraReward { value } => {
+                let mut appchain_settings = self.appchain_settings.get().unwrap();
+                appchain_settings.era_reward = *value;
+                self.internal_set_appchain_settings(&appchain_settings);
+            }
+            AnchorSettingChange::BonusForNewValidator { value } => {
+                let mut appchain_settings = self.appchain_settings.get().unwrap();
+                appchain_settings.bonus_for_new_validator = *value;
+                self.internal_set_appchain_settings(&appchain_settings);
+            }
+            AnchorSettingChange::ValidatorCommissionPercent { value } => {
+                assert!(*value < 100, "Invalid percent value.");
+                let mut protocol_settings = self.protocol_settings.get().unwrap();
+                protocol_settings.validator_commission_percent = *value;
+                self.internal_set_protocol_settings(&protocol_settings);
+            }
+            AnchorSettingChange::MaximumValidatorCount { value } => {
+                let mut protocol_settings = self.protocol_settings.get().unwrap();
+                assert!(
+                    value.0 > protocol_settings.minimum_validator_count.0,
+                    "The value should be greater than `minimum_validator_count`."
+                );
+                protocol_settings.maximum_validator_count = *value;
+                self.internal_set_protocol_settings(&protocol_settings);
+            }
+        }
+    }
+}
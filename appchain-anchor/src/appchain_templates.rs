@@ -0,0 +1,92 @@
+use crate::*;
+
+/// The behaviors of this contract which differ between the templates of appchain.
+pub trait AppchainTemplate {
+    /// Normalize an account id in appchain to the format stored in this contract and
+    /// exported in the validator sets of eras.
+    fn normalize_account_id(&self, id_in_appchain: &str) -> String;
+    /// Check whether a normalized account id in appchain is valid.
+    fn is_valid_account_id(&self, normalized_id: &str) -> bool;
+    /// Get the way of assigning block production slots of the template,
+    /// return `None` if the template does not support duty hints.
+    fn duty_weighting(&self) -> Option<DutyWeighting>;
+    /// Get the expected block time (in seconds) of the template.
+    fn expected_block_time_in_secs(&self) -> u64;
+}
+
+/// The substrate based appchain template, whose account id is a 32 bytes public key.
+struct BarnacleTemplate;
+
+/// The substrate based appchain template with EVM compatibility,
+/// whose account id is a 20 bytes EVM address.
+struct BarnacleEvmTemplate;
+
+impl AppchainTemplate for BarnacleTemplate {
+    //
+    fn normalize_account_id(&self, id_in_appchain: &str) -> String {
+        normalize_hex_account_id(id_in_appchain)
+    }
+    //
+    fn is_valid_account_id(&self, normalized_id: &str) -> bool {
+        hex_account_id_length(normalized_id) == Some(32)
+    }
+    //
+    fn duty_weighting(&self) -> Option<DutyWeighting> {
+        Some(DutyWeighting::ByWeight)
+    }
+    //
+    fn expected_block_time_in_secs(&self) -> u64 {
+        6
+    }
+}
+
+impl AppchainTemplate for BarnacleEvmTemplate {
+    //
+    fn normalize_account_id(&self, id_in_appchain: &str) -> String {
+        normalize_hex_account_id(id_in_appchain)
+    }
+    //
+    fn is_valid_account_id(&self, normalized_id: &str) -> bool {
+        hex_account_id_length(normalized_id) == Some(20)
+    }
+    //
+    fn duty_weighting(&self) -> Option<DutyWeighting> {
+        Some(DutyWeighting::ByWeight)
+    }
+    //
+    fn expected_block_time_in_secs(&self) -> u64 {
+        6
+    }
+}
+
+impl AppchainTemplateType {
+    /// Get the implementation of the behaviors of the template.
+    pub fn template(&self) -> Box<dyn AppchainTemplate> {
+        match self {
+            AppchainTemplateType::Barnacle => Box::new(BarnacleTemplate),
+            AppchainTemplateType::BarnacleEvm => Box::new(BarnacleEvmTemplate),
+        }
+    }
+}
+
+// Add prefix `0x` to the account id if it is missing, and convert it to lowercase.
+fn normalize_hex_account_id(id_in_appchain: &str) -> String {
+    let mut value = String::new();
+    if !id_in_appchain.to_lowercase().starts_with("0x") {
+        value.push_str("0x");
+    }
+    value.push_str(id_in_appchain);
+    value.to_lowercase()
+}
+
+// Get the length (in bytes) of a normalized hex account id,
+// return `None` if it is not a valid hex string.
+fn hex_account_id_length(normalized_id: &str) -> Option<usize> {
+    if normalized_id.len() > 2 {
+        hex::decode(&normalized_id[2..])
+            .ok()
+            .map(|bytes| bytes.len())
+    } else {
+        None
+    }
+}
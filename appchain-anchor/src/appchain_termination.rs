@@ -0,0 +1,219 @@
+use crate::*;
+
+impl AppchainAnchor {
+    // Switch the appchain to `terminating` state. The latest era becomes the final era,
+    // it is concluded without reward, and the staged appchain messages which are not applied
+    // yet are dropped. The summary of the final state is recorded in the termination.
+    pub fn internal_go_terminating(&mut self, reason: String) {
+        match self.appchain_state {
+            AppchainState::Active | AppchainState::Broken => (),
+            _ => panic!(
+                "Cannot terminate appchain while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        assert!(
+            !reason.trim().is_empty(),
+            "The reason of termination should not be empty."
+        );
+        let processing_status = self.permissionless_actions_status.get().unwrap();
+        assert!(
+            processing_status.switching_era_number.is_none()
+                && processing_status.distributing_reward_era_number.is_none(),
+            "Cannot terminate appchain while switching era or distributing reward."
+        );
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let final_era_number = validator_set_histories.index_range().end_index.0;
+        let final_validator_set = validator_set_histories.get(&final_era_number).unwrap();
+        self.conclude_final_era_summary(
+            &final_validator_set,
+            processing_status.latest_applied_appchain_message_nonce,
+        );
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.appchain_termination.set(&AppchainTermination {
+            reason,
+            final_era_number: U64::from(final_era_number),
+            latest_applied_appchain_message_nonce: processing_status
+                .latest_applied_appchain_message_nonce,
+            validator_count: U64::from(next_validator_set.validator_count()),
+            delegator_count: U64::from(next_validator_set.delegator_count()),
+            total_stake: U128::from(next_validator_set.total_stake()),
+            total_unbonded_stake: U128::from(self.total_unbonded_stake),
+            terminated_block_height: U64::from(env::block_height()),
+            terminated_timestamp: U64::from(env::block_timestamp()),
+            status: AppchainTerminationStatus::ApplyingStakingHistory {
+                applying_index: U64::from(final_validator_set.staking_history_index() + 1),
+                end_index: self
+                    .staking_histories
+                    .get()
+                    .unwrap()
+                    .index_range()
+                    .end_index,
+            },
+        });
+        self.appchain_state = AppchainState::Terminating;
+        self.sync_state_to_registry();
+    }
+    // Close the summary of the final era at the time of termination.
+    fn conclude_final_era_summary(
+        &mut self,
+        final_validator_set: &ValidatorSetOfEra,
+        latest_applied_appchain_message_nonce: u32,
+    ) {
+        let era_number = final_validator_set.era_number();
+        if let Some(mut era_summary) = self.era_summaries.get(&era_number) {
+            era_summary.duration = Some(U64::from(
+                env::block_timestamp() - final_validator_set.start_timestamp(),
+            ));
+            era_summary.validator_count = U64::from(final_validator_set.validator_count());
+            era_summary.total_stake = U128::from(final_validator_set.total_stake());
+            era_summary.end_message_nonce =
+                Some(U64::from(u64::from(latest_applied_appchain_message_nonce)));
+            self.era_summaries.insert(&era_number, &era_summary);
+        }
+    }
+    // Wind down the terminating appchain. The unbonded stakes recorded after the start of
+    // the final era are referenced first, then all validators and delegators in the next
+    // validator set are unbonded. The unbonded stakes are unlocked immediately while
+    // the appchain is terminating.
+    pub fn internal_wind_down_terminating_appchain(&mut self) -> MultiTxsOperationProcessingResult {
+        assert_eq!(
+            self.appchain_state,
+            AppchainState::Terminating,
+            "Appchain state must be 'terminating'."
+        );
+        let mut termination = self.appchain_termination.get().unwrap();
+        let final_era_number = termination.final_era_number.0;
+        let staking_histories = self.staking_histories.get().unwrap();
+        loop {
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                self.appchain_termination.set(&termination);
+                return MultiTxsOperationProcessingResult::NeedMoreGas;
+            }
+            match termination.status {
+                AppchainTerminationStatus::ApplyingStakingHistory {
+                    applying_index,
+                    end_index,
+                } => {
+                    if applying_index.0 > end_index.0 {
+                        termination.status = AppchainTerminationStatus::UnbondingStakes;
+                        continue;
+                    }
+                    if let Some(staking_history) = staking_histories.get(&applying_index.0) {
+                        self.add_unbonded_stake_references_of(&staking_history, final_era_number);
+                    }
+                    termination.status = AppchainTerminationStatus::ApplyingStakingHistory {
+                        applying_index: U64::from(applying_index.0 + 1),
+                        end_index,
+                    };
+                }
+                AppchainTerminationStatus::UnbondingStakes => {
+                    if !self.unbond_first_stake_of_next_validator_set(final_era_number) {
+                        let mut next_validator_set = self.next_validator_set.get().unwrap();
+                        next_validator_set.clear_unbonding_validator_ids();
+                        next_validator_set.clear_auto_unbonding_validator_ids();
+                        self.next_validator_set.set(&next_validator_set);
+                        termination.status = AppchainTerminationStatus::WoundDown;
+                        self.appchain_termination.set(&termination);
+                        self.sync_state_to_registry();
+                        log!("The terminating appchain is wound down.");
+                        return MultiTxsOperationProcessingResult::Ok;
+                    }
+                }
+                AppchainTerminationStatus::WoundDown => {
+                    return MultiTxsOperationProcessingResult::Ok;
+                }
+            }
+        }
+    }
+    // Unbond the first delegator of the first validator in the next validator set, or the
+    // validator itself (along with its vesting-locked stake and stake of secondary token)
+    // if it has no delegator. Return false if the next validator set is empty.
+    fn unbond_first_stake_of_next_validator_set(&mut self, final_era_number: u64) -> bool {
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        let validator = match next_validator_set
+            .validator_set()
+            .get_validator_by_index(&0)
+        {
+            Some(validator) => validator,
+            None => return false,
+        };
+        let mut staking_facts = Vec::new();
+        if let Some(delegator) = next_validator_set
+            .validator_set()
+            .get_delegator_by_index(&0, &validator.validator_id)
+        {
+            staking_facts.push(StakingFact::DelegatorAutoUnbonded {
+                delegator_id: delegator.delegator_id,
+                validator_id: delegator.validator_id,
+                amount: U128::from(delegator.deposit_amount),
+            });
+        } else {
+            let mut depositor_ids = validator
+                .vesting_locked_deposits
+                .keys()
+                .cloned()
+                .collect::<Vec<AccountId>>();
+            depositor_ids.sort();
+            for depositor_id in depositor_ids {
+                let amount = *validator
+                    .vesting_locked_deposits
+                    .get(&depositor_id)
+                    .unwrap();
+                staking_facts.push(StakingFact::VestingLockedStakeUnbonded {
+                    validator_id: validator.validator_id.clone(),
+                    depositor_id,
+                    amount: U128::from(amount),
+                });
+            }
+            if validator.secondary_deposit_amount > 0 {
+                staking_facts.push(StakingFact::SecondaryStakeUnbonded {
+                    validator_id: validator.validator_id.clone(),
+                    amount: U128::from(validator.secondary_deposit_amount),
+                    stake: U128::from(validator.secondary_stake),
+                });
+            }
+            staking_facts.push(StakingFact::ValidatorAutoUnbonded {
+                validator_id: validator.validator_id.clone(),
+                amount: U128::from(
+                    validator.deposit_amount - validator.vesting_locked_deposit_amount(),
+                ),
+            });
+        }
+        for staking_fact in staking_facts {
+            let staking_history = self.record_staking_fact(staking_fact);
+            self.add_unbonded_stake_references_of(&staking_history, final_era_number);
+            self.apply_staking_fact_to_next_validator_set(
+                &mut next_validator_set,
+                &staking_history.staking_fact,
+            );
+        }
+        self.next_validator_set.set(&next_validator_set);
+        true
+    }
+    // Reference the unbonded stake (in OCT token or secondary stake token) of a staking history
+    // in the final era, for the withdrawal of it.
+    fn add_unbonded_stake_references_of(
+        &mut self,
+        staking_history: &StakingHistory,
+        final_era_number: u64,
+    ) {
+        let reference = UnbondedStakeReference {
+            era_number: final_era_number,
+            staking_history_index: staking_history.index.0,
+        };
+        if let Some((account_id, _, _)) = staking_history.staking_fact.unbonded_stake() {
+            let mut stakes = self.unbonded_stakes.get(&account_id).unwrap_or_default();
+            stakes.push(reference.clone());
+            self.unbonded_stakes.insert(&account_id, &stakes);
+        }
+        if let Some((account_id, _)) = staking_history.staking_fact.unbonded_secondary_stake() {
+            let mut stakes = self
+                .unbonded_secondary_stakes
+                .get(&account_id)
+                .unwrap_or_default();
+            stakes.push(reference);
+            self.unbonded_secondary_stakes.insert(&account_id, &stakes);
+        }
+    }
+}
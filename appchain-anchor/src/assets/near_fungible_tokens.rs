@@ -2,8 +2,9 @@ use near_contract_standards::fungible_token::core::ext_ft_core;
 use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
 
 use crate::{
-    interfaces::NearFungibleTokenManager,
-    permissionless_actions::AppchainMessagesProcessingContext, *,
+    interfaces::{AnchorViewer, NearFungibleTokenManager},
+    permissionless_actions::AppchainMessagesProcessingContext,
+    *,
 };
 
 pub trait FungibleTokenContractResolver {
@@ -15,7 +16,10 @@ pub trait FungibleTokenContractResolver {
         receiver_id_in_near: AccountId,
         amount: U128,
         appchain_message_nonce: u32,
+        withdrawal_receipt_id: Option<U64>,
     );
+    /// Resolver for fetching metadata of NEAR fungible token
+    fn resolve_near_fungible_token_metadata_fetching(&mut self, contract_account: AccountId);
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -101,6 +105,7 @@ impl NearFungibleTokenManager for AppchainAnchor {
         contract_account: AccountId,
         price: U128,
     ) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut near_fungible_tokens = self.near_fungible_tokens.get().unwrap();
         assert!(
@@ -125,12 +130,13 @@ impl NearFungibleTokenManager for AppchainAnchor {
                 reference: None,
                 reference_hash: None,
             },
-            contract_account,
+            contract_account: contract_account.clone(),
             price_in_usd: price,
             locked_balance: U128::from(0),
             bridging_state: BridgingState::Closed,
         });
         self.near_fungible_tokens.set(&near_fungible_tokens);
+        self.fetch_metadata_of_near_fungible_token(contract_account);
     }
     //
     fn change_near_fungible_token_metadata(
@@ -140,6 +146,7 @@ impl NearFungibleTokenManager for AppchainAnchor {
         decimals: u8,
         contract_account: AccountId,
     ) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut near_fungible_tokens = self.near_fungible_tokens.get().unwrap();
         assert!(
@@ -157,11 +164,15 @@ impl NearFungibleTokenManager for AppchainAnchor {
         let mut near_fungible_token = near_fungible_tokens.get(&symbol).unwrap();
         near_fungible_token.metadata.name = name;
         near_fungible_token.metadata.decimals = decimals;
-        near_fungible_token.contract_account = contract_account;
+        self.near_fungible_token_metadata_mirrors
+            .remove(&near_fungible_token.contract_account);
+        near_fungible_token.contract_account = contract_account.clone();
         near_fungible_tokens.insert(&near_fungible_token);
+        self.fetch_metadata_of_near_fungible_token(contract_account);
     }
     //
     fn set_price_of_near_fungible_token(&mut self, symbol: String, price: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_token_price_maintainer();
         let mut near_fungible_tokens = self.near_fungible_tokens.get().unwrap();
         assert!(
@@ -170,11 +181,21 @@ impl NearFungibleTokenManager for AppchainAnchor {
             &symbol
         );
         let mut near_fungible_token = near_fungible_tokens.get(&symbol).unwrap();
+        if !self.check_price_update(
+            PricedToken::NearFungibleToken {
+                symbol: symbol.clone(),
+            },
+            near_fungible_token.price_in_usd.0,
+            price.0,
+        ) {
+            return;
+        }
         near_fungible_token.price_in_usd = price;
         near_fungible_tokens.insert(&near_fungible_token);
     }
     //
     fn open_bridging_of_near_fungible_token(&mut self, symbol: String) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut near_fungible_tokens = self.near_fungible_tokens.get().unwrap();
         assert!(
@@ -188,6 +209,7 @@ impl NearFungibleTokenManager for AppchainAnchor {
     }
     //
     fn close_bridging_of_near_fungible_token(&mut self, symbol: String) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut near_fungible_tokens = self.near_fungible_tokens.get().unwrap();
         assert!(
@@ -199,6 +221,18 @@ impl NearFungibleTokenManager for AppchainAnchor {
         near_fungible_token.bridging_state = BridgingState::Closed;
         near_fungible_tokens.insert(&near_fungible_token);
     }
+    //
+    fn refresh_metadata_of_near_fungible_token(&mut self, symbol: String) {
+        self.assert_not_in_maintenance_mode();
+        let near_fungible_tokens = self.near_fungible_tokens.get().unwrap();
+        assert!(
+            near_fungible_tokens.contains(&symbol),
+            "Token '{}' is not registered.",
+            &symbol
+        );
+        let near_fungible_token = near_fungible_tokens.get(&symbol).unwrap();
+        self.fetch_metadata_of_near_fungible_token(near_fungible_token.contract_account);
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -208,6 +242,24 @@ enum NearFungibleTokenDepositMessage {
 }
 
 impl AppchainAnchor {
+    // Fetch the metadata of a NEP-141 token from its contract,
+    // the result will be cached in the resolver function.
+    fn fetch_metadata_of_near_fungible_token(&self, contract_account: AccountId) {
+        Promise::new(contract_account.clone())
+            .function_call(
+                "ft_metadata".to_string(),
+                Vec::new(),
+                0,
+                Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION),
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_near_fungible_token_metadata_fetching(contract_account),
+            );
+    }
     //
     pub fn internal_process_near_fungible_token_deposit(
         &mut self,
@@ -321,6 +373,13 @@ impl AppchainAnchor {
                     None => U128::from(0),
                 };
             near_fungible_tokens.insert(&near_fungible_token);
+            let receipt = self.create_withdrawal_receipt(
+                WithdrawalKind::BridgeUnlock,
+                &receiver_id_in_near,
+                &near_fungible_token.contract_account,
+                &receiver_id_in_near,
+                amount.0,
+            );
             ext_ft_core::ext(near_fungible_token.contract_account)
                 .with_attached_deposit(1)
                 .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
@@ -337,6 +396,7 @@ impl AppchainAnchor {
                             receiver_id_in_near.clone(),
                             amount.clone(),
                             appchain_message_nonce,
+                            Some(receipt.index),
                         ),
                 );
             processing_context.add_prepaid_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER));
@@ -367,11 +427,18 @@ impl FungibleTokenContractResolver for AppchainAnchor {
         receiver_id_in_near: AccountId,
         amount: U128,
         appchain_message_nonce: u32,
+        withdrawal_receipt_id: Option<U64>,
     ) {
         assert_self();
         match env::promise_result(0) {
             PromiseResult::NotReady => unreachable!(),
             PromiseResult::Successful(_) => {
+                if let Some(receipt_id) = withdrawal_receipt_id {
+                    self.update_withdrawal_receipt_status(
+                        receipt_id.0,
+                        WithdrawalReceiptStatus::Succeeded,
+                    );
+                }
                 let message = format!(
                     "Near fungible token '{}' with amount '{}' for appchain account '{}' is unlocked.",
                     symbol, amount.0, sender_id_in_appchain
@@ -384,6 +451,12 @@ impl FungibleTokenContractResolver for AppchainAnchor {
                 );
             }
             PromiseResult::Failed => {
+                if let Some(receipt_id) = withdrawal_receipt_id {
+                    self.update_withdrawal_receipt_status(
+                        receipt_id.0,
+                        WithdrawalReceiptStatus::Failed,
+                    );
+                }
                 let reason = format!(
                     "Maybe the receiver account '{}' is not exised, or it is not registered in '{}' token contract.",
                     &receiver_id_in_near, &symbol
@@ -401,4 +474,43 @@ impl FungibleTokenContractResolver for AppchainAnchor {
             }
         }
     }
+    //
+    fn resolve_near_fungible_token_metadata_fetching(&mut self, contract_account: AccountId) {
+        assert_self();
+        let metadata = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => {
+                near_sdk::serde_json::from_slice::<FungibleTokenMetadata>(&value).ok()
+            }
+            PromiseResult::Failed => None,
+        };
+        match metadata {
+            Some(metadata) => {
+                // The token may be unregistered (by changing its contract account)
+                // before the metadata is fetched.
+                let near_fungible_tokens = self.near_fungible_tokens.get().unwrap();
+                if let Some(near_fungible_token) =
+                    near_fungible_tokens.get_by_contract_account(&contract_account)
+                {
+                    if near_fungible_token.metadata.decimals != metadata.decimals {
+                        log!(
+                            "Decimals of token contract '{}' differs from the registered value.",
+                            contract_account
+                        );
+                    }
+                    self.near_fungible_token_metadata_mirrors.insert(
+                        &contract_account,
+                        &FungibleTokenMetadataMirror {
+                            metadata,
+                            fetched_timestamp: U64::from(env::block_timestamp()),
+                        },
+                    );
+                }
+            }
+            None => log!(
+                "Failed to fetch metadata of token contract '{}'.",
+                contract_account
+            ),
+        }
+    }
 }
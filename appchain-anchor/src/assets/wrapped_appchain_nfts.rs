@@ -154,6 +154,7 @@ impl WrappedAppchainNFTs {
 impl WrappedAppchainNFTManager for AppchainAnchor {
     //
     fn register_wrapped_appchain_nft(&mut self, class_id: String, metadata: NFTContractMetadata) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert!(
             env::storage_has_key(&StorageKey::WrappedAppchainNFTContractWasm.into_bytes()),
@@ -202,6 +203,7 @@ impl WrappedAppchainNFTManager for AppchainAnchor {
         class_id: String,
         metadata: NFTContractMetadata,
     ) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut wrapped_appchain_nfts = self.wrapped_appchain_nfts.get().unwrap();
         if let Some(mut wrapped_appchain_nft) = wrapped_appchain_nfts.get(&class_id) {
@@ -213,6 +215,7 @@ impl WrappedAppchainNFTManager for AppchainAnchor {
     }
     //
     fn open_bridging_of_wrapped_appchain_nft(&mut self, class_id: String) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut wrapped_appchain_nfts = self.wrapped_appchain_nfts.get().unwrap();
         if let Some(mut wrapped_appchain_nft) = wrapped_appchain_nfts.get(&class_id) {
@@ -230,6 +233,7 @@ impl WrappedAppchainNFTManager for AppchainAnchor {
     }
     //
     fn close_bridging_of_wrapped_appchain_nft(&mut self, class_id: String) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut wrapped_appchain_nfts = self.wrapped_appchain_nfts.get().unwrap();
         if let Some(mut wrapped_appchain_nft) = wrapped_appchain_nfts.get(&class_id) {
@@ -1,5 +1,6 @@
 use core::convert::TryFrom;
 use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use near_contract_standards::storage_management::StorageBalance;
 use near_sdk::json_types::I128;
 
 use crate::{
@@ -15,6 +16,14 @@ pub trait WrappedAppchainTokenContractResolver {
         receiver_id_in_appchain: String,
         amount: U128,
     );
+    /// Resolver for burning wrapped appchain token deposited by `ft_transfer_call`,
+    /// return the amount to be refunded to the sender.
+    fn resolve_wrapped_appchain_token_deposit_burning(
+        &mut self,
+        sender_id_in_near: AccountId,
+        receiver_id_in_appchain: String,
+        amount: U128,
+    ) -> U128;
     /// Resolver for minting wrapped appchain token
     fn resolve_wrapped_appchain_token_minting(
         &mut self,
@@ -23,6 +32,13 @@ pub trait WrappedAppchainTokenContractResolver {
         amount: U128,
         appchain_message_nonce: u32,
     );
+    /// Resolver for querying storage balance of reward recipient
+    fn resolve_storage_balance_of_reward_recipient(
+        &mut self,
+        sponsor_id: AccountId,
+        account_id: AccountId,
+        amount: U128,
+    );
 }
 
 impl Default for WrappedAppchainToken {
@@ -61,6 +77,14 @@ impl WrappedAppchainToken {
     }
 }
 
+impl WrappedAppchainTokenIssuance {
+    /// The net issuance tracked by this contract, including the premined balance.
+    pub fn tracked_issuance(&self, premined_balance: Balance) -> Balance {
+        (premined_balance + self.minted_by_era_payouts.0 + self.minted_by_bridge_locks.0)
+            .saturating_sub(self.burnt_by_bridge_transfers.0)
+    }
+}
+
 #[near_bindgen]
 impl WrappedAppchainTokenManager for AppchainAnchor {
     //
@@ -79,6 +103,10 @@ impl WrappedAppchainTokenManager for AppchainAnchor {
             "Only '{}' can call this function.",
             contract_account
         );
+        assert!(
+            wrapped_appchain_token.premined_beneficiary.is_none(),
+            "Basedata of wrapped appchain token has already been synced."
+        );
         wrapped_appchain_token.metadata = metadata;
         wrapped_appchain_token.premined_beneficiary = Some(premined_beneficiary);
         wrapped_appchain_token.premined_balance = premined_balance;
@@ -87,13 +115,19 @@ impl WrappedAppchainTokenManager for AppchainAnchor {
     }
     //
     fn set_account_of_wrapped_appchain_token(&mut self, contract_account: AccountId) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
+        assert!(
+            wrapped_appchain_token.premined_beneficiary.is_none(),
+            "Cannot change the contract account after basedata of wrapped appchain token is synced."
+        );
         wrapped_appchain_token.contract_account = Some(contract_account);
         self.wrapped_appchain_token.set(&wrapped_appchain_token);
     }
     //
     fn set_total_supply_of_wrapped_appchain_token(&mut self, total_supply: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
         wrapped_appchain_token.total_supply = total_supply;
@@ -101,13 +135,22 @@ impl WrappedAppchainTokenManager for AppchainAnchor {
     }
     //
     fn set_price_of_wrapped_appchain_token(&mut self, price: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_token_price_maintainer();
         let mut wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
+        if !self.check_price_update(
+            PricedToken::WrappedAppchainToken,
+            wrapped_appchain_token.price_in_usd.0,
+            price.0,
+        ) {
+            return;
+        }
         wrapped_appchain_token.price_in_usd = price;
         self.wrapped_appchain_token.set(&wrapped_appchain_token);
     }
     //
     fn burn_wrapped_appchain_token(&self, receiver_id: String, amount: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_asset_transfer_is_not_paused();
         self.assert_contract_account_of_wrapped_appchain_token_is_set();
         let wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
@@ -147,9 +190,166 @@ impl WrappedAppchainTokenManager for AppchainAnchor {
                     ),
             );
     }
+    //
+    #[payable]
+    fn pay_storage_for_reward_recipient(&mut self, account_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_contract_account_of_wrapped_appchain_token_is_set();
+        let amount = env::attached_deposit();
+        assert!(
+            amount >= STORAGE_DEPOSIT_FOR_NEP141_TOEKN,
+            "Attached deposit should not be less than {}.",
+            STORAGE_DEPOSIT_FOR_NEP141_TOEKN
+        );
+        let wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
+        // query storage balance of the account in wrapped appchain token contract
+        #[derive(near_sdk::serde::Serialize)]
+        #[serde(crate = "near_sdk::serde")]
+        struct Args {
+            account_id: AccountId,
+        }
+        let args = Args {
+            account_id: account_id.clone(),
+        };
+        let args = near_sdk::serde_json::to_vec(&args)
+            .expect("Failed to serialize the cross contract args using JSON.");
+        Promise::new(wrapped_appchain_token.contract_account.unwrap())
+            .function_call(
+                "storage_balance_of".to_string(),
+                args,
+                0,
+                Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION),
+            )
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(
+                        Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION + T_GAS_FOR_STORAGE_DEPOSIT),
+                    )
+                    .with_unused_gas_weight(0)
+                    .resolve_storage_balance_of_reward_recipient(
+                        env::predecessor_account_id(),
+                        account_id,
+                        U128::from(amount),
+                    ),
+            );
+    }
 }
 
 impl AppchainAnchor {
+    // Record the attested issuance of wrapped appchain token, and compare it with
+    // the issuance tracked by this contract. Return whether they are divergent.
+    pub fn record_attested_issuance_of_wrapped_appchain_token(
+        &mut self,
+        nonce: u32,
+        attested_issuance: U128,
+    ) -> bool {
+        let mut issuance = self.wrapped_appchain_token_issuance.get().unwrap();
+        let tracked_issuance = issuance.tracked_issuance(
+            self.wrapped_appchain_token
+                .get()
+                .unwrap()
+                .premined_balance
+                .0,
+        );
+        let is_divergent = tracked_issuance != attested_issuance.0;
+        issuance.latest_attested_issuance = Some(AttestedIssuance {
+            nonce,
+            timestamp: U64::from(env::block_timestamp()),
+            attested_issuance,
+            tracked_issuance: U128::from(tracked_issuance),
+            is_divergent,
+        });
+        self.wrapped_appchain_token_issuance.set(&issuance);
+        is_divergent
+    }
+    // The deposited wrapped appchain token is burnt from the account of this contract,
+    // and it will be refunded to the sender (by `ft_resolve_transfer` of the token contract)
+    // if the burning fails.
+    pub fn internal_process_wrapped_appchain_token_deposit(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        deposit_message: FTDepositMessage,
+    ) -> PromiseOrValue<U128> {
+        let receiver_id_in_appchain = match deposit_message {
+            FTDepositMessage::BridgeToAppchain {
+                receiver_id_in_appchain,
+            } => receiver_id_in_appchain,
+            _ => panic!(
+                "Internal error: misuse of internal function 'internal_process_wrapped_appchain_token_deposit'."
+            ),
+        };
+        AccountIdInAppchain::new(
+            Some(receiver_id_in_appchain.clone()),
+            &self.appchain_template_type,
+        )
+        .assert_valid();
+        #[derive(near_sdk::serde::Serialize)]
+        #[serde(crate = "near_sdk::serde")]
+        struct Args {
+            account_id: AccountId,
+            amount: U128,
+        }
+        let args = Args {
+            account_id: env::current_account_id(),
+            amount,
+        };
+        let args = near_sdk::serde_json::to_vec(&args)
+            .expect("Failed to serialize the cross contract args using JSON.");
+        PromiseOrValue::Promise(
+            Promise::new(env::predecessor_account_id())
+                .function_call(
+                    "burn".to_string(),
+                    args,
+                    1,
+                    Gas::ONE_TERA.mul(T_GAS_FOR_BURN_FUNGIBLE_TOKEN),
+                )
+                .then(
+                    ext_self::ext(env::current_account_id())
+                        .with_attached_deposit(0)
+                        .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                        .with_unused_gas_weight(0)
+                        .resolve_wrapped_appchain_token_deposit_burning(
+                            sender_id,
+                            receiver_id_in_appchain,
+                            amount,
+                        ),
+                ),
+        )
+    }
+    // Update the balance and issuance of wrapped appchain token for the burnt amount,
+    // and generate the appchain notification for the appchain to mint native token.
+    fn record_wrapped_appchain_token_burnt(
+        &mut self,
+        sender_id_in_near: &AccountId,
+        receiver_id_in_appchain: &String,
+        amount: U128,
+    ) {
+        let mut wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
+        wrapped_appchain_token.changed_balance = I128::from(
+            wrapped_appchain_token.changed_balance.0 - i128::try_from(amount.0).unwrap(),
+        );
+        self.wrapped_appchain_token.set(&wrapped_appchain_token);
+        let mut issuance = self.wrapped_appchain_token_issuance.get().unwrap();
+        issuance.burnt_by_bridge_transfers.0 += amount.0;
+        self.wrapped_appchain_token_issuance.set(&issuance);
+        let appchain_notification_history = self.internal_append_appchain_notification(
+            AppchainNotification::WrappedAppchainTokenBurnt {
+                sender_id_in_near: sender_id_in_near.clone(),
+                receiver_id_in_appchain: receiver_id_in_appchain.clone(),
+                amount: U128::from(amount),
+            },
+        );
+        log!(
+            "Wrapped appchain token burnt in contract '{}' by '{}' for '{}' of appchain. Amount: '{}', Crosschain notification index: '{}'.",
+            &wrapped_appchain_token.contract_account.unwrap(),
+            sender_id_in_near,
+            receiver_id_in_appchain,
+            &amount.0,
+            &appchain_notification_history.index.0
+        );
+    }
     //
     pub fn internal_mint_wrapped_appchain_token(
         &mut self,
@@ -230,38 +430,52 @@ impl WrappedAppchainTokenContractResolver for AppchainAnchor {
         amount: U128,
     ) {
         assert_self();
-        let mut wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
         match env::promise_result(0) {
             PromiseResult::NotReady => unreachable!(),
             PromiseResult::Successful(_) => {
-                wrapped_appchain_token.changed_balance = I128::from(
-                    wrapped_appchain_token.changed_balance.0 - i128::try_from(amount.0).unwrap(),
-                );
-                self.wrapped_appchain_token.set(&wrapped_appchain_token);
-                let appchain_notification_history = self.internal_append_appchain_notification(
-                    AppchainNotification::WrappedAppchainTokenBurnt {
-                        sender_id_in_near: sender_id_in_near.clone(),
-                        receiver_id_in_appchain: receiver_id_in_appchain.clone(),
-                        amount: U128::from(amount),
-                    },
+                self.record_wrapped_appchain_token_burnt(
+                    &sender_id_in_near,
+                    &receiver_id_in_appchain,
+                    amount,
                 );
+            }
+            PromiseResult::Failed => {
                 log!(
-                    "Wrapped appchain token burnt in contract '{}' by '{}' for '{}' of appchain. Amount: '{}', Crosschain notification index: '{}'.",
-                    &wrapped_appchain_token.contract_account.unwrap(),
+                    "Failed to burn wrapped appchain token in contract '{}' by '{}' for '{}' in appchain. Amount: '{}'",
+                    &self.wrapped_appchain_token.get().unwrap().contract_account.unwrap(),
+                    &sender_id_in_near,
+                    &receiver_id_in_appchain,
+                    &amount.0
+                );
+            }
+        }
+    }
+    //
+    fn resolve_wrapped_appchain_token_deposit_burning(
+        &mut self,
+        sender_id_in_near: AccountId,
+        receiver_id_in_appchain: String,
+        amount: U128,
+    ) -> U128 {
+        assert_self();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => {
+                self.record_wrapped_appchain_token_burnt(
                     &sender_id_in_near,
                     &receiver_id_in_appchain,
-                    &amount.0,
-                    &appchain_notification_history.index.0
+                    amount,
                 );
+                U128::from(0)
             }
             PromiseResult::Failed => {
                 log!(
-                    "Failed to burn wrapped appchain token in contract '{}' by '{}' for '{}' in appchain. Amount: '{}'",
-                    &wrapped_appchain_token.contract_account.unwrap(),
+                    "Failed to burn wrapped appchain token deposited by '{}' for '{}' in appchain. Return deposit. Amount: '{}'",
                     &sender_id_in_near,
                     &receiver_id_in_appchain,
                     &amount.0
                 );
+                amount
             }
         }
     }
@@ -282,6 +496,13 @@ impl WrappedAppchainTokenContractResolver for AppchainAnchor {
                     wrapped_appchain_token.changed_balance.0 + i128::try_from(amount.0).unwrap(),
                 );
                 self.wrapped_appchain_token.set(&wrapped_appchain_token);
+                // The reward of eras is minted without a sender in appchain.
+                let mut issuance = self.wrapped_appchain_token_issuance.get().unwrap();
+                match sender_id_in_appchain {
+                    Some(_) => issuance.minted_by_bridge_locks.0 += amount.0,
+                    None => issuance.minted_by_era_payouts.0 += amount.0,
+                }
+                self.wrapped_appchain_token_issuance.set(&issuance);
                 if let Some(sender_id) = sender_id_in_appchain {
                     // Only generate appchain message processing result in cross-chain transfer case
                     let message = format!(
@@ -310,5 +531,69 @@ impl WrappedAppchainTokenContractResolver for AppchainAnchor {
                 );
             }
         }
+    } //
+    fn resolve_storage_balance_of_reward_recipient(
+        &mut self,
+        sponsor_id: AccountId,
+        account_id: AccountId,
+        amount: U128,
+    ) {
+        assert_self();
+        let storage_balance = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => {
+                near_sdk::serde_json::from_slice::<Option<StorageBalance>>(&value)
+                    .unwrap_or_default()
+            }
+            PromiseResult::Failed => {
+                log!(
+                    "Failed to query storage balance of '{}' in wrapped appchain token contract.",
+                    &account_id
+                );
+                Promise::new(sponsor_id).transfer(amount.0);
+                return;
+            }
+        };
+        if storage_balance.is_some() {
+            log!(
+                "Account '{}' is already registered in wrapped appchain token contract.",
+                &account_id
+            );
+            Promise::new(sponsor_id).transfer(amount.0);
+            return;
+        }
+        #[derive(near_sdk::serde::Serialize)]
+        #[serde(crate = "near_sdk::serde")]
+        struct Args {
+            account_id: AccountId,
+            registration_only: bool,
+        }
+        let args = Args {
+            account_id: account_id.clone(),
+            registration_only: true,
+        };
+        let args = near_sdk::serde_json::to_vec(&args)
+            .expect("Failed to serialize the cross contract args using JSON.");
+        Promise::new(
+            self.wrapped_appchain_token
+                .get()
+                .unwrap()
+                .contract_account
+                .unwrap(),
+        )
+        .function_call(
+            "storage_deposit".to_string(),
+            args,
+            STORAGE_DEPOSIT_FOR_NEP141_TOEKN,
+            Gas::ONE_TERA.mul(T_GAS_FOR_STORAGE_DEPOSIT),
+        );
+        log!(
+            "Storage deposit of '{}' in wrapped appchain token contract is paid by '{}'.",
+            &account_id,
+            &sponsor_id
+        );
+        if amount.0 > STORAGE_DEPOSIT_FOR_NEP141_TOEKN {
+            Promise::new(sponsor_id).transfer(amount.0 - STORAGE_DEPOSIT_FOR_NEP141_TOEKN);
+        }
     }
 }
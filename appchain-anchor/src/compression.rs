@@ -0,0 +1,98 @@
+use crate::*;
+
+/// The compression algorithm of the encoded appchain messages submitted by relayers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum PayloadCompression {
+    /// The LZ4 block format (without frame header).
+    Lz4Block,
+}
+
+/// The names of variants of `PayloadCompression`, which should be updated along with the enum.
+pub const PAYLOAD_COMPRESSION_VARIANTS: [&str; 1] = ["Lz4Block"];
+
+impl PayloadCompression {
+    /// Decompress the given payload, the size of decompressed data is limited
+    /// by `maximum_output_size`.
+    pub fn decompress(&self, input: &[u8], maximum_output_size: usize) -> Result<Vec<u8>, String> {
+        match self {
+            PayloadCompression::Lz4Block => decompress_lz4_block(input, maximum_output_size),
+        }
+    }
+}
+
+// Read the extended length of a literal run or a match in LZ4 block format.
+fn read_lz4_length(input: &[u8], position: &mut usize, length: &mut usize) -> Result<(), String> {
+    loop {
+        let byte = *input
+            .get(*position)
+            .ok_or(format!("Unexpected end of input at '{}'.", position))?;
+        *position += 1;
+        *length += byte as usize;
+        if byte != 255 {
+            return Ok(());
+        }
+    }
+}
+
+// Decompress data in LZ4 block format.
+//
+// The whole block consists of sequences, each of which starts with a token byte,
+// followed by the literals and then the offset (2 bytes, little endian) of the match.
+// The last sequence only contains literals.
+fn decompress_lz4_block(input: &[u8], maximum_output_size: usize) -> Result<Vec<u8>, String> {
+    let mut output = Vec::<u8>::new();
+    let mut position = 0;
+    while position < input.len() {
+        let token = input[position];
+        position += 1;
+        //
+        let mut literal_length = (token >> 4) as usize;
+        if literal_length == 15 {
+            read_lz4_length(input, &mut position, &mut literal_length)?;
+        }
+        if position + literal_length > input.len() {
+            return Err(format!(
+                "Literals at '{}' exceed the end of input.",
+                position
+            ));
+        }
+        if output.len() + literal_length > maximum_output_size {
+            return Err(format!(
+                "The size of decompressed data exceeds the limit '{}'.",
+                maximum_output_size
+            ));
+        }
+        output.extend_from_slice(&input[position..position + literal_length]);
+        position += literal_length;
+        if position == input.len() {
+            break;
+        }
+        //
+        if position + 2 > input.len() {
+            return Err(format!("Unexpected end of input at '{}'.", position));
+        }
+        let offset = u16::from_le_bytes([input[position], input[position + 1]]) as usize;
+        position += 2;
+        if offset == 0 || offset > output.len() {
+            return Err(format!("Invalid match offset '{}'.", offset));
+        }
+        let mut match_length = (token & 0x0f) as usize;
+        if match_length == 15 {
+            read_lz4_length(input, &mut position, &mut match_length)?;
+        }
+        match_length += 4;
+        if output.len() + match_length > maximum_output_size {
+            return Err(format!(
+                "The size of decompressed data exceeds the limit '{}'.",
+                maximum_output_size
+            ));
+        }
+        // The match may overlap with the bytes being copied, so copy them one by one.
+        let match_start = output.len() - offset;
+        for i in 0..match_length {
+            output.push(output[match_start + i]);
+        }
+    }
+    Ok(output)
+}
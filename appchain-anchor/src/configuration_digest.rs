@@ -0,0 +1,80 @@
+use crate::{appchain_messages::PAYLOAD_TYPE_VARIANTS, *};
+use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
+use serde_json::{Map, Value};
+
+/// The configuration of this contract which is expected to be the same across deployments
+/// with the same settings. The values which change along with the running of the appchain
+/// (e.g. token prices and supplies, pausing flags) are not included.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct AnchorConfiguration {
+    anchor_version: String,
+    appchain_template_type: AppchainTemplateType,
+    oct_token_contract: AccountId,
+    wrapped_appchain_token_contract: Option<AccountId>,
+    wrapped_appchain_token_metadata: FungibleTokenMetadata,
+    appchain_settings: AppchainSettings,
+    anchor_settings: AnchorSettings,
+    protocol_settings: ProtocolSettings,
+    appchain_message_handler_configs: BTreeMap<String, AppchainMessageHandlerConfig>,
+}
+
+impl AppchainAnchor {
+    // Serialize the configuration into a canonical JSON string, in which the keys of all
+    // objects are sorted, and calculate the hash of it.
+    pub fn internal_get_configuration_digest(&self) -> AnchorConfigurationDigest {
+        let wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
+        let appchain_message_handler_configs = PAYLOAD_TYPE_VARIANTS
+            .iter()
+            .map(|name| {
+                let payload_type: PayloadType =
+                    serde_json::from_value(Value::String(name.to_string())).unwrap();
+                (
+                    name.to_string(),
+                    self.get_appchain_message_handler_config_of(&payload_type),
+                )
+            })
+            .collect();
+        let configuration = AnchorConfiguration {
+            anchor_version: ANCHOR_VERSION.to_string(),
+            appchain_template_type: self.appchain_template_type.clone(),
+            oct_token_contract: self.oct_token.get().unwrap().contract_account,
+            wrapped_appchain_token_contract: wrapped_appchain_token.contract_account,
+            wrapped_appchain_token_metadata: wrapped_appchain_token.metadata,
+            appchain_settings: self.appchain_settings.get().unwrap(),
+            anchor_settings: self.anchor_settings.get().unwrap(),
+            protocol_settings: self.protocol_settings.get().unwrap(),
+            appchain_message_handler_configs,
+        };
+        let canonical_json =
+            canonicalize_json_value(serde_json::to_value(&configuration).unwrap()).to_string();
+        AnchorConfigurationDigest {
+            hash: format!(
+                "0x{}",
+                hex::encode(env::keccak256(canonical_json.as_bytes()))
+            ),
+            canonical_json,
+        }
+    }
+}
+
+// Sort the keys of all objects in a JSON value recursively, regardless of
+// whether the order of insertion is preserved by `serde_json`.
+fn canonicalize_json_value(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize_json_value(value)))
+                    .collect::<Map<String, Value>>(),
+            )
+        }
+        Value::Array(values) => {
+            Value::Array(values.into_iter().map(canonicalize_json_value).collect())
+        }
+        value => value,
+    }
+}
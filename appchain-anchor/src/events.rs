@@ -0,0 +1,69 @@
+use crate::*;
+
+/// The standard name of the events emitted by this contract, in the format of
+/// [NEP-297](https://github.com/near/NEPs/blob/master/neps/nep-0297.md).
+pub const EVENT_STANDARD: &str = "appchain_anchor";
+/// The version of the schema of the events. It must be bumped on any change
+/// of the name or the data of an event which is not backward compatible.
+pub const EVENT_STANDARD_VERSION: &str = "1.0.0";
+
+/// The events emitted by this contract. The data of each event is the same as
+/// the record of it which can be queried by the view functions of this contract.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum Nep297Event<'a> {
+    StakingFactRecorded(&'a StakingHistory),
+    AppchainNotificationRecorded(&'a AppchainNotificationHistory),
+    AppchainMessageProcessed(&'a AppchainMessageProcessingResult),
+    EraRewardDistributed {
+        era_number: U64,
+        era_reward: U128,
+        reward_rounding: Option<RewardRoundingOfEra>,
+    },
+    AppchainSettingsChanged(&'a AppchainSettings),
+    AnchorSettingsChanged(&'a AnchorSettings),
+    ProtocolSettingsChanged(&'a ProtocolSettings),
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct EventLog<'a> {
+    standard: &'static str,
+    version: &'static str,
+    #[serde(flatten)]
+    event: &'a Nep297Event<'a>,
+}
+
+impl Nep297Event<'_> {
+    /// Emit the event as a log with prefix `EVENT_JSON:`.
+    pub fn emit(&self) {
+        log!(
+            "EVENT_JSON:{}",
+            serde_json::to_string(&EventLog {
+                standard: EVENT_STANDARD,
+                version: EVENT_STANDARD_VERSION,
+                event: self,
+            })
+            .unwrap()
+        );
+    }
+}
+
+impl AppchainAnchor {
+    // Store the changed appchain settings and emit the event of it.
+    pub fn internal_set_appchain_settings(&mut self, appchain_settings: &AppchainSettings) {
+        self.appchain_settings.set(appchain_settings);
+        Nep297Event::AppchainSettingsChanged(appchain_settings).emit();
+    }
+    // Store the changed anchor settings and emit the event of it.
+    pub fn internal_set_anchor_settings(&mut self, anchor_settings: &AnchorSettings) {
+        self.anchor_settings.set(anchor_settings);
+        Nep297Event::AnchorSettingsChanged(anchor_settings).emit();
+    }
+    // Store the changed protocol settings and emit the event of it.
+    pub fn internal_set_protocol_settings(&mut self, protocol_settings: &ProtocolSettings) {
+        self.protocol_settings.set(protocol_settings);
+        Nep297Event::ProtocolSettingsChanged(protocol_settings).emit();
+    }
+}
@@ -0,0 +1,75 @@
+use crate::*;
+
+impl AppchainAnchor {
+    // Check the initialization of the sub-structures of this contract. The settings and
+    // the histories are created in function `new` (or by state migration), while the token
+    // configs and the other settings are set by the owner (or synced by the token contract)
+    // after the deployment.
+    pub fn internal_get_initialization_status(&self) -> AnchorInitializationStatus {
+        let settings_are_created = self.appchain_settings.is_some()
+            && self.anchor_settings.is_some()
+            && self.protocol_settings.is_some()
+            && self.permissionless_actions_status.is_some();
+        let histories_are_created = self.validator_set_histories.is_some()
+            && self.next_validator_set.is_some()
+            && self.staking_histories.is_some()
+            && self.user_staking_histories.is_some()
+            && self.appchain_notification_histories.is_some()
+            && self.appchain_messages.is_some()
+            && self.appchain_challenges.is_some()
+            && self.reward_distribution_records.is_some();
+        let oct_token_price_is_set = self
+            .oct_token
+            .get()
+            .map_or(false, |oct_token| oct_token.price_in_usd.0 > 0);
+        let wrapped_appchain_token = self.wrapped_appchain_token.get();
+        let wrapped_appchain_token_contract_is_set = wrapped_appchain_token
+            .as_ref()
+            .map_or(false, |token| token.contract_account.is_some());
+        let wrapped_appchain_token_basedata_is_synced = wrapped_appchain_token
+            .as_ref()
+            .map_or(false, |token| token.premined_beneficiary.is_some());
+        let appchain_settings = self.appchain_settings.get();
+        let appchain_endpoints_are_set = appchain_settings.as_ref().map_or(false, |settings| {
+            !settings.rpc_endpoint.is_empty() && !settings.subql_endpoint.is_empty()
+        });
+        let era_reward_is_set = appchain_settings
+            .as_ref()
+            .map_or(false, |settings| settings.era_reward.0 > 0);
+        let anchor_settings = self.anchor_settings.get();
+        let token_price_maintainer_account_is_set =
+            anchor_settings.as_ref().map_or(false, |settings| {
+                settings.token_price_maintainer_account.is_some()
+            });
+        let relayer_is_set = self
+            .protocol_settings
+            .get()
+            .map_or(false, |settings| settings.minimum_relayer_bond.0 > 0)
+            || anchor_settings
+                .as_ref()
+                .map_or(false, |settings| settings.relayer_account.is_some());
+        let beefy_light_client_is_initialized = self.beefy_light_client_state.is_some();
+        AnchorInitializationStatus {
+            is_fully_initialized: settings_are_created
+                && histories_are_created
+                && oct_token_price_is_set
+                && wrapped_appchain_token_contract_is_set
+                && wrapped_appchain_token_basedata_is_synced
+                && appchain_endpoints_are_set
+                && era_reward_is_set
+                && token_price_maintainer_account_is_set
+                && relayer_is_set
+                && beefy_light_client_is_initialized,
+            settings_are_created,
+            histories_are_created,
+            oct_token_price_is_set,
+            wrapped_appchain_token_contract_is_set,
+            wrapped_appchain_token_basedata_is_synced,
+            appchain_endpoints_are_set,
+            era_reward_is_set,
+            token_price_maintainer_account_is_set,
+            relayer_is_set,
+            beefy_light_client_is_initialized,
+        }
+    }
+}
@@ -9,6 +9,11 @@ use crate::*;
 pub trait AnchorViewer {
     /// Get version of this contract.
     fn get_anchor_version(&self) -> String;
+    /// Get version of the JSON API of view functions of this contract.
+    fn get_view_api_version(&self) -> u32;
+    /// Get the metadata of this contract, including the version, the supported messages
+    /// and payload types, and the feature flags, for tools to adapt to the deployment.
+    fn contract_metadata(&self) -> AnchorContractMetadata;
     /// Get the template type of corresponding appchain.
     fn get_appchain_template_type(&self) -> AppchainTemplateType;
     /// Get the public key of owner account.
@@ -20,24 +25,102 @@ pub trait AnchorViewer {
     fn get_appchain_settings(&self) -> AppchainSettings;
     /// Get protocol settings detail.
     fn get_protocol_settings(&self) -> ProtocolSettings;
+    /// Get the snapshot of protocol settings which are effective in a certain era.
+    fn get_protocol_settings_of_era(&self, era_number: U64) -> Option<ProtocolSettings>;
     /// Get info of OCT token.
     fn get_oct_token(&self) -> OctToken;
     /// Get info of wrapped appchain token.
     fn get_wrapped_appchain_token(&self) -> WrappedAppchainToken;
+    /// Get the market value (in USD) of the OCT token staked in the next validator set.
+    fn get_market_value_of_staked_oct_token(&self) -> U128;
     /// Get info of near fungible tokens which has registered in this contract.
     fn get_near_fungible_tokens(&self) -> Vec<NearFungibleToken>;
+    /// Get the metadata of a NEAR fungible token fetched from its contract.
+    fn get_metadata_mirror_of_near_fungible_token(
+        &self,
+        symbol: String,
+    ) -> Option<FungibleTokenMetadataMirror>;
+    /// Get the latest attestation of the balances of wrapped assets issued in appchain.
+    fn get_latest_wrapped_asset_balance_attestation(
+        &self,
+    ) -> Option<WrappedAssetBalanceAttestation>;
+    /// Get the accumulated issuance of wrapped appchain token by sources, along with
+    /// the latest issuance attested by appchain.
+    fn get_wrapped_appchain_token_issuance(&self) -> WrappedAppchainTokenIssuance;
     /// Get info of wrapped appchain NFT contracts which has registered in this contract.
     fn get_wrapped_appchain_nfts(&self) -> Vec<WrappedAppchainNFT>;
     /// Get state of corresponding appchain.
     fn get_appchain_state(&self) -> AppchainState;
+    /// Get the record of the termination of corresponding appchain, which includes
+    /// the summary of the final state of the appchain.
+    fn get_appchain_termination(&self) -> Option<AppchainTermination>;
     /// Get current status of anchor.
     fn get_anchor_status(&self) -> AnchorStatus;
+    /// Get the checksum of key aggregates of the state of anchor, which changes along with
+    /// the staking actions, the switching of era and the appchain notifications and messages.
+    fn get_state_checksum(&self) -> AnchorStateChecksum;
+    /// Get the initialization status of the sub-structures (settings, token configs and
+    /// histories) of anchor, for detecting a partially initialized deployment.
+    fn get_initialization_status(&self) -> AnchorInitializationStatus;
+    /// Get all settings of anchor as a canonical JSON string along with its hash,
+    /// for diffing the configurations of multiple anchors.
+    fn get_configuration_digest(&self) -> AnchorConfigurationDigest;
     /// Get validator set history info.
     fn get_validator_set_info_of(&self, era_number: U64) -> Option<ValidatorSetInfo>;
+    /// Get the merkle proof of membership of an account in the validator set of an era,
+    /// return `None` if the account is not a validator of the era.
+    fn verify_validator_of_era(
+        &self,
+        era_number: U64,
+        account_id: AccountId,
+    ) -> Option<ValidatorMembershipProof>;
+    /// Get the amount of appchain-native token allocated to a validator in booting state.
+    fn get_bootstrap_allocation_of(&self, validator_id: AccountId) -> U128;
+    ///
+    fn get_total_bootstrap_allocation(&self) -> U128;
     /// Get processing status of validator set of era.
     fn get_processing_status_of(&self, era_number: U64) -> Option<ValidatorSetProcessingStatus>;
+    /// Get the summaries of eras by start era number and quantity.
+    /// If the param `quantity` is omitted, up to 50 records will be returned.
+    fn get_era_summaries(&self, start_era: U64, quantity: Option<U64>) -> Vec<EraSummary>;
+    /// Get the drift of the observed duration of a closed era from the expected one.
+    /// Return `None` if the expected era duration is not set or the era is not closed yet.
+    fn get_era_duration_drift(&self, era_number: U64) -> Option<EraDurationDrift>;
+    /// Preview the reward distribution of a certain era without changing any state,
+    /// starting from the validator with index `start_index` in the era.
+    /// If the param `quantity` is omitted, up to 10 validators will be returned.
+    fn preview_reward_distribution(
+        &self,
+        era_number: U64,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<ValidatorRewardPreview>;
+    /// Get the summary of the delegations of a delegator, including the expected reward
+    /// and the slash exposure of each delegation, calculated from the data of the latest era.
+    fn get_delegator_exposure(&self, delegator_id: AccountId) -> Option<DelegatorExposure>;
+    /// Recalculate the reward distribution of a certain era (whose reward is distributed)
+    /// and report the divergences from the stored rewards, starting from the validator with
+    /// index `start_index` in the era. If the param `quantity` is omitted, up to 10 validators
+    /// will be audited.
+    fn audit_era_distribution(
+        &self,
+        era_number: U64,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Option<EraDistributionAudit>;
+    /// Get the index range of a certain kind of history-like data stored in anchor.
+    fn get_index_range_of(&self, history_kind: HistoryKind) -> IndexRange;
     /// Get the index range of staking histories stored in anchor.
     fn get_index_range_of_staking_history(&self) -> IndexRange;
+    /// Get staking histories in the given direction, starting from (and including) the
+    /// given index, or from the earliest (latest) one if the param `start_index` is omitted.
+    /// If the param `quantity` is omitted, up to 50 histories will be returned.
+    fn get_staking_histories_in_order(
+        &self,
+        direction: SortDirection,
+        start_index: Option<U64>,
+        quantity: Option<U64>,
+    ) -> Vec<StakingHistory>;
     /// Get staking history by start index and quantity.
     /// If the param `quantity` is omitted, up to 50 events will be returned.
     fn get_staking_histories(&self, start_index: U64, quantity: Option<U64>)
@@ -47,8 +130,14 @@ pub trait AnchorViewer {
     /// If the paran `index` is smaller than the start index, or bigger than the end index
     /// stored in anchor, or there is no history in anchor yet, `Option::None` will be returned.
     fn get_staking_history(&self, index: Option<U64>) -> Option<StakingHistory>;
+    /// Get the archive of the original staking histories which are merged into
+    /// the staking history of a certain index.
+    fn get_compacted_staking_histories(&self, index: U64) -> Option<CompactedStakingHistories>;
     /// Get the index range of appchain notification histories stored in anchor.
     fn get_index_range_of_appchain_notification_history(&self) -> IndexRange;
+    /// Get the latest appchain notification acknowledged by appchain, and the count of
+    /// appchain notifications which are not acknowledged yet.
+    fn get_appchain_notification_acknowledgment(&self) -> AppchainNotificationAcknowledgment;
     /// Get appchain notification by index.
     /// If the param `index `is omitted, the latest notification will be returned.
     /// If the paran `index` is smaller than the start index, or bigger than the end index
@@ -64,8 +153,43 @@ pub trait AnchorViewer {
         start_index: U64,
         quantity: Option<U64>,
     ) -> Vec<AppchainNotificationHistory>;
+    /// Get the anchor events (appchain notifications) of the given classes, starting from
+    /// a certain index. At most 100 histories are scanned in a call, use the `next_index`
+    /// of the result to continue. If the param `filters` is omitted, all events are returned.
+    fn get_anchor_events_since(
+        &self,
+        index: U64,
+        filters: Option<Vec<AnchorEventClass>>,
+    ) -> AnchorEventsPage;
     /// Get the validator list of a certain era.
     fn get_validator_list_of(&self, era_number: Option<U64>) -> Vec<AppchainValidator>;
+    /// Get the hints of duties (e.g. the share of block production slots) of validators
+    /// in a certain era. Return `None` if the appchain template does not support it
+    /// or the validator set of the era is not ready.
+    fn get_duty_schedule_of(&self, era_number: U64) -> Option<DutyScheduleOfEra>;
+    /// Get a page of the validators in the next validator set, sorted by the given key
+    /// and direction. The param `cursor` is the `next_cursor` of the previous page.
+    /// If the param `quantity` is omitted, up to 50 validators will be returned.
+    fn get_sorted_validators(
+        &self,
+        sort_key: ListSortKey,
+        direction: SortDirection,
+        cursor: Option<ListCursor>,
+        quantity: Option<U64>,
+    ) -> ListPage<AppchainValidator>;
+    /// Get a page of the delegators of a validator in the next validator set,
+    /// sorted by the given key and direction.
+    fn get_sorted_delegators_of(
+        &self,
+        validator_id: AccountId,
+        sort_key: ListSortKey,
+        direction: SortDirection,
+        cursor: Option<ListCursor>,
+        quantity: Option<U64>,
+    ) -> ListPage<AppchainDelegator>;
+    /// Get the validator list which is expected to be used in next era, that is
+    /// the next validator set excluding the validators which are scheduled to be unbonded.
+    fn get_next_validator_list(&self) -> Vec<AppchainValidator>;
     /// Get the delegators of a validator of a certain era.
     /// If the param `era_number` is omitted, the latest validator set will be used.
     fn get_delegators_of_validator_in_era(
@@ -75,6 +199,158 @@ pub trait AnchorViewer {
     ) -> Vec<AppchainDelegator>;
     /// Get unbonded stakes of an account.
     fn get_unbonded_stakes_of(&self, account_id: AccountId) -> Vec<UnbondedStake>;
+    /// Get the unbonded stakes of secondary token of a certain account.
+    fn get_unbonded_secondary_stakes_of(&self, account_id: AccountId) -> Vec<UnbondedStake>;
+    /// Get the account for receiving the commission of a certain validator.
+    fn get_commission_payout_account_of(&self, validator_id: AccountId) -> Option<AccountId>;
+    /// Get the era number in which a certain validator retires, if it announced the retirement.
+    fn get_retiring_era_number_of(&self, validator_id: AccountId) -> Option<U64>;
+    /// Get the fallback validator of a certain delegator.
+    fn get_fallback_validator_of(&self, delegator_id: AccountId) -> Option<AccountId>;
+    /// Get the commitment of the validator set of a certain era.
+    /// If the param `era_number` is omitted, the commitment of the latest era is returned.
+    fn get_validator_set_commitment(
+        &self,
+        era_number: Option<U64>,
+    ) -> Option<ValidatorSetCommitment>;
+    /// Get all registered peer anchors.
+    fn get_peer_anchors(&self) -> Vec<PeerAnchor>;
+    ///
+    fn get_peer_anchor(&self, anchor_account: AccountId) -> Option<PeerAnchor>;
+    /// Get the balance (of wrapped appchain token) of the insurance pool.
+    fn get_insurance_pool_balance(&self) -> U128;
+    /// Get the withdrawal allowlist of an account, including the pending change.
+    fn get_withdrawal_allowlist_of(&self, account_id: AccountId) -> Option<WithdrawalAllowlist>;
+    /// Get the reward distribution of a certain era which is held by the circuit breaker.
+    fn get_held_era_reward_distribution(
+        &self,
+        era_number: U64,
+    ) -> Option<HeldEraRewardDistribution>;
+    /// Get the proposals of validator set override by start index and quantity.
+    /// If the param `quantity` is omitted, up to 50 proposals will be returned.
+    fn get_validator_set_override_proposals(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<ValidatorSetOverrideProposal>;
+    /// Get the proposals of setting change requested by the appchain by start index
+    /// and quantity. If the param `quantity` is omitted, up to 50 proposals will be returned.
+    fn get_appchain_setting_change_proposals(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<AppchainSettingChangeProposal>;
+    /// Get the proposals of setting change requested by the appchain which are
+    /// waiting for the decision of the owner.
+    fn get_pending_appchain_setting_change_proposals(&self) -> Vec<AppchainSettingChangeProposal>;
+    /// Get the escrows of slashed stakes by start index and quantity.
+    /// If the param `quantity` is omitted, up to 50 escrows will be returned.
+    fn get_penalty_escrows(&self, start_index: U64, quantity: Option<U64>) -> Vec<PenaltyEscrow>;
+    /// Get the total amount held in the penalty escrows which are not settled.
+    fn get_penalty_escrow_balance(&self) -> U128;
+    /// Get the exports of stake balances from other staking contracts by start index
+    /// and quantity. If the param `quantity` is omitted, up to 50 exports will be returned.
+    fn get_stake_migration_exports(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<StakeMigrationExport>;
+    /// Get the remaining amount of the allocation for crediting the migrated stakes.
+    fn get_stake_migration_allocation(&self) -> U128;
+    /// Get the validator registrations queued while the appchain is booting by start index
+    /// and quantity. If the param `quantity` is omitted, up to 50 records will be returned.
+    fn get_queued_validator_registrations(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<QueuedValidatorRegistration>;
+    ///
+    fn get_staking_status(&self) -> StakingStatus;
+    /// Get the history of staking status changes by start index and quantity.
+    /// If the param `quantity` is omitted, up to 50 records will be returned.
+    fn get_staking_status_changes(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<StakingStatusChange>;
+    /// Get the current obligations (in OCT token) of this contract.
+    fn get_oct_obligations(&self) -> OctObligations;
+    /// Get the reconciliations of the balance of OCT token by start index and quantity.
+    /// If the param `quantity` is omitted, up to 50 records will be returned.
+    fn get_oct_balance_reconciliations(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<OctBalanceReconciliation>;
+    ///
+    fn get_latest_oct_balance_reconciliation(&self) -> Option<OctBalanceReconciliation>;
+    /// Get the bond of a certain relayer.
+    fn get_relayer_bond_of(&self, relayer_id: AccountId) -> Option<RelayerBond>;
+    /// Get the bonds of all relayers, including the unbonding ones.
+    fn get_relayer_bonds(&self) -> Vec<RelayerBond>;
+    /// Get the whitelisted relayers, whose bonds meet the minimum relayer bond.
+    fn get_whitelisted_relayers(&self) -> Vec<AccountId>;
+    /// Get the slashes of the bonds of relayers by start index and quantity.
+    /// If the param `quantity` is omitted, up to 50 records will be returned.
+    fn get_relayer_bond_slashes(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<RelayerBondSlash>;
+    /// Get the percent of the rewards which is tipped to the third party who withdraws
+    /// the rewards of a certain delegator.
+    fn get_reward_claim_tip_percent_of(&self, delegator_id: AccountId) -> Option<u16>;
+    /// Get the lock of the delegation of a delegator to a certain validator.
+    fn get_delegation_lock_of(
+        &self,
+        delegator_id: AccountId,
+        validator_id: AccountId,
+    ) -> Option<DelegationLock>;
+    /// Get the rounding of the reward distribution of a certain era,
+    /// including the remainder which is not distributed to validators and delegators.
+    fn get_reward_rounding_of_era(&self, era_number: U64) -> Option<RewardRoundingOfEra>;
+    /// Get the endpoints of the appchain node of a certain validator.
+    fn get_validator_endpoints_of(&self, validator_id: AccountId) -> Option<ValidatorEndpoints>;
+    /// Get the boot nodes of appchain, along with the visible endpoints of the validators
+    /// in the next validator set, for newly joining validators to peer with.
+    fn get_appchain_peers(&self) -> AppchainPeers;
+    /// Get all stake change intents by start index and quantity, for keepers to check the
+    /// conditions of them. If the param `quantity` is omitted, up to 50 intents will be returned.
+    fn get_stake_change_intents(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<StakeChangeIntent>;
+    /// Get the stake change intents of a certain delegator.
+    fn get_stake_change_intents_of(&self, delegator_id: AccountId) -> Vec<StakeChangeIntent>;
+    /// Get the payload types of appchain messages accepted in a certain appchain state,
+    /// `None` means all types are accepted.
+    fn get_accepted_payload_types_of_state(
+        &self,
+        appchain_state: AppchainState,
+    ) -> Option<Vec<PayloadType>>;
+    /// Get the config of the handler of appchain messages of a certain payload type.
+    fn get_appchain_message_handler_config(
+        &self,
+        payload_type: PayloadType,
+    ) -> AppchainMessageHandlerConfig;
+    /// Get the price updates rejected for violating the limits by start index and quantity.
+    /// If the param `quantity` is omitted, up to 50 records will be returned.
+    fn get_price_update_violations(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<PriceUpdateViolation>;
+    /// Get a certain insurance claim event.
+    fn get_insurance_claim_event(&self, index: U64) -> Option<InsuranceClaimEvent>;
+    /// Get the unclaimed compensation of a delegator in a certain insurance claim event.
+    fn get_insurance_compensation_of(&self, event_index: U64, delegator_id: AccountId) -> U128;
+    /// Get the balance of NEAR in the pool for reimbursing relayers.
+    fn get_relayer_reimbursement_pool_balance(&self) -> U128;
+    /// Get the unclaimed gas consumed by a relayer in a certain era.
+    fn get_relayer_gas_usage_of(&self, era_number: U64, relayer_id: AccountId) -> U64;
+    /// Get the total reimbursement (in NEAR) which a relayer can claim currently.
+    fn get_relayer_reimbursement_of(&self, relayer_id: AccountId) -> U128;
     /// Get validator rewards of a certain era range.
     fn get_validator_rewards_of(
         &self,
@@ -82,6 +358,13 @@ pub trait AnchorViewer {
         end_era: U64,
         validator_id: AccountId,
     ) -> Vec<RewardHistory>;
+    /// Get the vesting parts of the rewards of a validator which are not fully released yet.
+    fn get_validator_reward_vestings_of(
+        &self,
+        validator_id: AccountId,
+    ) -> Vec<ValidatorRewardVesting>;
+    /// Get the vested reward of a validator which can be withdrawn currently.
+    fn get_releasable_vested_reward_of(&self, validator_id: AccountId) -> U128;
     /// Get validator rewards of a certain era range.
     fn get_delegator_rewards_of(
         &self,
@@ -90,6 +373,9 @@ pub trait AnchorViewer {
         delegator_id: AccountId,
         validator_id: AccountId,
     ) -> Vec<RewardHistory>;
+    /// Get the unwithdrawn rewards of a certain account (as a validator and as a delegator)
+    /// in the eras whose rewards can still be withdrawn, broken down by eras.
+    fn get_unwithdrawn_rewards_of(&self, account_id: AccountId) -> Vec<UnwithdrawnReward>;
     /// Get current storage balance needed by this contract account.
     fn get_storage_balance(&self) -> U128;
     /// Get deposit of a certain validator in a certain era.
@@ -122,6 +408,14 @@ pub trait AnchorViewer {
     fn get_beefy_light_client_status(&self) -> BeefyLightClientStatus;
     /// Get staking histories related to the given account id.
     fn get_user_staking_histories_of(&self, account_id: AccountId) -> Vec<UserStakingHistory>;
+    /// Get the activities (staking facts, reward withdrawals and bridging operations)
+    /// of the given account id, in the order of time.
+    fn get_account_activity(
+        &self,
+        account_id: AccountId,
+        start: U64,
+        limit: Option<U64>,
+    ) -> Vec<AccountActivityRecord>;
     /// Get a certain appchain message.
     fn get_appchain_message_of(&self, nonce: u32) -> Option<AppchainMessage>;
     /// Get appchain messages.
@@ -141,6 +435,8 @@ pub trait AnchorViewer {
         start_nonce: u32,
         quantity: Option<u32>,
     ) -> Vec<AppchainMessageProcessingResult>;
+    /// Get the range of nonce of the appchain messages applied during a certain era.
+    fn get_messages_applied_in_era(&self, era_number: U64) -> Option<AppliedMessagesOfEra>;
     /// Get appchain challenge by index.
     /// If the param `index `is omitted, the latest challenge data will be returned.
     /// If the paran `index` is smaller than the start index, or bigger than the end index
@@ -154,6 +450,144 @@ pub trait AnchorViewer {
         start_index: U64,
         quantity: Option<U64>,
     ) -> Vec<AppchainChallenge>;
+    /// Get the freezing of a certain account.
+    /// If the account is not frozen or the freezing is expired, `None` will be returned.
+    fn get_freezing_of_account(&self, account_id: AccountId) -> Option<AccountFreezing>;
+    /// Get the index range of appchain referenda stored in anchor.
+    fn get_index_range_of_appchain_referenda(&self) -> IndexRange;
+    /// Get appchain referendum by index.
+    /// If the param `index `is omitted, the latest referendum will be returned.
+    fn get_appchain_referendum(&self, index: Option<U64>) -> Option<AppchainReferendumInfo>;
+    /// Get appchain referenda by start index and quantity.
+    /// If the param `quantity` is omitted, up to 50 records will be returned.
+    fn get_appchain_referenda(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<AppchainReferendumInfo>;
+    /// Get the notice of appchain runtime upgrade by index.
+    /// If the param `index` is omitted, the latest notice will be returned.
+    fn get_appchain_runtime_upgrade_notice(
+        &self,
+        index: Option<U64>,
+    ) -> Option<AppchainRuntimeUpgradeNotice>;
+    /// Get the notices of appchain runtime upgrades by start index and quantity.
+    /// If the param `quantity` is omitted, up to 50 records will be returned.
+    fn get_appchain_runtime_upgrade_notices(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<AppchainRuntimeUpgradeNotice>;
+    /// Get the vote of a certain voter on a certain appchain referendum.
+    fn get_vote_on_appchain_referendum(
+        &self,
+        referendum_index: U64,
+        voter_id: AccountId,
+    ) -> Option<ReferendumVote>;
+    /// Get the vote of a certain delegator (with the delegation to a certain validator)
+    /// on a certain appchain referendum.
+    fn get_delegator_vote_on_appchain_referendum(
+        &self,
+        referendum_index: U64,
+        delegator_id: AccountId,
+        validator_id: AccountId,
+    ) -> Option<ReferendumVote>;
+    /// Dump the validators in the next validator set (including the unbonding ones) in chunks,
+    /// for indexers to bootstrap from. The param `cursor` is the `next_cursor` of the
+    /// previous chunk, or omitted for the first chunk.
+    fn dump_validators(&self, cursor: Option<U64>) -> StateDumpChunk<AppchainValidator, U64>;
+    /// Dump the delegations in the next validator set in chunks, for indexers to bootstrap from.
+    fn dump_delegations(
+        &self,
+        cursor: Option<DelegationDumpCursor>,
+    ) -> StateDumpChunk<AppchainDelegator, DelegationDumpCursor>;
+    /// Dump the unbonded stakes which are not withdrawn yet in chunks, for indexers
+    /// to bootstrap from. A chunk may be empty while there are more chunks to query.
+    fn dump_unbonded_stakes(&self, cursor: Option<U64>) -> StateDumpChunk<UnbondedStake, U64>;
+    /// Get the receipt of an outbound transfer of withdrawal by its receipt id.
+    fn get_withdrawal_receipt(&self, receipt_id: U64) -> Option<WithdrawalReceipt>;
+    /// Get the receipts of outbound transfers of withdrawals by start index and quantity.
+    /// If the param `quantity` is omitted, up to 50 records will be returned.
+    fn get_withdrawal_receipts(
+        &self,
+        start_index: U64,
+        quantity: Option<U64>,
+    ) -> Vec<WithdrawalReceipt>;
+    /// Get the histories of slashes applied by the offences reported by the appchain,
+    /// by start index and quantity.
+    /// If the param `quantity` is omitted, up to 50 records will be returned.
+    fn get_slash_histories(&self, start_index: U64, quantity: Option<U64>) -> Vec<SlashHistory>;
+    /// Get the histories of slashes of a validator applied by the offences reported
+    /// by the appchain, in the order of slashes. Each history contains the era and the kind
+    /// of the offence, the amount slashed from the validator and the impact on its delegators.
+    fn get_slash_histories_of_validator(&self, validator_id: AccountId) -> Vec<SlashHistory>;
+    /// Get the destinations (with percents) of the amounts of a treasury source.
+    fn get_treasury_routes_of(&self, source: TreasurySource) -> Vec<TreasuryRoute>;
+    /// Get the amounts transferred from treasury sources in a certain era.
+    fn get_treasury_flows_of_era(&self, era_number: U64) -> Vec<TreasuryFlow>;
+    /// Get the projection of the stake which a validator will carry into the next era,
+    /// including the pending slashes and the stake change intents whose conditions are met.
+    fn get_projected_stake_of(&self, validator_id: AccountId) -> Option<ProjectedStakeOfValidator>;
+}
+
+pub trait GuardianActions {
+    /// Freeze the staking operations and withdrawals of a certain account,
+    /// while an investigation involving the account is pending.
+    /// The freezing expires automatically after the given period.
+    fn freeze_account(&mut self, account_id: AccountId, reason: String, period_in_seconds: U64);
+    /// Unfreeze a certain account before the freezing expires.
+    /// This function can be called by the guardian or the owner.
+    fn unfreeze_account(&mut self, account_id: AccountId);
+}
+
+pub trait StakingAdminActions {
+    /// Slash a certain amount of the deposit of a validator.
+    /// The slashed amount is held in a penalty escrow for the dispute period.
+    fn slash_validator(&mut self, validator_id: AccountId, amount: U128);
+    /// Slash a certain amount of the delegation of a delegator.
+    /// The slashed amount is held in a penalty escrow for the dispute period.
+    fn slash_delegator(&mut self, delegator_id: AccountId, validator_id: AccountId, amount: U128);
+    /// Force a validator (and all of its delegators) to be unbonded in next era.
+    fn force_unbond_validator(&mut self, validator_id: AccountId);
+    ///
+    fn jail_validator(&mut self, validator_id: AccountId);
+    ///
+    fn unjail_validator(&mut self, validator_id: AccountId);
+    /// Conclude the appeal against a penalty. If the appeal is upheld, the slashed stake
+    /// is returned to the slashed account.
+    fn conclude_penalty_appeal(&mut self, escrow_index: U64, is_upheld: bool);
+    /// Change the commission percent of a validator.
+    /// If `commission_percent` is `None`, the default value in protocol settings will be used.
+    fn change_commission_percent_of_validator(
+        &mut self,
+        validator_id: AccountId,
+        commission_percent: Option<u16>,
+    );
+}
+
+pub trait AppchainReferendaManager {
+    /// Create a referendum for appchain governance signaling.
+    /// The validators in the latest validator set can vote on it (stake-weighted).
+    fn create_appchain_referendum(
+        &mut self,
+        title: String,
+        description: String,
+        voting_period_in_seconds: U64,
+    ) -> U64;
+    /// Vote on a certain referendum. The former vote of the caller will be replaced.
+    fn vote_on_appchain_referendum(&mut self, referendum_index: U64, option: ReferendumVoteOption);
+    /// Vote on a certain referendum as a delegator of a certain validator, with the delegation
+    /// of the caller in the validator set of the referendum. This vote overrides the vote of
+    /// the validator for the portion of the delegation.
+    fn vote_on_appchain_referendum_as_delegator(
+        &mut self,
+        referendum_index: U64,
+        validator_id: AccountId,
+        option: ReferendumVoteOption,
+    );
+    /// Conclude a certain referendum after its voting period, and append the result
+    /// to appchain notification histories.
+    fn conclude_appchain_referendum(&mut self, referendum_index: U64);
 }
 
 pub trait AppchainLifecycleManager {
@@ -163,6 +597,12 @@ pub trait AppchainLifecycleManager {
     fn go_live(&mut self);
     /// Initialize the beefy light client
     fn initialize_beefy_light_client(&mut self, initial_public_keys: Vec<String>);
+    /// Allocate initial appchain-native balances to the appchain accounts of validators
+    /// in booting state, bounded by the bootstrap budget in appchain settings.
+    fn allocate_bootstrap_balances(&mut self, allocations: Vec<(AccountId, U128)>);
+    /// Verify and change the state of corresponding appchain to `terminating`,
+    /// the latest era becomes the final era of the appchain.
+    fn go_terminating(&mut self, reason: String);
 }
 
 pub trait NearFungibleTokenManager {
@@ -189,6 +629,9 @@ pub trait NearFungibleTokenManager {
     fn open_bridging_of_near_fungible_token(&mut self, symbol: String);
     ///
     fn close_bridging_of_near_fungible_token(&mut self, symbol: String);
+    /// Fetch the metadata of a NEAR fungible token from its contract again.
+    /// This function can be called by any account.
+    fn refresh_metadata_of_near_fungible_token(&mut self, symbol: String);
 }
 
 pub trait OwnerActions {
@@ -196,8 +639,74 @@ pub trait OwnerActions {
     fn remove_validator_set_before(&mut self, era_number: U64);
     ///
     fn remove_staking_history_before(&mut self, index: U64);
-    ///
+    /// Only the appchain notifications acknowledged by appchain can be removed.
     fn remove_appchain_notification_history_before(&mut self, index: U64);
+    /// Merge the runs of increasing/decreasing facts of stake or delegation of an account,
+    /// which take effect more than `minimum_era_count_for_compacting_staking_history`
+    /// eras ago, into single net facts.
+    fn compact_staking_histories_of(
+        &mut self,
+        account_id: AccountId,
+    ) -> MultiTxsOperationProcessingResult;
+    /// Record the notice of an upcoming runtime upgrade of appchain.
+    /// The `spec_version` should be bigger than the one in the latest notice.
+    fn record_appchain_runtime_upgrade_notice(
+        &mut self,
+        target_block_number: U64,
+        spec_version: u32,
+        notes: String,
+    ) -> U64;
+    /// Create an event for the delegators slashed in the given staking histories to claim
+    /// compensation from the insurance pool. A `coverage_percent` of the balance of the pool
+    /// is allocated to the delegators pro rata to their slashed amounts.
+    fn create_insurance_claim_event(
+        &mut self,
+        slashing_history_indexes: Vec<U64>,
+        coverage_percent: u16,
+        claim_window_in_days: U64,
+    ) -> U64;
+    /// Close an insurance claim event after its claim window,
+    /// the unclaimed compensation is returned to the insurance pool.
+    fn close_insurance_claim_event(&mut self, index: U64);
+    /// Put the attached NEAR into the pool for reimbursing relayers.
+    fn fund_relayer_reimbursement_pool(&mut self);
+    /// Withdraw a certain amount of NEAR from the pool for reimbursing relayers.
+    fn withdraw_from_relayer_reimbursement_pool(&mut self, amount: U128);
+    /// Set the payload types of appchain messages accepted in a certain appchain state.
+    /// If the param `payload_types` is `None`, all types are accepted in the state.
+    fn set_accepted_payload_types_of_state(
+        &mut self,
+        appchain_state: AppchainState,
+        payload_types: Option<Vec<PayloadType>>,
+    );
+    /// Set the config of the handler of appchain messages of a certain payload type.
+    /// The param `gas_budget` is in TGas, `None` means no gas is reserved for the handler.
+    fn set_appchain_message_handler_config(
+        &mut self,
+        payload_type: PayloadType,
+        is_enabled: bool,
+        gas_budget: Option<U64>,
+    );
+    /// Hide or show the endpoints of a certain validator in the peers of appchain.
+    fn set_validator_endpoints_visibility(&mut self, validator_id: AccountId, is_visible: bool);
+    /// Confirm the reward distribution of a certain era which is held by the circuit breaker,
+    /// the distribution will be performed in the following processing of appchain messages.
+    fn confirm_era_reward_distribution(&mut self, era_number: U64);
+    /// Reject the reward distribution of a certain era which is held by the circuit breaker,
+    /// the corresponding appchain message will be skipped without minting any reward.
+    fn reject_era_reward_distribution(&mut self, era_number: U64);
+    /// Propose to override the validator set of the next era with an explicit list of
+    /// validators in the next validator set, for recovering from the catastrophic consensus
+    /// failures of appchain. All other validators will be pushed to standby once the proposal
+    /// is approved by the validators of the latest era with more than 2/3 of the total stake.
+    fn propose_validator_set_override(&mut self, validator_ids: Vec<AccountId>) -> U64;
+    /// Cancel the pending proposal of validator set override.
+    fn cancel_validator_set_override(&mut self, proposal_index: U64);
+    /// Approve a pending proposal of setting change requested by the appchain,
+    /// the change is applied immediately.
+    fn approve_appchain_setting_change(&mut self, proposal_index: U64);
+    /// Reject a pending proposal of setting change requested by the appchain.
+    fn reject_appchain_setting_change(&mut self, proposal_index: U64);
 }
 
 pub trait PermissionlessActions {
@@ -213,18 +722,26 @@ pub trait PermissionlessActions {
     fn try_complete_updating_state_of_beefy_light_client(
         &mut self,
     ) -> MultiTxsOperationProcessingResult;
-    ///
+    /// The param `compression` indicates that the `encoded_messages` is compressed,
+    /// it will be decompressed before verifying and decoding.
     fn verify_and_stage_appchain_messages(
         &mut self,
         encoded_messages: Vec<u8>,
         header: Vec<u8>,
         mmr_leaf: Vec<u8>,
         mmr_proof: Vec<u8>,
+        compression: Option<PayloadCompression>,
     );
     ///
     fn process_appchain_messages(&mut self) -> MultiTxsOperationProcessingResult;
     ///
     fn commit_appchain_challenge(&mut self, appchain_challenge: AppchainChallenge);
+    /// Move the delegations of a retiring validator to the fallback validators of
+    /// the delegators, and unbond the validator along with the remaining delegations.
+    fn complete_validator_retirement(
+        &mut self,
+        validator_id: AccountId,
+    ) -> MultiTxsOperationProcessingResult;
     //
     fn process_appchain_messages_with_all_proofs(
         &mut self,
@@ -236,7 +753,40 @@ pub trait PermissionlessActions {
         header: Vec<u8>,
         mmr_leaf_for_header: Vec<u8>,
         mmr_proof_for_header: Vec<u8>,
-    );
+        compression: Option<PayloadCompression>,
+    ) -> MultiTxsOperationProcessingResult;
+    /// Claim the reimbursement (in NEAR) of the sender for the gas consumed in relaying
+    /// appchain messages in the eras which are over.
+    fn claim_relayer_reimbursement(&mut self);
+    /// Clean up the residual storage (profile, mapping of account id in appchain,
+    /// commission payout account and withdrawal allowlist) of the validators which are
+    /// fully unbonded and have withdrawn all of their stakes and rewards.
+    fn clean_up_deregistered_validators(
+        &mut self,
+        validator_ids: Vec<AccountId>,
+    ) -> MultiTxsOperationProcessingResult;
+    /// Execute the stake change intent of a delegator for the delegation to a certain validator,
+    /// if the condition of it is true. An expired intent is removed without executing.
+    fn execute_stake_change_intent(&mut self, delegator_id: AccountId, validator_id: AccountId);
+    /// Settle a penalty escrow whose dispute window is over, the slashed stake is transferred
+    /// to the treasury (or burnt if the treasury has no destination).
+    fn settle_penalty_escrow(&mut self, escrow_index: U64);
+    /// Admit the validator registrations queued while the appchain is booting in order,
+    /// until the admissions of the day reach the limit in protocol settings.
+    /// If the appchain is not booting any more, the remaining registrations are dropped
+    /// and the deposits are returned.
+    fn admit_queued_validator_registrations(&mut self) -> MultiTxsOperationProcessingResult;
+    /// Query the balance of this contract in OCT token contract, and record the drift
+    /// of it from the obligations (in OCT token) of this contract. It can be called
+    /// no more often than the minimum interval in anchor settings.
+    fn reconcile_oct_balance(&mut self);
+    /// Continue switching era (or distributing reward of era) stage by stage, until it is
+    /// completed or the gas threshold is reached. Return `Ok` if there is no era switching
+    /// or reward distributing in progress any more.
+    fn try_complete_switching_era(&mut self) -> MultiTxsOperationProcessingResult;
+    /// Unbond all validators and delegators of a terminating appchain, to make all of
+    /// their stakes withdrawable immediately.
+    fn wind_down_terminating_appchain(&mut self) -> MultiTxsOperationProcessingResult;
 }
 
 pub trait ProtocolSettingsManager {
@@ -267,6 +817,8 @@ pub trait ProtocolSettingsManager {
     ///
     fn change_unlock_period_of_delegator_deposit(&mut self, value: U64);
     ///
+    fn change_unlock_period_of_slashed_remainder_stake(&mut self, value: U64);
+    ///
     fn change_maximum_era_count_of_unwithdrawn_reward(&mut self, value: U64);
     ///
     fn change_maximum_era_count_of_valid_appchain_message(&mut self, value: U64);
@@ -274,6 +826,59 @@ pub trait ProtocolSettingsManager {
     fn change_validator_commission_percent(&mut self, value: u16);
     ///
     fn change_maximum_allowed_unprofitable_era_count(&mut self, value: u16);
+    ///
+    fn change_fast_unbond_penalty_percent(&mut self, value: u16);
+    ///
+    fn change_maximum_fast_unbond_amount_per_era(&mut self, value: U128);
+    ///
+    fn change_loyalty_tiers_of_delegator(&mut self, value: Vec<DelegatorLoyaltyTier>);
+    ///
+    fn change_maximum_size_of_encoded_appchain_messages(&mut self, value: U64);
+    ///
+    fn change_maximum_count_of_appchain_messages_per_batch(&mut self, value: u32);
+    ///
+    fn change_minimum_era_count_for_compacting_staking_history(&mut self, value: U64);
+    /// Set the secondary token for staking (experimental).
+    /// Once it is set, only the `price_in_oct` can be changed.
+    fn change_secondary_stake_token(&mut self, value: SecondaryStakeToken);
+    ///
+    fn change_era_count_of_validator_retirement_notice(&mut self, value: U64);
+    /// The value should not be greater than 100.
+    fn change_insurance_pool_reward_percent(&mut self, value: u16);
+    ///
+    fn change_relayer_reimbursement_per_era(&mut self, value: U128);
+    ///
+    fn change_time_weighted_reward(&mut self, value: bool);
+    ///
+    fn change_withdrawal_allowlist_change_delay(&mut self, value: U64);
+    ///
+    fn change_minimum_bonded_era_count_for_validator(&mut self, value: U64);
+    ///
+    fn change_reward_rounding_policy(&mut self, policy: RewardRoundingPolicy);
+    ///
+    fn change_maximum_delegation_lock_era_count(&mut self, value: U64);
+    ///
+    fn change_delegation_lock_voting_boost_percent_per_era(&mut self, value: u16);
+    ///
+    fn change_maximum_validator_count_per_operator(&mut self, value: U64);
+    ///
+    fn change_penalty_dispute_period(&mut self, value: U64);
+    ///
+    fn change_maximum_validator_admissions_per_day_in_booting(&mut self, value: U64);
+    ///
+    fn change_validator_reward_vesting_percent(&mut self, value: u16);
+    ///
+    fn change_validator_reward_vesting_era_count(&mut self, value: U64);
+    ///
+    fn change_minimum_relayer_bond(&mut self, value: U128);
+    ///
+    fn change_relayer_bond_slash_percent(&mut self, value: u16);
+    ///
+    fn change_unlock_period_of_relayer_bond(&mut self, value: U64);
+    ///
+    fn change_validator_slash_percent(&mut self, value: u16);
+    ///
+    fn change_delegator_slash_percent(&mut self, value: u16);
 }
 
 pub trait AppchainSettingsManager {
@@ -285,6 +890,15 @@ pub trait AppchainSettingsManager {
     fn set_era_reward(&mut self, era_reward: U128);
     ///
     fn set_bonus_for_new_validator(&mut self, bonus_amount: U128);
+    /// Set the expected duration (in seconds) of an era of appchain,
+    /// 0 for disabling the checking of era duration drift.
+    fn set_expected_era_duration_in_secs(&mut self, duration: U64);
+    ///
+    fn set_era_duration_drift_tolerance_percent(&mut self, percent: u16);
+    ///
+    fn set_bootstrap_budget(&mut self, budget: U128);
+    ///
+    fn set_boot_nodes(&mut self, boot_nodes: Vec<String>);
 }
 
 pub trait AnchorSettingsManager {
@@ -293,9 +907,34 @@ pub trait AnchorSettingsManager {
     ///
     fn set_relayer_account(&mut self, account_id: AccountId);
     ///
+    fn set_guardian_account(&mut self, account_id: AccountId);
+    ///
+    fn set_treasury_account(&mut self, account_id: AccountId);
+    ///
     fn turn_on_beefy_light_client_witness_mode(&mut self);
     ///
     fn turn_off_beefy_light_client_witness_mode(&mut self);
+    /// Set the minimum interval (in seconds) between two price updates of a token.
+    fn set_minimum_interval_of_price_updates(&mut self, interval_in_secs: U64);
+    /// Set the maximum percentage of the change of price of a token in a single update.
+    fn set_maximum_price_change_percent_per_update(&mut self, percent: u16);
+    ///
+    fn turn_on_pause_asset_transfer_on_price_update_violation(&mut self);
+    ///
+    fn turn_off_pause_asset_transfer_on_price_update_violation(&mut self);
+    /// Set the seconds of a day used in the periods in days, only for testing on testnets.
+    fn set_seconds_of_a_day(&mut self, value: U64);
+    /// Set the maximum percentage of the change of the era reward from the reward paid
+    /// in the previous era, beyond which the reward distribution is held.
+    fn set_maximum_era_reward_change_percent(&mut self, percent: u16);
+    /// Set the maximum percentage of the unprofitable validators in the validator set of
+    /// an era, beyond which the reward distribution is held.
+    fn set_maximum_unprofitable_validator_percent(&mut self, percent: u16);
+    /// Set the account which can submit the attested exports of stake balances
+    /// from other staking contracts.
+    fn set_stake_migration_attester_account(&mut self, account_id: AccountId);
+    ///
+    fn set_minimum_interval_of_oct_balance_reconciliations(&mut self, interval_in_secs: U64);
 }
 
 pub trait StakingManager {
@@ -317,15 +956,49 @@ pub trait StakingManager {
     /// Unbond delegation of an account (delegator) to a validator.
     /// This function can only be called by a delegator.
     fn unbond_delegation(&mut self, validator_id: AccountId);
+    /// Move the whole delegation of an account (delegator) from a validator to another one,
+    /// without unbonding it. This function can only be called by a delegator.
+    fn change_delegated_validator(
+        &mut self,
+        old_validator_id: AccountId,
+        new_validator_id: AccountId,
+    );
     /// Withdraw unbonded stake(s) of a certain account.
     /// This function can be called by any account.
-    fn withdraw_stake(&mut self, account_id: AccountId);
+    /// The param `destination` is checked against the withdrawal allowlist of the account.
+    fn withdraw_stake(&mut self, account_id: AccountId, destination: Option<AccountId>);
+    /// Withdraw all unbonded stake(s) of the caller instantly, including the locked ones.
+    /// A certain percent of the locked stake(s) will be forfeited as penalty.
+    /// The total amount of locked stake(s) withdrawn in this way is limited per era.
+    fn fast_withdraw_stake(&mut self, destination: Option<AccountId>);
     /// Withdraw rewards of a certain validator.
     /// This function can be called by any account.
-    fn withdraw_validator_rewards(&mut self, validator_id: AccountId);
+    fn withdraw_validator_rewards(
+        &mut self,
+        validator_id: AccountId,
+        destination: Option<AccountId>,
+    );
     /// Withdraw rewards of a certain delegator to a validator.
+    /// This function can be called by any account. If it is called by an account other than
+    /// the delegator, the caller gets the tip (a percent of the rewards) opted in by the delegator.
+    fn withdraw_delegator_rewards(
+        &mut self,
+        delegator_id: AccountId,
+        validator_id: AccountId,
+        destination: Option<AccountId>,
+    );
+    /// Decrease the stake of secondary token of an account (validator).
+    /// This function can only be called by a validator.
+    fn decrease_secondary_stake(&mut self, amount: U128);
+    /// Withdraw unbonded stake(s) of secondary token of a certain account.
     /// This function can be called by any account.
-    fn withdraw_delegator_rewards(&mut self, delegator_id: AccountId, validator_id: AccountId);
+    fn withdraw_secondary_stake(&mut self, account_id: AccountId, destination: Option<AccountId>);
+    /// Appeal against the penalty held in a certain escrow in its dispute window.
+    /// This function can only be called by the slashed account.
+    fn appeal_penalty(&mut self, escrow_index: U64);
+    /// Cancel the registration of the caller (validator) which is queued while the appchain
+    /// is booting, the deposit is returned to the caller.
+    fn cancel_queued_validator_registration(&mut self);
 }
 
 pub trait SudoActions {
@@ -351,6 +1024,9 @@ pub trait SudoActions {
     fn pause_rewards_withdrawal(&mut self);
     ///
     fn resume_rewards_withdrawal(&mut self);
+    /// Change the staking status of this contract with a reason, which is recorded
+    /// in the history of staking status changes.
+    fn set_staking_status(&mut self, status: StakingStatus, reason: String);
     ///
     fn change_account_id_in_appchain_of_validator(
         &mut self,
@@ -366,6 +1042,51 @@ pub trait SudoActions {
         validator_id: AccountId,
         staking_history_index: U64,
     );
+    ///
+    fn turn_on_maintenance_mode(&mut self);
+    ///
+    fn turn_off_maintenance_mode(&mut self);
+    /// Rebuild the next validator set by replaying the staking histories
+    /// from `start_index`, for recovering from a corrupted state.
+    ///
+    /// If `start_index` is the first index of staking histories, the next validator set
+    /// will be cleared before replaying. If more gas is needed, the index to continue with
+    /// is logged, and this function should be called again with it.
+    fn rebuild_next_validator_set_from_history(
+        &mut self,
+        start_index: U64,
+    ) -> MultiTxsOperationProcessingResult;
+}
+
+pub trait DelegatorActions {
+    /// Set a short label for the delegation to a certain validator,
+    /// or remove the label if the param `label` is omitted.
+    fn set_delegation_label(&mut self, validator_id: AccountId, label: Option<String>);
+    /// Set the validator which the delegations of the sender are moved to when
+    /// the validators it delegates to retire, or remove it if the param `validator_id` is omitted.
+    fn set_fallback_validator(&mut self, validator_id: Option<AccountId>);
+    /// Claim the compensation of the sender in a certain insurance claim event.
+    fn claim_insurance_compensation(&mut self, event_index: U64);
+    /// Submit an intent to change the delegation to a certain validator when the condition
+    /// becomes true, which replaces the existing intent for the same delegation.
+    /// The param `expiry_timestamp` is in nanoseconds.
+    fn submit_stake_change_intent(
+        &mut self,
+        validator_id: AccountId,
+        action: StakeChangeAction,
+        condition: StakeChangeCondition,
+        expiry_timestamp: U64,
+    );
+    /// Cancel the stake change intent for the delegation to a certain validator.
+    fn cancel_stake_change_intent(&mut self, validator_id: AccountId);
+    /// Lock the delegation to a certain validator for `era_count` eras (from the latest era),
+    /// in exchange for boosted voting weight in appchain referenda. The locked delegation
+    /// cannot be decreased or unbonded by the sender until the lock expires.
+    /// An existing lock can only be extended.
+    fn lock_delegation(&mut self, validator_id: AccountId, era_count: U64);
+    /// Set the percent of the rewards which is tipped to the third party who withdraws
+    /// the rewards of the sender, or remove it if the param `percent` is omitted.
+    fn set_reward_claim_tip_percent(&mut self, percent: Option<u16>);
 }
 
 pub trait ValidatorActions {
@@ -373,6 +1094,35 @@ pub trait ValidatorActions {
     fn set_validator_id_in_appchain(&mut self, account_id_in_appchain: String);
     ///
     fn set_validator_profile(&mut self, profile: HashMap<String, String>);
+    /// Set the account id in appchain for receiving appchain-native benefits
+    /// of the validator. It will take effect in next era.
+    fn set_reward_address_in_appchain(&mut self, reward_address_in_appchain: String);
+    /// Set the account for receiving the commission portion of the rewards of the validator,
+    /// or remove it if the param `account_id` is omitted.
+    /// It takes effect in the reward distribution after the setting.
+    fn set_commission_payout_account(&mut self, account_id: Option<AccountId>);
+    /// Announce the retirement of the sender, which can be completed after
+    /// `era_count_of_validator_retirement_notice` eras.
+    fn announce_retirement(&mut self);
+    ///
+    fn cancel_retirement(&mut self);
+    /// Set the public endpoints of the appchain node of the sender, including
+    /// the P2P addresses (in multiaddr format) and the RPC endpoints.
+    fn set_validator_endpoints(&mut self, p2p_multiaddrs: Vec<String>, rpc_endpoints: Vec<String>);
+    ///
+    fn remove_validator_endpoints(&mut self);
+    /// Approve the pending proposal of validator set override, the sender should be
+    /// a validator in the validator set of the base era of the proposal.
+    fn approve_validator_set_override(&mut self, proposal_index: U64);
+}
+
+pub trait WithdrawalAllowlistManager {
+    /// Set the allowlist of destinations which the stake and rewards of the caller
+    /// can be withdrawn to. Once set, the changes take effect after a delay
+    /// specified in protocol settings. An empty list removes the allowlist.
+    fn set_withdrawal_allowlist(&mut self, destinations: Vec<AccountId>);
+    /// Cancel the pending change of the withdrawal allowlist of the caller.
+    fn cancel_withdrawal_allowlist_change(&mut self);
 }
 
 pub trait WrappedAppchainTokenManager {
@@ -391,6 +1141,23 @@ pub trait WrappedAppchainTokenManager {
     fn set_price_of_wrapped_appchain_token(&mut self, price: U128);
     ///
     fn burn_wrapped_appchain_token(&self, receiver_id: String, amount: U128);
+    /// Register a certain account in the wrapped appchain token contract (by `storage_deposit`)
+    /// with the attached NEAR, so that the rewards can be transferred to it. The attached NEAR
+    /// should not be less than 0.0125 NEAR, and the remaining part will be refunded to the sender.
+    fn pay_storage_for_reward_recipient(&mut self, account_id: AccountId);
+}
+
+pub trait PeerAnchorManager {
+    /// Register the anchor of another appchain as a peer anchor.
+    fn register_peer_anchor(&mut self, anchor_account: AccountId, appchain_id: AppchainId);
+    ///
+    fn remove_peer_anchor(&mut self, anchor_account: AccountId);
+    /// Send the commitment of the validator set of the latest era
+    /// to all registered peer anchors.
+    fn publish_validator_set_commitment(&mut self);
+    /// Submit the validator set commitment of a peer anchor.
+    /// Only callable by the registered peer anchors.
+    fn submit_validator_set_commitment(&mut self, commitment: ValidatorSetCommitment);
 }
 
 pub trait WrappedAppchainNFTManager {
@@ -407,3 +1174,49 @@ pub trait WrappedAppchainNFTManager {
     ///
     fn close_bridging_of_wrapped_appchain_nft(&mut self, class_id: String);
 }
+
+pub trait StakeMigrationManager {
+    /// Submit an attested export of stake balances from another staking contract,
+    /// which is staged for the approval of the owner. Return the index of the export.
+    /// Only callable by the stake migration attester account.
+    fn submit_stake_migration_export(
+        &mut self,
+        source: StakeMigrationSource,
+        entries: Vec<StakeMigrationEntry>,
+    ) -> U64;
+    /// Approve a pending export, the stake balances in it are credited to the validators
+    /// and delegators in the next validator set from the allocation of stake migration.
+    fn approve_stake_migration_export(&mut self, export_index: U64);
+    ///
+    fn reject_stake_migration_export(&mut self, export_index: U64);
+    /// Withdraw a certain amount of OCT token from the allocation of stake migration
+    /// to the owner account.
+    fn withdraw_stake_migration_allocation(&mut self, amount: U128);
+}
+
+pub trait RelayerBondManager {
+    /// Start unbonding the bond of the caller (relayer). The relayer is not whitelisted
+    /// any more, and the bond can be withdrawn after the unlock period in protocol settings.
+    fn unbond_relayer(&mut self);
+    /// Withdraw the remaining bond of the caller (relayer) after its unlock period.
+    fn withdraw_relayer_bond(&mut self);
+    /// Slash the bond of a relayer by a misbehavior finding. Only callable by the owner.
+    fn slash_relayer_bond(
+        &mut self,
+        relayer_id: AccountId,
+        finding: RelayerMisbehaviorFinding,
+    ) -> RelayerBondSlash;
+}
+
+pub trait TreasuryManager {
+    /// Set the destinations (with percents) of the amounts of a treasury source.
+    /// The sum of the percents should not exceed 100, the rest of the amounts is transferred
+    /// to the treasury account (or burnt if there is no treasury account).
+    fn set_treasury_routes(&mut self, source: TreasurySource, routes: Vec<TreasuryRoute>);
+}
+
+pub trait WithdrawalReceiptManager {
+    /// Refund the amount of a failed withdrawal to the caller, who is the account whose
+    /// asset is withdrawn in the withdrawal receipt.
+    fn refund_failed_withdrawal(&mut self, receipt_id: U64);
+}
@@ -1,19 +1,46 @@
 mod anchor_viewer;
 pub mod appchain_challenge;
 pub mod appchain_messages;
+mod appchain_referenda;
+mod appchain_setting_changes;
+mod appchain_templates;
+mod appchain_termination;
 mod assets;
+pub mod compression;
+mod configuration_digest;
+mod events;
+mod initialization_status;
 pub mod interfaces;
 mod lookup_array;
+mod oct_balance_reconciliations;
+mod penalty_escrows;
 mod permissionless_actions;
+mod relayer_bonds;
 mod reward_distribution_records;
+mod reward_vestings;
+mod slashing;
+mod sorted_indices;
+mod stake_migrations;
+mod stake_projections;
+mod state_dumps;
 mod storage_key;
 pub mod storage_migration;
+/// Helpers for writing integration tests of this contract.
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+mod time_provider;
+mod treasury;
 pub mod types;
+mod unlock_eligibility;
+mod unwithdrawn_rewards;
 mod upgrade;
 mod user_actions;
 mod user_staking_histories;
+mod validator_onboarding;
 mod validator_profiles;
 mod validator_set;
+mod validator_set_override;
+mod withdrawal_receipts;
 
 use core::convert::TryInto;
 use getrandom::{register_custom_getrandom, Error};
@@ -21,29 +48,34 @@ use near_contract_standards::non_fungible_token::metadata::TokenMetadata;
 use near_contract_standards::non_fungible_token::TokenId;
 use near_contract_standards::upgrade::Ownable;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, UnorderedSet};
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet};
 use near_sdk::json_types::{U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{
     assert_self, env, ext_contract, log, near_bindgen, serde_json, AccountId, Balance, Gas,
     PanicOnDefault, Promise, PromiseOrValue, PromiseResult, PublicKey, Timestamp,
 };
+use std::collections::BTreeMap;
 use std::ops::Mul;
 
-pub use appchain_messages::AppchainMessage;
+pub use appchain_messages::{AppchainMessage, PayloadType};
 pub use permissionless_actions::AppchainEvent;
 
 use appchain_challenge::AppchainChallenge;
 use appchain_messages::AppchainMessages;
+use appchain_referenda::AppchainReferendum;
 use assets::near_fungible_tokens::NearFungibleTokens;
 use assets::wrapped_appchain_nfts::WrappedAppchainNFTs;
 use beefy_light_client::Hash;
 use beefy_light_client::LightClient;
+use compression::PayloadCompression;
+use events::Nep297Event;
 use lookup_array::{IndexedAndClearable, LookupArray};
 use reward_distribution_records::RewardDistributionRecords;
+use sorted_indices::StakingSortedIndices;
 use storage_key::StorageKey;
 use types::*;
-use user_actions::UnbondedStakeReference;
+use user_actions::{UnbondedStakeReference, UnwithdrawnRewardReference};
 use user_staking_histories::UserStakingHistories;
 use validator_profiles::ValidatorProfiles;
 use validator_set::next_validator_set::NextValidatorSet;
@@ -54,6 +86,9 @@ register_custom_getrandom!(get_random_in_near);
 
 /// Version of this contract (the same as in Cargo.toml)
 const ANCHOR_VERSION: &str = "v2.2.0";
+/// Version of the JSON API of view functions, which should be increased
+/// when the return type of any existing view function is changed incompatibly.
+const ANCHOR_VIEW_API_VERSION: u32 = 1;
 /// Constants for gas.
 const T_GAS_FOR_FT_TRANSFER: u64 = 10;
 const T_GAS_FOR_BURN_FUNGIBLE_TOKEN: u64 = 10;
@@ -67,12 +102,18 @@ const T_GAS_CAP_FOR_PROCESSING_APPCHAIN_MESSAGES: u64 = 240;
 const T_GAS_FOR_NFT_CONTRACT_INITIALIZATION: u64 = 50;
 const T_GAS_FOR_REGISTER_VALIDATOR: u64 = 100;
 const T_GAS_FOR_BURN_WRAPPED_APPCHAIN_TOKEN: u64 = 50;
+const T_GAS_FOR_SUBMIT_VALIDATOR_SET_COMMITMENT: u64 = 10;
+const T_GAS_FOR_STORAGE_DEPOSIT: u64 = 10;
+const T_GAS_FOR_RECONCILING_OCT_BALANCE: u64 = 50;
+const T_GAS_CAP_FOR_STATE_DUMP: u64 = 100;
 /// The value of decimals value of USD.
 const USD_DECIMALS_VALUE: Balance = 1_000_000;
 /// The value of decimals value of OCT token.
 const OCT_DECIMALS_VALUE: Balance = 1_000_000_000_000_000_000;
 /// The seconds of a day.
 const SECONDS_OF_A_DAY: u64 = 86400;
+/// The minimum seconds of a day which can be set in anchor settings for testing.
+const MINIMUM_SECONDS_OF_A_DAY: u64 = 60;
 /// Multiple of nano seconds for a second.
 const NANO_SECONDS_MULTIPLE: u64 = 1_000_000_000;
 /// Storage deposit for NEP-141 token (in yocto)
@@ -81,6 +122,14 @@ const STORAGE_DEPOSIT_FOR_NEP141_TOEKN: Balance = 12_500_000_000_000_000_000_000
 const STORAGE_DEPOSIT_FOR_MINT_NFT: Balance = 100_000_000_000_000_000_000_000;
 /// Storage deposit for wrapped appchain NFT contract (in yocto)
 const WRAPPED_APPCHAIN_NFT_CONTRACT_INIT_BALANCE: Balance = 3_200_000_000_000_000_000_000_000;
+/// The key in validator profile for declaring the operator entity of the validator
+const VALIDATOR_PROFILE_KEY_OF_OPERATOR: &str = "operator";
+/// The key in validator profile for the contact email of the validator
+const VALIDATOR_PROFILE_KEY_OF_EMAIL: &str = "email";
+/// The key in validator profile for the website of the validator
+const VALIDATOR_PROFILE_KEY_OF_WEBSITE: &str = "website";
+/// The key in validator profile for the url of the logo of the validator
+const VALIDATOR_PROFILE_KEY_OF_LOGO: &str = "logo";
 
 #[ext_contract(ext_self)]
 trait ResolverForSelfCallback {
@@ -91,6 +140,13 @@ trait ResolverForSelfCallback {
         receiver_id_in_appchain: String,
         amount: U128,
     );
+    /// Resolver for burning wrapped appchain token deposited by `ft_transfer_call`
+    fn resolve_wrapped_appchain_token_deposit_burning(
+        &mut self,
+        sender_id_in_near: AccountId,
+        receiver_id_in_appchain: String,
+        amount: U128,
+    ) -> U128;
     /// Resolver for minting wrapped appchain token
     fn resolve_wrapped_appchain_token_minting(
         &mut self,
@@ -107,7 +163,10 @@ trait ResolverForSelfCallback {
         receiver_id_in_near: AccountId,
         amount: U128,
         appchain_message_nonce: u32,
+        withdrawal_receipt_id: Option<U64>,
     );
+    /// Resolver for fetching metadata of NEAR fungible token
+    fn resolve_near_fungible_token_metadata_fetching(&mut self, contract_account: AccountId);
     /// Resolver for transfer wrapped appchain NFT
     fn resolve_wrapped_appchain_nft_transfer(
         &mut self,
@@ -128,6 +187,23 @@ trait ResolverForSelfCallback {
         token_metadata: TokenMetadata,
         appchain_message_nonce: u32,
     );
+    /// Resolver for querying storage balance of reward recipient in wrapped appchain token
+    fn resolve_storage_balance_of_reward_recipient(
+        &mut self,
+        sponsor_id: AccountId,
+        account_id: AccountId,
+        amount: U128,
+    );
+    /// Resolver for querying balance of this contract in OCT token contract
+    fn resolve_oct_balance_reconciliation(&mut self, keeper_id: AccountId);
+    /// Resolver for transferring the claimed reimbursement to relayer
+    fn resolve_relayer_reimbursement_claiming(
+        &mut self,
+        relayer_id: AccountId,
+        gas_usages: Vec<(U64, U64)>,
+    );
+    /// Resolver for the outbound transfer of a withdrawal
+    fn resolve_withdrawal_transfer(&mut self, receipt_id: U64, is_refund: bool);
 }
 
 #[near_bindgen]
@@ -194,6 +270,157 @@ pub struct AppchainAnchor {
     appchain_challenges: LazyOption<LookupArray<AppchainChallenge>>,
     /// The wrapped appchain NFT data
     wrapped_appchain_nfts: LazyOption<WrappedAppchainNFTs>,
+    /// Whether the contract is in (read-only) maintenance mode
+    maintenance_mode_is_on: bool,
+    /// The referenda for appchain governance signaling
+    appchain_referenda: LazyOption<LookupArray<AppchainReferendum>>,
+    /// The accounts which are frozen by the guardian
+    frozen_accounts: LookupMap<AccountId, AccountFreezing>,
+    /// The summaries of eras, derived at era close
+    era_summaries: LookupMap<u64, EraSummary>,
+    /// The total amount of locked unbonded stake withdrawn instantly in eras
+    fast_unbonded_amounts: LookupMap<u64, Balance>,
+    /// The labels of delegations, keyed by `(delegator_id, validator_id)`
+    delegation_labels: LookupMap<(AccountId, AccountId), String>,
+    /// The archives of compacted staking histories,
+    /// keyed by the index of the merged staking history
+    compacted_staking_histories: LookupMap<u64, CompactedStakingHistories>,
+    /// The notices of upcoming runtime upgrades of appchain, recorded by the owner
+    appchain_runtime_upgrade_notices: LazyOption<LookupArray<AppchainRuntimeUpgradeNotice>>,
+    /// The unbonded stakes of the secondary stake token of validators
+    unbonded_secondary_stakes: LookupMap<AccountId, Vec<UnbondedStakeReference>>,
+    /// The latest attestation of the balances of wrapped assets issued in appchain
+    latest_wrapped_asset_balance_attestation: LazyOption<WrappedAssetBalanceAttestation>,
+    /// The accounts for receiving the commission of validators, keyed by validator id
+    commission_payout_accounts: LookupMap<AccountId, AccountId>,
+    /// The unwithdrawn commission of validators which is routed to their payout accounts
+    unwithdrawn_commission_rewards: LookupMap<(u64, AccountId), Balance>,
+    /// The map from validator id to the era number in which the validator retires.
+    validator_retirements: LookupMap<AccountId, U64>,
+    /// The map from delegator id to the validator id which its delegations are moved to
+    /// when the validators it delegates to retire.
+    delegator_fallback_validators: LookupMap<AccountId, AccountId>,
+    /// The snapshots of protocol settings which are effective in each era.
+    protocol_settings_of_eras: LookupMap<u64, VersionedProtocolSettings>,
+    /// The balance of wrapped appchain token in the insurance pool.
+    insurance_pool_balance: Balance,
+    /// The events for claiming compensation of slashing from the insurance pool.
+    insurance_claim_events: LazyOption<LookupArray<InsuranceClaimEvent>>,
+    /// The unclaimed compensation of delegators in each insurance claim event.
+    insurance_compensations: LookupMap<(u64, AccountId), Balance>,
+    /// The map from the index of a covered slashing history to
+    /// the index of insurance claim event.
+    covered_slashing_histories: LookupMap<u64, u64>,
+    /// The registered peer anchors (of other appchains), keyed by their account id.
+    peer_anchors: UnorderedMap<AccountId, PeerAnchor>,
+    /// The balance of NEAR in the pool for reimbursing relayers.
+    relayer_reimbursement_pool_balance: Balance,
+    /// The unclaimed gas consumed by each relayer in each era.
+    relayer_gas_usages: LookupMap<(u64, AccountId), u64>,
+    /// The total gas consumed by relayers in each era.
+    total_relayer_gas_of_eras: LookupMap<u64, u64>,
+    /// The amount of NEAR allocated to the relayers of each era.
+    relayer_reimbursements_of_eras: LookupMap<u64, Balance>,
+    /// The amounts of appchain-native token allocated to validators in booting state.
+    bootstrap_allocations: LookupMap<AccountId, Balance>,
+    /// The total amount of appchain-native token allocated to validators in booting state.
+    total_bootstrap_allocation: Balance,
+    /// The timestamps of the latest accepted price updates of tokens.
+    last_price_update_timestamps: LookupMap<PricedToken, Timestamp>,
+    /// The price updates rejected for violating the rate limits in anchor settings.
+    price_update_violations: LazyOption<LookupArray<PriceUpdateViolation>>,
+    /// The allowlists of withdrawal destinations of accounts.
+    withdrawal_allowlists: LookupMap<AccountId, WithdrawalAllowlist>,
+    /// The sorted indices of the validators and delegators in the next validator set.
+    staking_sorted_indices: LazyOption<StakingSortedIndices>,
+    /// The payload types of appchain messages accepted in each appchain state,
+    /// all types are accepted in the states which are not in this map.
+    accepted_payload_types_of_states: LookupMap<AppchainState, Vec<PayloadType>>,
+    /// The stake change intents of delegators, from (delegator id, validator id) to the intent.
+    stake_change_intents: UnorderedMap<(AccountId, AccountId), StakeChangeIntent>,
+    /// The appchain messages applied during each era.
+    applied_messages_of_eras: LookupMap<u64, AppliedMessagesOfEra>,
+    /// The public endpoints of the appchain nodes of validators.
+    validator_endpoints: LookupMap<AccountId, ValidatorEndpoints>,
+    /// The histories of reward withdrawals of accounts.
+    reward_withdrawal_histories: LookupMap<AccountId, Vec<RewardWithdrawalHistory>>,
+    /// The indexes of the bridging appchain notification histories related to accounts.
+    user_appchain_notification_indexes: LookupMap<AccountId, Vec<u64>>,
+    /// The era reward distributions which are held by the circuit breaker on payout anomalies.
+    held_era_reward_distributions: LookupMap<u64, HeldEraRewardDistribution>,
+    /// The accumulated issuance of wrapped appchain token by this contract.
+    wrapped_appchain_token_issuance: LazyOption<WrappedAppchainTokenIssuance>,
+    /// The rounding of the reward distribution of eras.
+    reward_roundings_of_eras: LookupMap<u64, RewardRoundingOfEra>,
+    /// The locks of delegations for governance participation,
+    /// keyed by `(delegator_id, validator_id)`.
+    delegation_locks: LookupMap<(AccountId, AccountId), DelegationLock>,
+    /// The validators which are pushed to standby by the diversity constraints in each era.
+    standby_validator_ids_of_eras: LookupMap<u64, Vec<AccountId>>,
+    /// The proposals of emergency override of the validator set.
+    validator_set_override_proposals: LazyOption<LookupArray<ValidatorSetOverrideProposal>>,
+    /// The percent of the rewards which is tipped to the third party who withdraws
+    /// the rewards of a delegator, opted in by the delegator.
+    reward_claim_tip_percents: LookupMap<AccountId, u16>,
+    /// The requests of appchain governance to change anchor-side settings,
+    /// which are staged for the approval of the owner.
+    appchain_setting_change_proposals: LazyOption<LookupArray<AppchainSettingChangeProposal>>,
+    /// The escrows of slashed stakes in their dispute windows.
+    penalty_escrows: LazyOption<LookupArray<PenaltyEscrow>>,
+    /// The total amount (of OCT token) held in the escrows of slashed stakes.
+    penalty_escrow_balance: Balance,
+    /// The metadata of NEP-141 tokens fetched from their contracts, mapped by the contract account.
+    near_fungible_token_metadata_mirrors: LookupMap<AccountId, FungibleTokenMetadataMirror>,
+    /// The index of the latest appchain notification which the appchain confirms processing.
+    latest_acknowledged_appchain_notification_index: Option<u64>,
+    /// The configs of the handlers of appchain messages, mapped by the payload type.
+    appchain_message_handler_configs: LookupMap<PayloadType, AppchainMessageHandlerConfig>,
+    /// The attested exports of stake balances from other staking contracts,
+    /// which are staged for the approval of the owner.
+    stake_migration_exports: LazyOption<LookupArray<StakeMigrationExport>>,
+    /// The amount (of OCT token) funded by the owner for crediting the migrated stakes.
+    stake_migration_allocation: Balance,
+    /// The validator registrations queued while the appchain is booting.
+    queued_validator_registrations: LazyOption<LookupArray<QueuedValidatorRegistration>>,
+    /// The index of the next queued validator registration to be admitted.
+    next_queued_validator_registration_index: u64,
+    /// The count of validator registrations admitted in the latest day
+    /// while the appchain is booting.
+    daily_validator_admissions: DailyValidatorAdmissions,
+    /// The vesting parts of the rewards of validators which are not fully released yet.
+    validator_reward_vestings: LookupMap<AccountId, Vec<ValidatorRewardVesting>>,
+    /// The bonds of relayers.
+    relayer_bonds: UnorderedMap<AccountId, RelayerBond>,
+    /// The slashes of the bonds of relayers.
+    relayer_bond_slashes: LazyOption<LookupArray<RelayerBondSlash>>,
+    /// The total amount of the unbonded stakes (in OCT token) which are not withdrawn yet.
+    total_unbonded_stake: Balance,
+    /// The reconciliations of the balance of OCT token of this contract.
+    oct_balance_reconciliations: LazyOption<LookupArray<OctBalanceReconciliation>>,
+    /// The status of staking in this contract.
+    staking_status: StakingStatus,
+    /// The history of the changes of staking status.
+    staking_status_changes: LazyOption<LookupArray<StakingStatusChange>>,
+    /// The receipts of all outbound transfers of withdrawals, with the status of them
+    withdrawal_receipts: LazyOption<LookupArray<WithdrawalReceipt>>,
+    /// The histories of slashes applied by the offences reported by the appchain.
+    slash_histories: LazyOption<LookupArray<SlashHistory>>,
+    /// The references to the unwithdrawn rewards (and commissions) of accounts,
+    /// in the order of eras.
+    unwithdrawn_reward_references: LookupMap<AccountId, Vec<UnwithdrawnRewardReference>>,
+    /// The first era whose unwithdrawn rewards are all referenced in
+    /// `unwithdrawn_reward_references`. The rewards of the earlier eras are not referenced,
+    /// as they are distributed before the references are introduced.
+    first_era_of_unwithdrawn_reward_references: u64,
+    /// The destinations (with percents) of the amounts of each treasury source,
+    /// the rest of the amounts is transferred to the treasury account.
+    treasury_routes: LookupMap<TreasurySource, Vec<TreasuryRoute>>,
+    /// The amounts transferred from treasury sources in each era.
+    treasury_flows_of_eras: LookupMap<u64, Vec<TreasuryFlow>>,
+    /// The record of the termination of the appchain.
+    appchain_termination: LazyOption<AppchainTermination>,
+    /// The indexes of the slash histories of each validator, in the order of slashes.
+    slash_history_indexes_of_validators: LookupMap<AccountId, Vec<u64>>,
 }
 
 #[near_bindgen]
@@ -305,6 +532,185 @@ impl AppchainAnchor {
                 StorageKey::WrappedAppchainNFTs.into_bytes(),
                 Some(&WrappedAppchainNFTs::new()),
             ),
+            maintenance_mode_is_on: false,
+            appchain_referenda: LazyOption::new(
+                StorageKey::AppchainReferenda.into_bytes(),
+                Some(&LookupArray::new(StorageKey::AppchainReferendaMap)),
+            ),
+            frozen_accounts: LookupMap::new(StorageKey::FrozenAccounts.into_bytes()),
+            era_summaries: LookupMap::new(StorageKey::EraSummaries.into_bytes()),
+            fast_unbonded_amounts: LookupMap::new(StorageKey::FastUnbondedAmounts.into_bytes()),
+            delegation_labels: LookupMap::new(StorageKey::DelegationLabels.into_bytes()),
+            compacted_staking_histories: LookupMap::new(
+                StorageKey::CompactedStakingHistories.into_bytes(),
+            ),
+            appchain_runtime_upgrade_notices: LazyOption::new(
+                StorageKey::AppchainRuntimeUpgradeNotices.into_bytes(),
+                Some(&LookupArray::new(
+                    StorageKey::AppchainRuntimeUpgradeNoticesMap,
+                )),
+            ),
+            unbonded_secondary_stakes: LookupMap::new(
+                StorageKey::UnbondedSecondaryStakes.into_bytes(),
+            ),
+            latest_wrapped_asset_balance_attestation: LazyOption::new(
+                StorageKey::LatestWrappedAssetBalanceAttestation.into_bytes(),
+                None,
+            ),
+            commission_payout_accounts: LookupMap::new(
+                StorageKey::CommissionPayoutAccounts.into_bytes(),
+            ),
+            unwithdrawn_commission_rewards: LookupMap::new(
+                StorageKey::UnwithdrawnCommissionRewards.into_bytes(),
+            ),
+            validator_retirements: LookupMap::new(StorageKey::ValidatorRetirements.into_bytes()),
+            delegator_fallback_validators: LookupMap::new(
+                StorageKey::DelegatorFallbackValidators.into_bytes(),
+            ),
+            protocol_settings_of_eras: LookupMap::new(
+                StorageKey::ProtocolSettingsOfEras.into_bytes(),
+            ),
+            insurance_pool_balance: 0,
+            insurance_claim_events: LazyOption::new(
+                StorageKey::InsuranceClaimEvents.into_bytes(),
+                Some(&LookupArray::new(StorageKey::InsuranceClaimEventsMap)),
+            ),
+            insurance_compensations: LookupMap::new(
+                StorageKey::InsuranceCompensations.into_bytes(),
+            ),
+            covered_slashing_histories: LookupMap::new(
+                StorageKey::CoveredSlashingHistories.into_bytes(),
+            ),
+            peer_anchors: UnorderedMap::new(StorageKey::PeerAnchors.into_bytes()),
+            relayer_reimbursement_pool_balance: 0,
+            relayer_gas_usages: LookupMap::new(StorageKey::RelayerGasUsages.into_bytes()),
+            total_relayer_gas_of_eras: LookupMap::new(
+                StorageKey::TotalRelayerGasOfEras.into_bytes(),
+            ),
+            relayer_reimbursements_of_eras: LookupMap::new(
+                StorageKey::RelayerReimbursementsOfEras.into_bytes(),
+            ),
+            bootstrap_allocations: LookupMap::new(StorageKey::BootstrapAllocations.into_bytes()),
+            total_bootstrap_allocation: 0,
+            last_price_update_timestamps: LookupMap::new(
+                StorageKey::LastPriceUpdateTimestamps.into_bytes(),
+            ),
+            price_update_violations: LazyOption::new(
+                StorageKey::PriceUpdateViolations.into_bytes(),
+                Some(&LookupArray::new(StorageKey::PriceUpdateViolationsMap)),
+            ),
+            withdrawal_allowlists: LookupMap::new(StorageKey::WithdrawalAllowlists.into_bytes()),
+            staking_sorted_indices: LazyOption::new(
+                StorageKey::StakingSortedIndices.into_bytes(),
+                Some(&StakingSortedIndices::new()),
+            ),
+            accepted_payload_types_of_states: LookupMap::new(
+                StorageKey::AcceptedPayloadTypesOfStates.into_bytes(),
+            ),
+            stake_change_intents: UnorderedMap::new(StorageKey::StakeChangeIntents.into_bytes()),
+            applied_messages_of_eras: LookupMap::new(
+                StorageKey::AppliedMessagesOfEras.into_bytes(),
+            ),
+            validator_endpoints: LookupMap::new(StorageKey::ValidatorEndpoints.into_bytes()),
+            reward_withdrawal_histories: LookupMap::new(
+                StorageKey::RewardWithdrawalHistories.into_bytes(),
+            ),
+            user_appchain_notification_indexes: LookupMap::new(
+                StorageKey::UserAppchainNotificationIndexes.into_bytes(),
+            ),
+            held_era_reward_distributions: LookupMap::new(
+                StorageKey::HeldEraRewardDistributions.into_bytes(),
+            ),
+            wrapped_appchain_token_issuance: LazyOption::new(
+                StorageKey::WrappedAppchainTokenIssuance.into_bytes(),
+                Some(&WrappedAppchainTokenIssuance::default()),
+            ),
+            reward_roundings_of_eras: LookupMap::new(
+                StorageKey::RewardRoundingsOfEras.into_bytes(),
+            ),
+            delegation_locks: LookupMap::new(StorageKey::DelegationLocks.into_bytes()),
+            standby_validator_ids_of_eras: LookupMap::new(
+                StorageKey::StandbyValidatorIdsOfEras.into_bytes(),
+            ),
+            validator_set_override_proposals: LazyOption::new(
+                StorageKey::ValidatorSetOverrideProposals.into_bytes(),
+                Some(&LookupArray::new(
+                    StorageKey::ValidatorSetOverrideProposalsMap,
+                )),
+            ),
+            reward_claim_tip_percents: LookupMap::new(
+                StorageKey::RewardClaimTipPercents.into_bytes(),
+            ),
+            appchain_setting_change_proposals: LazyOption::new(
+                StorageKey::AppchainSettingChangeProposals.into_bytes(),
+                Some(&LookupArray::new(
+                    StorageKey::AppchainSettingChangeProposalsMap,
+                )),
+            ),
+            penalty_escrows: LazyOption::new(
+                StorageKey::PenaltyEscrows.into_bytes(),
+                Some(&LookupArray::new(StorageKey::PenaltyEscrowsMap)),
+            ),
+            penalty_escrow_balance: 0,
+            near_fungible_token_metadata_mirrors: LookupMap::new(
+                StorageKey::NearFungibleTokenMetadataMirrors.into_bytes(),
+            ),
+            latest_acknowledged_appchain_notification_index: None,
+            appchain_message_handler_configs: LookupMap::new(
+                StorageKey::AppchainMessageHandlerConfigs.into_bytes(),
+            ),
+            stake_migration_exports: LazyOption::new(
+                StorageKey::StakeMigrationExports.into_bytes(),
+                Some(&LookupArray::new(StorageKey::StakeMigrationExportsMap)),
+            ),
+            stake_migration_allocation: 0,
+            queued_validator_registrations: LazyOption::new(
+                StorageKey::QueuedValidatorRegistrations.into_bytes(),
+                Some(&LookupArray::new(
+                    StorageKey::QueuedValidatorRegistrationsMap,
+                )),
+            ),
+            next_queued_validator_registration_index: 0,
+            daily_validator_admissions: DailyValidatorAdmissions::default(),
+            validator_reward_vestings: LookupMap::new(
+                StorageKey::ValidatorRewardVestings.into_bytes(),
+            ),
+            relayer_bonds: UnorderedMap::new(StorageKey::RelayerBonds.into_bytes()),
+            relayer_bond_slashes: LazyOption::new(
+                StorageKey::RelayerBondSlashes.into_bytes(),
+                Some(&LookupArray::new(StorageKey::RelayerBondSlashesMap)),
+            ),
+            total_unbonded_stake: 0,
+            oct_balance_reconciliations: LazyOption::new(
+                StorageKey::OctBalanceReconciliations.into_bytes(),
+                Some(&LookupArray::new(StorageKey::OctBalanceReconciliationsMap)),
+            ),
+            staking_status: StakingStatus::Active,
+            staking_status_changes: LazyOption::new(
+                StorageKey::StakingStatusChanges.into_bytes(),
+                Some(&LookupArray::new(StorageKey::StakingStatusChangesMap)),
+            ),
+            withdrawal_receipts: LazyOption::new(
+                StorageKey::WithdrawalReceipts.into_bytes(),
+                Some(&LookupArray::new(StorageKey::WithdrawalReceiptsMap)),
+            ),
+            slash_histories: LazyOption::new(
+                StorageKey::SlashHistories.into_bytes(),
+                Some(&LookupArray::new(StorageKey::SlashHistoriesMap)),
+            ),
+            unwithdrawn_reward_references: LookupMap::new(
+                StorageKey::UnwithdrawnRewardReferences.into_bytes(),
+            ),
+            first_era_of_unwithdrawn_reward_references: 0,
+            treasury_routes: LookupMap::new(StorageKey::TreasuryRoutes.into_bytes()),
+            treasury_flows_of_eras: LookupMap::new(StorageKey::TreasuryFlowsOfEras.into_bytes()),
+            appchain_termination: LazyOption::new(
+                StorageKey::AppchainTermination.into_bytes(),
+                None,
+            ),
+            slash_history_indexes_of_validators: LookupMap::new(
+                StorageKey::SlashHistoryIndexesOfValidators.into_bytes(),
+            ),
         }
     }
     // Assert that the contract called by the owner.
@@ -329,7 +735,29 @@ impl AppchainAnchor {
         );
     }
     //
+    fn assert_guardian(&self) {
+        let anchor_settings = self.anchor_settings.get().unwrap();
+        let guardian_account = anchor_settings
+            .guardian_account
+            .expect("Guardian account is not set.");
+        assert_eq!(
+            env::predecessor_account_id(),
+            guardian_account,
+            "Only '{}' can call this function.",
+            guardian_account
+        );
+    }
+    //
     fn assert_relayer(&self) {
+        if self.protocol_settings.get().unwrap().minimum_relayer_bond.0 > 0 {
+            let relayer_id = env::predecessor_account_id();
+            assert!(
+                self.is_whitelisted_relayer(&relayer_id),
+                "Account '{}' is not a whitelisted relayer.",
+                relayer_id
+            );
+            return;
+        }
         let anchor_settings = self.anchor_settings.get().unwrap();
         let relayer_account = anchor_settings
             .relayer_account
@@ -401,6 +829,55 @@ impl AppchainAnchor {
             "Rewards withdrawal is now paused."
         );
     }
+    // Assert the staking status allows the staking actions which change the stakes.
+    fn assert_staking_is_active(&self) {
+        assert!(
+            self.staking_status == StakingStatus::Active,
+            "Staking is now {:?}. Reason: {}",
+            self.staking_status,
+            self.get_latest_staking_status_change_reason()
+        );
+    }
+    // Assert the staking status allows the withdrawals of unbonded stakes and rewards.
+    fn assert_staking_is_not_frozen(&self) {
+        assert!(
+            self.staking_status != StakingStatus::Frozen,
+            "Staking is now frozen. Reason: {}",
+            self.get_latest_staking_status_change_reason()
+        );
+    }
+    //
+    fn get_latest_staking_status_change_reason(&self) -> String {
+        let staking_status_changes = self.staking_status_changes.get().unwrap();
+        staking_status_changes
+            .get(&staking_status_changes.index_range().end_index.0)
+            .map_or(String::new(), |change| change.reason)
+    }
+    //
+    fn assert_appchain_is_not_terminating(&self) {
+        assert!(
+            self.appchain_state != AppchainState::Terminating,
+            "The appchain is terminating."
+        );
+    }
+    //
+    fn assert_not_in_maintenance_mode(&self) {
+        assert!(
+            !self.maintenance_mode_is_on,
+            "The contract is under maintenance. Please try again later."
+        );
+    }
+    // Assert the given account is not frozen by the guardian (or the freezing is expired).
+    fn assert_account_is_not_frozen(&self, account_id: &AccountId) {
+        if let Some(account_freezing) = self.frozen_accounts.get(account_id) {
+            assert!(
+                env::block_timestamp() >= account_freezing.expiry_timestamp.0,
+                "Account '{}' is frozen. Reason: {}",
+                account_id,
+                account_freezing.reason
+            );
+        }
+    }
     //
     fn assert_contract_account_of_wrapped_appchain_token_is_set(&self) {
         let wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
@@ -417,35 +894,43 @@ impl AppchainAnchor {
             "The deposit of the validator is too few.",
         );
         if let Some(total_stake) = total_stake {
-            if self.appchain_state.eq(&AppchainState::Active) {
-                let validator_set_histories = self.validator_set_histories.get().unwrap();
-                let validator_set = validator_set_histories
-                    .get(&validator_set_histories.index_range().end_index.0)
-                    .unwrap();
-                let maximum_allowed_deposit = validator_set.total_stake()
-                    * u128::from(protocol_settings.maximum_validator_stake_percent)
-                    / 100;
-                assert!(
-                    total_stake <= maximum_allowed_deposit,
-                    "The total stake of the validator is too much."
-                );
-            }
+            assert!(
+                self.is_total_stake_of_validator_allowed(&protocol_settings, total_stake),
+                "The total stake of the validator is too much."
+            );
+        }
+    }
+    // Whether the total stake of a validator is within the limit of
+    // `maximum_validator_stake_percent` of the total stake of the latest era.
+    fn is_total_stake_of_validator_allowed(
+        &self,
+        protocol_settings: &ProtocolSettings,
+        total_stake: u128,
+    ) -> bool {
+        if self.appchain_state.eq(&AppchainState::Active) {
+            let validator_set_histories = self.validator_set_histories.get().unwrap();
+            let validator_set = validator_set_histories
+                .get(&validator_set_histories.index_range().end_index.0)
+                .unwrap();
+            let maximum_allowed_deposit = validator_set.total_stake()
+                * u128::from(protocol_settings.maximum_validator_stake_percent)
+                / 100;
+            total_stake <= maximum_allowed_deposit
+        } else {
+            true
         }
     }
     /// Set the price (in USD) of OCT token
     pub fn set_price_of_oct_token(&mut self, price: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_token_price_maintainer();
         let mut oct_token = self.oct_token.get().unwrap();
+        if !self.check_price_update(PricedToken::OctToken, oct_token.price_in_usd.0, price.0) {
+            return;
+        }
         oct_token.price_in_usd = price;
         self.oct_token.set(&oct_token);
     }
-    ///
-    pub fn get_market_value_of_staked_oct_token(&self) -> U128 {
-        U128::from(
-            self.next_validator_set.get().unwrap().total_stake() / OCT_DECIMALS_VALUE
-                * self.oct_token.get().unwrap().price_in_usd.0,
-        )
-    }
 }
 
 #[near_bindgen]
@@ -456,6 +941,7 @@ impl Ownable for AppchainAnchor {
     }
     //
     fn set_owner(&mut self, owner: AccountId) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert!(!owner.eq(&self.owner), "Owner is not changed.",);
         self.owner = owner;
@@ -471,6 +957,8 @@ impl AppchainAnchor {
         amount: U128,
         msg: String,
     ) -> PromiseOrValue<U128> {
+        self.assert_not_in_maintenance_mode();
+        self.assert_appchain_is_not_terminating();
         self.assert_asset_transfer_is_not_paused();
         log!(
             "Deposit {} from '@{}' received. msg: '{}'",
@@ -493,7 +981,12 @@ impl AppchainAnchor {
             FTDepositMessage::RegisterValidator { .. }
             | FTDepositMessage::IncreaseStake
             | FTDepositMessage::RegisterDelegator { .. }
-            | FTDepositMessage::IncreaseDelegation { .. } => {
+            | FTDepositMessage::IncreaseDelegation { .. }
+            | FTDepositMessage::IncreaseStakeFor { .. }
+            | FTDepositMessage::IncreaseDelegationFor { .. }
+            | FTDepositMessage::IncreaseVestingLockedStakeFor { .. }
+            | FTDepositMessage::FundStakeMigrationAllocation
+            | FTDepositMessage::BondRelayer => {
                 assert!(
                     predecessor_account_id.eq(&self.oct_token.get().unwrap().contract_account),
                     "Received invalid deposit '{}' in contract '{}' from '{}'. Return deposit.",
@@ -503,13 +996,42 @@ impl AppchainAnchor {
                 );
                 self.internal_process_oct_deposit(sender_id, amount, deposit_message)
             }
-            FTDepositMessage::BridgeToAppchain { .. } => self
-                .internal_process_near_fungible_token_deposit(
+            FTDepositMessage::IncreaseSecondaryStake => {
+                assert!(
+                    self.protocol_settings
+                        .get()
+                        .unwrap()
+                        .secondary_stake_token
+                        .map_or(false, |token| predecessor_account_id
+                            .eq(&token.contract_account)),
+                    "Received invalid deposit '{}' in contract '{}' from '{}'. Return deposit.",
+                    &amount.0,
+                    &predecessor_account_id,
+                    &sender_id,
+                );
+                self.internal_process_secondary_stake_deposit(sender_id, amount)
+            }
+            FTDepositMessage::BridgeToAppchain { .. } => {
+                if self
+                    .wrapped_appchain_token
+                    .get()
+                    .unwrap()
+                    .contract_account
+                    .map_or(false, |account| predecessor_account_id.eq(&account))
+                {
+                    return self.internal_process_wrapped_appchain_token_deposit(
+                        sender_id,
+                        amount,
+                        deposit_message,
+                    );
+                }
+                self.internal_process_near_fungible_token_deposit(
                     predecessor_account_id,
                     sender_id,
                     amount,
                     deposit_message,
-                ),
+                )
+            }
         }
     }
 }
@@ -524,6 +1046,8 @@ impl AppchainAnchor {
         token_id: TokenId,
         msg: String,
     ) -> PromiseOrValue<bool> {
+        self.assert_not_in_maintenance_mode();
+        self.assert_appchain_is_not_terminating();
         self.assert_asset_transfer_is_not_paused();
         log!(
             "NFT transfer from '@{}' received. msg: '{}'",
@@ -554,6 +1078,136 @@ impl AppchainAnchor {
 }
 
 impl AppchainAnchor {
+    /// Get the protocol settings which are effective in a certain era.
+    /// For the eras before the snapshots are recorded, the current settings are returned.
+    pub fn get_effective_protocol_settings_of(&self, era_number: u64) -> ProtocolSettings {
+        self.protocol_settings_of_eras
+            .get(&era_number)
+            .map(|protocol_settings| protocol_settings.into_current())
+            .unwrap_or_else(|| self.protocol_settings.get().unwrap())
+    }
+    /// Limit the weights of validators in the validator list of a certain era to the stake
+    /// which has been bonded for at least one full era, that is the stake which is also
+    /// in the validator set of the previous era. The new validators get zero weight.
+    pub fn apply_bonded_era_guard_to_weights(
+        &self,
+        validator_list: Vec<AppchainValidator>,
+        era_number: u64,
+    ) -> Vec<AppchainValidator> {
+        if era_number == 0 {
+            return validator_list;
+        }
+        let previous_validator_set = match self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .get(&(era_number - 1))
+        {
+            Some(validator_set) => validator_set,
+            None => return validator_list,
+        };
+        validator_list
+            .into_iter()
+            .map(|mut validator| {
+                let previous_stake = previous_validator_set
+                    .get_validator(&validator.validator_id)
+                    .map_or(0, |previous_validator| previous_validator.total_stake);
+                validator.weight = U128::from(validator.total_stake.0.min(previous_stake));
+                validator
+            })
+            .collect()
+    }
+    /// Select the validators which exceed `maximum_validator_count_per_operator` of protocol
+    /// settings in a validator set. The validators of a same operator are ranked by their
+    /// total stake (and by account id for the same stake), the ones out of the limit are
+    /// selected as standby. The validators without declared operator are not limited.
+    pub fn select_standby_validators<V: ValidatorSetViewer>(
+        &self,
+        validator_set: &V,
+        maximum_validator_count_per_operator: u64,
+    ) -> Vec<AccountId> {
+        if maximum_validator_count_per_operator == 0 {
+            return Vec::new();
+        }
+        let validator_profiles = self.validator_profiles.get().unwrap();
+        let mut validators_of_operators: BTreeMap<String, Vec<(AccountId, Balance)>> =
+            BTreeMap::new();
+        for validator_id in validator_set.get_validator_ids() {
+            let operator = match validator_profiles
+                .get(&validator_id)
+                .and_then(|profile| {
+                    profile
+                        .profile
+                        .get(VALIDATOR_PROFILE_KEY_OF_OPERATOR)
+                        .cloned()
+                })
+                .map(|operator| operator.trim().to_lowercase())
+            {
+                Some(operator) if !operator.is_empty() => operator,
+                _ => continue,
+            };
+            let total_stake = validator_set
+                .get_validator(&validator_id)
+                .unwrap()
+                .total_stake;
+            validators_of_operators
+                .entry(operator)
+                .or_default()
+                .push((validator_id, total_stake));
+        }
+        let mut standby_validator_ids = Vec::new();
+        for (_, mut validators) in validators_of_operators {
+            validators.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            standby_validator_ids.extend(
+                validators
+                    .into_iter()
+                    .skip(usize::try_from(maximum_validator_count_per_operator).unwrap())
+                    .map(|(validator_id, _)| validator_id),
+            );
+        }
+        standby_validator_ids
+    }
+    /// Mark the standby validators in the validator list, with zero weight.
+    pub fn apply_standby_to_validator_list(
+        &self,
+        validator_list: Vec<AppchainValidator>,
+        standby_validator_ids: &Vec<AccountId>,
+    ) -> Vec<AppchainValidator> {
+        validator_list
+            .into_iter()
+            .map(|mut validator| {
+                if standby_validator_ids.contains(&validator.validator_id) {
+                    validator.is_standby = true;
+                    validator.weight = U128::from(0);
+                }
+                validator
+            })
+            .collect()
+    }
+    /// Get the drift of the observed duration of a closed era from the expected one,
+    /// return `None` if the checking is disabled or the era is not closed yet.
+    pub fn get_era_duration_drift_of(&self, era_number: u64) -> Option<EraDurationDrift> {
+        let appchain_settings = self.appchain_settings.get().unwrap();
+        let expected_duration_in_secs = appchain_settings.expected_era_duration_in_secs.0;
+        if expected_duration_in_secs == 0 {
+            return None;
+        }
+        let observed_duration_in_secs =
+            self.era_summaries.get(&era_number)?.duration?.0 / NANO_SECONDS_MULTIPLE;
+        let drift_in_secs = match observed_duration_in_secs > expected_duration_in_secs {
+            true => observed_duration_in_secs - expected_duration_in_secs,
+            false => expected_duration_in_secs - observed_duration_in_secs,
+        };
+        let drift_percent = drift_in_secs * 100 / expected_duration_in_secs;
+        Some(EraDurationDrift {
+            era_number: U64::from(era_number),
+            expected_duration_in_secs: U64::from(expected_duration_in_secs),
+            observed_duration_in_secs: U64::from(observed_duration_in_secs),
+            drift_percent: U64::from(drift_percent),
+            is_beyond_tolerance: drift_percent
+                > u64::from(appchain_settings.era_duration_drift_tolerance_percent),
+        })
+    }
     ///
     pub fn internal_append_appchain_notification(
         &mut self,
@@ -570,6 +1224,19 @@ impl AppchainAnchor {
             });
         self.appchain_notification_histories
             .set(&appchain_notification_histories);
+        if let Some(account_id) = appchain_notification_history
+            .appchain_notification
+            .bridging_account_id()
+        {
+            let mut indexes = self
+                .user_appchain_notification_indexes
+                .get(account_id)
+                .unwrap_or_default();
+            indexes.push(appchain_notification_history.index.0);
+            self.user_appchain_notification_indexes
+                .insert(account_id, &indexes);
+        }
+        Nep297Event::AppchainNotificationRecorded(&appchain_notification_history).emit();
         appchain_notification_history
     }
     ///
@@ -599,6 +1266,73 @@ impl AppchainAnchor {
             Gas::ONE_TERA.mul(T_GAS_FOR_SYNC_STATE_TO_REGISTRY),
         );
     }
+    /// Check a price update of a token against the limits in anchor settings.
+    /// If the update violates the limits, the violation is recorded (and asset transfer is
+    /// paused if configured) and `false` is returned, the price should not be changed then.
+    pub fn check_price_update(
+        &mut self,
+        token: PricedToken,
+        current_price: Balance,
+        new_price: Balance,
+    ) -> bool {
+        let anchor_settings = self.anchor_settings.get().unwrap();
+        let mut violation_reason = None;
+        if let Some(last_update_timestamp) = self.last_price_update_timestamps.get(&token) {
+            if env::block_timestamp()
+                < last_update_timestamp
+                    + anchor_settings.minimum_interval_of_price_updates.0 * NANO_SECONDS_MULTIPLE
+            {
+                violation_reason = Some(PriceUpdateViolationReason::TooFrequent {
+                    last_update_timestamp: U64::from(last_update_timestamp),
+                });
+            }
+        }
+        if violation_reason.is_none()
+            && current_price > 0
+            && anchor_settings.maximum_price_change_percent_per_update > 0
+        {
+            let change = match new_price > current_price {
+                true => new_price - current_price,
+                false => current_price - new_price,
+            };
+            let change_percent = change * 100 / current_price;
+            if change_percent > u128::from(anchor_settings.maximum_price_change_percent_per_update)
+            {
+                violation_reason = Some(PriceUpdateViolationReason::ChangeTooLarge {
+                    change_percent: U64::from(u64::try_from(change_percent).unwrap_or(u64::MAX)),
+                });
+            }
+        }
+        match violation_reason {
+            Some(reason) => {
+                let mut price_update_violations = self.price_update_violations.get().unwrap();
+                let violation = price_update_violations.append(&mut PriceUpdateViolation {
+                    token,
+                    current_price: U128::from(current_price),
+                    rejected_price: U128::from(new_price),
+                    reason,
+                    block_height: U64::from(env::block_height()),
+                    timestamp: U64::from(env::block_timestamp()),
+                    index: U64::from(0),
+                });
+                self.price_update_violations.set(&price_update_violations);
+                log!(
+                    "Price update of {:?} is rejected: {:?}",
+                    violation.token,
+                    violation.reason
+                );
+                if anchor_settings.pause_asset_transfer_on_price_update_violation {
+                    self.asset_transfer_is_paused = true;
+                }
+                false
+            }
+            None => {
+                self.last_price_update_timestamps
+                    .insert(&token, &env::block_timestamp());
+                true
+            }
+        }
+    }
 }
 
 pub fn get_random_in_near(buf: &mut [u8]) -> Result<(), Error> {
@@ -622,6 +1356,157 @@ impl IndexedAndClearable for AppchainNotificationHistory {
     }
 }
 
+impl IndexedAndClearable for AppchainRuntimeUpgradeNotice {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+            MultiTxsOperationProcessingResult::NeedMoreGas
+        } else {
+            MultiTxsOperationProcessingResult::Ok
+        }
+    }
+}
+
+impl IndexedAndClearable for PriceUpdateViolation {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
+impl IndexedAndClearable for InsuranceClaimEvent {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+            MultiTxsOperationProcessingResult::NeedMoreGas
+        } else {
+            MultiTxsOperationProcessingResult::Ok
+        }
+    }
+}
+
+impl IndexedAndClearable for ValidatorSetOverrideProposal {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
+impl IndexedAndClearable for AppchainSettingChangeProposal {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
+impl IndexedAndClearable for PenaltyEscrow {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
+impl IndexedAndClearable for StakeMigrationExport {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
+impl IndexedAndClearable for QueuedValidatorRegistration {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
+impl IndexedAndClearable for RelayerBondSlash {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
+impl IndexedAndClearable for OctBalanceReconciliation {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
+impl IndexedAndClearable for WithdrawalReceipt {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
+impl IndexedAndClearable for SlashHistory {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
+impl IndexedAndClearable for StakingStatusChange {
+    //
+    fn set_index(&mut self, index: &u64) {
+        self.index = U64::from(*index);
+    }
+    //
+    fn clear_extra_storage(&mut self) -> MultiTxsOperationProcessingResult {
+        MultiTxsOperationProcessingResult::Ok
+    }
+}
+
 impl IndexedAndClearable for StakingHistory {
     //
     fn set_index(&mut self, index: &u64) {
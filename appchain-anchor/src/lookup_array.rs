@@ -41,21 +41,23 @@ where
     pub fn get(&self, index: &u64) -> Option<T> {
         self.lookup_map.get(index)
     }
-    ///
+    /// Get the records from the given start index, no more than 50 records are returned.
     pub fn get_slice_of(&self, start_index: &u64, quantity: Option<u64>) -> Vec<T> {
         let mut results = Vec::<T>::new();
+        if quantity == Some(0) {
+            return results;
+        }
         let start_index = match self.start_index > *start_index {
             true => self.start_index,
             false => *start_index,
         };
-        let mut end_index = start_index
-            + match quantity {
-                Some(quantity) => match quantity > 50 {
-                    true => 49,
-                    false => quantity - 1,
-                },
-                None => 49,
-            };
+        let mut end_index = start_index.saturating_add(match quantity {
+            Some(quantity) => match quantity > 50 {
+                true => 49,
+                false => quantity - 1,
+            },
+            None => 49,
+        });
         end_index = match end_index < self.end_index {
             true => end_index,
             false => self.end_index,
@@ -85,12 +87,16 @@ where
             end_index: U64::from(self.end_index),
         }
     }
-    ///
-    pub fn append(&mut self, record: &mut T) -> T {
-        let index = match self.lookup_map.contains_key(&0) {
+    /// Get the index of the next record to be appended.
+    pub fn next_index(&self) -> u64 {
+        match self.lookup_map.contains_key(&0) {
             true => self.end_index + 1,
             false => 0,
-        };
+        }
+    }
+    ///
+    pub fn append(&mut self, record: &mut T) -> T {
+        let index = self.next_index();
         record.set_index(&index);
         self.lookup_map.insert(&index, &record);
         self.end_index = index;
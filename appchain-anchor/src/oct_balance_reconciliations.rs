@@ -0,0 +1,138 @@
+use crate::*;
+use core::convert::TryFrom;
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use near_sdk::json_types::I128;
+
+pub trait OctBalanceReconciliationResolver {
+    /// Resolver for querying balance of this contract in OCT token contract
+    fn resolve_oct_balance_reconciliation(&mut self, keeper_id: AccountId);
+}
+
+impl AppchainAnchor {
+    // Query the balance of this contract in OCT token contract, and compare it with
+    // the obligations of this contract in the callback.
+    pub fn internal_reconcile_oct_balance(&mut self) {
+        let anchor_settings = self.anchor_settings.get().unwrap();
+        if let Some(reconciliation) = self.last_oct_balance_reconciliation() {
+            assert!(
+                env::block_timestamp()
+                    >= reconciliation.timestamp.0
+                        + anchor_settings
+                            .minimum_interval_of_oct_balance_reconciliations
+                            .0
+                            * NANO_SECONDS_MULTIPLE,
+                "The interval since the latest reconciliation is too short."
+            );
+        }
+        ext_ft_core::ext(self.oct_token.get().unwrap().contract_account)
+            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+            .with_unused_gas_weight(0)
+            .ft_balance_of(env::current_account_id())
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RECONCILING_OCT_BALANCE))
+                    .with_unused_gas_weight(0)
+                    .resolve_oct_balance_reconciliation(env::predecessor_account_id()),
+            );
+    }
+    //
+    pub fn calculate_oct_obligations(&self) -> OctObligations {
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        let secondary_stake: Balance = next_validator_set
+            .get_validator_ids()
+            .iter()
+            .filter_map(|validator_id| next_validator_set.get_validator(validator_id))
+            .map(|validator| validator.secondary_stake)
+            .sum();
+        let bonded_stake = next_validator_set.validator_set().total_stake() - secondary_stake;
+        let relayer_bonds: Balance = self.relayer_bonds.values().map(|bond| bond.amount.0).sum();
+        let queued_registration_deposits: Balance = self
+            .get_pending_queued_validator_registrations()
+            .iter()
+            .map(|registration| registration.deposit_amount.0)
+            .sum();
+        OctObligations {
+            bonded_stake: U128::from(bonded_stake),
+            unbonded_stake: U128::from(self.total_unbonded_stake),
+            penalty_escrow_balance: U128::from(self.penalty_escrow_balance),
+            stake_migration_allocation: U128::from(self.stake_migration_allocation),
+            relayer_bonds: U128::from(relayer_bonds),
+            queued_registration_deposits: U128::from(queued_registration_deposits),
+            total: U128::from(
+                bonded_stake
+                    + self.total_unbonded_stake
+                    + self.penalty_escrow_balance
+                    + self.stake_migration_allocation
+                    + relayer_bonds
+                    + queued_registration_deposits,
+            ),
+        }
+    }
+    // Decrease the total amount of the unbonded stakes which are not withdrawn yet.
+    // A shortfall means the total drifts from the unbonded stakes, which must not be hidden.
+    pub fn decrease_total_unbonded_stake(&mut self, amount: Balance) {
+        self.total_unbonded_stake = self
+            .total_unbonded_stake
+            .checked_sub(amount)
+            .expect("The total unbonded stake is less than the withdrawn amount.");
+    }
+    //
+    pub fn last_oct_balance_reconciliation(&self) -> Option<OctBalanceReconciliation> {
+        let reconciliations = self.oct_balance_reconciliations.get().unwrap();
+        reconciliations.get(&reconciliations.index_range().end_index.0)
+    }
+}
+
+#[near_bindgen]
+impl OctBalanceReconciliationResolver for AppchainAnchor {
+    //
+    fn resolve_oct_balance_reconciliation(&mut self, keeper_id: AccountId) {
+        assert_self();
+        let actual_balance = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => {
+                match near_sdk::serde_json::from_slice::<U128>(&value) {
+                    Ok(balance) => balance,
+                    Err(_) => {
+                        log!("Invalid balance returned from OCT token contract.");
+                        return;
+                    }
+                }
+            }
+            PromiseResult::Failed => {
+                log!("Failed to query balance of this contract in OCT token contract.");
+                return;
+            }
+        };
+        let obligations = self.calculate_oct_obligations();
+        let drift = i128::try_from(actual_balance.0).unwrap()
+            - i128::try_from(obligations.total.0).unwrap();
+        let mut reconciliations = self.oct_balance_reconciliations.get().unwrap();
+        let reconciliation = reconciliations.append(&mut OctBalanceReconciliation {
+            obligations,
+            actual_balance,
+            drift: I128::from(drift),
+            keeper_id,
+            timestamp: U64::from(env::block_timestamp()),
+            index: U64::from(0),
+        });
+        self.oct_balance_reconciliations.set(&reconciliations);
+        match drift {
+            0 => log!(
+                "Balance of OCT token matches the obligations. Reconciliation: '{}'",
+                reconciliation.index.0
+            ),
+            drift if drift < 0 => log!(
+                "ALERT: Balance of OCT token is short of obligations by '{}'. Reconciliation: '{}'",
+                -drift,
+                reconciliation.index.0
+            ),
+            drift => log!(
+                "Balance of OCT token exceeds the obligations by '{}'. Reconciliation: '{}'",
+                drift,
+                reconciliation.index.0
+            ),
+        }
+    }
+}
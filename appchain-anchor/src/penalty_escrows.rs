@@ -0,0 +1,124 @@
+use crate::*;
+use near_contract_standards::fungible_token::core::ext_ft_core;
+
+impl AppchainAnchor {
+    // Hold the stake slashed by a certain staking history in escrow
+    // for the dispute period in protocol settings.
+    pub fn internal_escrow_penalty(
+        &mut self,
+        account_id: &AccountId,
+        amount: Balance,
+        staking_history_index: u64,
+    ) {
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let dispute_end_timestamp = env::block_timestamp()
+            + self
+                .time_provider()
+                .nanoseconds_of_days(protocol_settings.penalty_dispute_period.0);
+        let mut penalty_escrows = self.penalty_escrows.get().unwrap();
+        let escrow = penalty_escrows.append(&mut PenaltyEscrow {
+            account_id: account_id.clone(),
+            amount: U128::from(amount),
+            staking_history_index: U64::from(staking_history_index),
+            escrowed_timestamp: U64::from(env::block_timestamp()),
+            dispute_end_timestamp: U64::from(dispute_end_timestamp),
+            status: PenaltyEscrowStatus::Escrowed,
+            index: U64::from(0),
+        });
+        self.penalty_escrows.set(&penalty_escrows);
+        self.penalty_escrow_balance += amount;
+        log!(
+            "Slashed stake of '{}' is held in penalty escrow '{}'. Amount: '{}'",
+            account_id,
+            escrow.index.0,
+            amount
+        );
+    }
+    //
+    fn get_penalty_escrow(&self, escrow_index: u64) -> PenaltyEscrow {
+        self.penalty_escrows
+            .get()
+            .unwrap()
+            .get(&escrow_index)
+            .expect("The penalty escrow is not existed.")
+    }
+    //
+    fn update_penalty_escrow_status(
+        &mut self,
+        escrow: &mut PenaltyEscrow,
+        status: PenaltyEscrowStatus,
+    ) {
+        match status {
+            PenaltyEscrowStatus::Escrowed | PenaltyEscrowStatus::Appealed => (),
+            _ => self.penalty_escrow_balance -= escrow.amount.0,
+        }
+        escrow.status = status;
+        let mut penalty_escrows = self.penalty_escrows.get().unwrap();
+        penalty_escrows.insert(&escrow.index.0, escrow);
+        self.penalty_escrows.set(&penalty_escrows);
+    }
+    // Record the appeal of the slashed account against a penalty in its dispute window.
+    pub fn internal_appeal_penalty(&mut self, account_id: &AccountId, escrow_index: u64) {
+        let mut escrow = self.get_penalty_escrow(escrow_index);
+        assert!(
+            escrow.account_id.eq(account_id),
+            "Only the slashed account can appeal."
+        );
+        assert!(
+            escrow.status == PenaltyEscrowStatus::Escrowed,
+            "The penalty escrow is not appealable."
+        );
+        assert!(
+            env::block_timestamp() < escrow.dispute_end_timestamp.0,
+            "The dispute window of the penalty is over."
+        );
+        self.update_penalty_escrow_status(&mut escrow, PenaltyEscrowStatus::Appealed);
+    }
+    // Conclude the appeal against a penalty with the decision of the owner. The slashed stake
+    // is returned if the appeal is upheld, otherwise the escrow can be settled
+    // once the dispute window is over.
+    pub fn internal_conclude_penalty_appeal(&mut self, escrow_index: u64, is_upheld: bool) {
+        let mut escrow = self.get_penalty_escrow(escrow_index);
+        assert!(
+            escrow.status == PenaltyEscrowStatus::Appealed,
+            "The penalty is not appealed."
+        );
+        match is_upheld {
+            true => {
+                self.update_penalty_escrow_status(&mut escrow, PenaltyEscrowStatus::Returned);
+                ext_ft_core::ext(self.oct_token.get().unwrap().contract_account)
+                    .with_attached_deposit(1)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+                    .with_unused_gas_weight(0)
+                    .ft_transfer(escrow.account_id, escrow.amount, None);
+            }
+            false => self.update_penalty_escrow_status(&mut escrow, PenaltyEscrowStatus::Escrowed),
+        }
+    }
+    // Settle a penalty escrow whose dispute window is over, the slashed stake is transferred
+    // to the treasury, or burnt (kept in this contract) if there is no destination of it.
+    pub fn internal_settle_penalty_escrow(&mut self, escrow_index: u64) {
+        let mut escrow = self.get_penalty_escrow(escrow_index);
+        assert!(
+            escrow.status == PenaltyEscrowStatus::Escrowed,
+            "The penalty escrow is not settleable."
+        );
+        assert!(
+            env::block_timestamp() >= escrow.dispute_end_timestamp.0,
+            "The dispute window of the penalty is not over yet."
+        );
+        match self.treasury_source_has_destination(&TreasurySource::SettledPenaltyEscrow) {
+            true => {
+                self.update_penalty_escrow_status(
+                    &mut escrow,
+                    PenaltyEscrowStatus::TransferredToTreasury,
+                );
+                self.transfer_oct_token_to_treasury(
+                    TreasurySource::SettledPenaltyEscrow,
+                    escrow.amount.0,
+                );
+            }
+            false => self.update_penalty_escrow_status(&mut escrow, PenaltyEscrowStatus::Burnt),
+        }
+    }
+}
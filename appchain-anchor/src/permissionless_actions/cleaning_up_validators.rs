@@ -0,0 +1,107 @@
+use crate::*;
+
+impl AppchainAnchor {
+    //
+    pub fn internal_clean_up_deregistered_validators(
+        &mut self,
+        validator_ids: &Vec<AccountId>,
+    ) -> MultiTxsOperationProcessingResult {
+        let mut validator_profiles = self.validator_profiles.get().unwrap();
+        for validator_id in validator_ids {
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                self.validator_profiles.set(&validator_profiles);
+                return MultiTxsOperationProcessingResult::NeedMoreGas;
+            }
+            if validator_profiles.get(validator_id).is_none() {
+                continue;
+            }
+            if let Err(reason) = self.check_validator_is_fully_deregistered(validator_id) {
+                self.validator_profiles.set(&validator_profiles);
+                return MultiTxsOperationProcessingResult::Error(format!(
+                    "Validator '{}' cannot be cleaned up: {}",
+                    validator_id, reason
+                ));
+            }
+            validator_profiles.remove(validator_id);
+            self.commission_payout_accounts.remove(validator_id);
+            self.validator_retirements.remove(validator_id);
+            self.validator_endpoints.remove(validator_id);
+            if self
+                .next_validator_set
+                .get()
+                .unwrap()
+                .get_validator_count_of(validator_id)
+                == 0
+            {
+                self.withdrawal_allowlists.remove(validator_id);
+            }
+            log!(
+                "Residual storage of deregistered validator '{}' is cleaned up.",
+                validator_id
+            );
+        }
+        self.validator_profiles.set(&validator_profiles);
+        MultiTxsOperationProcessingResult::Ok
+    }
+    // Check that the validator is unbonded from the next validator set, is not included
+    // in any validator set of the eras whose reward can still be withdrawn, and has
+    // withdrawn all of its unbonded stakes and vesting rewards.
+    fn check_validator_is_fully_deregistered(
+        &self,
+        validator_id: &AccountId,
+    ) -> Result<(), String> {
+        if self.frozen_accounts.contains_key(validator_id) {
+            return Err("the account is frozen.".to_string());
+        }
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        if next_validator_set.contains_validator(validator_id)
+            || next_validator_set
+                .get_unbonding_validator_ids()
+                .contains(validator_id)
+            || next_validator_set
+                .get_auto_unbonding_validator_ids()
+                .contains(validator_id)
+        {
+            return Err("it is not unbonded yet.".to_string());
+        }
+        if self.unbonded_stakes.contains_key(validator_id)
+            || self.unbonded_secondary_stakes.contains_key(validator_id)
+        {
+            return Err("its unbonded stakes are not withdrawn yet.".to_string());
+        }
+        if self.validator_reward_vestings.contains_key(validator_id) {
+            return Err("its vesting rewards are not fully released yet.".to_string());
+        }
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let end_era = validator_set_histories.index_range().end_index.0;
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let start_era = match end_era > protocol_settings.maximum_era_count_of_unwithdrawn_reward.0
+        {
+            true => end_era - protocol_settings.maximum_era_count_of_unwithdrawn_reward.0 + 1,
+            false => 0,
+        };
+        for era_number in start_era..end_era + 1 {
+            if let Some(validator_set) = validator_set_histories.get(&era_number) {
+                if validator_set.contains_validator(validator_id) {
+                    return Err(format!(
+                        "it is in the validator set of era '{}'.",
+                        era_number
+                    ));
+                }
+            }
+            if self
+                .unwithdrawn_validator_rewards
+                .contains_key(&(era_number, validator_id.clone()))
+                || self
+                    .unwithdrawn_commission_rewards
+                    .contains_key(&(era_number, validator_id.clone()))
+            {
+                return Err(format!(
+                    "its reward of era '{}' is not withdrawn yet.",
+                    era_number
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,16 @@
 use super::{AppchainMessagesProcessingContext, ResultOfLoopingValidatorSet};
+use crate::validator_set::{Delegator, Validator};
 use crate::*;
 use core::convert::{TryFrom, TryInto};
 
+/// The window of time weighting for the rewards of an era, which is the previous era.
+struct TimeWeightingWindow {
+    start_staking_history_index: u64,
+    end_staking_history_index: u64,
+    start_timestamp: Timestamp,
+    end_timestamp: Timestamp,
+}
+
 impl ValidatorSetProcessingStatus {
     ///
     pub fn can_distribute_reward(&self) -> bool {
@@ -13,6 +22,19 @@ impl ValidatorSetProcessingStatus {
     }
 }
 
+impl RewardRoundingOfEra {
+    /// The rounding in `Floor` policy, with the total stake of the validators sharing the reward.
+    pub fn floor(total_stake: Balance) -> Self {
+        Self {
+            policy: RewardRoundingPolicy::Floor,
+            total_stake_units: U128::from(total_stake / OCT_DECIMALS_VALUE),
+            rounded_up_validator_ids: Vec::new(),
+            accounted_reward: U128::from(0),
+            remainder: None,
+        }
+    }
+}
+
 impl AppchainAnchor {
     //
     pub fn internal_start_distributing_reward_of_era(
@@ -22,6 +44,7 @@ impl AppchainAnchor {
         appchain_message_nonce: u32,
         era_number: u64,
         unprofitable_validator_ids: &Vec<String>,
+        validator_set_hash: &Option<String>,
     ) -> MultiTxsOperationProcessingResult {
         if !validator_set_histories.contains(&era_number) {
             return MultiTxsOperationProcessingResult::Error(format!(
@@ -34,38 +57,39 @@ impl AppchainAnchor {
                 "Validator set is not ready for distributing reward."
             ));
         }
-        let mut unprofitable_validator_ids_in_near = Vec::<AccountId>::new();
-        let validator_profiles = self.validator_profiles.get().unwrap();
-        for id_in_appchain in unprofitable_validator_ids {
-            let account_id_in_appchain = AccountIdInAppchain::new(
-                Some(id_in_appchain.clone()),
-                &self.appchain_template_type,
-            );
-            match validator_profiles.get_by_id_in_appchain(&account_id_in_appchain.to_string()) {
-                Some(validator_profile) => {
-                    if validator_set.contains_validator(&validator_profile.validator_id) {
-                        if !unprofitable_validator_ids_in_near
-                            .contains(&validator_profile.validator_id)
-                        {
-                            unprofitable_validator_ids_in_near.push(validator_profile.validator_id);
-                        }
-                    } else {
-                        return MultiTxsOperationProcessingResult::Error(format!(
-                            "Validator id in appchain '{}' is not a valid validator in era '{}'.",
-                            id_in_appchain, era_number
-                        ));
-                    }
-                }
-                None => {
-                    return MultiTxsOperationProcessingResult::Error(format!(
-                        "Invalid validator id in appchain: '{}'",
-                        id_in_appchain
-                    ));
-                }
+        if let Some(validator_set_hash) = validator_set_hash {
+            let expected_hash = validator_set.validator_set_hash();
+            if !expected_hash.eq(validator_set_hash) {
+                return MultiTxsOperationProcessingResult::Error(format!(
+                    "Hash of validator set of era '{}' mismatched. Expected '{}', got '{}'.",
+                    era_number, expected_hash, validator_set_hash
+                ));
             }
         }
+        let unprofitable_validator_ids_in_near = match self
+            .get_unprofitable_validator_ids_in_near(&validator_set, unprofitable_validator_ids)
+        {
+            Ok(validator_ids) => validator_ids,
+            Err(message) => return MultiTxsOperationProcessingResult::Error(message),
+        };
         validator_set.set_unprofitable_validator_ids(unprofitable_validator_ids_in_near);
-        validator_set.calculate_valid_total_stake();
+        let excluded_validator_ids = self.get_reward_excluded_validator_ids(
+            era_number,
+            validator_set.unprofitable_validator_ids(),
+        );
+        validator_set.calculate_valid_total_stake(&excluded_validator_ids);
+        self.reward_roundings_of_eras.insert(
+            &era_number,
+            &calculate_reward_rounding(
+                &self
+                    .get_effective_protocol_settings_of(era_number)
+                    .reward_rounding_policy,
+                self.get_distributable_era_reward(era_number),
+                &validator_set,
+                &excluded_validator_ids,
+                validator_set.valid_total_stake(),
+            ),
+        );
         validator_set.set_processing_status(ValidatorSetProcessingStatus::DistributingReward {
             appchain_message_nonce,
             distributing_validator_index: U64::from(0),
@@ -83,6 +107,12 @@ impl AppchainAnchor {
             processing_context,
         );
         if result.is_ok() {
+            if let Some(mut era_summary) = self.era_summaries.get(&era_number) {
+                era_summary.reward_paid = appchain_settings.era_reward;
+                self.era_summaries.insert(&era_number, &era_summary);
+            }
+            self.insurance_pool_balance +=
+                appchain_settings.era_reward.0 - self.get_distributable_era_reward(era_number);
             result = MultiTxsOperationProcessingResult::NeedMoreGas;
         }
         result
@@ -113,14 +143,24 @@ impl AppchainAnchor {
                 distributing_delegator_index,
             } => {
                 let validator_commission_percent = u128::from(
-                    self.protocol_settings
-                        .get()
-                        .unwrap()
+                    self.get_effective_protocol_settings_of(era_number)
                         .validator_commission_percent,
                 );
                 let mut validator_index = distributing_validator_index.0;
                 let mut delegator_index = distributing_delegator_index.0;
-                let era_reward = self.appchain_settings.get().unwrap().era_reward;
+                let era_reward = self.get_distributable_era_reward(era_number);
+                let commission_rebate_thresholds =
+                    self.get_commission_rebate_thresholds(validator_set_histories, era_number);
+                let time_weighting_window =
+                    self.get_time_weighting_window(validator_set_histories, era_number);
+                // The distribution which is started before the rounding policy is introduced
+                // is performed in `Floor` policy.
+                let mut reward_rounding = self
+                    .reward_roundings_of_eras
+                    .get(&era_number)
+                    .unwrap_or_else(|| {
+                        RewardRoundingOfEra::floor(validator_set.valid_total_stake())
+                    });
                 while processing_context.used_gas_of_current_function_call()
                     < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING)
                 {
@@ -129,14 +169,19 @@ impl AppchainAnchor {
                         &mut validator_set,
                         validator_index,
                         delegator_index,
-                        era_reward.0,
+                        era_reward,
                         validator_commission_percent,
+                        &commission_rebate_thresholds,
+                        &time_weighting_window,
+                        &mut reward_rounding,
                     ) {
                         ResultOfLoopingValidatorSet::NoMoreDelegator => {
                             validator_index += 1;
                             delegator_index = 0;
                         }
                         ResultOfLoopingValidatorSet::NoMoreValidator => {
+                            self.reward_roundings_of_eras
+                                .insert(&era_number, &reward_rounding);
                             validator_set.set_processing_status(
                                 ValidatorSetProcessingStatus::CheckingForAutoUnbondingValidator {
                                     unprofitable_validator_index: U64::from(0),
@@ -148,6 +193,8 @@ impl AppchainAnchor {
                         ResultOfLoopingValidatorSet::NeedToContinue => delegator_index += 1,
                     }
                 }
+                self.reward_roundings_of_eras
+                    .insert(&era_number, &reward_rounding);
                 validator_set.set_processing_status(
                     ValidatorSetProcessingStatus::DistributingReward {
                         appchain_message_nonce,
@@ -226,6 +273,13 @@ impl AppchainAnchor {
                 MultiTxsOperationProcessingResult::NeedMoreGas
             }
             ValidatorSetProcessingStatus::Completed => {
+                self.transfer_reward_remainder_to_treasury(processing_context, era_number);
+                Nep297Event::EraRewardDistributed {
+                    era_number: U64::from(era_number),
+                    era_reward: U128::from(self.get_distributable_era_reward(era_number)),
+                    reward_rounding: self.reward_roundings_of_eras.get(&era_number),
+                }
+                .emit();
                 self.record_appchain_message_processing_result(
                     &AppchainMessageProcessingResult::Ok {
                         nonce: processing_context.processing_nonce().unwrap_or(0),
@@ -240,6 +294,578 @@ impl AppchainAnchor {
             }
         }
     }
+    /// Calculate the reward distribution of validators in a certain era without changing
+    /// any state, starting from the validator with index `start_index` in the era.
+    ///
+    /// If the reward of the era is concluded by a staged appchain message which is not
+    /// applied yet, the unprofitable validators carried by the message are used.
+    pub fn internal_preview_reward_distribution(
+        &self,
+        era_number: u64,
+        start_index: u64,
+        quantity: u64,
+    ) -> Vec<ValidatorRewardPreview> {
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let validator_set = match validator_set_histories.get(&era_number) {
+            Some(validator_set) => validator_set,
+            None => return Vec::new(),
+        };
+        let unprofitable_validator_ids = match validator_set.processing_status() {
+            ValidatorSetProcessingStatus::DistributingReward { .. } => {
+                validator_set.unprofitable_validator_ids()
+            }
+            ValidatorSetProcessingStatus::ReadyForDistributingReward
+            | ValidatorSetProcessingStatus::Completed => {
+                match self.get_staged_unprofitable_validator_ids_of_era(era_number) {
+                    Some(validator_ids) => self
+                        .get_unprofitable_validator_ids_in_near(&validator_set, &validator_ids)
+                        .unwrap_or_else(|message| panic!("{}", message)),
+                    None => validator_set.unprofitable_validator_ids(),
+                }
+            }
+            _ => return Vec::new(),
+        };
+        let unprofitable_validator_ids =
+            self.get_reward_excluded_validator_ids(era_number, unprofitable_validator_ids);
+        let valid_total_stake = validator_set.total_stake()
+            - unprofitable_validator_ids
+                .iter()
+                .map(|validator_id| {
+                    validator_set
+                        .get_validator(validator_id)
+                        .unwrap()
+                        .total_stake
+                })
+                .sum::<Balance>();
+        let era_reward = self.get_distributable_era_reward(era_number);
+        let reward_rounding = match self.reward_roundings_of_eras.get(&era_number) {
+            Some(reward_rounding) => reward_rounding,
+            None => match validator_set.processing_status() {
+                ValidatorSetProcessingStatus::ReadyForDistributingReward => {
+                    calculate_reward_rounding(
+                        &self
+                            .get_effective_protocol_settings_of(era_number)
+                            .reward_rounding_policy,
+                        era_reward,
+                        &validator_set,
+                        &unprofitable_validator_ids,
+                        valid_total_stake,
+                    )
+                }
+                _ => RewardRoundingOfEra::floor(valid_total_stake),
+            },
+        };
+        let default_commission_percent = u128::from(
+            self.get_effective_protocol_settings_of(era_number)
+                .validator_commission_percent,
+        );
+        let commission_rebate_thresholds =
+            self.get_commission_rebate_thresholds(&validator_set_histories, era_number);
+        let time_weighting_window =
+            self.get_time_weighting_window(&validator_set_histories, era_number);
+        let mut results = Vec::<ValidatorRewardPreview>::new();
+        let mut index = start_index;
+        while index < validator_set.validator_count() && index < start_index + quantity {
+            let validator = validator_set.get_validator_by_index(&index).unwrap();
+            index += 1;
+            let delegator_ids = validator_set.get_delegator_ids_of(&validator.validator_id);
+            if validator.is_jailed || unprofitable_validator_ids.contains(&validator.validator_id) {
+                results.push(ValidatorRewardPreview {
+                    validator_id: validator.validator_id.clone(),
+                    total_stake: U128::from(validator.total_stake),
+                    is_excluded: true,
+                    total_reward: U128::from(0),
+                    commission_reward: U128::from(0),
+                    validator_reward: U128::from(0),
+                    delegator_rewards: delegator_ids
+                        .iter()
+                        .map(|delegator_id| DelegatorRewardPreview {
+                            delegator_id: delegator_id.clone(),
+                            reward: U128::from(0),
+                            commission_rebate: U128::from(0),
+                        })
+                        .collect(),
+                });
+                continue;
+            }
+            let (total_reward_of_validator, validator_commission_reward) =
+                calculate_total_reward_of_validator(
+                    era_reward,
+                    &reward_rounding,
+                    &validator,
+                    default_commission_percent,
+                );
+            let delegator_rewards = delegator_ids
+                .iter()
+                .map(|delegator_id| {
+                    let delegator = validator_set
+                        .get_delegator(delegator_id, &validator.validator_id)
+                        .unwrap();
+                    let (reward, commission_rebate) = calculate_delegator_reward(
+                        total_reward_of_validator,
+                        validator_commission_reward,
+                        &validator,
+                        &delegator,
+                        &commission_rebate_thresholds,
+                    );
+                    let withheld_reward = calculate_withheld_reward(
+                        reward + commission_rebate,
+                        self.get_unearned_stake_of(
+                            &time_weighting_window,
+                            &validator.validator_id,
+                            Some(delegator_id),
+                        ),
+                        delegator.deposit_amount,
+                    );
+                    DelegatorRewardPreview {
+                        delegator_id: delegator_id.clone(),
+                        reward: U128::from(reward + commission_rebate - withheld_reward),
+                        commission_rebate: U128::from(commission_rebate),
+                    }
+                })
+                .collect::<Vec<DelegatorRewardPreview>>();
+            let total_commission_rebate = delegator_rewards
+                .iter()
+                .map(|reward| reward.commission_rebate.0)
+                .sum::<Balance>();
+            let validator_reward = calculate_validator_reward(
+                total_reward_of_validator,
+                validator_commission_reward,
+                &validator,
+            );
+            let withheld_reward = calculate_withheld_reward(
+                validator_reward - validator_commission_reward,
+                self.get_unearned_stake_of(&time_weighting_window, &validator.validator_id, None),
+                validator.deposit_amount,
+            );
+            results.push(ValidatorRewardPreview {
+                validator_id: validator.validator_id.clone(),
+                total_stake: U128::from(validator.total_stake),
+                is_excluded: false,
+                total_reward: U128::from(total_reward_of_validator),
+                commission_reward: U128::from(validator_commission_reward),
+                validator_reward: U128::from(
+                    validator_reward - total_commission_rebate - withheld_reward,
+                ),
+                delegator_rewards,
+            });
+        }
+        results
+    }
+    /// Calculate the exposure of the delegations of a delegator in the latest era,
+    /// assuming that all validators in the era are profitable.
+    pub fn internal_get_delegator_exposure(
+        &self,
+        delegator_id: &AccountId,
+    ) -> Option<DelegatorExposure> {
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let era_number = validator_set_histories.index_range().end_index.0;
+        let validator_set = validator_set_histories.get(&era_number)?;
+        let validator_ids = validator_set.get_validator_ids_of(delegator_id);
+        if validator_ids.is_empty() {
+            return None;
+        }
+        let era_reward = self.get_distributable_era_reward(era_number);
+        let default_commission_percent = self
+            .get_effective_protocol_settings_of(era_number)
+            .validator_commission_percent;
+        let commission_rebate_thresholds =
+            self.get_commission_rebate_thresholds(&validator_set_histories, era_number);
+        let mut delegations = Vec::<DelegationExposure>::new();
+        for validator_id in validator_ids {
+            let validator = validator_set.get_validator(&validator_id).unwrap();
+            let delegator = validator_set
+                .get_delegator(delegator_id, &validator_id)
+                .unwrap();
+            let expected_reward = match validator.total_stake / OCT_DECIMALS_VALUE > 0 {
+                true => {
+                    let (total_reward_of_validator, validator_commission_reward) =
+                        calculate_total_reward_of_validator(
+                            era_reward,
+                            &RewardRoundingOfEra::floor(validator_set.total_stake()),
+                            &validator,
+                            u128::from(default_commission_percent),
+                        );
+                    let (reward, commission_rebate) = calculate_delegator_reward(
+                        total_reward_of_validator,
+                        validator_commission_reward,
+                        &validator,
+                        &delegator,
+                        &commission_rebate_thresholds,
+                    );
+                    reward + commission_rebate
+                }
+                false => 0,
+            };
+            delegations.push(DelegationExposure {
+                validator_id: validator_id.clone(),
+                delegated_amount: U128::from(delegator.deposit_amount),
+                validator_total_stake: U128::from(validator.total_stake),
+                share_of_validator_stake: match validator.total_stake > 0 {
+                    true => u16::try_from(delegator.deposit_amount * 10000 / validator.total_stake)
+                        .unwrap_or(10000),
+                    false => 0,
+                },
+                commission_percent: validator
+                    .commission_percent
+                    .unwrap_or(default_commission_percent),
+                expected_reward: U128::from(expected_reward),
+                slash_exposure: U128::from(delegator.deposit_amount),
+                is_jailed: validator.is_jailed,
+            });
+        }
+        Some(DelegatorExposure {
+            delegator_id: delegator_id.clone(),
+            era_number: U64::from(era_number),
+            total_delegated_amount: U128::from(
+                delegations
+                    .iter()
+                    .map(|delegation| delegation.delegated_amount.0)
+                    .sum::<Balance>(),
+            ),
+            expected_reward: U128::from(
+                delegations
+                    .iter()
+                    .map(|delegation| delegation.expected_reward.0)
+                    .sum::<Balance>(),
+            ),
+            delegations,
+        })
+    }
+    /// Recalculate the rewards of validators (and their delegators) in a certain era whose
+    /// reward is distributed, starting from the validator with index `start_index`, and
+    /// compare them with the stored rewards in the validator set of the era.
+    ///
+    /// The recalculation uses the current era reward in appchain settings, so the result
+    /// is only meaningful if the era reward is not changed after the distribution.
+    pub fn internal_audit_era_distribution(
+        &self,
+        era_number: u64,
+        start_index: u64,
+        quantity: u64,
+    ) -> Option<EraDistributionAudit> {
+        let validator_set = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .get(&era_number)?;
+        if validator_set.processing_status() != ValidatorSetProcessingStatus::Completed {
+            return None;
+        }
+        let previews = self.internal_preview_reward_distribution(era_number, start_index, quantity);
+        let mut divergences = Vec::<RewardDivergence>::new();
+        for preview in previews.iter() {
+            let stored_reward = validator_set
+                .get_validator_rewards_of(&preview.validator_id)
+                .unwrap_or(0);
+            if stored_reward != preview.validator_reward.0 {
+                divergences.push(RewardDivergence {
+                    validator_id: preview.validator_id.clone(),
+                    delegator_id: None,
+                    expected_reward: preview.validator_reward,
+                    stored_reward: U128::from(stored_reward),
+                });
+            }
+            for delegator_reward in preview.delegator_rewards.iter() {
+                let stored_reward = validator_set
+                    .get_delegator_rewards_of(&delegator_reward.delegator_id, &preview.validator_id)
+                    .unwrap_or(0);
+                if stored_reward != delegator_reward.reward.0 {
+                    divergences.push(RewardDivergence {
+                        validator_id: preview.validator_id.clone(),
+                        delegator_id: Some(delegator_reward.delegator_id.clone()),
+                        expected_reward: delegator_reward.reward,
+                        stored_reward: U128::from(stored_reward),
+                    });
+                }
+            }
+        }
+        Some(EraDistributionAudit {
+            era_number: U64::from(era_number),
+            audited_validator_count: U64::from(u64::try_from(previews.len()).unwrap()),
+            divergences,
+        })
+    }
+    // Get the unprofitable validator ids (in appchain) of a certain era, which are carried by
+    // a staged appchain message that is not applied yet.
+    fn get_staged_unprofitable_validator_ids_of_era(&self, era_number: u64) -> Option<Vec<String>> {
+        let permissionless_actions_status = self.permissionless_actions_status.get().unwrap();
+        let appchain_messages = self.appchain_messages.get().unwrap();
+        for nonce in permissionless_actions_status.latest_applied_appchain_message_nonce + 1
+            ..permissionless_actions_status.max_nonce_of_staged_appchain_messages + 1
+        {
+            if let Some(AppchainMessage {
+                appchain_event:
+                    AppchainEvent::EraRewardConcluded {
+                        era_number: concluded_era_number,
+                        unprofitable_validator_ids,
+                        ..
+                    },
+                ..
+            }) = appchain_messages.get_message(&nonce)
+            {
+                if u64::from(concluded_era_number) == era_number {
+                    return Some(unprofitable_validator_ids);
+                }
+            }
+        }
+        None
+    }
+    // Check the reward distribution concluded by an appchain message against the circuit
+    // breaker in anchor settings. Return the reason of holding if the distribution is
+    // anomalous and is not confirmed by the owner yet.
+    pub fn check_era_reward_circuit_breaker(
+        &mut self,
+        validator_set_histories: &LookupArray<ValidatorSetOfEra>,
+        appchain_message: &AppchainMessage,
+    ) -> Option<String> {
+        let (era_number, unprofitable_validator_ids) = match &appchain_message.appchain_event {
+            AppchainEvent::EraRewardConcluded {
+                era_number,
+                unprofitable_validator_ids,
+                ..
+            } => (u64::from(*era_number), unprofitable_validator_ids),
+            _ => return None,
+        };
+        if let Some(held_distribution) = self.held_era_reward_distributions.get(&era_number) {
+            if held_distribution.appchain_message_nonce == appchain_message.nonce {
+                return match held_distribution.is_confirmed {
+                    true => None,
+                    false => Some(format!(
+                        "Reward distribution of era '{}' is held for confirmation.",
+                        era_number
+                    )),
+                };
+            }
+        }
+        // The invalid messages are handled in the normal processing.
+        let validator_set = validator_set_histories.get(&era_number)?;
+        let unprofitable_validator_ids_in_near = self
+            .get_unprofitable_validator_ids_in_near(&validator_set, unprofitable_validator_ids)
+            .ok()?;
+        let anchor_settings = self.anchor_settings.get().unwrap();
+        let era_reward = self.appchain_settings.get().unwrap().era_reward.0;
+        let unprofitable_validator_count =
+            u64::try_from(unprofitable_validator_ids_in_near.len()).unwrap();
+        let mut anomalies = Vec::<String>::new();
+        let maximum_change_percent = anchor_settings.maximum_era_reward_change_percent;
+        if maximum_change_percent > 0 && era_number > 0 {
+            if let Some(previous_reward) = self
+                .era_summaries
+                .get(&(era_number - 1))
+                .map(|era_summary| era_summary.reward_paid.0)
+                .filter(|reward| *reward > 0)
+            {
+                let change = match era_reward > previous_reward {
+                    true => era_reward - previous_reward,
+                    false => previous_reward - era_reward,
+                };
+                if change * 100 > previous_reward * u128::from(maximum_change_percent) {
+                    anomalies.push(format!(
+                        "The era reward '{}' changes more than {}% from '{}' of the previous era.",
+                        era_reward, maximum_change_percent, previous_reward
+                    ));
+                }
+            }
+        }
+        if anchor_settings.maximum_unprofitable_validator_percent > 0
+            && unprofitable_validator_count * 100
+                > validator_set.validator_count()
+                    * u64::from(anchor_settings.maximum_unprofitable_validator_percent)
+        {
+            anomalies.push(format!(
+                "'{}' of '{}' validators are unprofitable, more than {}%.",
+                unprofitable_validator_count,
+                validator_set.validator_count(),
+                anchor_settings.maximum_unprofitable_validator_percent
+            ));
+        }
+        if anomalies.is_empty() {
+            return None;
+        }
+        let message = format!(
+            "Reward distribution of era '{}' is held for confirmation: {}",
+            era_number,
+            anomalies.join(" ")
+        );
+        self.held_era_reward_distributions.insert(
+            &era_number,
+            &HeldEraRewardDistribution {
+                era_number: U64::from(era_number),
+                appchain_message_nonce: appchain_message.nonce,
+                era_reward: U128::from(era_reward),
+                unprofitable_validator_count: U64::from(unprofitable_validator_count),
+                validator_count: U64::from(validator_set.validator_count()),
+                anomalies,
+                held_timestamp: U64::from(env::block_timestamp()),
+                is_confirmed: false,
+            },
+        );
+        Some(message)
+    }
+    // Convert the unprofitable validator ids in appchain to the account ids in NEAR.
+    fn get_unprofitable_validator_ids_in_near(
+        &self,
+        validator_set: &ValidatorSetOfEra,
+        unprofitable_validator_ids: &Vec<String>,
+    ) -> Result<Vec<AccountId>, String> {
+        let mut unprofitable_validator_ids_in_near = Vec::<AccountId>::new();
+        let validator_profiles = self.validator_profiles.get().unwrap();
+        for id_in_appchain in unprofitable_validator_ids {
+            let account_id_in_appchain = AccountIdInAppchain::new(
+                Some(id_in_appchain.clone()),
+                &self.appchain_template_type,
+            );
+            match validator_profiles.get_by_id_in_appchain(&account_id_in_appchain.to_string()) {
+                Some(validator_profile) => {
+                    if validator_set.contains_validator(&validator_profile.validator_id) {
+                        if !unprofitable_validator_ids_in_near
+                            .contains(&validator_profile.validator_id)
+                        {
+                            unprofitable_validator_ids_in_near.push(validator_profile.validator_id);
+                        }
+                    } else {
+                        return Err(format!(
+                            "Validator id in appchain '{}' is not a valid validator in era '{}'.",
+                            id_in_appchain,
+                            validator_set.era_number()
+                        ));
+                    }
+                }
+                None => {
+                    return Err(format!(
+                        "Invalid validator id in appchain: '{}'",
+                        id_in_appchain
+                    ));
+                }
+            }
+        }
+        Ok(unprofitable_validator_ids_in_near)
+    }
+    // Get the era reward which is distributed to validators and delegators, excluding
+    // the part which is put into the insurance pool.
+    fn get_distributable_era_reward(&self, era_number: u64) -> Balance {
+        let era_reward = self.appchain_settings.get().unwrap().era_reward.0;
+        let insurance_pool_reward_percent = u128::from(
+            self.get_effective_protocol_settings_of(era_number)
+                .insurance_pool_reward_percent,
+        );
+        era_reward - era_reward * insurance_pool_reward_percent / 100
+    }
+    /// Get the thresholds of registration timestamp of delegators for the loyalty tiers,
+    /// as `(timestamp, commission_rebate_percent)`.
+    ///
+    /// A delegator in the validator set of era `E` is continuously bonded for at least
+    /// `N` eras, if it is registered before the start of era `E + 1 - N`.
+    fn get_commission_rebate_thresholds(
+        &self,
+        validator_set_histories: &LookupArray<ValidatorSetOfEra>,
+        era_number: u64,
+    ) -> Vec<(Timestamp, u128)> {
+        let protocol_settings = self.get_effective_protocol_settings_of(era_number);
+        protocol_settings
+            .loyalty_tiers_of_delegator
+            .iter()
+            .filter(|tier| era_number + 1 >= tier.minimum_bonded_era_count.0)
+            .filter_map(|tier| {
+                validator_set_histories
+                    .get(&(era_number + 1 - tier.minimum_bonded_era_count.0))
+                    .map(|validator_set| {
+                        (
+                            validator_set.start_timestamp(),
+                            u128::from(tier.commission_rebate_percent),
+                        )
+                    })
+            })
+            .collect()
+    }
+    // Get the window of time weighting for the rewards of a certain era, which is
+    // the previous era. `None` if the time-weighted reward is not enabled in the era.
+    fn get_time_weighting_window(
+        &self,
+        validator_set_histories: &LookupArray<ValidatorSetOfEra>,
+        era_number: u64,
+    ) -> Option<TimeWeightingWindow> {
+        if era_number == 0
+            || !self
+                .get_effective_protocol_settings_of(era_number)
+                .time_weighted_reward
+        {
+            return None;
+        }
+        let previous_validator_set = validator_set_histories.get(&(era_number - 1))?;
+        let validator_set = validator_set_histories.get(&era_number)?;
+        if validator_set.start_timestamp() <= previous_validator_set.start_timestamp() {
+            return None;
+        }
+        Some(TimeWeightingWindow {
+            start_staking_history_index: previous_validator_set.staking_history_index(),
+            end_staking_history_index: validator_set.staking_history_index(),
+            start_timestamp: previous_validator_set.start_timestamp(),
+            end_timestamp: validator_set.start_timestamp(),
+        })
+    }
+    // Get the part of the stake of a validator (or a delegation if `delegator_id` is given)
+    // which is not earned in the time weighting window. The stake increased in the window
+    // is weighted by the time from its increasing to the end of the window.
+    fn get_unearned_stake_of(
+        &self,
+        time_weighting_window: &Option<TimeWeightingWindow>,
+        validator_id: &AccountId,
+        delegator_id: Option<&AccountId>,
+    ) -> Balance {
+        let window = match time_weighting_window {
+            Some(window) => window,
+            None => return 0,
+        };
+        let staking_histories = self.staking_histories.get().unwrap();
+        self.user_staking_histories
+            .get()
+            .unwrap()
+            .get_staking_history_indexes_of(delegator_id.unwrap_or(validator_id))
+            .iter()
+            .filter(|index| {
+                **index > window.start_staking_history_index
+                    && **index <= window.end_staking_history_index
+            })
+            .filter_map(|index| staking_histories.get(index))
+            .map(|staking_history| {
+                let increased_amount = match &staking_history.staking_fact {
+                    StakingFact::ValidatorRegistered {
+                        validator_id: id,
+                        amount,
+                        ..
+                    }
+                    | StakingFact::StakeIncreased {
+                        validator_id: id,
+                        amount,
+                    } if delegator_id.is_none() && id.eq(validator_id) => amount.0,
+                    StakingFact::SecondaryStakeIncreased {
+                        validator_id: id,
+                        stake,
+                        ..
+                    } if delegator_id.is_none() && id.eq(validator_id) => stake.0,
+                    StakingFact::DelegatorRegistered {
+                        validator_id: id,
+                        amount,
+                        ..
+                    }
+                    | StakingFact::DelegationIncreased {
+                        validator_id: id,
+                        amount,
+                        ..
+                    } if delegator_id.is_some() && id.eq(validator_id) => amount.0,
+                    _ => 0,
+                };
+                let timestamp = staking_history
+                    .timestamp
+                    .0
+                    .clamp(window.start_timestamp, window.end_timestamp);
+                increased_amount * u128::from(timestamp - window.start_timestamp)
+                    / u128::from(window.end_timestamp - window.start_timestamp)
+            })
+            .sum()
+    }
     //
     fn distribute_reward_in_validator_set(
         &mut self,
@@ -249,6 +875,9 @@ impl AppchainAnchor {
         delegator_index: u64,
         era_reward: Balance,
         validator_commission_percent: u128,
+        commission_rebate_thresholds: &Vec<(Timestamp, u128)>,
+        time_weighting_window: &Option<TimeWeightingWindow>,
+        reward_rounding: &mut RewardRoundingOfEra,
     ) -> ResultOfLoopingValidatorSet {
         if validator_index >= validator_set.validator_count() {
             return ResultOfLoopingValidatorSet::NoMoreValidator;
@@ -256,23 +885,59 @@ impl AppchainAnchor {
         let validator = validator_set
             .get_validator_by_index(&validator_index)
             .unwrap();
-        if validator_set
-            .unprofitable_validator_ids()
+        if self
+            .get_reward_excluded_validator_ids(
+                validator_set.era_number(),
+                validator_set.unprofitable_validator_ids(),
+            )
             .contains(&validator.validator_id)
         {
             return ResultOfLoopingValidatorSet::NoMoreDelegator;
         }
-        let total_reward_of_validator = era_reward * (validator.total_stake / OCT_DECIMALS_VALUE)
-            / (validator_set.valid_total_stake() / OCT_DECIMALS_VALUE);
-        let validator_commission_reward =
-            total_reward_of_validator * validator_commission_percent / 100;
+        let (total_reward_of_validator, validator_commission_reward) =
+            calculate_total_reward_of_validator(
+                era_reward,
+                reward_rounding,
+                &validator,
+                validator_commission_percent,
+            );
+        // The share of a jailed validator is not distributed, and is not a part of the remainder.
+        if validator.is_jailed {
+            reward_rounding.accounted_reward.0 += total_reward_of_validator;
+            return ResultOfLoopingValidatorSet::NoMoreDelegator;
+        }
         let mut reward_distribution_records = self.reward_distribution_records.get().unwrap();
         if delegator_index >= validator_set.get_delegator_count_of(&validator.validator_id) {
-            let validator_reward = validator_commission_reward
-                + (total_reward_of_validator - validator_commission_reward)
-                    * (validator.deposit_amount / OCT_DECIMALS_VALUE)
-                    / (validator.total_stake / OCT_DECIMALS_VALUE);
-            self.add_reward_for_validator(validator_set, &validator.validator_id, validator_reward);
+            let commission_rebate = validator_set.get_commission_rebate_of(&validator.validator_id);
+            let validator_reward = calculate_validator_reward(
+                total_reward_of_validator,
+                validator_commission_reward,
+                &validator,
+            );
+            let withheld_reward = calculate_withheld_reward(
+                validator_reward - validator_commission_reward,
+                self.get_unearned_stake_of(time_weighting_window, &validator.validator_id, None),
+                validator.deposit_amount,
+            );
+            self.insurance_pool_balance += withheld_reward;
+            reward_rounding.accounted_reward.0 += validator_reward - commission_rebate;
+            self.add_reward_for_validator(
+                validator_set,
+                &validator.validator_id,
+                validator_reward - commission_rebate - withheld_reward,
+            );
+            // The commission (excluding the rebates for delegators) is routed to
+            // the payout account of the validator, if it is set.
+            if self
+                .commission_payout_accounts
+                .contains_key(&validator.validator_id)
+            {
+                self.move_reward_to_commission_of_validator(
+                    validator_set.era_number(),
+                    &validator.validator_id,
+                    validator_commission_reward.saturating_sub(commission_rebate),
+                );
+            }
             reward_distribution_records.insert(
                 appchain_message_nonce,
                 validator_set.era_number(),
@@ -292,14 +957,32 @@ impl AppchainAnchor {
             &Some(delegator.delegator_id.clone()),
             &delegator.validator_id,
         ) {
-            let delegator_reward = (total_reward_of_validator - validator_commission_reward)
-                * (delegator.deposit_amount / OCT_DECIMALS_VALUE)
-                / (validator.total_stake / OCT_DECIMALS_VALUE);
+            let (delegator_reward, commission_rebate) = calculate_delegator_reward(
+                total_reward_of_validator,
+                validator_commission_reward,
+                &validator,
+                &delegator,
+                commission_rebate_thresholds,
+            );
+            if commission_rebate > 0 {
+                validator_set.add_commission_rebate(&delegator.validator_id, commission_rebate);
+            }
+            let withheld_reward = calculate_withheld_reward(
+                delegator_reward + commission_rebate,
+                self.get_unearned_stake_of(
+                    time_weighting_window,
+                    &delegator.validator_id,
+                    Some(&delegator.delegator_id),
+                ),
+                delegator.deposit_amount,
+            );
+            self.insurance_pool_balance += withheld_reward;
+            reward_rounding.accounted_reward.0 += delegator_reward + commission_rebate;
             self.add_reward_for_delegator(
                 validator_set,
                 &delegator.delegator_id,
                 &delegator.validator_id,
-                delegator_reward,
+                delegator_reward + commission_rebate - withheld_reward,
             );
             reward_distribution_records.insert(
                 appchain_message_nonce,
@@ -312,6 +995,54 @@ impl AppchainAnchor {
         }
         return ResultOfLoopingValidatorSet::NeedToContinue;
     }
+    // Get the validators which are excluded from the reward distribution of an era,
+    // that is, the unprofitable validators and the standby validators.
+    fn get_reward_excluded_validator_ids(
+        &self,
+        era_number: u64,
+        unprofitable_validator_ids: Vec<AccountId>,
+    ) -> Vec<AccountId> {
+        let mut validator_ids = unprofitable_validator_ids;
+        for validator_id in self
+            .standby_validator_ids_of_eras
+            .get(&era_number)
+            .unwrap_or_default()
+        {
+            if !validator_ids.contains(&validator_id) {
+                validator_ids.push(validator_id);
+            }
+        }
+        validator_ids
+    }
+    // Transfer the remainder of the reward of an era which is not distributed to the treasury.
+    // The remainder is kept in this contract if there is no destination of it.
+    fn transfer_reward_remainder_to_treasury(
+        &mut self,
+        processing_context: &mut AppchainMessagesProcessingContext,
+        era_number: u64,
+    ) {
+        let mut reward_rounding = match self.reward_roundings_of_eras.get(&era_number) {
+            Some(reward_rounding) if reward_rounding.remainder.is_none() => reward_rounding,
+            _ => return,
+        };
+        let remainder = self
+            .get_distributable_era_reward(era_number)
+            .saturating_sub(reward_rounding.accounted_reward.0);
+        reward_rounding.remainder = Some(U128::from(remainder));
+        self.reward_roundings_of_eras
+            .insert(&era_number, &reward_rounding);
+        if remainder == 0 {
+            return;
+        }
+        let wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
+        let transfer_count = self.internal_route_to_treasury(
+            TreasurySource::RewardRemainder,
+            &wrapped_appchain_token.contract_account.unwrap(),
+            remainder,
+        );
+        processing_context
+            .add_prepaid_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER * transfer_count));
+    }
     //
     fn add_reward_for_validator(
         &mut self,
@@ -335,6 +1066,34 @@ impl AppchainAnchor {
             &(validator_set.era_number(), validator_id.clone()),
             &unwithdrawn_validator_reward,
         );
+        self.add_unwithdrawn_reward_reference(
+            validator_id,
+            UnwithdrawnRewardReference {
+                era_number: validator_set.era_number(),
+                validator_id: None,
+            },
+        );
+    }
+    // Move a part of unwithdrawn reward of a validator to its unwithdrawn commission.
+    fn move_reward_to_commission_of_validator(
+        &mut self,
+        era_number: u64,
+        validator_id: &AccountId,
+        amount: u128,
+    ) {
+        let key = (era_number, validator_id.clone());
+        let unwithdrawn_validator_reward =
+            self.unwithdrawn_validator_rewards.get(&key).unwrap_or(0);
+        let amount = amount.min(unwithdrawn_validator_reward);
+        if amount == 0 {
+            return;
+        }
+        self.unwithdrawn_validator_rewards
+            .insert(&key, &(unwithdrawn_validator_reward - amount));
+        let unwithdrawn_commission_reward =
+            self.unwithdrawn_commission_rewards.get(&key).unwrap_or(0) + amount;
+        self.unwithdrawn_commission_rewards
+            .insert(&key, &unwithdrawn_commission_reward);
     }
     //
     fn add_reward_for_delegator(
@@ -366,5 +1125,131 @@ impl AppchainAnchor {
             ),
             &unwithdrawn_delegator_reward,
         );
+        self.add_unwithdrawn_reward_reference(
+            delegator_id,
+            UnwithdrawnRewardReference {
+                era_number: validator_set.era_number(),
+                validator_id: Some(validator_id.clone()),
+            },
+        );
+    }
+}
+
+// Calculate the total reward of a validator (including the rewards of its delegators)
+// and the commission of the validator, as `(total_reward, commission_reward)`.
+fn calculate_total_reward_of_validator(
+    era_reward: Balance,
+    reward_rounding: &RewardRoundingOfEra,
+    validator: &Validator,
+    default_commission_percent: u128,
+) -> (Balance, Balance) {
+    let validator_commission_percent = validator
+        .commission_percent
+        .map_or(default_commission_percent, u128::from);
+    let mut total_reward_of_validator = era_reward * (validator.total_stake / OCT_DECIMALS_VALUE)
+        / reward_rounding.total_stake_units.0;
+    if reward_rounding
+        .rounded_up_validator_ids
+        .contains(&validator.validator_id)
+    {
+        total_reward_of_validator += 1;
+    }
+    (
+        total_reward_of_validator,
+        total_reward_of_validator * validator_commission_percent / 100,
+    )
+}
+
+// Calculate the rounding of the shares of validators (excluding the unprofitable ones)
+// in the reward of an era.
+fn calculate_reward_rounding(
+    policy: &RewardRoundingPolicy,
+    era_reward: Balance,
+    validator_set: &ValidatorSetOfEra,
+    unprofitable_validator_ids: &Vec<AccountId>,
+    valid_total_stake: Balance,
+) -> RewardRoundingOfEra {
+    if *policy == RewardRoundingPolicy::Floor {
+        return RewardRoundingOfEra::floor(valid_total_stake);
+    }
+    let stake_units = (0..validator_set.validator_count())
+        .filter_map(|index| validator_set.get_validator_by_index(&index))
+        .filter(|validator| !unprofitable_validator_ids.contains(&validator.validator_id))
+        .map(|validator| {
+            (
+                validator.validator_id,
+                validator.total_stake / OCT_DECIMALS_VALUE,
+            )
+        })
+        .collect::<Vec<(AccountId, u128)>>();
+    let total_stake_units = stake_units.iter().map(|(_, units)| units).sum::<u128>();
+    if total_stake_units == 0 {
+        return RewardRoundingOfEra::floor(valid_total_stake);
+    }
+    let rounded_down_reward = stake_units
+        .iter()
+        .map(|(_, units)| era_reward * units / total_stake_units)
+        .sum::<Balance>();
+    let mut remainders = stake_units
+        .into_iter()
+        .map(|(validator_id, units)| (validator_id, era_reward * units % total_stake_units))
+        .collect::<Vec<(AccountId, u128)>>();
+    // The sorting is stable, so the validators with a same remainder keep their order.
+    remainders.sort_by(|a, b| b.1.cmp(&a.1));
+    RewardRoundingOfEra {
+        policy: policy.clone(),
+        total_stake_units: U128::from(total_stake_units),
+        rounded_up_validator_ids: remainders
+            .into_iter()
+            .take(usize::try_from(era_reward - rounded_down_reward).unwrap())
+            .map(|(validator_id, _)| validator_id)
+            .collect(),
+        accounted_reward: U128::from(0),
+        remainder: None,
+    }
+}
+
+// Calculate the reward of a validator, excluding the commission rebates for its delegators.
+fn calculate_validator_reward(
+    total_reward_of_validator: Balance,
+    validator_commission_reward: Balance,
+    validator: &Validator,
+) -> Balance {
+    validator_commission_reward
+        + (total_reward_of_validator - validator_commission_reward)
+            * (validator.deposit_amount / OCT_DECIMALS_VALUE)
+            / (validator.total_stake / OCT_DECIMALS_VALUE)
+}
+
+// Calculate the reward of a delegator, as `(reward, commission_rebate)`.
+fn calculate_delegator_reward(
+    total_reward_of_validator: Balance,
+    validator_commission_reward: Balance,
+    validator: &Validator,
+    delegator: &Delegator,
+    commission_rebate_thresholds: &Vec<(Timestamp, u128)>,
+) -> (Balance, Balance) {
+    let delegator_reward = (total_reward_of_validator - validator_commission_reward)
+        * (delegator.deposit_amount / OCT_DECIMALS_VALUE)
+        / (validator.total_stake / OCT_DECIMALS_VALUE);
+    let commission_rebate_percent = commission_rebate_thresholds
+        .iter()
+        .filter(|(timestamp, _)| delegator.registered_timestamp < *timestamp)
+        .map(|(_, percent)| *percent)
+        .max()
+        .unwrap_or(0);
+    let commission_rebate = validator_commission_reward
+        * (delegator.deposit_amount / OCT_DECIMALS_VALUE)
+        / (validator.total_stake / OCT_DECIMALS_VALUE)
+        * commission_rebate_percent
+        / 100;
+    (delegator_reward, commission_rebate)
+}
+
+// Calculate the part of a reward which is withheld for the unearned part of the stake.
+fn calculate_withheld_reward(reward: Balance, unearned_stake: Balance, stake: Balance) -> Balance {
+    if stake / OCT_DECIMALS_VALUE == 0 {
+        return 0;
     }
+    reward * (unearned_stake.min(stake) / OCT_DECIMALS_VALUE) / (stake / OCT_DECIMALS_VALUE)
 }
@@ -0,0 +1,78 @@
+use crate::*;
+
+impl AppchainAnchor {
+    //
+    pub fn internal_execute_stake_change_intent(
+        &mut self,
+        delegator_id: &AccountId,
+        validator_id: &AccountId,
+    ) {
+        let key = (delegator_id.clone(), validator_id.clone());
+        let intent = match self.stake_change_intents.get(&key) {
+            Some(intent) => intent,
+            None => panic!(
+                "No stake change intent of delegator '{}' for validator '{}'.",
+                delegator_id, validator_id
+            ),
+        };
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        // The intent is useless if it is expired or the delegation is already unbonded.
+        if intent.expiry_timestamp.0 <= env::block_timestamp()
+            || !next_validator_set.contains_delegator(delegator_id, validator_id)
+        {
+            self.stake_change_intents.remove(&key);
+            log!(
+                "Stake change intent of delegator '{}' for validator '{}' is removed.",
+                delegator_id,
+                validator_id
+            );
+            return;
+        }
+        assert!(
+            self.is_stake_change_condition_met(&intent, &next_validator_set),
+            "The condition of the stake change intent is not met."
+        );
+        self.stake_change_intents.remove(&key);
+        match intent.action {
+            StakeChangeAction::UnbondDelegation => {
+                self.internal_unbond_delegation(delegator_id, validator_id)
+            }
+            StakeChangeAction::DecreaseDelegation { amount } => {
+                self.internal_decrease_delegation(delegator_id, validator_id, amount)
+            }
+        }
+        log!(
+            "Stake change intent of delegator '{}' for validator '{}' is executed.",
+            delegator_id,
+            validator_id
+        );
+    }
+    //
+    pub fn is_stake_change_condition_met(
+        &self,
+        intent: &StakeChangeIntent,
+        next_validator_set: &NextValidatorSet,
+    ) -> bool {
+        let validator = match next_validator_set.get_validator(&intent.validator_id) {
+            Some(validator) => validator,
+            None => return false,
+        };
+        match intent.condition {
+            StakeChangeCondition::CommissionExceeds { percent } => {
+                let default_commission_percent = self
+                    .protocol_settings
+                    .get()
+                    .unwrap()
+                    .validator_commission_percent;
+                validator
+                    .commission_percent
+                    .unwrap_or(default_commission_percent)
+                    > percent
+            }
+            StakeChangeCondition::ValidatorIsJailed => validator.is_jailed,
+            StakeChangeCondition::ValidatorIsRetiring => self
+                .validator_retirements
+                .contains_key(&intent.validator_id),
+        }
+    }
+}
@@ -0,0 +1,371 @@
+use super::AppchainMessagesProcessingContext;
+use crate::*;
+use core::convert::TryFrom;
+use std::str::FromStr;
+
+/// The function which applies an appchain message of a certain payload type.
+pub type AppchainMessageHandler = fn(
+    &mut AppchainAnchor,
+    &mut AppchainMessagesProcessingContext,
+    &mut LookupArray<ValidatorSetOfEra>,
+    &AppchainMessage,
+) -> MultiTxsOperationProcessingResult;
+
+impl PayloadType {
+    /// Get the handler of the appchain messages of this payload type.
+    /// A new payload type only needs to register its handler here.
+    pub fn handler(&self) -> AppchainMessageHandler {
+        match self {
+            PayloadType::Lock => AppchainAnchor::apply_lock_message,
+            PayloadType::BurnAsset => AppchainAnchor::apply_burn_asset_message,
+            PayloadType::PlanNewEra => AppchainAnchor::apply_plan_new_era_message,
+            PayloadType::EraPayout => AppchainAnchor::apply_era_payout_message,
+            PayloadType::LockNft => AppchainAnchor::apply_lock_nft_message,
+            PayloadType::BalanceAttestation => AppchainAnchor::apply_balance_attestation_message,
+            PayloadType::SettingChangeRequest => {
+                AppchainAnchor::apply_setting_change_request_message
+            }
+            PayloadType::NotificationAck => AppchainAnchor::apply_notification_ack_message,
+            PayloadType::Slash => AppchainAnchor::apply_slash_message,
+        }
+    }
+}
+
+impl Default for AppchainMessageHandlerConfig {
+    fn default() -> Self {
+        Self {
+            is_enabled: true,
+            gas_budget: None,
+        }
+    }
+}
+
+impl AppchainAnchor {
+    //
+    pub fn get_appchain_message_handler_config_of(
+        &self,
+        payload_type: &PayloadType,
+    ) -> AppchainMessageHandlerConfig {
+        self.appchain_message_handler_configs
+            .get(payload_type)
+            .unwrap_or_default()
+    }
+    // Check whether the remaining gas of the current function call is enough for
+    // the gas budget of the handler of a certain payload type.
+    pub fn has_gas_budget_for(&self, payload_type: &PayloadType) -> bool {
+        self.get_appchain_message_handler_config_of(payload_type)
+            .gas_budget
+            .map_or(true, |gas_budget| {
+                env::used_gas() + Gas::ONE_TERA.mul(gas_budget.0)
+                    <= Gas::ONE_TERA.mul(T_GAS_CAP_FOR_PROCESSING_APPCHAIN_MESSAGES)
+            })
+    }
+    // Check whether the handler of a certain payload type is enabled by the owner.
+    pub fn check_appchain_message_handler_of(
+        &self,
+        payload_type: &PayloadType,
+    ) -> MultiTxsOperationProcessingResult {
+        match self
+            .get_appchain_message_handler_config_of(payload_type)
+            .is_enabled
+        {
+            true => MultiTxsOperationProcessingResult::Ok,
+            false => MultiTxsOperationProcessingResult::Error(format!(
+                "Handler of payload type '{:?}' is disabled.",
+                payload_type
+            )),
+        }
+    }
+    // Record the error of an appchain message as its processing result.
+    fn reject_appchain_message(
+        &mut self,
+        nonce: u32,
+        message: String,
+    ) -> MultiTxsOperationProcessingResult {
+        self.record_appchain_message_processing_result(&AppchainMessageProcessingResult::Error {
+            nonce,
+            message: message.clone(),
+        });
+        MultiTxsOperationProcessingResult::Error(message)
+    }
+    //
+    fn apply_burn_asset_message(
+        &mut self,
+        processing_context: &mut AppchainMessagesProcessingContext,
+        _validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        appchain_message: &AppchainMessage,
+    ) -> MultiTxsOperationProcessingResult {
+        if let AppchainEvent::NearFungibleTokenBurnt {
+            contract_account,
+            owner_id_in_appchain,
+            receiver_id_in_near,
+            amount,
+        } = &appchain_message.appchain_event
+        {
+            if self.asset_transfer_is_paused {
+                return self.reject_appchain_message(
+                    appchain_message.nonce,
+                    format!("Asset transfer is now paused."),
+                );
+            }
+            let contract_account_id = AccountId::from_str(&contract_account);
+            if contract_account_id.is_err() {
+                return self.reject_appchain_message(
+                    appchain_message.nonce,
+                    format!("Invalid contract account: '{}'.", contract_account),
+                );
+            }
+            self.internal_unlock_near_fungible_token(
+                owner_id_in_appchain,
+                &contract_account_id.unwrap(),
+                receiver_id_in_near,
+                amount,
+                appchain_message.nonce,
+                processing_context,
+            )
+        } else {
+            unreachable!()
+        }
+    }
+    //
+    fn apply_lock_message(
+        &mut self,
+        processing_context: &mut AppchainMessagesProcessingContext,
+        _validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        appchain_message: &AppchainMessage,
+    ) -> MultiTxsOperationProcessingResult {
+        if let AppchainEvent::NativeTokenLocked {
+            owner_id_in_appchain,
+            receiver_id_in_near,
+            amount,
+        } = &appchain_message.appchain_event
+        {
+            if self.asset_transfer_is_paused {
+                return self.reject_appchain_message(
+                    appchain_message.nonce,
+                    format!("Asset transfer is now paused."),
+                );
+            }
+            let wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
+            if i128::try_from(wrapped_appchain_token.premined_balance.0).unwrap()
+                + wrapped_appchain_token.changed_balance.0
+                + i128::try_from(amount.0).unwrap()
+                > i128::try_from(wrapped_appchain_token.total_supply.0).unwrap()
+            {
+                return self.reject_appchain_message(
+                    appchain_message.nonce,
+                    format!("Too much wrapped appchain token to mint."),
+                );
+            }
+            self.internal_mint_wrapped_appchain_token(
+                Some(owner_id_in_appchain),
+                receiver_id_in_near,
+                amount,
+                appchain_message.nonce,
+                processing_context,
+            )
+        } else {
+            unreachable!()
+        }
+    }
+    //
+    fn apply_plan_new_era_message(
+        &mut self,
+        processing_context: &mut AppchainMessagesProcessingContext,
+        validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        appchain_message: &AppchainMessage,
+    ) -> MultiTxsOperationProcessingResult {
+        if let AppchainEvent::EraSwitchPlaned { era_number } = &appchain_message.appchain_event {
+            if let Some(era_number) = processing_context.switching_era_number() {
+                self.continue_processing_stage_of_era(
+                    processing_context,
+                    validator_set_histories,
+                    era_number,
+                )
+            } else {
+                let index_range = validator_set_histories.index_range();
+                if u64::from(*era_number) <= index_range.end_index.0 {
+                    return self.reject_appchain_message(
+                        appchain_message.nonce,
+                        format!("Switching era number '{}' is too old.", era_number),
+                    );
+                }
+                let gas_at_start = processing_context.used_gas_of_current_function_call();
+                let result = self.internal_start_switching_era(
+                    processing_context,
+                    validator_set_histories,
+                    appchain_message.nonce,
+                    u64::from(*era_number),
+                );
+                self.record_used_gas_of_processing_stage(
+                    validator_set_histories,
+                    u64::from(*era_number),
+                    "StartingSwitchingEra",
+                    processing_context.used_gas_of_current_function_call() - gas_at_start,
+                );
+                result
+            }
+        } else {
+            unreachable!()
+        }
+    }
+    //
+    fn apply_era_payout_message(
+        &mut self,
+        processing_context: &mut AppchainMessagesProcessingContext,
+        validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        appchain_message: &AppchainMessage,
+    ) -> MultiTxsOperationProcessingResult {
+        if let AppchainEvent::EraRewardConcluded {
+            era_number,
+            unprofitable_validator_ids,
+            offenders: _,
+            validator_set_hash,
+        } = &appchain_message.appchain_event
+        {
+            if let Some(era_number) = processing_context.distributing_reward_era_number() {
+                self.continue_processing_stage_of_era(
+                    processing_context,
+                    validator_set_histories,
+                    era_number,
+                )
+            } else {
+                let gas_at_start = processing_context.used_gas_of_current_function_call();
+                let result = self.internal_start_distributing_reward_of_era(
+                    processing_context,
+                    validator_set_histories,
+                    appchain_message.nonce,
+                    u64::from(*era_number),
+                    unprofitable_validator_ids,
+                    validator_set_hash,
+                );
+                self.record_used_gas_of_processing_stage(
+                    validator_set_histories,
+                    u64::from(*era_number),
+                    "StartingDistributingReward",
+                    processing_context.used_gas_of_current_function_call() - gas_at_start,
+                );
+                result
+            }
+        } else {
+            unreachable!()
+        }
+    }
+    //
+    fn apply_lock_nft_message(
+        &mut self,
+        processing_context: &mut AppchainMessagesProcessingContext,
+        _validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        appchain_message: &AppchainMessage,
+    ) -> MultiTxsOperationProcessingResult {
+        if let AppchainEvent::NonFungibleTokenLocked {
+            owner_id_in_appchain,
+            receiver_id_in_near,
+            class_id,
+            instance_id,
+            token_metadata,
+        } = &appchain_message.appchain_event
+        {
+            if self.asset_transfer_is_paused {
+                return self.reject_appchain_message(
+                    appchain_message.nonce,
+                    format!("Asset transfer is now paused."),
+                );
+            }
+            self.internal_process_locked_nft_in_appchain(
+                processing_context,
+                appchain_message.nonce,
+                owner_id_in_appchain,
+                receiver_id_in_near,
+                class_id,
+                instance_id,
+                token_metadata,
+            )
+        } else {
+            unreachable!()
+        }
+    }
+    //
+    fn apply_balance_attestation_message(
+        &mut self,
+        _processing_context: &mut AppchainMessagesProcessingContext,
+        _validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        appchain_message: &AppchainMessage,
+    ) -> MultiTxsOperationProcessingResult {
+        if let AppchainEvent::WrappedAssetBalancesAttested { balances } =
+            &appchain_message.appchain_event
+        {
+            self.internal_record_wrapped_asset_balance_attestation(
+                appchain_message.nonce,
+                balances,
+            );
+            MultiTxsOperationProcessingResult::Ok
+        } else {
+            unreachable!()
+        }
+    }
+    //
+    fn apply_setting_change_request_message(
+        &mut self,
+        _processing_context: &mut AppchainMessagesProcessingContext,
+        _validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        appchain_message: &AppchainMessage,
+    ) -> MultiTxsOperationProcessingResult {
+        if let AppchainEvent::AnchorSettingChangeRequested { change } =
+            &appchain_message.appchain_event
+        {
+            let index = self.internal_stage_appchain_setting_change(appchain_message.nonce, change);
+            self.record_appchain_message_processing_result(&AppchainMessageProcessingResult::Ok {
+                nonce: appchain_message.nonce,
+                message: Some(format!(
+                    "Staged as appchain setting change proposal '{}'.",
+                    index
+                )),
+            });
+            MultiTxsOperationProcessingResult::Ok
+        } else {
+            unreachable!()
+        }
+    }
+    //
+    fn apply_notification_ack_message(
+        &mut self,
+        _processing_context: &mut AppchainMessagesProcessingContext,
+        _validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        appchain_message: &AppchainMessage,
+    ) -> MultiTxsOperationProcessingResult {
+        if let AppchainEvent::AppchainNotificationsAcknowledged { processed_index } =
+            &appchain_message.appchain_event
+        {
+            self.internal_acknowledge_appchain_notifications(
+                appchain_message.nonce,
+                processed_index.0,
+            )
+        } else {
+            unreachable!()
+        }
+    }
+    //
+    fn apply_slash_message(
+        &mut self,
+        _processing_context: &mut AppchainMessagesProcessingContext,
+        _validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        appchain_message: &AppchainMessage,
+    ) -> MultiTxsOperationProcessingResult {
+        if let AppchainEvent::ValidatorOffenceReported {
+            validator_id_in_appchain,
+            offence_kind,
+            era_number,
+        } = &appchain_message.appchain_event
+        {
+            self.internal_apply_reported_offence(
+                appchain_message.nonce,
+                validator_id_in_appchain,
+                offence_kind,
+                *era_number,
+            )
+        } else {
+            unreachable!()
+        }
+    }
+}
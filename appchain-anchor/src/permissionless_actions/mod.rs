@@ -1,10 +1,15 @@
+mod cleaning_up_validators;
 mod distributing_rewards;
+mod executing_intents;
+mod message_handlers;
+mod reimbursing_relayers;
+mod retiring_validator;
 mod switching_era;
 
 use crate::appchain_messages::Offender;
 use crate::interfaces::PermissionlessActions;
 use crate::*;
-use codec::Decode;
+use codec::{Compact, Decode};
 use core::convert::{TryFrom, TryInto};
 use near_contract_standards::non_fungible_token::metadata::TokenMetadata;
 use std::ops::Add;
@@ -34,6 +39,8 @@ pub enum AppchainEvent {
         era_number: u32,
         unprofitable_validator_ids: Vec<String>,
         offenders: Vec<Offender>,
+        /// The hash of the validator set which the appchain believes was active in the era.
+        validator_set_hash: Option<String>,
     },
     /// The fact that a certain non-fungible token is locked in the appchain.
     NonFungibleTokenLocked {
@@ -43,6 +50,38 @@ pub enum AppchainEvent {
         instance_id: String,
         token_metadata: TokenMetadata,
     },
+    /// The fact that the appchain attests the total balances of wrapped NEP-141 tokens
+    /// issued in the appchain, in pairs of `(contract_account, balance)`.
+    WrappedAssetBalancesAttested { balances: Vec<(String, U128)> },
+    /// The fact that the governance of appchain requests to change an anchor-side setting.
+    AnchorSettingChangeRequested { change: AnchorSettingChange },
+    /// The fact that the appchain confirms processing the appchain notifications
+    /// up to a certain index.
+    AppchainNotificationsAcknowledged { processed_index: U64 },
+    /// The fact that an offence of a validator is reported by the appchain,
+    /// for which the validator and its delegators are slashed.
+    ValidatorOffenceReported {
+        validator_id_in_appchain: String,
+        offence_kind: String,
+        era_number: u32,
+    },
+}
+
+impl AppchainEvent {
+    /// Get the type of the payload of appchain message which this event is decoded from.
+    pub fn payload_type(&self) -> PayloadType {
+        match self {
+            AppchainEvent::NearFungibleTokenBurnt { .. } => PayloadType::BurnAsset,
+            AppchainEvent::NativeTokenLocked { .. } => PayloadType::Lock,
+            AppchainEvent::EraSwitchPlaned { .. } => PayloadType::PlanNewEra,
+            AppchainEvent::EraRewardConcluded { .. } => PayloadType::EraPayout,
+            AppchainEvent::NonFungibleTokenLocked { .. } => PayloadType::LockNft,
+            AppchainEvent::WrappedAssetBalancesAttested { .. } => PayloadType::BalanceAttestation,
+            AppchainEvent::AnchorSettingChangeRequested { .. } => PayloadType::SettingChangeRequest,
+            AppchainEvent::AppchainNotificationsAcknowledged { .. } => PayloadType::NotificationAck,
+            AppchainEvent::ValidatorOffenceReported { .. } => PayloadType::Slash,
+        }
+    }
 }
 
 pub struct AppchainMessagesProcessingContext {
@@ -122,6 +161,35 @@ impl AppchainMessagesProcessingContext {
     }
 }
 
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct GasMetricsOfProcessingStage {
+    era_number: U64,
+    stage: String,
+    used_gas: U64,
+    total_used_gas_of_era: U64,
+}
+
+impl ValidatorSetProcessingStatus {
+    ///
+    pub fn stage_name(&self) -> &'static str {
+        match self {
+            ValidatorSetProcessingStatus::CopyingFromLastEra { .. } => "CopyingFromLastEra",
+            ValidatorSetProcessingStatus::ApplyingStakingHistory { .. } => "ApplyingStakingHistory",
+            ValidatorSetProcessingStatus::ReadyForDistributingReward => {
+                "ReadyForDistributingReward"
+            }
+            ValidatorSetProcessingStatus::DistributingReward { .. } => "DistributingReward",
+            ValidatorSetProcessingStatus::Completed => "Completed",
+            ValidatorSetProcessingStatus::UnbondingValidator { .. } => "UnbondingValidator",
+            ValidatorSetProcessingStatus::AutoUnbondingValidator { .. } => "AutoUnbondingValidator",
+            ValidatorSetProcessingStatus::CheckingForAutoUnbondingValidator { .. } => {
+                "CheckingForAutoUnbondingValidator"
+            }
+        }
+    }
+}
+
 enum ResultOfLoopingValidatorSet {
     NoMoreDelegator,
     NoMoreValidator,
@@ -138,6 +206,7 @@ impl PermissionlessActions for AppchainAnchor {
         mmr_leaf: Vec<u8>,
         mmr_proof: Vec<u8>,
     ) {
+        self.assert_not_in_maintenance_mode();
         let anchor_settings = self.anchor_settings.get().unwrap();
         assert!(
             !anchor_settings.beefy_light_client_witness_mode,
@@ -170,6 +239,7 @@ impl PermissionlessActions for AppchainAnchor {
     fn try_complete_updating_state_of_beefy_light_client(
         &mut self,
     ) -> MultiTxsOperationProcessingResult {
+        self.assert_not_in_maintenance_mode();
         let anchor_settings = self.anchor_settings.get().unwrap();
         assert!(
             !anchor_settings.beefy_light_client_witness_mode,
@@ -208,7 +278,13 @@ impl PermissionlessActions for AppchainAnchor {
         header: Vec<u8>,
         mmr_leaf: Vec<u8>,
         mmr_proof: Vec<u8>,
+        compression: Option<PayloadCompression>,
     ) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_appchain_is_not_terminating();
+        let processing_status = self.permissionless_actions_status.get().unwrap();
+        let encoded_messages = self.decompress_encoded_messages(encoded_messages, compression);
+        self.assert_encoded_messages_are_within_limits(&encoded_messages);
         let anchor_settings = self.anchor_settings.get().unwrap();
         if anchor_settings.beefy_light_client_witness_mode {
             self.assert_relayer();
@@ -228,75 +304,20 @@ impl PermissionlessActions for AppchainAnchor {
             Ok(messages) => self.internal_stage_appchain_messages(&messages),
             Err(err) => panic!("Failed to decode messages: {}", err),
         }
+        self.record_gas_usage_of_relayer(&processing_status);
     }
     //
     fn process_appchain_messages(&mut self) -> MultiTxsOperationProcessingResult {
+        self.assert_not_in_maintenance_mode();
+        self.assert_appchain_is_not_terminating();
         let processing_status = self.permissionless_actions_status.get().unwrap();
-        let appchain_messages = self.appchain_messages.get().unwrap();
-        let mut processing_context = AppchainMessagesProcessingContext::new(processing_status);
-        let mut validator_set_histories = self.validator_set_histories.get().unwrap();
-        let mut result = MultiTxsOperationProcessingResult::Ok;
-        while processing_context.used_gas_of_current_function_call()
-            < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING)
-            && env::used_gas() < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_PROCESSING_APPCHAIN_MESSAGES)
-        {
-            if let Some(processing_nonce) = processing_context.processing_nonce() {
-                if appchain_messages
-                    .get_processing_result(&processing_nonce)
-                    .is_some()
-                {
-                    processing_context.clear_processing_nonce();
-                    processing_context.set_latest_applied_nonce(processing_nonce);
-                    result = MultiTxsOperationProcessingResult::Ok;
-                    continue;
-                }
-                if let Some(appchain_message) = appchain_messages.get_message(&processing_nonce) {
-                    result = self.internal_apply_appchain_message(
-                        &mut processing_context,
-                        &mut validator_set_histories,
-                        &appchain_message,
-                    );
-                    match result {
-                        MultiTxsOperationProcessingResult::Ok => {
-                            processing_context.clear_processing_nonce();
-                            processing_context.set_latest_applied_nonce(processing_nonce);
-                        }
-                        MultiTxsOperationProcessingResult::NeedMoreGas => (),
-                        MultiTxsOperationProcessingResult::Error(..) => {
-                            // The loop should continue even if it fails to apply a certain message
-                            processing_context.clear_processing_nonce();
-                            processing_context.set_latest_applied_nonce(processing_nonce);
-                            result = MultiTxsOperationProcessingResult::Ok;
-                        }
-                    }
-                } else {
-                    result = MultiTxsOperationProcessingResult::Error(format!(
-                        "Missing appchain message with nonce '{}'.",
-                        processing_nonce
-                    ));
-                    break;
-                }
-            } else {
-                if processing_context.latest_applied_nonce() < processing_context.max_nonce() {
-                    processing_context
-                        .set_processing_nonce(processing_context.latest_applied_nonce() + 1);
-                } else {
-                    break;
-                }
-            }
-        }
-        self.permissionless_actions_status
-            .set(processing_context.processing_status());
-        self.validator_set_histories.set(&validator_set_histories);
-        if result.is_ok()
-            && processing_context.latest_applied_nonce() < processing_context.max_nonce()
-        {
-            result = MultiTxsOperationProcessingResult::NeedMoreGas;
-        }
+        let result = self.internal_process_staged_appchain_messages();
+        self.record_gas_usage_of_relayer(&processing_status);
         result
     }
     //
     fn commit_appchain_challenge(&mut self, appchain_challenge: AppchainChallenge) {
+        self.assert_not_in_maintenance_mode();
         match &appchain_challenge {
             AppchainChallenge::EquivocationChallenge {
                 submitter_account: _,
@@ -311,6 +332,16 @@ impl PermissionlessActions for AppchainAnchor {
         self.appchain_challenges.set(&appchain_challenges);
     }
     //
+    fn complete_validator_retirement(
+        &mut self,
+        validator_id: AccountId,
+    ) -> MultiTxsOperationProcessingResult {
+        self.assert_not_in_maintenance_mode();
+        self.assert_appchain_is_not_terminating();
+        self.assert_staking_is_active();
+        self.internal_complete_validator_retirement(&validator_id)
+    }
+    //
     fn process_appchain_messages_with_all_proofs(
         &mut self,
         signed_commitment: Vec<u8>,
@@ -321,7 +352,13 @@ impl PermissionlessActions for AppchainAnchor {
         header: Vec<u8>,
         mmr_leaf_for_header: Vec<u8>,
         mmr_proof_for_header: Vec<u8>,
-    ) {
+        compression: Option<PayloadCompression>,
+    ) -> MultiTxsOperationProcessingResult {
+        self.assert_not_in_maintenance_mode();
+        self.assert_appchain_is_not_terminating();
+        let processing_status = self.permissionless_actions_status.get().unwrap();
+        let encoded_messages = self.decompress_encoded_messages(encoded_messages, compression);
+        self.assert_encoded_messages_are_within_limits(&encoded_messages);
         self.assert_light_client_is_ready();
         let mut light_client = self.beefy_light_client_state.get().unwrap();
         match light_client.update_state(
@@ -354,26 +391,273 @@ impl PermissionlessActions for AppchainAnchor {
         }
         let messages = Decode::decode(&mut &encoded_messages[..]).unwrap();
         self.internal_stage_appchain_messages(&messages);
+        // The staged messages are applied one by one, and the processing stops
+        // before the gas threshold is reached. The remaining messages can be applied
+        // by calling function `process_appchain_messages`.
+        let result = self.internal_process_staged_appchain_messages();
+        self.record_gas_usage_of_relayer(&processing_status);
+        result
+    }
+    //
+    fn claim_relayer_reimbursement(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        let relayer_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&relayer_id);
+        self.internal_claim_relayer_reimbursement(relayer_id);
+    }
+    //
+    fn clean_up_deregistered_validators(
+        &mut self,
+        validator_ids: Vec<AccountId>,
+    ) -> MultiTxsOperationProcessingResult {
+        self.assert_not_in_maintenance_mode();
+        self.internal_clean_up_deregistered_validators(&validator_ids)
+    }
+    //
+    fn execute_stake_change_intent(&mut self, delegator_id: AccountId, validator_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_active();
+        self.internal_execute_stake_change_intent(&delegator_id, &validator_id);
+    }
+    //
+    fn settle_penalty_escrow(&mut self, escrow_index: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.internal_settle_penalty_escrow(escrow_index.0);
+    }
+    //
+    fn admit_queued_validator_registrations(&mut self) -> MultiTxsOperationProcessingResult {
+        self.assert_not_in_maintenance_mode();
+        self.internal_admit_queued_validator_registrations()
+    }
+    //
+    fn reconcile_oct_balance(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        self.internal_reconcile_oct_balance();
+    }
+    //
+    fn try_complete_switching_era(&mut self) -> MultiTxsOperationProcessingResult {
+        self.assert_not_in_maintenance_mode();
+        self.assert_appchain_is_not_terminating();
+        self.internal_try_complete_switching_era()
+    }
+    //
+    fn wind_down_terminating_appchain(&mut self) -> MultiTxsOperationProcessingResult {
+        self.assert_not_in_maintenance_mode();
+        self.internal_wind_down_terminating_appchain()
+    }
+}
+
+impl AppchainAnchor {
+    // Decompress the encoded appchain messages if they are compressed. The size of
+    // decompressed data is also limited by `maximum_size_of_encoded_appchain_messages`.
+    fn decompress_encoded_messages(
+        &self,
+        encoded_messages: Vec<u8>,
+        compression: Option<PayloadCompression>,
+    ) -> Vec<u8> {
+        match compression {
+            Some(compression) => {
+                let protocol_settings = self.protocol_settings.get().unwrap();
+                let maximum_size = usize::try_from(
+                    protocol_settings
+                        .maximum_size_of_encoded_appchain_messages
+                        .0,
+                )
+                .unwrap_or(usize::MAX);
+                match compression.decompress(&encoded_messages, maximum_size) {
+                    Ok(decompressed_messages) => decompressed_messages,
+                    Err(err) => panic!("Failed to decompress messages: {}", err),
+                }
+            }
+            None => encoded_messages,
+        }
+    }
+    // Assert that the size and the count of encoded appchain messages are within
+    // the limits in protocol settings, before the messages are verified and decoded.
+    fn assert_encoded_messages_are_within_limits(&self, encoded_messages: &Vec<u8>) {
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            u64::try_from(encoded_messages.len()).unwrap()
+                <= protocol_settings
+                    .maximum_size_of_encoded_appchain_messages
+                    .0,
+            "The size of encoded messages '{}' exceeds the limit '{}'.",
+            encoded_messages.len(),
+            protocol_settings
+                .maximum_size_of_encoded_appchain_messages
+                .0
+        );
+        // The encoded messages is a SCALE-encoded `Vec<RawMessage>`,
+        // which is prefixed by the count of messages in compact encoding.
+        match Compact::<u32>::decode(&mut &encoded_messages[..]) {
+            Ok(count) => assert!(
+                count.0 <= protocol_settings.maximum_count_of_appchain_messages_per_batch,
+                "The count of messages '{}' exceeds the limit '{}'.",
+                count.0,
+                protocol_settings.maximum_count_of_appchain_messages_per_batch
+            ),
+            Err(err) => panic!("Failed to decode the count of messages: {}", err),
+        }
+    }
+    /// Apply staged appchain messages in order of nonce, until all of them are applied
+    /// or the gas threshold is reached.
+    ///
+    /// The processing result of each message is committed once the message is applied,
+    /// and the nonce of the latest applied message (and the nonce of the message which is
+    /// being processed in multiple transactions) is persisted as the cursor for
+    /// the next call.
+    pub fn internal_process_staged_appchain_messages(
+        &mut self,
+    ) -> MultiTxsOperationProcessingResult {
+        let processing_status = self.permissionless_actions_status.get().unwrap();
+        let appchain_messages = self.appchain_messages.get().unwrap();
+        let mut processing_context = AppchainMessagesProcessingContext::new(processing_status);
+        let mut validator_set_histories = self.validator_set_histories.get().unwrap();
+        let mut result = MultiTxsOperationProcessingResult::Ok;
+        let mut has_applied_message = false;
+        while processing_context.used_gas_of_current_function_call()
+            < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING)
+            && env::used_gas() < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_PROCESSING_APPCHAIN_MESSAGES)
+        {
+            if let Some(processing_nonce) = processing_context.processing_nonce() {
+                if appchain_messages
+                    .get_processing_result(&processing_nonce)
+                    .is_some()
+                {
+                    self.mark_applied_appchain_message(
+                        &mut processing_context,
+                        &mut validator_set_histories,
+                        processing_nonce,
+                    );
+                    result = MultiTxsOperationProcessingResult::Ok;
+                    continue;
+                }
+                if let Some(appchain_message) = appchain_messages.get_message(&processing_nonce) {
+                    // The processing stops at a held reward distribution, until the owner
+                    // confirms or rejects it.
+                    if processing_context
+                        .distributing_reward_era_number()
+                        .is_none()
+                    {
+                        if let Some(message) = self.check_era_reward_circuit_breaker(
+                            &validator_set_histories,
+                            &appchain_message,
+                        ) {
+                            result = MultiTxsOperationProcessingResult::Error(message);
+                            break;
+                        }
+                    }
+                    // The processing also stops at a message whose handler is disabled,
+                    // until the owner enables it again.
+                    let payload_type = appchain_message.appchain_event.payload_type();
+                    result = self.check_appchain_message_handler_of(&payload_type);
+                    if !result.is_ok() {
+                        break;
+                    }
+                    // At least one message is applied in each call, to ensure the progress.
+                    if has_applied_message && !self.has_gas_budget_for(&payload_type) {
+                        result = MultiTxsOperationProcessingResult::NeedMoreGas;
+                        break;
+                    }
+                    result = self.internal_apply_appchain_message(
+                        &mut processing_context,
+                        &mut validator_set_histories,
+                        &appchain_message,
+                    );
+                    has_applied_message = true;
+                    match result {
+                        MultiTxsOperationProcessingResult::Ok => {
+                            self.mark_applied_appchain_message(
+                                &mut processing_context,
+                                &mut validator_set_histories,
+                                processing_nonce,
+                            );
+                        }
+                        MultiTxsOperationProcessingResult::NeedMoreGas => (),
+                        MultiTxsOperationProcessingResult::Error(..) => {
+                            // The loop should continue even if it fails to apply a certain message
+                            self.mark_applied_appchain_message(
+                                &mut processing_context,
+                                &mut validator_set_histories,
+                                processing_nonce,
+                            );
+                            result = MultiTxsOperationProcessingResult::Ok;
+                        }
+                    }
+                } else {
+                    result = MultiTxsOperationProcessingResult::Error(format!(
+                        "Missing appchain message with nonce '{}'.",
+                        processing_nonce
+                    ));
+                    break;
+                }
+            } else {
+                if processing_context.latest_applied_nonce() < processing_context.max_nonce() {
+                    processing_context
+                        .set_processing_nonce(processing_context.latest_applied_nonce() + 1);
+                } else {
+                    break;
+                }
+            }
+        }
+        self.permissionless_actions_status
+            .set(processing_context.processing_status());
+        self.validator_set_histories.set(&validator_set_histories);
+        if result.is_ok()
+            && processing_context.latest_applied_nonce() < processing_context.max_nonce()
+        {
+            result = MultiTxsOperationProcessingResult::NeedMoreGas;
+        }
+        result
+    }
+    /// Continue the processing of the validator set of the era which is switching
+    /// (or distributing reward), stage by stage, until it is completed or the gas threshold
+    /// is reached. The progress is persisted in the processing status of the validator set.
+    ///
+    /// Once the processing is completed, the corresponding appchain message is marked as
+    /// applied, and the remaining staged messages are applied by `process_appchain_messages`.
+    pub fn internal_try_complete_switching_era(&mut self) -> MultiTxsOperationProcessingResult {
         let processing_status = self.permissionless_actions_status.get().unwrap();
         let mut processing_context = AppchainMessagesProcessingContext::new(processing_status);
+        // The processing also stops at a message whose handler is disabled,
+        // until the owner enables it again.
+        if let Some(appchain_message) = processing_context
+            .processing_nonce()
+            .and_then(|nonce| self.appchain_messages.get().unwrap().get_message(&nonce))
+        {
+            let result = self
+                .check_appchain_message_handler_of(&appchain_message.appchain_event.payload_type());
+            if !result.is_ok() {
+                return result;
+            }
+        }
         let mut validator_set_histories = self.validator_set_histories.get().unwrap();
-        messages.iter().for_each(|raw_message| {
-            let appchain_messages = self.appchain_messages.get().unwrap();
-            if appchain_messages
-                .get_processing_result(&raw_message.nonce())
-                .is_none()
+        let mut result = MultiTxsOperationProcessingResult::Ok;
+        while processing_context.used_gas_of_current_function_call()
+            < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING)
+            && env::used_gas() < Gas::ONE_TERA.mul(T_GAS_CAP_FOR_PROCESSING_APPCHAIN_MESSAGES)
+        {
+            let era_number = match processing_context
+                .switching_era_number()
+                .or(processing_context.distributing_reward_era_number())
             {
-                self.internal_apply_appchain_message(
-                    &mut processing_context,
-                    &mut validator_set_histories,
-                    &appchain_messages.get_message(&raw_message.nonce()).unwrap(),
-                );
+                Some(era_number) => era_number,
+                None => break,
+            };
+            result = self.continue_processing_stage_of_era(
+                &mut processing_context,
+                &mut validator_set_histories,
+                era_number,
+            );
+            if let MultiTxsOperationProcessingResult::Error(..) = result {
+                break;
             }
-        });
+        }
+        self.permissionless_actions_status
+            .set(processing_context.processing_status());
+        self.validator_set_histories.set(&validator_set_histories);
+        result
     }
-}
-
-impl AppchainAnchor {
     /// Apply a certain `AppchainMessage`
     pub fn internal_apply_appchain_message(
         &mut self,
@@ -381,149 +665,243 @@ impl AppchainAnchor {
         validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
         appchain_message: &AppchainMessage,
     ) -> MultiTxsOperationProcessingResult {
-        match &appchain_message.appchain_event {
-            AppchainEvent::NearFungibleTokenBurnt {
-                contract_account,
-                owner_id_in_appchain,
-                receiver_id_in_near,
-                amount,
-            } => {
-                if self.asset_transfer_is_paused {
-                    let message = format!("Asset transfer is now paused.");
-                    let result = AppchainMessageProcessingResult::Error {
-                        nonce: appchain_message.nonce,
-                        message: message.clone(),
-                    };
-                    self.record_appchain_message_processing_result(&result);
-                    return MultiTxsOperationProcessingResult::Error(message);
-                }
-                let contract_account_id = AccountId::from_str(&contract_account);
-                if contract_account_id.is_err() {
-                    let message = format!("Invalid contract account: '{}'.", contract_account);
-                    let result = AppchainMessageProcessingResult::Error {
-                        nonce: appchain_message.nonce,
-                        message: message.clone(),
-                    };
-                    self.record_appchain_message_processing_result(&result);
-                    return MultiTxsOperationProcessingResult::Error(message);
-                }
-                self.internal_unlock_near_fungible_token(
-                    owner_id_in_appchain,
-                    &contract_account_id.unwrap(),
-                    receiver_id_in_near,
-                    amount,
-                    appchain_message.nonce,
-                    processing_context,
-                )
+        let payload_type = appchain_message.appchain_event.payload_type();
+        if let Some(accepted_payload_types) = self
+            .accepted_payload_types_of_states
+            .get(&self.appchain_state)
+        {
+            if !accepted_payload_types.contains(&payload_type) {
+                let message = format!(
+                    "Payload type '{:?}' is not accepted while appchain state is '{}'.",
+                    payload_type,
+                    serde_json::to_string(&self.appchain_state).unwrap()
+                );
+                let result = AppchainMessageProcessingResult::Error {
+                    nonce: appchain_message.nonce,
+                    message: message.clone(),
+                };
+                self.record_appchain_message_processing_result(&result);
+                return MultiTxsOperationProcessingResult::Error(message);
             }
-            AppchainEvent::NativeTokenLocked {
-                owner_id_in_appchain,
-                receiver_id_in_near,
-                amount,
-            } => {
-                if self.asset_transfer_is_paused {
-                    let message = format!("Asset transfer is now paused.");
-                    let result = AppchainMessageProcessingResult::Error {
-                        nonce: appchain_message.nonce,
-                        message: message.clone(),
-                    };
-                    self.record_appchain_message_processing_result(&result);
-                    return MultiTxsOperationProcessingResult::Error(message);
-                }
-                let wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
-                if i128::try_from(wrapped_appchain_token.premined_balance.0).unwrap()
-                    + wrapped_appchain_token.changed_balance.0
-                    + i128::try_from(amount.0).unwrap()
-                    > i128::try_from(wrapped_appchain_token.total_supply.0).unwrap()
-                {
-                    let message = format!("Too much wrapped appchain token to mint.");
-                    let result = AppchainMessageProcessingResult::Error {
-                        nonce: appchain_message.nonce,
-                        message: message.clone(),
-                    };
-                    self.record_appchain_message_processing_result(&result);
-                    return MultiTxsOperationProcessingResult::Error(message);
-                }
-                self.internal_mint_wrapped_appchain_token(
-                    Some(owner_id_in_appchain),
-                    receiver_id_in_near,
-                    amount,
-                    appchain_message.nonce,
-                    processing_context,
-                )
+        }
+        (payload_type.handler())(
+            self,
+            processing_context,
+            validator_set_histories,
+            appchain_message,
+        )
+    }
+    // Record the index of the latest appchain notification which the appchain confirms
+    // processing. The acknowledged index never goes backwards.
+    fn internal_acknowledge_appchain_notifications(
+        &mut self,
+        nonce: u32,
+        processed_index: u64,
+    ) -> MultiTxsOperationProcessingResult {
+        let next_index = self
+            .appchain_notification_histories
+            .get()
+            .unwrap()
+            .next_index();
+        if processed_index >= next_index {
+            let message = format!(
+                "Appchain notification '{}' is not existed yet.",
+                processed_index
+            );
+            self.record_appchain_message_processing_result(
+                &AppchainMessageProcessingResult::Error {
+                    nonce,
+                    message: message.clone(),
+                },
+            );
+            return MultiTxsOperationProcessingResult::Error(message);
+        }
+        let message = match self.latest_acknowledged_appchain_notification_index {
+            Some(acknowledged_index) if acknowledged_index >= processed_index => Some(format!(
+                "Appchain notification '{}' is already acknowledged.",
+                acknowledged_index
+            )),
+            _ => {
+                self.latest_acknowledged_appchain_notification_index = Some(processed_index);
+                None
             }
-            AppchainEvent::EraSwitchPlaned { era_number } => {
-                if let Some(era_number) = processing_context.switching_era_number() {
-                    self.complete_switching_era(
-                        processing_context,
-                        validator_set_histories,
-                        era_number,
-                    )
-                } else {
-                    let index_range = validator_set_histories.index_range();
-                    if u64::from(*era_number) <= index_range.end_index.0 {
-                        let message = format!("Switching era number '{}' is too old.", era_number);
-                        let result = AppchainMessageProcessingResult::Error {
-                            nonce: appchain_message.nonce,
-                            message: message.clone(),
-                        };
-                        self.record_appchain_message_processing_result(&result);
-                        return MultiTxsOperationProcessingResult::Error(message);
-                    }
-                    self.internal_start_switching_era(
-                        processing_context,
-                        validator_set_histories,
-                        u64::from(*era_number),
-                    )
+        };
+        self.record_appchain_message_processing_result(&AppchainMessageProcessingResult::Ok {
+            nonce,
+            message,
+        });
+        MultiTxsOperationProcessingResult::Ok
+    }
+    // Record the attestation of the balances of wrapped assets issued in appchain,
+    // and compare them with the locked balances of NEP-141 tokens in this contract.
+    fn internal_record_wrapped_asset_balance_attestation(
+        &mut self,
+        nonce: u32,
+        balances: &Vec<(String, U128)>,
+    ) {
+        // The balance attested for the contract account of wrapped appchain token is
+        // the issuance of wrapped appchain token which the appchain believes.
+        let wrapped_appchain_token_account = self
+            .wrapped_appchain_token
+            .get()
+            .unwrap()
+            .contract_account
+            .map(|account_id| account_id.to_string());
+        let issuance_is_divergent = balances
+            .iter()
+            .find(|(contract_account, _)| {
+                wrapped_appchain_token_account.as_ref() == Some(contract_account)
+            })
+            .map_or(false, |(_, attested_issuance)| {
+                self.record_attested_issuance_of_wrapped_appchain_token(nonce, *attested_issuance)
+            });
+        let near_fungible_tokens = self.near_fungible_tokens.get().unwrap();
+        let balances = balances
+            .iter()
+            .filter(|(contract_account, _)| {
+                wrapped_appchain_token_account.as_ref() != Some(contract_account)
+            })
+            .map(|(contract_account, attested_balance)| {
+                let locked_balance = AccountId::from_str(contract_account)
+                    .ok()
+                    .and_then(|account_id| {
+                        near_fungible_tokens.get_by_contract_account(&account_id)
+                    })
+                    .map(|near_fungible_token| near_fungible_token.locked_balance);
+                WrappedAssetBalance {
+                    contract_account: contract_account.clone(),
+                    attested_balance: *attested_balance,
+                    locked_balance,
+                    is_divergent: locked_balance.map_or(true, |locked_balance| {
+                        locked_balance.0 != attested_balance.0
+                    }),
                 }
+            })
+            .collect::<Vec<WrappedAssetBalance>>();
+        let attestation = WrappedAssetBalanceAttestation {
+            nonce,
+            timestamp: U64::from(env::block_timestamp()),
+            has_divergence: balances.iter().any(|balance| balance.is_divergent),
+            balances,
+        };
+        self.latest_wrapped_asset_balance_attestation
+            .set(&attestation);
+        let message = match attestation.has_divergence {
+            true => {
+                let divergent_tokens = attestation
+                    .balances
+                    .iter()
+                    .filter(|balance| balance.is_divergent)
+                    .map(|balance| balance.contract_account.clone())
+                    .collect::<Vec<String>>();
+                log!(
+                    "Attested balances of wrapped assets diverge from locked balances: {}",
+                    serde_json::to_string(&divergent_tokens).unwrap()
+                );
+                Some(format!(
+                    "Balances of wrapped assets diverge: {}",
+                    divergent_tokens.join(", ")
+                ))
             }
-            AppchainEvent::EraRewardConcluded {
-                era_number,
-                unprofitable_validator_ids,
-                offenders: _,
-            } => {
-                if let Some(era_number) = processing_context.distributing_reward_era_number() {
-                    self.complete_distributing_reward_of_era(
-                        processing_context,
-                        validator_set_histories,
-                        era_number,
-                    )
-                } else {
-                    self.internal_start_distributing_reward_of_era(
-                        processing_context,
-                        validator_set_histories,
-                        appchain_message.nonce,
-                        u64::from(*era_number),
-                        unprofitable_validator_ids,
-                    )
-                }
+            false => None,
+        };
+        let message = match issuance_is_divergent {
+            true => {
+                log!("Attested issuance of wrapped appchain token diverges from tracked issuance.");
+                Some(format!(
+                    "{}Issuance of wrapped appchain token diverges.",
+                    message.map_or(String::new(), |message| format!("{} ", message))
+                ))
             }
-            AppchainEvent::NonFungibleTokenLocked {
-                owner_id_in_appchain,
-                receiver_id_in_near,
-                class_id,
-                instance_id,
-                token_metadata,
-            } => {
-                if self.asset_transfer_is_paused {
-                    let message = format!("Asset transfer is now paused.");
-                    let result = AppchainMessageProcessingResult::Error {
-                        nonce: appchain_message.nonce,
-                        message: message.clone(),
-                    };
-                    self.record_appchain_message_processing_result(&result);
-                    return MultiTxsOperationProcessingResult::Error(message);
-                }
-                self.internal_process_locked_nft_in_appchain(
-                    processing_context,
-                    appchain_message.nonce,
-                    owner_id_in_appchain,
-                    receiver_id_in_near,
-                    class_id,
-                    instance_id,
-                    token_metadata,
-                )
+            false => message,
+        };
+        self.record_appchain_message_processing_result(&AppchainMessageProcessingResult::Ok {
+            nonce,
+            message,
+        });
+    }
+    /// Continue the processing of the validator set of the era which is switching
+    /// (or distributing reward) by one stage, and record the gas used by the stage.
+    fn continue_processing_stage_of_era(
+        &mut self,
+        processing_context: &mut AppchainMessagesProcessingContext,
+        validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        era_number: u64,
+    ) -> MultiTxsOperationProcessingResult {
+        let stage = validator_set_histories
+            .get(&era_number)
+            .unwrap()
+            .processing_status();
+        let gas_at_start = processing_context.used_gas_of_current_function_call();
+        let result = match processing_context.switching_era_number() {
+            Some(_) => {
+                self.complete_switching_era(processing_context, validator_set_histories, era_number)
             }
+            None => self.complete_distributing_reward_of_era(
+                processing_context,
+                validator_set_histories,
+                era_number,
+            ),
+        };
+        self.record_used_gas_of_processing_stage(
+            validator_set_histories,
+            era_number,
+            stage.stage_name(),
+            processing_context.used_gas_of_current_function_call() - gas_at_start,
+        );
+        result
+    }
+    /// Record the gas used in a certain stage of processing validator set of an era,
+    /// and emit the metrics in log.
+    fn record_used_gas_of_processing_stage(
+        &mut self,
+        validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        era_number: u64,
+        stage: &str,
+        used_gas: Gas,
+    ) {
+        if let Some(mut validator_set) = validator_set_histories.get(&era_number) {
+            validator_set.add_used_gas_of_processing(used_gas.0);
+            validator_set_histories.insert(&era_number, &validator_set);
+            log!(
+                "Gas metrics of processing validator set: {}",
+                serde_json::to_string(&GasMetricsOfProcessingStage {
+                    era_number: U64::from(era_number),
+                    stage: stage.to_string(),
+                    used_gas: U64::from(used_gas.0),
+                    total_used_gas_of_era: U64::from(validator_set.used_gas_of_processing()),
+                })
+                .unwrap()
+            );
+        }
+    }
+    // Move the cursor of processing appchain messages past an applied message, and record
+    // the message in the latest era of the validator set histories held by the caller.
+    fn mark_applied_appchain_message(
+        &mut self,
+        processing_context: &mut AppchainMessagesProcessingContext,
+        validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        nonce: u32,
+    ) {
+        processing_context.clear_processing_nonce();
+        processing_context.set_latest_applied_nonce(nonce);
+        self.record_applied_message_in_era(
+            validator_set_histories.index_range().end_index.0,
+            nonce,
+        );
+        self.watermark_applied_message_in_latest_era(validator_set_histories, nonce);
+    }
+    // Record the nonce of an applied appchain message in the latest validator set,
+    // which may be the set of the era being switched to.
+    fn watermark_applied_message_in_latest_era(
+        &mut self,
+        validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        nonce: u32,
+    ) {
+        let era_number = validator_set_histories.index_range().end_index.0;
+        if let Some(mut validator_set) = validator_set_histories.get(&era_number) {
+            validator_set.watermark_applied_message_nonce(nonce);
+            validator_set_histories.insert(&era_number, &validator_set);
         }
     }
     ///
@@ -556,6 +934,26 @@ impl AppchainAnchor {
             .unwrap(),
             serde_json::to_string::<AppchainMessageProcessingResult>(&processing_result).unwrap(),
         );
+        Nep297Event::AppchainMessageProcessed(processing_result).emit();
+    }
+    // Record the nonce of an applied appchain message in the applied messages of an era.
+    fn record_applied_message_in_era(&mut self, era_number: u64, nonce: u32) {
+        let applied_messages = match self.applied_messages_of_eras.get(&era_number) {
+            Some(mut applied_messages) => {
+                applied_messages.start_nonce = std::cmp::min(applied_messages.start_nonce, nonce);
+                applied_messages.end_nonce = std::cmp::max(applied_messages.end_nonce, nonce);
+                applied_messages.message_count += 1;
+                applied_messages
+            }
+            None => AppliedMessagesOfEra {
+                era_number: U64::from(era_number),
+                start_nonce: nonce,
+                end_nonce: nonce,
+                message_count: 1,
+            },
+        };
+        self.applied_messages_of_eras
+            .insert(&era_number, &applied_messages);
     }
 }
 
@@ -0,0 +1,182 @@
+use crate::*;
+use core::ops::Range;
+
+pub trait RelayerReimbursementResolver {
+    /// Resolver for transferring the claimed reimbursement to relayer
+    fn resolve_relayer_reimbursement_claiming(
+        &mut self,
+        relayer_id: AccountId,
+        gas_usages: Vec<(U64, U64)>,
+    );
+}
+
+/// The unit of gas (1 Ggas) used in calculating the reimbursement of relayers,
+/// to avoid overflow in the multiplication.
+const GAS_UNIT_FOR_CALCULATING_REIMBURSEMENT: u64 = 1_000_000_000;
+
+impl AppchainAnchor {
+    // Record the gas consumed by the current call of the relayer (the predecessor account)
+    // to the latest era. The gas is only recorded for the relayers eligible for reimbursement,
+    // and only if any appchain message is staged or applied in the current call.
+    pub fn record_gas_usage_of_relayer(
+        &mut self,
+        processing_status_before_call: &PermissionlessActionsStatus,
+    ) {
+        let relayer_id = env::predecessor_account_id();
+        if !self.is_reimbursable_relayer(&relayer_id) {
+            return;
+        }
+        let processing_status = self.permissionless_actions_status.get().unwrap();
+        if processing_status.max_nonce_of_staged_appchain_messages
+            <= processing_status_before_call.max_nonce_of_staged_appchain_messages
+            && processing_status.latest_applied_appchain_message_nonce
+                <= processing_status_before_call.latest_applied_appchain_message_nonce
+        {
+            return;
+        }
+        let era_number = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .index_range()
+            .end_index
+            .0;
+        let used_gas = env::used_gas().0;
+        let gas_usage = self
+            .relayer_gas_usages
+            .get(&(era_number, relayer_id.clone()))
+            .unwrap_or(0);
+        self.relayer_gas_usages
+            .insert(&(era_number, relayer_id), &(gas_usage + used_gas));
+        let total_gas = self.total_relayer_gas_of_eras.get(&era_number).unwrap_or(0);
+        self.total_relayer_gas_of_eras
+            .insert(&era_number, &(total_gas + used_gas));
+    }
+    // A relayer is eligible for reimbursement if it is a whitelisted relayer, or it is
+    // the relayer account in anchor settings when relayer bonds are not required.
+    fn is_reimbursable_relayer(&self, relayer_id: &AccountId) -> bool {
+        if self.protocol_settings.get().unwrap().minimum_relayer_bond.0 > 0 {
+            return self.is_whitelisted_relayer(relayer_id);
+        }
+        self.anchor_settings
+            .get()
+            .unwrap()
+            .relayer_account
+            .map_or(false, |relayer_account| relayer_account.eq(relayer_id))
+    }
+    // Claim the reimbursement of the relayer in all claimable eras. The gas usages of
+    // the relayer are removed before the transfer, and restored if the transfer fails.
+    pub fn internal_claim_relayer_reimbursement(&mut self, relayer_id: AccountId) {
+        let mut reimbursement_to_claim: u128 = 0;
+        let mut gas_usages = Vec::new();
+        for era_number in self.get_claimable_eras_of_relayer_reimbursement() {
+            reimbursement_to_claim +=
+                self.get_relayer_reimbursement_of_era(&relayer_id, era_number);
+            if let Some(gas_usage) = self
+                .relayer_gas_usages
+                .remove(&(era_number, relayer_id.clone()))
+            {
+                gas_usages.push((U64::from(era_number), U64::from(gas_usage)));
+            }
+        }
+        assert!(reimbursement_to_claim > 0, "No reimbursement to claim.");
+        Promise::new(relayer_id.clone())
+            .transfer(reimbursement_to_claim)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_relayer_reimbursement_claiming(relayer_id, gas_usages),
+            );
+    }
+    // Allocate the reimbursement of relayers of a certain era from the relayer
+    // reimbursement pool. This should be called when the era is over.
+    pub fn allocate_relayer_reimbursement_of_era(&mut self, era_number: u64) {
+        if self
+            .relayer_reimbursements_of_eras
+            .contains_key(&era_number)
+            || self.total_relayer_gas_of_eras.get(&era_number).unwrap_or(0) == 0
+        {
+            return;
+        }
+        let protocol_settings = self.get_effective_protocol_settings_of(era_number);
+        let amount = std::cmp::min(
+            protocol_settings.relayer_reimbursement_per_era.0,
+            self.relayer_reimbursement_pool_balance,
+        );
+        self.relayer_reimbursement_pool_balance -= amount;
+        self.relayer_reimbursements_of_eras
+            .insert(&era_number, &amount);
+    }
+    // Get the unclaimed reimbursement of a relayer in a certain era,
+    // which is zero if the era is not over yet.
+    pub fn get_relayer_reimbursement_of_era(
+        &self,
+        relayer_id: &AccountId,
+        era_number: u64,
+    ) -> Balance {
+        let gas_usage = match self
+            .relayer_gas_usages
+            .get(&(era_number, relayer_id.clone()))
+        {
+            Some(gas_usage) => gas_usage,
+            None => return 0,
+        };
+        let total_gas = self.total_relayer_gas_of_eras.get(&era_number).unwrap_or(0)
+            / GAS_UNIT_FOR_CALCULATING_REIMBURSEMENT;
+        match self.relayer_reimbursements_of_eras.get(&era_number) {
+            Some(amount) if total_gas > 0 => {
+                amount * u128::from(gas_usage / GAS_UNIT_FOR_CALCULATING_REIMBURSEMENT)
+                    / u128::from(total_gas)
+            }
+            _ => 0,
+        }
+    }
+    // Get the range of eras in which the reimbursement of relayers can be claimed.
+    // The reimbursement of the latest era is not allocated yet.
+    pub fn get_claimable_eras_of_relayer_reimbursement(&self) -> Range<u64> {
+        let end_era = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .index_range()
+            .end_index
+            .0;
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let start_era = match end_era > protocol_settings.maximum_era_count_of_unwithdrawn_reward.0
+        {
+            true => end_era - protocol_settings.maximum_era_count_of_unwithdrawn_reward.0 + 1,
+            false => 0,
+        };
+        start_era..end_era
+    }
+}
+
+#[near_bindgen]
+impl RelayerReimbursementResolver for AppchainAnchor {
+    //
+    fn resolve_relayer_reimbursement_claiming(
+        &mut self,
+        relayer_id: AccountId,
+        gas_usages: Vec<(U64, U64)>,
+    ) {
+        assert_self();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => {
+                log!("Reimbursement of relayer '{}' is claimed.", relayer_id);
+            }
+            PromiseResult::Failed => {
+                for (era_number, gas_usage) in gas_usages {
+                    self.relayer_gas_usages
+                        .insert(&(era_number.0, relayer_id.clone()), &gas_usage.0);
+                }
+                log!(
+                    "Failed to transfer reimbursement to relayer '{}'. Gas usages are restored.",
+                    relayer_id
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::validator_set::Delegator;
+use crate::*;
+
+impl AppchainAnchor {
+    //
+    pub fn internal_complete_validator_retirement(
+        &mut self,
+        validator_id: &AccountId,
+    ) -> MultiTxsOperationProcessingResult {
+        let retiring_era_number = match self.validator_retirements.get(validator_id) {
+            Some(era_number) => era_number,
+            None => {
+                return MultiTxsOperationProcessingResult::Error(format!(
+                    "Validator '{}' has not announced its retirement.",
+                    validator_id
+                ))
+            }
+        };
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        if next_validator_set.era_number() < retiring_era_number.0 {
+            return MultiTxsOperationProcessingResult::Error(format!(
+                "The retirement of validator '{}' cannot be completed before era '{}'.",
+                validator_id, retiring_era_number.0
+            ));
+        }
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        if next_validator_set.validator_count() <= protocol_settings.minimum_validator_count.0 {
+            return MultiTxsOperationProcessingResult::Error(
+                "Too few validators. Cannot unbond any more.".to_string(),
+            );
+        }
+        for delegator_id in next_validator_set.get_delegator_ids_of(validator_id) {
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                self.next_validator_set.set(&next_validator_set);
+                return MultiTxsOperationProcessingResult::NeedMoreGas;
+            }
+            let delegator = next_validator_set
+                .get_delegator(&delegator_id, validator_id)
+                .unwrap();
+            if let Some(fallback_validator_id) = self.get_available_fallback_validator_of(
+                &protocol_settings,
+                &next_validator_set,
+                &delegator,
+            ) {
+                let staking_history =
+                    self.record_staking_fact(StakingFact::DelegationRedelegated {
+                        delegator_id: delegator_id.clone(),
+                        from_validator_id: validator_id.clone(),
+                        to_validator_id: fallback_validator_id,
+                        amount: U128::from(delegator.deposit_amount),
+                    });
+                self.apply_staking_fact_to_next_validator_set(
+                    &mut next_validator_set,
+                    &staking_history.staking_fact,
+                );
+            }
+        }
+        // The remaining delegators will be auto-unbonded along with the validator
+        // in the next switching era.
+        self.record_unbonding_validator(
+            &protocol_settings,
+            &mut next_validator_set,
+            validator_id,
+            false,
+        );
+        self.next_validator_set.set(&next_validator_set);
+        self.validator_retirements.remove(validator_id);
+        //
+        self.sync_state_to_registry();
+        MultiTxsOperationProcessingResult::Ok
+    }
+    // Get the fallback validator of the delegator, if the delegation can be moved to it.
+    fn get_available_fallback_validator_of(
+        &self,
+        protocol_settings: &ProtocolSettings,
+        next_validator_set: &NextValidatorSet,
+        delegator: &Delegator,
+    ) -> Option<AccountId> {
+        let fallback_validator_id = self
+            .delegator_fallback_validators
+            .get(&delegator.delegator_id)?;
+        if fallback_validator_id.eq(&delegator.validator_id)
+            || self
+                .validator_retirements
+                .contains_key(&fallback_validator_id)
+        {
+            return None;
+        }
+        let fallback_validator = next_validator_set.get_validator(&fallback_validator_id)?;
+        if !fallback_validator.can_be_delegated_to || fallback_validator.is_jailed {
+            return None;
+        }
+        if !next_validator_set.contains_delegator(&delegator.delegator_id, &fallback_validator_id)
+            && next_validator_set.get_validator_count_of(&delegator.delegator_id)
+                > protocol_settings.maximum_validators_per_delegator.0
+        {
+            return None;
+        }
+        if !self.is_total_stake_of_validator_allowed(
+            protocol_settings,
+            fallback_validator.total_stake + delegator.deposit_amount,
+        ) {
+            return None;
+        }
+        Some(fallback_validator_id)
+    }
+}
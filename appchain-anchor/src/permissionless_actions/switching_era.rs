@@ -1,4 +1,5 @@
 use super::{AppchainMessagesProcessingContext, ResultOfLoopingValidatorSet};
+use crate::validator_set::Validator;
 use crate::*;
 use core::convert::TryFrom;
 use user_actions::UnbondedStakeReference;
@@ -9,6 +10,7 @@ impl AppchainAnchor {
         &mut self,
         processing_context: &mut AppchainMessagesProcessingContext,
         validator_set_histories: &mut LookupArray<ValidatorSetOfEra>,
+        appchain_message_nonce: u32,
         era_number: u64,
     ) -> MultiTxsOperationProcessingResult {
         if !validator_set_histories.contains(&era_number) {
@@ -24,10 +26,97 @@ impl AppchainAnchor {
                         .0,
                 ),
             );
+            if let Some(shuffle_seed) = validator_set_histories
+                .get(&era_number)
+                .and_then(|validator_set| validator_set.shuffle_seed())
+            {
+                self.internal_append_appchain_notification(
+                    AppchainNotification::ShuffleSeedGenerated {
+                        era_number: U64::from(era_number),
+                        shuffle_seed: format!("0x{}", hex::encode(shuffle_seed)),
+                    },
+                );
+            }
+            self.protocol_settings_of_eras.insert(
+                &era_number,
+                &VersionedProtocolSettings::new(self.protocol_settings.get().unwrap()),
+            );
+            if era_number > 0 {
+                self.allocate_relayer_reimbursement_of_era(era_number - 1);
+            }
+            self.update_era_summaries_on_switching_era(
+                validator_set_histories,
+                appchain_message_nonce,
+                era_number,
+            );
         }
         processing_context.set_switching_era_number(era_number);
         MultiTxsOperationProcessingResult::NeedMoreGas
     }
+    // Close the summary of last era, and open the summary of the new era.
+    fn update_era_summaries_on_switching_era(
+        &mut self,
+        validator_set_histories: &LookupArray<ValidatorSetOfEra>,
+        appchain_message_nonce: u32,
+        era_number: u64,
+    ) {
+        let validator_set = validator_set_histories.get(&era_number).unwrap();
+        if era_number > 0 {
+            if let Some(last_validator_set) = validator_set_histories.get(&(era_number - 1)) {
+                let mut last_era_summary =
+                    self.era_summaries
+                        .get(&(era_number - 1))
+                        .unwrap_or(EraSummary {
+                            era_number: U64::from(era_number - 1),
+                            start_block_height: U64::from(last_validator_set.start_block_height()),
+                            start_timestamp: U64::from(last_validator_set.start_timestamp()),
+                            duration: None,
+                            validator_count: U64::from(0),
+                            total_stake: U128::from(0),
+                            reward_paid: U128::from(0),
+                            start_message_nonce: None,
+                            end_message_nonce: None,
+                        });
+                last_era_summary.duration = Some(U64::from(
+                    validator_set.start_timestamp() - last_validator_set.start_timestamp(),
+                ));
+                last_era_summary.validator_count = U64::from(last_validator_set.validator_count());
+                last_era_summary.total_stake = U128::from(last_validator_set.total_stake());
+                last_era_summary.end_message_nonce = Some(U64::from(
+                    u64::from(appchain_message_nonce).saturating_sub(1),
+                ));
+                self.era_summaries
+                    .insert(&(era_number - 1), &last_era_summary);
+                if let Some(era_duration_drift) = self.get_era_duration_drift_of(era_number - 1) {
+                    if era_duration_drift.is_beyond_tolerance {
+                        self.internal_append_appchain_notification(
+                            AppchainNotification::EraDurationDrifted {
+                                era_number: era_duration_drift.era_number,
+                                expected_duration_in_secs: era_duration_drift
+                                    .expected_duration_in_secs,
+                                observed_duration_in_secs: era_duration_drift
+                                    .observed_duration_in_secs,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+        self.era_summaries.insert(
+            &era_number,
+            &EraSummary {
+                era_number: U64::from(era_number),
+                start_block_height: U64::from(validator_set.start_block_height()),
+                start_timestamp: U64::from(validator_set.start_timestamp()),
+                duration: None,
+                validator_count: U64::from(0),
+                total_stake: U128::from(0),
+                reward_paid: U128::from(0),
+                start_message_nonce: Some(U64::from(u64::from(appchain_message_nonce))),
+                end_message_nonce: None,
+            },
+        );
+    }
     //
     pub fn complete_switching_era(
         &mut self,
@@ -207,6 +296,21 @@ impl AppchainAnchor {
                     validator_set.set_processing_status(
                         ValidatorSetProcessingStatus::ReadyForDistributingReward,
                     );
+                    let mut standby_validator_ids = self.select_standby_validators(
+                        &validator_set,
+                        self.get_effective_protocol_settings_of(era_number)
+                            .maximum_validator_count_per_operator
+                            .0,
+                    );
+                    for validator_id in self.apply_validator_set_override_to(&validator_set) {
+                        if !standby_validator_ids.contains(&validator_id) {
+                            standby_validator_ids.push(validator_id);
+                        }
+                    }
+                    if !standby_validator_ids.is_empty() {
+                        self.standby_validator_ids_of_eras
+                            .insert(&era_number, &standby_validator_ids);
+                    }
                     self.record_appchain_message_processing_result(
                         &AppchainMessageProcessingResult::Ok {
                             nonce: processing_context.processing_nonce().unwrap_or(0),
@@ -280,9 +384,13 @@ impl AppchainAnchor {
                 .validator_set()
                 .get_delegator_count_of(&validator.validator_id)
         {
+            self.record_vesting_locked_stake_unbonded(&validator);
+            self.record_secondary_stake_unbonded(&validator);
             self.record_staking_fact(StakingFact::ValidatorUnbonded {
-                validator_id: validator.validator_id,
-                amount: U128::from(validator.deposit_amount),
+                validator_id: validator.validator_id.clone(),
+                amount: U128::from(
+                    validator.deposit_amount - validator.vesting_locked_deposit_amount(),
+                ),
             });
             return ResultOfLoopingValidatorSet::NoMoreDelegator;
         }
@@ -320,9 +428,13 @@ impl AppchainAnchor {
                 .validator_set()
                 .get_delegator_count_of(&validator.validator_id)
         {
+            self.record_vesting_locked_stake_unbonded(&validator);
+            self.record_secondary_stake_unbonded(&validator);
             self.record_staking_fact(StakingFact::ValidatorAutoUnbonded {
-                validator_id: validator.validator_id,
-                amount: U128::from(validator.deposit_amount),
+                validator_id: validator.validator_id.clone(),
+                amount: U128::from(
+                    validator.deposit_amount - validator.vesting_locked_deposit_amount(),
+                ),
             });
             return ResultOfLoopingValidatorSet::NoMoreDelegator;
         }
@@ -337,6 +449,39 @@ impl AppchainAnchor {
         });
         return ResultOfLoopingValidatorSet::NeedToContinue;
     }
+    // Record the unbonding of all vesting-locked stake of a validator, which should be
+    // recorded before the unbonding of the validator.
+    fn record_vesting_locked_stake_unbonded(&mut self, validator: &Validator) {
+        let mut depositor_ids = validator
+            .vesting_locked_deposits
+            .keys()
+            .cloned()
+            .collect::<Vec<AccountId>>();
+        depositor_ids.sort();
+        for depositor_id in depositor_ids {
+            let amount = *validator
+                .vesting_locked_deposits
+                .get(&depositor_id)
+                .unwrap();
+            self.record_staking_fact(StakingFact::VestingLockedStakeUnbonded {
+                validator_id: validator.validator_id.clone(),
+                depositor_id,
+                amount: U128::from(amount),
+            });
+        }
+    }
+    //
+    // Record the unbonding of the stake of the secondary token of a validator, which should be
+    // recorded before the unbonding of the validator.
+    fn record_secondary_stake_unbonded(&mut self, validator: &Validator) {
+        if validator.secondary_deposit_amount > 0 {
+            self.record_staking_fact(StakingFact::SecondaryStakeUnbonded {
+                validator_id: validator.validator_id.clone(),
+                amount: U128::from(validator.secondary_deposit_amount),
+                stake: U128::from(validator.secondary_stake),
+            });
+        }
+    }
     //
     fn apply_staking_history_to_validator_set_of_era(
         &mut self,
@@ -388,14 +533,44 @@ impl AppchainAnchor {
                     },
                 );
             }
+            StakingFact::VestingLockedStakeUnbonded {
+                validator_id: _,
+                depositor_id,
+                amount: _,
+            } => {
+                self.add_unbonded_stake_of(
+                    depositor_id,
+                    UnbondedStakeReference {
+                        era_number: validator_set.era_number(),
+                        staking_history_index: staking_history.index.0,
+                    },
+                );
+            }
+            StakingFact::SecondaryStakeDecreased { validator_id, .. }
+            | StakingFact::SecondaryStakeUnbonded { validator_id, .. } => {
+                let mut stakes = self
+                    .unbonded_secondary_stakes
+                    .get(validator_id)
+                    .unwrap_or_default();
+                stakes.push(UnbondedStakeReference {
+                    era_number: validator_set.era_number(),
+                    staking_history_index: staking_history.index.0,
+                });
+                self.unbonded_secondary_stakes.insert(validator_id, &stakes);
+            }
             _ => (),
         }
         match &staking_history.staking_fact {
             StakingFact::ValidatorUnbonded { .. }
             | StakingFact::ValidatorAutoUnbonded { .. }
-            | StakingFact::DelegatorAutoUnbonded { .. } => {
+            | StakingFact::DelegatorAutoUnbonded { .. }
+            | StakingFact::VestingLockedStakeUnbonded { .. }
+            | StakingFact::SecondaryStakeUnbonded { .. } => {
                 let mut next_validator_set = self.next_validator_set.get().unwrap();
-                next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+                self.apply_staking_fact_to_next_validator_set(
+                    &mut next_validator_set,
+                    &staking_history.staking_fact,
+                );
                 self.next_validator_set.set(&next_validator_set);
                 self.sync_state_to_registry();
             }
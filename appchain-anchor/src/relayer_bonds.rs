@@ -0,0 +1,127 @@
+use crate::*;
+use near_contract_standards::fungible_token::core::ext_ft_core;
+
+impl AppchainAnchor {
+    // A relayer is whitelisted when its bond is not unbonding and is not less than
+    // the minimum relayer bond in protocol settings.
+    pub fn is_whitelisted_relayer(&self, relayer_id: &AccountId) -> bool {
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        self.relayer_bonds.get(relayer_id).map_or(false, |bond| {
+            bond.status == RelayerBondStatus::Bonded
+                && bond.amount.0 >= protocol_settings.minimum_relayer_bond.0
+        })
+    }
+    //
+    pub fn internal_bond_relayer(&mut self, relayer_id: AccountId, amount: Balance) {
+        let mut bond = self.relayer_bonds.get(&relayer_id).unwrap_or(RelayerBond {
+            relayer_id: relayer_id.clone(),
+            amount: U128::from(0),
+            slashed_amount: U128::from(0),
+            bonded_timestamp: U64::from(env::block_timestamp()),
+            status: RelayerBondStatus::Bonded,
+        });
+        assert!(
+            bond.status == RelayerBondStatus::Bonded,
+            "The bond of relayer '{}' is unbonding.",
+            relayer_id
+        );
+        bond.amount = U128::from(bond.amount.0 + amount);
+        self.relayer_bonds.insert(&relayer_id, &bond);
+        log!(
+            "Bond of relayer '{}' is increased by '{}'. Current bond: '{}'",
+            relayer_id,
+            amount,
+            bond.amount.0
+        );
+    }
+    // The bond is still slashable in its unlock period.
+    pub fn internal_unbond_relayer(&mut self, relayer_id: &AccountId) {
+        let mut bond = self.get_relayer_bond(relayer_id);
+        assert!(
+            bond.status == RelayerBondStatus::Bonded,
+            "The bond of relayer '{}' is already unbonding.",
+            relayer_id
+        );
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        bond.status = RelayerBondStatus::Unbonding {
+            unlock_timestamp: U64::from(
+                env::block_timestamp()
+                    + self
+                        .time_provider()
+                        .nanoseconds_of_days(protocol_settings.unlock_period_of_relayer_bond.0),
+            ),
+        };
+        self.relayer_bonds.insert(relayer_id, &bond);
+    }
+    //
+    pub fn internal_withdraw_relayer_bond(&mut self, relayer_id: &AccountId) {
+        let bond = self.get_relayer_bond(relayer_id);
+        match bond.status {
+            RelayerBondStatus::Unbonding { unlock_timestamp } => assert!(
+                env::block_timestamp() >= unlock_timestamp.0,
+                "The bond of relayer '{}' is not unlocked yet.",
+                relayer_id
+            ),
+            RelayerBondStatus::Bonded => {
+                panic!("The bond of relayer '{}' is not unbonded.", relayer_id)
+            }
+        }
+        self.relayer_bonds.remove(relayer_id);
+        if bond.amount.0 > 0 {
+            ext_ft_core::ext(self.oct_token.get().unwrap().contract_account)
+                .with_attached_deposit(1)
+                .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+                .with_unused_gas_weight(0)
+                .ft_transfer(relayer_id.clone(), bond.amount, None);
+        }
+    }
+    // Slash the bond of a relayer by the slash percent in protocol settings. The slashed
+    // amount is transferred to the treasury, or burnt (kept in this contract)
+    // if there is no destination of it.
+    pub fn internal_slash_relayer_bond(
+        &mut self,
+        relayer_id: &AccountId,
+        finding: RelayerMisbehaviorFinding,
+    ) -> RelayerBondSlash {
+        if let RelayerMisbehaviorFinding::AppchainChallenge { challenge_index } = &finding {
+            assert!(
+                self.appchain_challenges
+                    .get()
+                    .unwrap()
+                    .get(&challenge_index.0)
+                    .is_some(),
+                "The appchain challenge '{}' is not existed.",
+                challenge_index.0
+            );
+        }
+        let mut bond = self.get_relayer_bond(relayer_id);
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let amount = bond.amount.0 * u128::from(protocol_settings.relayer_bond_slash_percent) / 100;
+        bond.amount = U128::from(bond.amount.0 - amount);
+        bond.slashed_amount = U128::from(bond.slashed_amount.0 + amount);
+        self.relayer_bonds.insert(relayer_id, &bond);
+        let mut relayer_bond_slashes = self.relayer_bond_slashes.get().unwrap();
+        let slash = relayer_bond_slashes.append(&mut RelayerBondSlash {
+            relayer_id: relayer_id.clone(),
+            amount: U128::from(amount),
+            finding,
+            slashed_timestamp: U64::from(env::block_timestamp()),
+            index: U64::from(0),
+        });
+        self.relayer_bond_slashes.set(&relayer_bond_slashes);
+        self.transfer_oct_token_to_treasury(TreasurySource::RelayerBondSlash, amount);
+        log!(
+            "Bond of relayer '{}' is slashed by '{}'. Remaining bond: '{}'",
+            relayer_id,
+            amount,
+            bond.amount.0
+        );
+        slash
+    }
+    //
+    fn get_relayer_bond(&self, relayer_id: &AccountId) -> RelayerBond {
+        self.relayer_bonds
+            .get(relayer_id)
+            .expect("The relayer is not bonded.")
+    }
+}
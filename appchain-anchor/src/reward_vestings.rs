@@ -0,0 +1,94 @@
+use crate::*;
+
+impl AppchainAnchor {
+    // Take the vesting part out of the reward of a validator in a certain era, by the
+    // protocol settings effective in the era. Return the rest of the reward.
+    // There is no vesting while the appchain is terminating.
+    pub fn internal_take_vesting_part_of_validator_reward(
+        &mut self,
+        validator_id: &AccountId,
+        era_number: u64,
+        reward: Balance,
+    ) -> Balance {
+        let protocol_settings = self.get_effective_protocol_settings_of(era_number);
+        if self.appchain_state == AppchainState::Terminating
+            || protocol_settings.validator_reward_vesting_percent == 0
+            || protocol_settings.validator_reward_vesting_era_count.0 == 0
+        {
+            return reward;
+        }
+        let vesting_amount =
+            reward * u128::from(protocol_settings.validator_reward_vesting_percent) / 100;
+        if vesting_amount == 0 {
+            return reward;
+        }
+        let mut vestings = self
+            .validator_reward_vestings
+            .get(validator_id)
+            .unwrap_or_default();
+        vestings.push(ValidatorRewardVesting {
+            era_number: U64::from(era_number),
+            vesting_era_count: protocol_settings.validator_reward_vesting_era_count,
+            total_amount: U128::from(vesting_amount),
+            released_amount: U128::from(0),
+        });
+        self.validator_reward_vestings
+            .insert(validator_id, &vestings);
+        reward - vesting_amount
+    }
+    // Release the vested parts of all reward vestings of a validator at a certain era.
+    // The fully released vestings are removed. Return the released amount.
+    pub fn internal_release_vested_rewards_of(
+        &mut self,
+        validator_id: &AccountId,
+        era_number: u64,
+    ) -> Balance {
+        let era_number = self.get_vesting_release_era(era_number);
+        let mut released_amount: Balance = 0;
+        if let Some(vestings) = self.validator_reward_vestings.get(validator_id) {
+            let remaining_vestings: Vec<ValidatorRewardVesting> = vestings
+                .into_iter()
+                .filter_map(|mut vesting| {
+                    let vested_amount = vesting.vested_amount_at(era_number);
+                    released_amount += vested_amount - vesting.released_amount.0;
+                    vesting.released_amount = U128::from(vested_amount);
+                    match vested_amount < vesting.total_amount.0 {
+                        true => Some(vesting),
+                        false => None,
+                    }
+                })
+                .collect();
+            match remaining_vestings.is_empty() {
+                true => self.validator_reward_vestings.remove(validator_id),
+                false => self
+                    .validator_reward_vestings
+                    .insert(validator_id, &remaining_vestings),
+            };
+        }
+        released_amount
+    }
+    //
+    pub fn calculate_releasable_vested_reward_of(&self, validator_id: &AccountId) -> Balance {
+        let end_era = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .index_range()
+            .end_index
+            .0;
+        let release_era = self.get_vesting_release_era(end_era);
+        self.validator_reward_vestings
+            .get(validator_id)
+            .unwrap_or_default()
+            .iter()
+            .map(|vesting| vesting.vested_amount_at(release_era) - vesting.released_amount.0)
+            .sum()
+    }
+    // All reward vestings are fully released while the appchain is terminating.
+    fn get_vesting_release_era(&self, era_number: u64) -> u64 {
+        match self.appchain_state {
+            AppchainState::Terminating => u64::MAX,
+            _ => era_number,
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use crate::*;
+
+impl AppchainAnchor {
+    // Apply the slash for an offence of a validator reported by the appchain. The slashable
+    // deposit of the validator and the delegations of its delegators are slashed by the
+    // percents in protocol settings, and the slashed amounts are held in penalty escrows.
+    //
+    // As the count of delegators is not limited, they are slashed in batches, and the
+    // progress is kept in the latest slash history until all of them are slashed.
+    pub fn internal_apply_reported_offence(
+        &mut self,
+        nonce: u32,
+        validator_id_in_appchain: &String,
+        offence_kind: &String,
+        era_number: u32,
+    ) -> MultiTxsOperationProcessingResult {
+        let mut slash_history = match self.get_slash_history_in_progress(nonce) {
+            Some(slash_history) => slash_history,
+            None => {
+                match self.start_slash(nonce, validator_id_in_appchain, offence_kind, era_number) {
+                    Ok(slash_history) => slash_history,
+                    Err(message) => {
+                        self.record_appchain_message_processing_result(
+                            &AppchainMessageProcessingResult::Error {
+                                nonce,
+                                message: message.clone(),
+                            },
+                        );
+                        return MultiTxsOperationProcessingResult::Error(message);
+                    }
+                }
+            }
+        };
+        if let SlashStatus::SlashingDelegators {
+            mut pending_delegator_ids,
+        } = slash_history.status.clone()
+        {
+            let delegator_slash_percent = self
+                .protocol_settings
+                .get()
+                .unwrap()
+                .delegator_slash_percent;
+            let mut next_validator_set = self.next_validator_set.get().unwrap();
+            while let Some(delegator_id) = pending_delegator_ids.pop() {
+                self.slash_delegator_for_offence(
+                    &mut next_validator_set,
+                    &mut slash_history.slash_fact,
+                    &delegator_id,
+                    delegator_slash_percent,
+                );
+                if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                    break;
+                }
+            }
+            self.next_validator_set.set(&next_validator_set);
+            slash_history.status = match pending_delegator_ids.is_empty() {
+                true => SlashStatus::Completed,
+                false => SlashStatus::SlashingDelegators {
+                    pending_delegator_ids,
+                },
+            };
+        }
+        let mut slash_histories = self.slash_histories.get().unwrap();
+        slash_histories.insert(&slash_history.index.0, &slash_history);
+        self.slash_histories.set(&slash_histories);
+        if slash_history.status != SlashStatus::Completed {
+            return MultiTxsOperationProcessingResult::NeedMoreGas;
+        }
+        self.sync_state_to_registry();
+        let slash_fact = &slash_history.slash_fact;
+        self.record_appchain_message_processing_result(&AppchainMessageProcessingResult::Ok {
+            nonce,
+            message: Some(format!(
+                "Validator '{}' is slashed by '{}', and its {} delegator(s) by '{}' in total.",
+                slash_fact.validator_id,
+                slash_fact.validator_slashed_amount.0,
+                slash_fact.slashed_delegator_count.0,
+                slash_fact.delegator_slashed_amount.0
+            )),
+        });
+        MultiTxsOperationProcessingResult::Ok
+    }
+    // Get the latest slash history if it is applied by the appchain message with
+    // the given nonce and is not completed yet.
+    fn get_slash_history_in_progress(&self, nonce: u32) -> Option<SlashHistory> {
+        let slash_histories = self.slash_histories.get().unwrap();
+        slash_histories
+            .get(&slash_histories.index_range().end_index.0)
+            .filter(|slash_history| {
+                slash_history.slash_fact.appchain_message_nonce == nonce
+                    && slash_history.status != SlashStatus::Completed
+            })
+    }
+    // Slash the deposit of the offending validator, and record the slash history
+    // with the delegators of the validator to be slashed.
+    fn start_slash(
+        &mut self,
+        nonce: u32,
+        validator_id_in_appchain: &String,
+        offence_kind: &String,
+        era_number: u32,
+    ) -> Result<SlashHistory, String> {
+        let formatted_id = AccountIdInAppchain::new(
+            Some(validator_id_in_appchain.clone()),
+            &self.appchain_template_type,
+        );
+        if !formatted_id.is_valid() {
+            return Err(format!(
+                "Invalid validator id in appchain: '{}'.",
+                validator_id_in_appchain
+            ));
+        }
+        let validator_id = match self
+            .validator_profiles
+            .get()
+            .unwrap()
+            .get_by_id_in_appchain(&formatted_id.to_string())
+        {
+            Some(profile) => profile.validator_id,
+            None => {
+                return Err(format!(
+                    "Validator '{}' is not registered.",
+                    validator_id_in_appchain
+                ))
+            }
+        };
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        let validator = match next_validator_set
+            .validator_set()
+            .get_validator(&validator_id)
+        {
+            Some(validator) => validator,
+            None => {
+                return Err(format!(
+                    "Validator '{}' is not in the next validator set.",
+                    validator_id
+                ))
+            }
+        };
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let validator_slashed_amount = validator.slashable_deposit_amount()
+            * u128::from(protocol_settings.validator_slash_percent)
+            / 100;
+        if validator_slashed_amount > 0 {
+            let staking_history = self.record_staking_fact(StakingFact::ValidatorSlashed {
+                validator_id: validator_id.clone(),
+                amount: U128::from(validator_slashed_amount),
+            });
+            self.apply_staking_fact_to_next_validator_set(
+                &mut next_validator_set,
+                &staking_history.staking_fact,
+            );
+            self.next_validator_set.set(&next_validator_set);
+            self.internal_escrow_penalty(
+                &validator_id,
+                validator_slashed_amount,
+                staking_history.index.0,
+            );
+        }
+        let mut pending_delegator_ids = match protocol_settings.delegator_slash_percent {
+            0 => Vec::new(),
+            _ => next_validator_set
+                .validator_set()
+                .get_delegator_ids_of(&validator_id),
+        };
+        pending_delegator_ids.sort();
+        pending_delegator_ids.reverse();
+        let mut slash_histories = self.slash_histories.get().unwrap();
+        let slash_history = slash_histories.append(&mut SlashHistory {
+            slash_fact: SlashFact {
+                validator_id,
+                validator_id_in_appchain: formatted_id.to_string(),
+                offence_kind: offence_kind.clone(),
+                era_number: U64::from(u64::from(era_number)),
+                appchain_message_nonce: nonce,
+                validator_slashed_amount: U128::from(validator_slashed_amount),
+                delegator_slashed_amount: U128::from(0),
+                slashed_delegator_count: U64::from(0),
+            },
+            status: SlashStatus::SlashingDelegators {
+                pending_delegator_ids,
+            },
+            block_height: U64::from(env::block_height()),
+            timestamp: U64::from(env::block_timestamp()),
+            index: U64::from(0),
+        });
+        self.slash_histories.set(&slash_histories);
+        let mut indexes = self
+            .slash_history_indexes_of_validators
+            .get(&slash_history.slash_fact.validator_id)
+            .unwrap_or_default();
+        indexes.push(slash_history.index.0);
+        self.slash_history_indexes_of_validators
+            .insert(&slash_history.slash_fact.validator_id, &indexes);
+        Ok(slash_history)
+    }
+    // Slash the delegation of a delegator of the offending validator, if it is still
+    // delegating to the validator.
+    fn slash_delegator_for_offence(
+        &mut self,
+        next_validator_set: &mut NextValidatorSet,
+        slash_fact: &mut SlashFact,
+        delegator_id: &AccountId,
+        delegator_slash_percent: u16,
+    ) {
+        let delegator = match next_validator_set
+            .validator_set()
+            .get_delegator(delegator_id, &slash_fact.validator_id)
+        {
+            Some(delegator) => delegator,
+            None => return,
+        };
+        let amount = delegator.deposit_amount * u128::from(delegator_slash_percent) / 100;
+        if amount == 0 {
+            return;
+        }
+        let staking_history = self.record_staking_fact(StakingFact::DelegatorSlashed {
+            delegator_id: delegator_id.clone(),
+            validator_id: slash_fact.validator_id.clone(),
+            amount: U128::from(amount),
+        });
+        self.apply_staking_fact_to_next_validator_set(
+            next_validator_set,
+            &staking_history.staking_fact,
+        );
+        self.internal_escrow_penalty(delegator_id, amount, staking_history.index.0);
+        slash_fact.delegator_slashed_amount =
+            U128::from(slash_fact.delegator_slashed_amount.0 + amount);
+        slash_fact.slashed_delegator_count = U64::from(slash_fact.slashed_delegator_count.0 + 1);
+    }
+}
@@ -0,0 +1,199 @@
+use crate::validator_set::{Delegator, Validator};
+use crate::*;
+use near_sdk::collections::TreeMap;
+
+/// The maximum number of items returned in a page of a sorted list.
+const MAXIMUM_PAGE_SIZE: u64 = 50;
+
+/// The sorted index of accounts, by the tuple of `(sort_value, account_id)`.
+type SortedAccountIndex = TreeMap<(u128, AccountId), ()>;
+
+/// The sorted indices of the validators and delegators in the next validator set,
+/// which are maintained along with the applying of staking facts, so that the
+/// sorted list views do not need to sort the whole set in view calls.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct StakingSortedIndices {
+    validators_by_stake: SortedAccountIndex,
+    validators_by_registration_time: SortedAccountIndex,
+    /// The sorted indices of delegators of validators, mapped by validator id.
+    delegators_by_stake: LookupMap<AccountId, SortedAccountIndex>,
+    delegators_by_registration_time: LookupMap<AccountId, SortedAccountIndex>,
+    /// The indexed `(stake, registration_time)` of validators and delegators,
+    /// mapped by `(validator_id, delegator_id)`, for removing the stale entries.
+    indexed_sort_values: LookupMap<(AccountId, Option<AccountId>), (u128, u128)>,
+}
+
+impl StakingSortedIndices {
+    /// Create the empty indices, which are filled along with the applying of staking facts.
+    pub fn new() -> Self {
+        Self {
+            validators_by_stake: TreeMap::new(StorageKey::ValidatorsByStake.into_bytes()),
+            validators_by_registration_time: TreeMap::new(
+                StorageKey::ValidatorsByRegistrationTime.into_bytes(),
+            ),
+            delegators_by_stake: LookupMap::new(StorageKey::DelegatorsByStake.into_bytes()),
+            delegators_by_registration_time: LookupMap::new(
+                StorageKey::DelegatorsByRegistrationTime.into_bytes(),
+            ),
+            indexed_sort_values: LookupMap::new(StorageKey::IndexedSortValues.into_bytes()),
+        }
+    }
+    /// Update the entries of a validator, remove them if the validator is `None`.
+    pub fn sync_validator(&mut self, validator_id: &AccountId, validator: Option<&Validator>) {
+        let key = (validator_id.clone(), None);
+        if let Some((stake, registration_time)) = self.indexed_sort_values.get(&key) {
+            self.validators_by_stake
+                .remove(&(stake, validator_id.clone()));
+            self.validators_by_registration_time
+                .remove(&(registration_time, validator_id.clone()));
+            self.indexed_sort_values.remove(&key);
+        }
+        if let Some(validator) = validator {
+            let registration_time = u128::from(validator.registered_timestamp);
+            self.validators_by_stake
+                .insert(&(validator.total_stake, validator_id.clone()), &());
+            self.validators_by_registration_time
+                .insert(&(registration_time, validator_id.clone()), &());
+            self.indexed_sort_values
+                .insert(&key, &(validator.total_stake, registration_time));
+        }
+    }
+    /// Update the entries of a delegator, remove them if the delegator is `None`.
+    pub fn sync_delegator(
+        &mut self,
+        validator_id: &AccountId,
+        delegator_id: &AccountId,
+        delegator: Option<&Delegator>,
+    ) {
+        let key = (validator_id.clone(), Some(delegator_id.clone()));
+        let mut by_stake = self
+            .delegators_by_stake
+            .get(validator_id)
+            .unwrap_or_else(|| {
+                TreeMap::new(
+                    StorageKey::DelegatorsByStakeOfValidator(validator_id.clone()).into_bytes(),
+                )
+            });
+        let mut by_registration_time = self
+            .delegators_by_registration_time
+            .get(validator_id)
+            .unwrap_or_else(|| {
+                TreeMap::new(
+                    StorageKey::DelegatorsByRegistrationTimeOfValidator(validator_id.clone())
+                        .into_bytes(),
+                )
+            });
+        if let Some((stake, registration_time)) = self.indexed_sort_values.get(&key) {
+            by_stake.remove(&(stake, delegator_id.clone()));
+            by_registration_time.remove(&(registration_time, delegator_id.clone()));
+            self.indexed_sort_values.remove(&key);
+        }
+        if let Some(delegator) = delegator {
+            let registration_time = u128::from(delegator.registered_timestamp);
+            by_stake.insert(&(delegator.deposit_amount, delegator_id.clone()), &());
+            by_registration_time.insert(&(registration_time, delegator_id.clone()), &());
+            self.indexed_sort_values
+                .insert(&key, &(delegator.deposit_amount, registration_time));
+        }
+        self.delegators_by_stake.insert(validator_id, &by_stake);
+        self.delegators_by_registration_time
+            .insert(validator_id, &by_registration_time);
+    }
+    /// Get a page of the sorted validator ids.
+    pub fn get_validator_ids(
+        &self,
+        sort_key: &ListSortKey,
+        direction: &SortDirection,
+        cursor: Option<ListCursor>,
+        quantity: Option<u64>,
+    ) -> (Vec<AccountId>, Option<ListCursor>) {
+        let index = match sort_key {
+            ListSortKey::Stake => &self.validators_by_stake,
+            ListSortKey::RegistrationTime => &self.validators_by_registration_time,
+        };
+        get_page_of(index, direction, cursor, quantity)
+    }
+    /// Get a page of the sorted delegator ids of a validator.
+    pub fn get_delegator_ids(
+        &self,
+        validator_id: &AccountId,
+        sort_key: &ListSortKey,
+        direction: &SortDirection,
+        cursor: Option<ListCursor>,
+        quantity: Option<u64>,
+    ) -> (Vec<AccountId>, Option<ListCursor>) {
+        let index = match sort_key {
+            ListSortKey::Stake => self.delegators_by_stake.get(validator_id),
+            ListSortKey::RegistrationTime => self.delegators_by_registration_time.get(validator_id),
+        };
+        match index {
+            Some(index) => get_page_of(&index, direction, cursor, quantity),
+            None => (Vec::new(), None),
+        }
+    }
+}
+
+//
+fn get_page_of(
+    index: &SortedAccountIndex,
+    direction: &SortDirection,
+    cursor: Option<ListCursor>,
+    quantity: Option<u64>,
+) -> (Vec<AccountId>, Option<ListCursor>) {
+    let quantity = quantity.map_or(MAXIMUM_PAGE_SIZE, |q| q.max(1).min(MAXIMUM_PAGE_SIZE));
+    let cursor = cursor.map(|cursor| (cursor.sort_value.0, cursor.account_id));
+    let iter: Box<dyn Iterator<Item = ((u128, AccountId), ())>> = match (direction, cursor) {
+        (SortDirection::Ascending, Some(cursor)) => Box::new(index.iter_from(cursor)),
+        (SortDirection::Ascending, None) => Box::new(index.iter()),
+        (SortDirection::Descending, Some(cursor)) => Box::new(index.iter_rev_from(cursor)),
+        (SortDirection::Descending, None) => Box::new(index.iter_rev()),
+    };
+    let keys: Vec<(u128, AccountId)> = iter
+        .take(usize::try_from(quantity + 1).unwrap())
+        .map(|(key, _)| key)
+        .collect();
+    let next_cursor = match keys.len() as u64 > quantity {
+        true => keys
+            .get(usize::try_from(quantity).unwrap() - 1)
+            .map(|(sort_value, account_id)| ListCursor {
+                sort_value: U128::from(*sort_value),
+                account_id: account_id.clone(),
+            }),
+        false => None,
+    };
+    let account_ids = keys
+        .into_iter()
+        .take(usize::try_from(quantity).unwrap())
+        .map(|(_, account_id)| account_id)
+        .collect();
+    (account_ids, next_cursor)
+}
+
+impl AppchainAnchor {
+    // Apply a staking fact to the next validator set, and update the sorted indices
+    // of the validators and delegators affected by the fact.
+    pub fn apply_staking_fact_to_next_validator_set(
+        &mut self,
+        next_validator_set: &mut NextValidatorSet,
+        staking_fact: &StakingFact,
+    ) {
+        next_validator_set.apply_staking_fact(staking_fact);
+        let mut staking_sorted_indices = self.staking_sorted_indices.get().unwrap();
+        for (validator_id, delegator_id) in staking_fact.affected_stake_holders() {
+            staking_sorted_indices.sync_validator(
+                &validator_id,
+                next_validator_set.get_validator(&validator_id).as_ref(),
+            );
+            if let Some(delegator_id) = delegator_id {
+                staking_sorted_indices.sync_delegator(
+                    &validator_id,
+                    &delegator_id,
+                    next_validator_set
+                        .get_delegator(&delegator_id, &validator_id)
+                        .as_ref(),
+                );
+            }
+        }
+        self.staking_sorted_indices.set(&staking_sorted_indices);
+    }
+}
@@ -0,0 +1,237 @@
+use crate::*;
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use std::collections::HashSet;
+
+/// The maximum count of entries in an export, to make sure that an approval can be
+/// applied in a single transaction.
+const MAXIMUM_ENTRY_COUNT_OF_STAKE_MIGRATION_EXPORT: usize = 50;
+
+impl AppchainAnchor {
+    //
+    pub fn internal_fund_stake_migration_allocation(
+        &mut self,
+        sender_id: AccountId,
+        amount: Balance,
+    ) {
+        assert_eq!(
+            sender_id, self.owner,
+            "Only the owner can fund the allocation of stake migration."
+        );
+        self.stake_migration_allocation += amount;
+        log!(
+            "Allocation of stake migration is increased by '{}'. Current allocation: '{}'",
+            amount,
+            self.stake_migration_allocation
+        );
+    }
+    //
+    pub fn internal_withdraw_stake_migration_allocation(&mut self, amount: Balance) {
+        assert!(
+            amount > 0 && amount <= self.stake_migration_allocation,
+            "Invalid amount to withdraw."
+        );
+        self.stake_migration_allocation -= amount;
+        ext_ft_core::ext(self.oct_token.get().unwrap().contract_account)
+            .with_attached_deposit(1)
+            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+            .with_unused_gas_weight(0)
+            .ft_transfer(self.owner.clone(), U128::from(amount), None);
+    }
+    // Stage an export of stake balances submitted by the attester account.
+    // Return the index of the staged export.
+    pub fn internal_submit_stake_migration_export(
+        &mut self,
+        source: StakeMigrationSource,
+        entries: Vec<StakeMigrationEntry>,
+    ) -> u64 {
+        let anchor_settings = self.anchor_settings.get().unwrap();
+        let attester_id = env::predecessor_account_id();
+        assert!(
+            anchor_settings
+                .stake_migration_attester_account
+                .map_or(false, |account| attester_id.eq(&account)),
+            "Only the stake migration attester can call this function."
+        );
+        assert!(
+            !entries.is_empty() && entries.len() <= MAXIMUM_ENTRY_COUNT_OF_STAKE_MIGRATION_EXPORT,
+            "The count of entries should be between 1 and {}.",
+            MAXIMUM_ENTRY_COUNT_OF_STAKE_MIGRATION_EXPORT
+        );
+        let mut keys = HashSet::new();
+        let mut total_amount: Balance = 0;
+        for entry in &entries {
+            assert!(
+                entry.amount.0 > 0,
+                "The amount of an entry should not be zero."
+            );
+            assert!(
+                keys.insert((entry.account_id.clone(), entry.validator_id.clone())),
+                "Duplicated entry of account '{}'.",
+                entry.account_id
+            );
+            total_amount = total_amount
+                .checked_add(entry.amount.0)
+                .expect("The total amount of the export overflows.");
+        }
+        let mut exports = self.stake_migration_exports.get().unwrap();
+        let export = exports.append(&mut StakeMigrationExport {
+            source,
+            entries,
+            total_amount: U128::from(total_amount),
+            attester_id,
+            submitted_timestamp: U64::from(env::block_timestamp()),
+            status: StakeMigrationExportStatus::Pending,
+            index: U64::from(0),
+        });
+        self.stake_migration_exports.set(&exports);
+        log!(
+            "Stake migration export from '{}' is staged as '{}'.",
+            serde_json::to_string(&export.source).unwrap(),
+            export.index.0
+        );
+        export.index.0
+    }
+    //
+    pub fn get_pending_stake_migration_export(&self, export_index: u64) -> StakeMigrationExport {
+        self.stake_migration_exports
+            .get()
+            .unwrap()
+            .get(&export_index)
+            .filter(|export| export.status == StakeMigrationExportStatus::Pending)
+            .expect("The stake migration export is not pending.")
+    }
+    // Conclude a pending export with the decision of the owner. If it is approved,
+    // the entries are credited from the allocation of stake migration.
+    pub fn internal_conclude_stake_migration_export(
+        &mut self,
+        export_index: u64,
+        is_approved: bool,
+    ) {
+        let mut export = self.get_pending_stake_migration_export(export_index);
+        export.status = match is_approved {
+            true => self.credit_stake_migration_export(&export),
+            false => StakeMigrationExportStatus::Rejected,
+        };
+        let mut exports = self.stake_migration_exports.get().unwrap();
+        exports.insert(&export_index, &export);
+        self.stake_migration_exports.set(&exports);
+    }
+    // The entries which cannot be credited in the current state of the next validator set
+    // are skipped, and their amounts remain in the allocation.
+    fn credit_stake_migration_export(
+        &mut self,
+        export: &StakeMigrationExport,
+    ) -> StakeMigrationExportStatus {
+        match self.appchain_state {
+            AppchainState::Staging | AppchainState::Active => (),
+            _ => panic!(
+                "Cannot credit migrated stakes while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        assert!(
+            export.total_amount.0 <= self.stake_migration_allocation,
+            "The allocation of stake migration is not enough for the export."
+        );
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        let mut credited_amount: Balance = 0;
+        let mut skipped_entry_count: u32 = 0;
+        for entry in &export.entries {
+            match self.credit_migrated_stake(&mut next_validator_set, entry) {
+                Ok(()) => credited_amount += entry.amount.0,
+                Err(reason) => {
+                    skipped_entry_count += 1;
+                    log!(
+                        "Migrated stake of account '{}' is skipped: {}",
+                        entry.account_id,
+                        reason
+                    );
+                }
+            }
+        }
+        self.next_validator_set.set(&next_validator_set);
+        self.stake_migration_allocation -= credited_amount;
+        if credited_amount > 0 {
+            self.sync_state_to_registry();
+        }
+        StakeMigrationExportStatus::Approved {
+            credited_amount: U128::from(credited_amount),
+            skipped_entry_count,
+        }
+    }
+    // Credit a migrated stake as the deposit of a registered validator,
+    // or the delegation of a delegator to a registered validator.
+    fn credit_migrated_stake(
+        &mut self,
+        next_validator_set: &mut NextValidatorSet,
+        entry: &StakeMigrationEntry,
+    ) -> Result<(), String> {
+        if self
+            .frozen_accounts
+            .get(&entry.account_id)
+            .map_or(false, |freezing| {
+                env::block_timestamp() < freezing.expiry_timestamp.0
+            })
+        {
+            return Err("the account is frozen.".to_string());
+        }
+        let validator_id = entry.validator_id.as_ref().unwrap_or(&entry.account_id);
+        let validator = next_validator_set
+            .get_validator(validator_id)
+            .ok_or(format!("validator '{}' is not registered.", validator_id))?;
+        if self.validator_retirements.contains_key(validator_id) {
+            return Err(format!("validator '{}' is retiring.", validator_id));
+        }
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        if !self.is_total_stake_of_validator_allowed(
+            &protocol_settings,
+            validator.total_stake + entry.amount.0,
+        ) {
+            return Err(format!(
+                "the total stake of validator '{}' is too much.",
+                validator_id
+            ));
+        }
+        let staking_fact = match &entry.validator_id {
+            None => StakingFact::StakeIncreased {
+                validator_id: validator_id.clone(),
+                amount: entry.amount,
+            },
+            Some(validator_id) => {
+                if next_validator_set.contains_delegator(&entry.account_id, validator_id) {
+                    StakingFact::DelegationIncreased {
+                        delegator_id: entry.account_id.clone(),
+                        validator_id: validator_id.clone(),
+                        amount: entry.amount,
+                    }
+                } else {
+                    if !validator.can_be_delegated_to {
+                        return Err(format!(
+                            "validator '{}' cannot be delegated to.",
+                            validator_id
+                        ));
+                    }
+                    if next_validator_set.get_validator_count_of(&entry.account_id)
+                        >= protocol_settings.maximum_validators_per_delegator.0
+                    {
+                        return Err("too many validators delegated.".to_string());
+                    }
+                    if entry.amount.0 < protocol_settings.minimum_delegator_deposit.0 {
+                        return Err("the amount is too few for a delegator.".to_string());
+                    }
+                    StakingFact::DelegatorRegistered {
+                        delegator_id: entry.account_id.clone(),
+                        validator_id: validator_id.clone(),
+                        amount: entry.amount,
+                    }
+                }
+            }
+        };
+        let staking_history = self.record_staking_fact(staking_fact);
+        self.apply_staking_fact_to_next_validator_set(
+            next_validator_set,
+            &staking_history.staking_fact,
+        );
+        Ok(())
+    }
+}
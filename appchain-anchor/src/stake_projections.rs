@@ -0,0 +1,136 @@
+use crate::validator_set::Validator;
+use crate::*;
+
+impl AppchainAnchor {
+    // Project the stake of a validator in the next era from the next validator set,
+    // the slashes of the reported offences which are not applied yet, and the stake change
+    // intents whose conditions are met. The slashes are estimated with the current slash
+    // percents, and are applied before the intents.
+    pub fn internal_get_projected_stake_of(
+        &self,
+        validator_id: &AccountId,
+    ) -> Option<ProjectedStakeOfValidator> {
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        let validator = next_validator_set.get_validator(validator_id)?;
+        let is_unbonding = next_validator_set
+            .get_unbonding_validator_ids()
+            .contains(validator_id)
+            || next_validator_set
+                .get_auto_unbonding_validator_ids()
+                .contains(validator_id);
+        let (delegators_in_slashing, staged_slash_count) = self.get_pending_slashes_of(&validator);
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let slash = |amount: Balance, percent: u16, times: u32| -> Balance {
+            (0..times).fold(amount, |remaining, _| {
+                remaining - remaining * u128::from(percent) / 100
+            })
+        };
+        let mut pending_slash_amount = validator.slashable_deposit_amount()
+            - slash(
+                validator.slashable_deposit_amount(),
+                protocol_settings.validator_slash_percent,
+                staged_slash_count,
+            );
+        let mut pending_intent_decrease_amount = 0;
+        for delegator_id in next_validator_set
+            .validator_set()
+            .get_delegator_ids_of(validator_id)
+        {
+            let delegator = next_validator_set
+                .validator_set()
+                .get_delegator(&delegator_id, validator_id)
+                .unwrap();
+            let slash_times = match delegators_in_slashing
+                .as_ref()
+                .map_or(false, |delegator_ids| delegator_ids.contains(&delegator_id))
+            {
+                true => staged_slash_count + 1,
+                false => staged_slash_count,
+            };
+            let remaining = slash(
+                delegator.deposit_amount,
+                protocol_settings.delegator_slash_percent,
+                slash_times,
+            );
+            pending_slash_amount += delegator.deposit_amount - remaining;
+            if let Some(intent) = self
+                .stake_change_intents
+                .get(&(delegator_id.clone(), validator_id.clone()))
+            {
+                if intent.expiry_timestamp.0 > env::block_timestamp()
+                    && self.is_stake_change_condition_met(&intent, &next_validator_set)
+                {
+                    pending_intent_decrease_amount += match intent.action {
+                        StakeChangeAction::UnbondDelegation => remaining,
+                        StakeChangeAction::DecreaseDelegation { amount } => {
+                            std::cmp::min(amount.0, remaining)
+                        }
+                    };
+                }
+            }
+        }
+        let projected_total_stake = match is_unbonding {
+            true => 0,
+            false => validator
+                .total_stake
+                .saturating_sub(pending_slash_amount + pending_intent_decrease_amount),
+        };
+        Some(ProjectedStakeOfValidator {
+            validator_id: validator_id.clone(),
+            total_stake: U128::from(validator.total_stake),
+            is_unbonding,
+            pending_slash_count: staged_slash_count + u32::from(delegators_in_slashing.is_some()),
+            pending_slash_amount: U128::from(pending_slash_amount),
+            pending_intent_decrease_amount: U128::from(pending_intent_decrease_amount),
+            projected_total_stake: U128::from(projected_total_stake),
+        })
+    }
+    // Get the delegators which are not slashed yet in the slash of a validator in progress
+    // (if any), along with the count of the staged offences of the validator which are
+    // not applied yet.
+    fn get_pending_slashes_of(&self, validator: &Validator) -> (Option<Vec<AccountId>>, u32) {
+        let mut delegators_in_slashing = None;
+        let mut staged_slash_count = 0;
+        let mut slash_in_progress_nonce = None;
+        let slash_histories = self.slash_histories.get().unwrap();
+        if let Some(slash_history) = slash_histories.get(&slash_histories.index_range().end_index.0)
+        {
+            if let SlashStatus::SlashingDelegators {
+                pending_delegator_ids,
+            } = slash_history.status
+            {
+                slash_in_progress_nonce = Some(slash_history.slash_fact.appchain_message_nonce);
+                if slash_history.slash_fact.validator_id == validator.validator_id {
+                    delegators_in_slashing = Some(pending_delegator_ids);
+                }
+            }
+        }
+        let processing_status = self.permissionless_actions_status.get().unwrap();
+        let appchain_messages = self.appchain_messages.get().unwrap();
+        for nonce in processing_status.latest_applied_appchain_message_nonce + 1
+            ..=processing_status.max_nonce_of_staged_appchain_messages
+        {
+            if slash_in_progress_nonce == Some(nonce) {
+                continue;
+            }
+            if let Some(AppchainMessage {
+                appchain_event:
+                    AppchainEvent::ValidatorOffenceReported {
+                        validator_id_in_appchain,
+                        ..
+                    },
+                ..
+            }) = appchain_messages.get_message(&nonce)
+            {
+                let formatted_id = AccountIdInAppchain::new(
+                    Some(validator_id_in_appchain),
+                    &self.appchain_template_type,
+                );
+                if formatted_id.to_string() == validator.validator_id_in_appchain {
+                    staged_slash_count += 1;
+                }
+            }
+        }
+        (delegators_in_slashing, staged_slash_count)
+    }
+}
@@ -0,0 +1,145 @@
+use crate::*;
+
+/// The maximum count of items in a chunk of state dump.
+const MAXIMUM_ITEM_COUNT_OF_STATE_DUMP_CHUNK: usize = 50;
+/// The maximum count of staking histories scanned in a chunk of the dump of unbonded stakes.
+const MAXIMUM_SCANNED_STAKING_HISTORIES_OF_STATE_DUMP_CHUNK: u64 = 200;
+
+impl AppchainAnchor {
+    // Dump the validators in the next validator set (including the unbonding ones),
+    // starting from the validator with index `cursor` in the set.
+    pub fn internal_dump_validators(&self, cursor: u64) -> StateDumpChunk<AppchainValidator, U64> {
+        let latest_staking_history_index = self.get_latest_staking_history_index();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        let validator_set = next_validator_set.validator_set();
+        let unbonding_validator_ids = next_validator_set.get_unbonding_validator_ids();
+        let auto_unbonding_validator_ids = next_validator_set.get_auto_unbonding_validator_ids();
+        let mut items = Vec::new();
+        let mut index = cursor;
+        while let Some(validator) = validator_set.get_validator_by_index(&index) {
+            if is_state_dump_chunk_full(items.len()) {
+                return StateDumpChunk {
+                    items,
+                    next_cursor: Some(U64::from(index)),
+                    latest_staking_history_index,
+                };
+            }
+            let delegator_count = validator_set.get_delegator_count_of(&validator.validator_id);
+            let is_unbonding = unbonding_validator_ids.contains(&validator.validator_id)
+                || auto_unbonding_validator_ids.contains(&validator.validator_id);
+            items.push(AppchainValidator::from_validator(
+                validator,
+                delegator_count,
+                is_unbonding,
+            ));
+            index += 1;
+        }
+        StateDumpChunk {
+            items,
+            next_cursor: None,
+            latest_staking_history_index,
+        }
+    }
+    // Dump the delegations in the next validator set, grouped by validators in the order
+    // of the validators in the set.
+    pub fn internal_dump_delegations(
+        &self,
+        cursor: DelegationDumpCursor,
+    ) -> StateDumpChunk<AppchainDelegator, DelegationDumpCursor> {
+        let latest_staking_history_index = self.get_latest_staking_history_index();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        let validator_set = next_validator_set.validator_set();
+        let mut items = Vec::new();
+        let mut validator_index = cursor.validator_index.0;
+        let mut delegator_index = cursor.delegator_index.0;
+        while let Some(validator) = validator_set.get_validator_by_index(&validator_index) {
+            while let Some(delegator) =
+                validator_set.get_delegator_by_index(&delegator_index, &validator.validator_id)
+            {
+                if is_state_dump_chunk_full(items.len()) {
+                    return StateDumpChunk {
+                        items,
+                        next_cursor: Some(DelegationDumpCursor {
+                            validator_index: U64::from(validator_index),
+                            delegator_index: U64::from(delegator_index),
+                        }),
+                        latest_staking_history_index,
+                    };
+                }
+                items.push(AppchainDelegator {
+                    label: self.delegation_labels.get(&(
+                        delegator.delegator_id.clone(),
+                        delegator.validator_id.clone(),
+                    )),
+                    delegator_id: delegator.delegator_id,
+                    validator_id: delegator.validator_id,
+                    delegation_amount: U128::from(delegator.deposit_amount),
+                });
+                delegator_index += 1;
+            }
+            validator_index += 1;
+            delegator_index = 0;
+        }
+        StateDumpChunk {
+            items,
+            next_cursor: None,
+            latest_staking_history_index,
+        }
+    }
+    // Dump the unbonded stakes which are not withdrawn yet, by scanning the staking histories
+    // starting from index `cursor`. As the scanned count is limited, a chunk may be empty
+    // while the next cursor is still returned.
+    pub fn internal_dump_unbonded_stakes(&self, cursor: u64) -> StateDumpChunk<UnbondedStake, U64> {
+        let staking_histories = self.staking_histories.get().unwrap();
+        let index_range = staking_histories.index_range();
+        let mut items = Vec::new();
+        let mut index = cursor.max(index_range.start_index.0);
+        let mut scanned_count = 0;
+        while index <= index_range.end_index.0 {
+            if scanned_count >= MAXIMUM_SCANNED_STAKING_HISTORIES_OF_STATE_DUMP_CHUNK
+                || is_state_dump_chunk_full(items.len())
+            {
+                return StateDumpChunk {
+                    items,
+                    next_cursor: Some(U64::from(index)),
+                    latest_staking_history_index: index_range.end_index,
+                };
+            }
+            if let Some((account_id, _, _)) = staking_histories
+                .get(&index)
+                .and_then(|staking_history| staking_history.staking_fact.unbonded_stake())
+            {
+                if let Some(entry) = self
+                    .evaluate_unbonded_stakes_of(&account_id)
+                    .entries
+                    .into_iter()
+                    .find(|entry| entry.reference.staking_history_index == index)
+                {
+                    items.push(entry.unbonded_stake);
+                }
+            }
+            index += 1;
+            scanned_count += 1;
+        }
+        StateDumpChunk {
+            items,
+            next_cursor: None,
+            latest_staking_history_index: index_range.end_index,
+        }
+    }
+    //
+    fn get_latest_staking_history_index(&self) -> U64 {
+        self.staking_histories
+            .get()
+            .unwrap()
+            .index_range()
+            .end_index
+    }
+}
+
+// Check whether a chunk of state dump should be ended before adding the next item,
+// to keep the view call in the gas limit.
+fn is_state_dump_chunk_full(item_count: usize) -> bool {
+    item_count >= MAXIMUM_ITEM_COUNT_OF_STATE_DUMP_CHUNK
+        || env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_STATE_DUMP)
+}
@@ -49,6 +49,82 @@ pub enum StorageKey {
     WrappedAppchainNFTsClassIds,
     WrappedAppchainNFTsNFTs,
     WrappedAppchainNFTContractWasm,
+    AppchainReferenda,
+    AppchainReferendaMap,
+    FrozenAccounts,
+    EraSummaries,
+    FastUnbondedAmounts,
+    DelegationLabels,
+    CompactedStakingHistories,
+    AppchainRuntimeUpgradeNotices,
+    AppchainRuntimeUpgradeNoticesMap,
+    UnbondedSecondaryStakes,
+    LatestWrappedAssetBalanceAttestation,
+    CommissionPayoutAccounts,
+    UnwithdrawnCommissionRewards,
+    ValidatorRetirements,
+    DelegatorFallbackValidators,
+    ProtocolSettingsOfEras,
+    InsuranceClaimEvents,
+    InsuranceClaimEventsMap,
+    InsuranceCompensations,
+    CoveredSlashingHistories,
+    PeerAnchors,
+    RelayerGasUsages,
+    TotalRelayerGasOfEras,
+    RelayerReimbursementsOfEras,
+    BootstrapAllocations,
+    LastPriceUpdateTimestamps,
+    PriceUpdateViolations,
+    PriceUpdateViolationsMap,
+    WithdrawalAllowlists,
+    StakingSortedIndices,
+    ValidatorsByStake,
+    ValidatorsByRegistrationTime,
+    DelegatorsByStake,
+    DelegatorsByRegistrationTime,
+    IndexedSortValues,
+    AcceptedPayloadTypesOfStates,
+    StakeChangeIntents,
+    AppliedMessagesOfEras,
+    ValidatorEndpoints,
+    RewardWithdrawalHistories,
+    UserAppchainNotificationIndexes,
+    HeldEraRewardDistributions,
+    WrappedAppchainTokenIssuance,
+    RewardRoundingsOfEras,
+    DelegationLocks,
+    StandbyValidatorIdsOfEras,
+    ValidatorSetOverrideProposals,
+    ValidatorSetOverrideProposalsMap,
+    RewardClaimTipPercents,
+    AppchainSettingChangeProposals,
+    AppchainSettingChangeProposalsMap,
+    PenaltyEscrows,
+    PenaltyEscrowsMap,
+    NearFungibleTokenMetadataMirrors,
+    AppchainMessageHandlerConfigs,
+    StakeMigrationExports,
+    StakeMigrationExportsMap,
+    QueuedValidatorRegistrations,
+    QueuedValidatorRegistrationsMap,
+    ValidatorRewardVestings,
+    RelayerBonds,
+    RelayerBondSlashes,
+    RelayerBondSlashesMap,
+    OctBalanceReconciliations,
+    OctBalanceReconciliationsMap,
+    StakingStatusChanges,
+    StakingStatusChangesMap,
+    WithdrawalReceipts,
+    WithdrawalReceiptsMap,
+    SlashHistories,
+    SlashHistoriesMap,
+    UnwithdrawnRewardReferences,
+    TreasuryRoutes,
+    TreasuryFlowsOfEras,
+    AppchainTermination,
+    SlashHistoryIndexesOfValidators,
     ValidatorIdsOfEra(u64),
     ValidatorToDelegatorsMapOfEra(u64),
     DelegatorToValidatorsMapOfEra(u64),
@@ -57,6 +133,7 @@ pub enum StorageKey {
     UnprofitableValidatorIdsOfEra(u64),
     ValidatorRewardsOfEra(u64),
     DelegatorRewardsOfEra(u64),
+    CommissionRebatesOfEra(u64),
     DelegatorIdsInMapOfVToDOfEra {
         era_number: u64,
         validator_id: AccountId,
@@ -66,6 +143,11 @@ pub enum StorageKey {
         delegator_id: AccountId,
     },
     WrappedAppchainNFTsLockedTokenIdSet(String),
+    AppchainReferendumVotes(u64),
+    AppchainReferendumDelegatorVotes(u64),
+    AppchainReferendumOverriddenStakes(u64),
+    DelegatorsByStakeOfValidator(AccountId),
+    DelegatorsByRegistrationTimeOfValidator(AccountId),
 }
 
 impl StorageKey {
@@ -118,6 +200,82 @@ impl StorageKey {
             StorageKey::WrappedAppchainNFTsClassIds => "wanftscis".to_string(),
             StorageKey::WrappedAppchainNFTsNFTs => "wanftsnfts".to_string(),
             StorageKey::WrappedAppchainNFTContractWasm => "wanftwasm".to_string(),
+            StorageKey::AppchainReferenda => "ards".to_string(),
+            StorageKey::AppchainReferendaMap => "ardsm".to_string(),
+            StorageKey::FrozenAccounts => "fas".to_string(),
+            StorageKey::EraSummaries => "ess".to_string(),
+            StorageKey::FastUnbondedAmounts => "fuas".to_string(),
+            StorageKey::DelegationLabels => "dls".to_string(),
+            StorageKey::CompactedStakingHistories => "cshs".to_string(),
+            StorageKey::AppchainRuntimeUpgradeNotices => "aruns".to_string(),
+            StorageKey::AppchainRuntimeUpgradeNoticesMap => "arunsm".to_string(),
+            StorageKey::UnbondedSecondaryStakes => "usss".to_string(),
+            StorageKey::LatestWrappedAssetBalanceAttestation => "lwaba".to_string(),
+            StorageKey::CommissionPayoutAccounts => "cpas".to_string(),
+            StorageKey::UnwithdrawnCommissionRewards => "ucrs".to_string(),
+            StorageKey::ValidatorRetirements => "vrs".to_string(),
+            StorageKey::DelegatorFallbackValidators => "dfvs".to_string(),
+            StorageKey::ProtocolSettingsOfEras => "psoe".to_string(),
+            StorageKey::InsuranceClaimEvents => "ices".to_string(),
+            StorageKey::InsuranceClaimEventsMap => "icesm".to_string(),
+            StorageKey::InsuranceCompensations => "icps".to_string(),
+            StorageKey::CoveredSlashingHistories => "cvshs".to_string(),
+            StorageKey::PeerAnchors => "pras".to_string(),
+            StorageKey::RelayerGasUsages => "rgus".to_string(),
+            StorageKey::TotalRelayerGasOfEras => "trgoe".to_string(),
+            StorageKey::RelayerReimbursementsOfEras => "rroe".to_string(),
+            StorageKey::BootstrapAllocations => "bsa".to_string(),
+            StorageKey::LastPriceUpdateTimestamps => "lput".to_string(),
+            StorageKey::PriceUpdateViolations => "puv".to_string(),
+            StorageKey::PriceUpdateViolationsMap => "puvm".to_string(),
+            StorageKey::WithdrawalAllowlists => "wal".to_string(),
+            StorageKey::StakingSortedIndices => "ssi".to_string(),
+            StorageKey::ValidatorsByStake => "ssivbs".to_string(),
+            StorageKey::ValidatorsByRegistrationTime => "ssivbrt".to_string(),
+            StorageKey::DelegatorsByStake => "ssidbs".to_string(),
+            StorageKey::DelegatorsByRegistrationTime => "ssidbrt".to_string(),
+            StorageKey::IndexedSortValues => "ssiisv".to_string(),
+            StorageKey::AcceptedPayloadTypesOfStates => "aptos".to_string(),
+            StorageKey::StakeChangeIntents => "sci".to_string(),
+            StorageKey::AppliedMessagesOfEras => "ame".to_string(),
+            StorageKey::ValidatorEndpoints => "ve".to_string(),
+            StorageKey::RewardWithdrawalHistories => "rwh".to_string(),
+            StorageKey::UserAppchainNotificationIndexes => "uani".to_string(),
+            StorageKey::HeldEraRewardDistributions => "herd".to_string(),
+            StorageKey::WrappedAppchainTokenIssuance => "wati".to_string(),
+            StorageKey::RewardRoundingsOfEras => "rwroe".to_string(),
+            StorageKey::DelegationLocks => "dlks".to_string(),
+            StorageKey::StandbyValidatorIdsOfEras => "svioe".to_string(),
+            StorageKey::ValidatorSetOverrideProposals => "vsops".to_string(),
+            StorageKey::ValidatorSetOverrideProposalsMap => "vsopsm".to_string(),
+            StorageKey::RewardClaimTipPercents => "rctps".to_string(),
+            StorageKey::AppchainSettingChangeProposals => "ascps".to_string(),
+            StorageKey::AppchainSettingChangeProposalsMap => "ascpsm".to_string(),
+            StorageKey::PenaltyEscrows => "pes".to_string(),
+            StorageKey::PenaltyEscrowsMap => "pesm".to_string(),
+            StorageKey::NearFungibleTokenMetadataMirrors => "nftmm".to_string(),
+            StorageKey::AppchainMessageHandlerConfigs => "amhc".to_string(),
+            StorageKey::StakeMigrationExports => "smes".to_string(),
+            StorageKey::StakeMigrationExportsMap => "smesm".to_string(),
+            StorageKey::QueuedValidatorRegistrations => "qvrs".to_string(),
+            StorageKey::QueuedValidatorRegistrationsMap => "qvrsm".to_string(),
+            StorageKey::ValidatorRewardVestings => "vrv".to_string(),
+            StorageKey::RelayerBonds => "rlb".to_string(),
+            StorageKey::RelayerBondSlashes => "rbss".to_string(),
+            StorageKey::RelayerBondSlashesMap => "rbssm".to_string(),
+            StorageKey::OctBalanceReconciliations => "obrs".to_string(),
+            StorageKey::OctBalanceReconciliationsMap => "obrsm".to_string(),
+            StorageKey::StakingStatusChanges => "sscs".to_string(),
+            StorageKey::StakingStatusChangesMap => "sscsm".to_string(),
+            StorageKey::WithdrawalReceipts => "wrcs".to_string(),
+            StorageKey::WithdrawalReceiptsMap => "wrcsm".to_string(),
+            StorageKey::SlashHistories => "slhs".to_string(),
+            StorageKey::SlashHistoriesMap => "slhsm".to_string(),
+            StorageKey::UnwithdrawnRewardReferences => "uwrrs".to_string(),
+            StorageKey::TreasuryRoutes => "tsrts".to_string(),
+            StorageKey::TreasuryFlowsOfEras => "tsfes".to_string(),
+            StorageKey::AppchainTermination => "at".to_string(),
+            StorageKey::SlashHistoryIndexesOfValidators => "slhivs".to_string(),
             StorageKey::ValidatorIdsOfEra(era_number) => format!("{}vis", era_number),
             StorageKey::ValidatorToDelegatorsMapOfEra(era_number) => format!("{}lmvtd", era_number),
             StorageKey::DelegatorToValidatorsMapOfEra(era_number) => format!("{}lmdtv", era_number),
@@ -128,6 +286,7 @@ impl StorageKey {
             }
             StorageKey::ValidatorRewardsOfEra(era_number) => format!("{}vrs", era_number),
             StorageKey::DelegatorRewardsOfEra(era_number) => format!("{}drs", era_number),
+            StorageKey::CommissionRebatesOfEra(era_number) => format!("{}crs", era_number),
             StorageKey::DelegatorIdsInMapOfVToDOfEra {
                 era_number,
                 validator_id,
@@ -139,6 +298,15 @@ impl StorageKey {
             StorageKey::WrappedAppchainNFTsLockedTokenIdSet(class_id) => {
                 format!("{}wanltis", class_id)
             }
+            StorageKey::AppchainReferendumVotes(index) => format!("{}ardvs", index),
+            StorageKey::AppchainReferendumDelegatorVotes(index) => format!("{}arddvs", index),
+            StorageKey::AppchainReferendumOverriddenStakes(index) => format!("{}ardos", index),
+            StorageKey::DelegatorsByStakeOfValidator(validator_id) => {
+                format!("ssidbs{}", validator_id)
+            }
+            StorageKey::DelegatorsByRegistrationTimeOfValidator(validator_id) => {
+                format!("ssidbrt{}", validator_id)
+            }
         }
     }
     pub fn into_bytes(&self) -> Vec<u8> {
@@ -1,6 +1,8 @@
+use crate::validator_set::{Validator, ValidatorSet};
 use crate::*;
+use core::convert::TryFrom;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap};
+use near_sdk::collections::{LazyOption, LookupMap, UnorderedSet};
 use near_sdk::{env, near_bindgen, AccountId, Balance, BlockHeight};
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -73,6 +75,82 @@ pub struct OldStakingHistory {
     pub index: U64,
 }
 
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldAppchainSettings {
+    pub rpc_endpoint: String,
+    pub subql_endpoint: String,
+    pub era_reward: U128,
+    pub bonus_for_new_validator: U128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldAnchorSettings {
+    pub token_price_maintainer_account: Option<AccountId>,
+    pub relayer_account: Option<AccountId>,
+    pub beefy_light_client_witness_mode: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldProtocolSettings {
+    pub minimum_validator_deposit: U128,
+    pub minimum_validator_deposit_changing_amount: U128,
+    pub maximum_validator_stake_percent: u16,
+    pub minimum_delegator_deposit: U128,
+    pub minimum_delegator_deposit_changing_amount: U128,
+    pub minimum_total_stake_price_for_booting: U128,
+    pub maximum_market_value_percent_of_near_fungible_tokens: u16,
+    pub maximum_market_value_percent_of_wrapped_appchain_token: u16,
+    pub minimum_validator_count: U64,
+    pub maximum_validator_count: U64,
+    pub maximum_validators_per_delegator: U64,
+    pub unlock_period_of_validator_deposit: U64,
+    pub unlock_period_of_delegator_deposit: U64,
+    pub maximum_era_count_of_unwithdrawn_reward: U64,
+    pub maximum_era_count_of_valid_appchain_message: U64,
+    pub validator_commission_percent: u16,
+    pub maximum_allowed_unprofitable_era_count: u16,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldValidator {
+    /// The validator's id in NEAR protocol.
+    pub validator_id: AccountId,
+    /// The validator's id in the appchain.
+    pub validator_id_in_appchain: String,
+    /// The block height when the validator is registered.
+    pub registered_block_height: BlockHeight,
+    /// The timestamp when the validator is registered.
+    pub registered_timestamp: Timestamp,
+    /// Total deposited balance of the validator.
+    pub deposit_amount: Balance,
+    /// Total stake of the validator, including delegations of all delegators.
+    pub total_stake: Balance,
+    /// Whether the validator accepts delegation from delegators.
+    pub can_be_delegated_to: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldValidatorSetOfEra {
+    /// The validator set of this era
+    pub validator_set: ValidatorSet,
+    /// The block height when the era starts.
+    pub start_block_height: BlockHeight,
+    /// The timestamp when the era starts.
+    pub start_timestamp: Timestamp,
+    /// The index of the latest staking history happened in the era of corresponding appchain.
+    pub staking_history_index: u64,
+    /// The set of validator id which will not be profited.
+    pub unprofitable_validator_id_set: UnorderedSet<AccountId>,
+    /// Total stake excluding all unprofitable validators' stake.
+    pub valid_total_stake: Balance,
+    /// The rewards of validators in this era
+    pub validator_rewards: LookupMap<AccountId, Balance>,
+    /// The rewards of delegators in this era
+    pub delegator_rewards: LookupMap<(AccountId, AccountId), Balance>,
+    /// The status of creation of this set
+    pub processing_status: ValidatorSetProcessingStatus,
+}
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct OldAppchainAnchor {
     /// The id of corresponding appchain.
@@ -105,11 +183,11 @@ pub struct OldAppchainAnchor {
     /// The validators' profiles data.
     validator_profiles: LazyOption<ValidatorProfiles>,
     /// The custom settings for appchain.
-    appchain_settings: LazyOption<AppchainSettings>,
+    appchain_settings: LazyOption<OldAppchainSettings>,
     /// The anchor settings for appchain.
-    anchor_settings: LazyOption<AnchorSettings>,
+    anchor_settings: LazyOption<OldAnchorSettings>,
     /// The protocol settings for appchain anchor.
-    protocol_settings: LazyOption<ProtocolSettings>,
+    protocol_settings: LazyOption<OldProtocolSettings>,
     /// The state of the corresponding appchain.
     appchain_state: AppchainState,
     /// The staking history data happened in this contract.
@@ -149,6 +227,15 @@ impl AppchainAnchor {
         near_sdk::assert_self();
         //
         old_contract.clear_anchor_events();
+        // The rewards of the existing eras are not referenced by accounts.
+        let first_era_of_unwithdrawn_reward_references = old_contract
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .index_range()
+            .end_index
+            .0
+            + 1;
         // Create the new contract using the data from the old contract.
         let new_contract = AppchainAnchor {
             appchain_id: old_contract.appchain_id,
@@ -165,9 +252,24 @@ impl AppchainAnchor {
             unwithdrawn_delegator_rewards: old_contract.unwithdrawn_delegator_rewards,
             unbonded_stakes: old_contract.unbonded_stakes,
             validator_profiles: old_contract.validator_profiles,
-            appchain_settings: old_contract.appchain_settings,
-            anchor_settings: old_contract.anchor_settings,
-            protocol_settings: old_contract.protocol_settings,
+            appchain_settings: LazyOption::new(
+                StorageKey::AppchainSettings.into_bytes(),
+                Some(&AppchainSettings::from_old_version(
+                    old_contract.appchain_settings.get().unwrap(),
+                )),
+            ),
+            anchor_settings: LazyOption::new(
+                StorageKey::AnchorSettings.into_bytes(),
+                Some(&AnchorSettings::from_old_version(
+                    old_contract.anchor_settings.get().unwrap(),
+                )),
+            ),
+            protocol_settings: LazyOption::new(
+                StorageKey::ProtocolSettings.into_bytes(),
+                Some(&ProtocolSettings::from_old_version(
+                    old_contract.protocol_settings.get().unwrap(),
+                )),
+            ),
             appchain_state: old_contract.appchain_state,
             staking_histories: old_contract.staking_histories,
             appchain_notification_histories: old_contract.appchain_notification_histories,
@@ -180,6 +282,187 @@ impl AppchainAnchor {
             appchain_messages: old_contract.appchain_messages,
             appchain_challenges: old_contract.appchain_challenges,
             wrapped_appchain_nfts: old_contract.wrapped_appchain_nfts,
+            // Keep the contract in maintenance mode until the owner confirms
+            // that all migration of stored data is completed.
+            maintenance_mode_is_on: true,
+            appchain_referenda: LazyOption::new(
+                StorageKey::AppchainReferenda.into_bytes(),
+                Some(&LookupArray::new(StorageKey::AppchainReferendaMap)),
+            ),
+            frozen_accounts: LookupMap::new(StorageKey::FrozenAccounts.into_bytes()),
+            era_summaries: LookupMap::new(StorageKey::EraSummaries.into_bytes()),
+            fast_unbonded_amounts: LookupMap::new(StorageKey::FastUnbondedAmounts.into_bytes()),
+            delegation_labels: LookupMap::new(StorageKey::DelegationLabels.into_bytes()),
+            compacted_staking_histories: LookupMap::new(
+                StorageKey::CompactedStakingHistories.into_bytes(),
+            ),
+            appchain_runtime_upgrade_notices: LazyOption::new(
+                StorageKey::AppchainRuntimeUpgradeNotices.into_bytes(),
+                Some(&LookupArray::new(
+                    StorageKey::AppchainRuntimeUpgradeNoticesMap,
+                )),
+            ),
+            unbonded_secondary_stakes: LookupMap::new(
+                StorageKey::UnbondedSecondaryStakes.into_bytes(),
+            ),
+            latest_wrapped_asset_balance_attestation: LazyOption::new(
+                StorageKey::LatestWrappedAssetBalanceAttestation.into_bytes(),
+                None,
+            ),
+            commission_payout_accounts: LookupMap::new(
+                StorageKey::CommissionPayoutAccounts.into_bytes(),
+            ),
+            unwithdrawn_commission_rewards: LookupMap::new(
+                StorageKey::UnwithdrawnCommissionRewards.into_bytes(),
+            ),
+            validator_retirements: LookupMap::new(StorageKey::ValidatorRetirements.into_bytes()),
+            delegator_fallback_validators: LookupMap::new(
+                StorageKey::DelegatorFallbackValidators.into_bytes(),
+            ),
+            protocol_settings_of_eras: LookupMap::new(
+                StorageKey::ProtocolSettingsOfEras.into_bytes(),
+            ),
+            insurance_pool_balance: 0,
+            insurance_claim_events: LazyOption::new(
+                StorageKey::InsuranceClaimEvents.into_bytes(),
+                Some(&LookupArray::new(StorageKey::InsuranceClaimEventsMap)),
+            ),
+            insurance_compensations: LookupMap::new(
+                StorageKey::InsuranceCompensations.into_bytes(),
+            ),
+            covered_slashing_histories: LookupMap::new(
+                StorageKey::CoveredSlashingHistories.into_bytes(),
+            ),
+            peer_anchors: UnorderedMap::new(StorageKey::PeerAnchors.into_bytes()),
+            relayer_reimbursement_pool_balance: 0,
+            relayer_gas_usages: LookupMap::new(StorageKey::RelayerGasUsages.into_bytes()),
+            total_relayer_gas_of_eras: LookupMap::new(
+                StorageKey::TotalRelayerGasOfEras.into_bytes(),
+            ),
+            relayer_reimbursements_of_eras: LookupMap::new(
+                StorageKey::RelayerReimbursementsOfEras.into_bytes(),
+            ),
+            bootstrap_allocations: LookupMap::new(StorageKey::BootstrapAllocations.into_bytes()),
+            total_bootstrap_allocation: 0,
+            last_price_update_timestamps: LookupMap::new(
+                StorageKey::LastPriceUpdateTimestamps.into_bytes(),
+            ),
+            price_update_violations: LazyOption::new(
+                StorageKey::PriceUpdateViolations.into_bytes(),
+                Some(&LookupArray::new(StorageKey::PriceUpdateViolationsMap)),
+            ),
+            withdrawal_allowlists: LookupMap::new(StorageKey::WithdrawalAllowlists.into_bytes()),
+            staking_sorted_indices: LazyOption::new(
+                StorageKey::StakingSortedIndices.into_bytes(),
+                Some(&StakingSortedIndices::new()),
+            ),
+            accepted_payload_types_of_states: LookupMap::new(
+                StorageKey::AcceptedPayloadTypesOfStates.into_bytes(),
+            ),
+            stake_change_intents: UnorderedMap::new(StorageKey::StakeChangeIntents.into_bytes()),
+            applied_messages_of_eras: LookupMap::new(
+                StorageKey::AppliedMessagesOfEras.into_bytes(),
+            ),
+            validator_endpoints: LookupMap::new(StorageKey::ValidatorEndpoints.into_bytes()),
+            reward_withdrawal_histories: LookupMap::new(
+                StorageKey::RewardWithdrawalHistories.into_bytes(),
+            ),
+            user_appchain_notification_indexes: LookupMap::new(
+                StorageKey::UserAppchainNotificationIndexes.into_bytes(),
+            ),
+            held_era_reward_distributions: LookupMap::new(
+                StorageKey::HeldEraRewardDistributions.into_bytes(),
+            ),
+            wrapped_appchain_token_issuance: LazyOption::new(
+                StorageKey::WrappedAppchainTokenIssuance.into_bytes(),
+                Some(&WrappedAppchainTokenIssuance::default()),
+            ),
+            reward_roundings_of_eras: LookupMap::new(
+                StorageKey::RewardRoundingsOfEras.into_bytes(),
+            ),
+            delegation_locks: LookupMap::new(StorageKey::DelegationLocks.into_bytes()),
+            standby_validator_ids_of_eras: LookupMap::new(
+                StorageKey::StandbyValidatorIdsOfEras.into_bytes(),
+            ),
+            validator_set_override_proposals: LazyOption::new(
+                StorageKey::ValidatorSetOverrideProposals.into_bytes(),
+                Some(&LookupArray::new(
+                    StorageKey::ValidatorSetOverrideProposalsMap,
+                )),
+            ),
+            reward_claim_tip_percents: LookupMap::new(
+                StorageKey::RewardClaimTipPercents.into_bytes(),
+            ),
+            appchain_setting_change_proposals: LazyOption::new(
+                StorageKey::AppchainSettingChangeProposals.into_bytes(),
+                Some(&LookupArray::new(
+                    StorageKey::AppchainSettingChangeProposalsMap,
+                )),
+            ),
+            penalty_escrows: LazyOption::new(
+                StorageKey::PenaltyEscrows.into_bytes(),
+                Some(&LookupArray::new(StorageKey::PenaltyEscrowsMap)),
+            ),
+            penalty_escrow_balance: 0,
+            near_fungible_token_metadata_mirrors: LookupMap::new(
+                StorageKey::NearFungibleTokenMetadataMirrors.into_bytes(),
+            ),
+            latest_acknowledged_appchain_notification_index: None,
+            appchain_message_handler_configs: LookupMap::new(
+                StorageKey::AppchainMessageHandlerConfigs.into_bytes(),
+            ),
+            stake_migration_exports: LazyOption::new(
+                StorageKey::StakeMigrationExports.into_bytes(),
+                Some(&LookupArray::new(StorageKey::StakeMigrationExportsMap)),
+            ),
+            stake_migration_allocation: 0,
+            queued_validator_registrations: LazyOption::new(
+                StorageKey::QueuedValidatorRegistrations.into_bytes(),
+                Some(&LookupArray::new(
+                    StorageKey::QueuedValidatorRegistrationsMap,
+                )),
+            ),
+            next_queued_validator_registration_index: 0,
+            daily_validator_admissions: DailyValidatorAdmissions::default(),
+            validator_reward_vestings: LookupMap::new(
+                StorageKey::ValidatorRewardVestings.into_bytes(),
+            ),
+            relayer_bonds: UnorderedMap::new(StorageKey::RelayerBonds.into_bytes()),
+            relayer_bond_slashes: LazyOption::new(
+                StorageKey::RelayerBondSlashes.into_bytes(),
+                Some(&LookupArray::new(StorageKey::RelayerBondSlashesMap)),
+            ),
+            total_unbonded_stake: 0,
+            oct_balance_reconciliations: LazyOption::new(
+                StorageKey::OctBalanceReconciliations.into_bytes(),
+                Some(&LookupArray::new(StorageKey::OctBalanceReconciliationsMap)),
+            ),
+            staking_status: StakingStatus::Active,
+            staking_status_changes: LazyOption::new(
+                StorageKey::StakingStatusChanges.into_bytes(),
+                Some(&LookupArray::new(StorageKey::StakingStatusChangesMap)),
+            ),
+            withdrawal_receipts: LazyOption::new(
+                StorageKey::WithdrawalReceipts.into_bytes(),
+                Some(&LookupArray::new(StorageKey::WithdrawalReceiptsMap)),
+            ),
+            slash_histories: LazyOption::new(
+                StorageKey::SlashHistories.into_bytes(),
+                Some(&LookupArray::new(StorageKey::SlashHistoriesMap)),
+            ),
+            unwithdrawn_reward_references: LookupMap::new(
+                StorageKey::UnwithdrawnRewardReferences.into_bytes(),
+            ),
+            first_era_of_unwithdrawn_reward_references,
+            treasury_routes: LookupMap::new(StorageKey::TreasuryRoutes.into_bytes()),
+            treasury_flows_of_eras: LookupMap::new(StorageKey::TreasuryFlowsOfEras.into_bytes()),
+            appchain_termination: LazyOption::new(
+                StorageKey::AppchainTermination.into_bytes(),
+                None,
+            ),
+            slash_history_indexes_of_validators: LookupMap::new(
+                StorageKey::SlashHistoryIndexesOfValidators.into_bytes(),
+            ),
         };
         //
         //
@@ -251,6 +534,129 @@ impl AppchainAnchor {
         MultiTxsOperationProcessingResult::Ok
     }
     ///
+    pub fn migrate_validator_set_histories(
+        &mut self,
+        start_era_number: U64,
+    ) -> MultiTxsOperationProcessingResult {
+        near_sdk::assert_self();
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let index_range = validator_set_histories.index_range();
+        let start_era_number = match start_era_number.0 > index_range.start_index.0 {
+            true => start_era_number.0,
+            false => index_range.start_index.0,
+        };
+        for era_number in start_era_number..index_range.end_index.0 + 1 {
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                return MultiTxsOperationProcessingResult::Error(format!(
+                    "Not all records are migrated. Call this function again with start_era_number '{}'.",
+                    era_number
+                ));
+            }
+            if let Some(old_data) = env::storage_read(&get_storage_key_in_lookup_array(
+                &StorageKey::ValidatorSetHistoriesMap,
+                &era_number,
+            )) {
+                if let Ok(old_version) = OldValidatorSetOfEra::try_from_slice(&old_data) {
+                    env::storage_write(
+                        &get_storage_key_in_lookup_array(
+                            &StorageKey::ValidatorSetHistoriesMap,
+                            &era_number,
+                        ),
+                        &ValidatorSetOfEra::from_old_version(old_version)
+                            .try_to_vec()
+                            .unwrap(),
+                    );
+                }
+            }
+        }
+        MultiTxsOperationProcessingResult::Ok
+    }
+    /// Migrate the validators in the validator set of a certain era.
+    /// If the `era_number` is `None`, migrate the validators in the next validator set.
+    pub fn migrate_validators_of_validator_set(
+        &mut self,
+        era_number: Option<U64>,
+        start_index: U64,
+    ) -> MultiTxsOperationProcessingResult {
+        near_sdk::assert_self();
+        let (era_number, validator_ids) = match era_number {
+            Some(era_number) => {
+                let validator_set_histories = self.validator_set_histories.get().unwrap();
+                let validator_set = validator_set_histories
+                    .get(&era_number.0)
+                    .expect("Validator set of the era is not existed.");
+                (era_number.0, validator_set.get_validator_ids())
+            }
+            None => {
+                let next_validator_set = self.next_validator_set.get().unwrap();
+                (
+                    next_validator_set.era_number(),
+                    next_validator_set.validator_set().get_validator_ids(),
+                )
+            }
+        };
+        for index in start_index.0..u64::try_from(validator_ids.len()).unwrap() {
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                return MultiTxsOperationProcessingResult::Error(format!(
+                    "Not all records are migrated. Call this function again with start_index '{}'.",
+                    index
+                ));
+            }
+            let validator_id = validator_ids.get(usize::try_from(index).unwrap()).unwrap();
+            let storage_key = get_storage_key_in_lookup_array(
+                &StorageKey::ValidatorsOfEra(era_number),
+                validator_id,
+            );
+            if let Some(old_data) = env::storage_read(&storage_key) {
+                if let Ok(old_version) = OldValidator::try_from_slice(&old_data) {
+                    env::storage_write(
+                        &storage_key,
+                        &Validator::from_old_version(old_version)
+                            .try_to_vec()
+                            .unwrap(),
+                    );
+                }
+            }
+        }
+        MultiTxsOperationProcessingResult::Ok
+    }
+    /// Build the sorted indices of the validators (and their delegators)
+    /// in the next validator set, starting from the validator with `start_index`.
+    pub fn migrate_staking_sorted_indices(
+        &mut self,
+        start_index: U64,
+    ) -> MultiTxsOperationProcessingResult {
+        near_sdk::assert_self();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        let validator_ids = next_validator_set.validator_set().get_validator_ids();
+        let mut staking_sorted_indices = self.staking_sorted_indices.get().unwrap();
+        for index in start_index.0..u64::try_from(validator_ids.len()).unwrap() {
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                self.staking_sorted_indices.set(&staking_sorted_indices);
+                return MultiTxsOperationProcessingResult::Error(format!(
+                    "Not all records are migrated. Call this function again with start_index '{}'.",
+                    index
+                ));
+            }
+            let validator_id = validator_ids.get(usize::try_from(index).unwrap()).unwrap();
+            staking_sorted_indices.sync_validator(
+                validator_id,
+                next_validator_set.get_validator(validator_id).as_ref(),
+            );
+            for delegator_id in next_validator_set.get_delegator_ids_of(validator_id) {
+                staking_sorted_indices.sync_delegator(
+                    validator_id,
+                    &delegator_id,
+                    next_validator_set
+                        .get_delegator(&delegator_id, validator_id)
+                        .as_ref(),
+                );
+            }
+        }
+        self.staking_sorted_indices.set(&staking_sorted_indices);
+        MultiTxsOperationProcessingResult::Ok
+    }
+    ///
     pub fn migrate_appchain_messages(
         &mut self,
         start_nonce: u32,
@@ -280,6 +686,67 @@ impl AppchainAnchor {
         }
         MultiTxsOperationProcessingResult::Ok
     }
+    /// Backfill the total amount of the unbonded stakes which are not withdrawn yet,
+    /// by checking the staking histories starting from `start_index`. The total is
+    /// recounted from zero if `start_index` is 0.
+    pub fn migrate_total_unbonded_stake(
+        &mut self,
+        start_index: U64,
+    ) -> MultiTxsOperationProcessingResult {
+        near_sdk::assert_self();
+        if start_index.0 == 0 {
+            self.total_unbonded_stake = 0;
+        }
+        // The unbonded stakes of the staking histories from this index are not referenced
+        // by accounts yet, they will be referenced when they are applied to a validator set.
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let era_number = validator_set_histories.index_range().end_index.0;
+        let first_unreferenced_index = match validator_set_histories.get(&era_number) {
+            Some(validator_set) => match validator_set.processing_status() {
+                ValidatorSetProcessingStatus::CopyingFromLastEra { .. }
+                | ValidatorSetProcessingStatus::UnbondingValidator { .. }
+                | ValidatorSetProcessingStatus::AutoUnbondingValidator { .. } => {
+                    match era_number > 0 {
+                        true => validator_set_histories
+                            .get(&(era_number - 1))
+                            .map_or(0, |validator_set| validator_set.staking_history_index() + 1),
+                        false => 0,
+                    }
+                }
+                ValidatorSetProcessingStatus::ApplyingStakingHistory { applying_index } => {
+                    applying_index.0
+                }
+                _ => validator_set.staking_history_index() + 1,
+            },
+            None => 0,
+        };
+        let staking_histories = self.staking_histories.get().unwrap();
+        let index_range = staking_histories.index_range();
+        for index in start_index.0.max(index_range.start_index.0)..index_range.end_index.0 + 1 {
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                return MultiTxsOperationProcessingResult::Error(format!(
+                    "Not all records are migrated. Call this function again with start_index '{}'.",
+                    index
+                ));
+            }
+            if let Some(staking_history) = staking_histories.get(&index) {
+                if let Some((account_id, _, amount)) = staking_history.staking_fact.unbonded_stake()
+                {
+                    let is_not_withdrawn = index >= first_unreferenced_index
+                        || self
+                            .unbonded_stakes
+                            .get(&account_id)
+                            .unwrap_or_default()
+                            .iter()
+                            .any(|reference| reference.staking_history_index == index);
+                    if is_not_withdrawn {
+                        self.total_unbonded_stake += amount.0;
+                    }
+                }
+            }
+        }
+        MultiTxsOperationProcessingResult::Ok
+    }
 }
 
 fn get_storage_key_in_lookup_array<T: BorshSerialize>(prefix: &StorageKey, index: &T) -> Vec<u8> {
@@ -403,6 +870,7 @@ impl AppchainEvent {
                 era_number,
                 unprofitable_validator_ids,
                 offenders: Vec::new(),
+                validator_set_hash: None,
             },
             OldAppchainEvent::NonFungibleTokenLocked {
                 owner_id_in_appchain,
@@ -430,3 +898,98 @@ impl AppchainMessage {
         }
     }
 }
+
+impl AppchainSettings {
+    pub fn from_old_version(old_version: OldAppchainSettings) -> Self {
+        AppchainSettings {
+            rpc_endpoint: old_version.rpc_endpoint,
+            subql_endpoint: old_version.subql_endpoint,
+            era_reward: old_version.era_reward,
+            bonus_for_new_validator: old_version.bonus_for_new_validator,
+            expected_era_duration_in_secs: U64::from(0),
+            era_duration_drift_tolerance_percent: 10,
+            bootstrap_budget: U128::from(0),
+            boot_nodes: Vec::new(),
+        }
+    }
+}
+
+impl AnchorSettings {
+    pub fn from_old_version(old_version: OldAnchorSettings) -> Self {
+        AnchorSettings {
+            token_price_maintainer_account: old_version.token_price_maintainer_account,
+            relayer_account: old_version.relayer_account,
+            beefy_light_client_witness_mode: old_version.beefy_light_client_witness_mode,
+            guardian_account: None,
+            treasury_account: None,
+            minimum_interval_of_price_updates: U64::from(0),
+            maximum_price_change_percent_per_update: 0,
+            pause_asset_transfer_on_price_update_violation: false,
+            seconds_of_a_day: U64::from(SECONDS_OF_A_DAY),
+            maximum_era_reward_change_percent: 0,
+            maximum_unprofitable_validator_percent: 0,
+            stake_migration_attester_account: None,
+            minimum_interval_of_oct_balance_reconciliations: U64::from(3600),
+        }
+    }
+}
+
+impl ProtocolSettings {
+    pub fn from_old_version(old_version: OldProtocolSettings) -> Self {
+        ProtocolSettings {
+            minimum_validator_deposit: old_version.minimum_validator_deposit,
+            minimum_validator_deposit_changing_amount: old_version
+                .minimum_validator_deposit_changing_amount,
+            maximum_validator_stake_percent: old_version.maximum_validator_stake_percent,
+            minimum_delegator_deposit: old_version.minimum_delegator_deposit,
+            minimum_delegator_deposit_changing_amount: old_version
+                .minimum_delegator_deposit_changing_amount,
+            minimum_total_stake_price_for_booting: old_version
+                .minimum_total_stake_price_for_booting,
+            maximum_market_value_percent_of_near_fungible_tokens: old_version
+                .maximum_market_value_percent_of_near_fungible_tokens,
+            maximum_market_value_percent_of_wrapped_appchain_token: old_version
+                .maximum_market_value_percent_of_wrapped_appchain_token,
+            minimum_validator_count: old_version.minimum_validator_count,
+            maximum_validator_count: old_version.maximum_validator_count,
+            maximum_validators_per_delegator: old_version.maximum_validators_per_delegator,
+            unlock_period_of_validator_deposit: old_version.unlock_period_of_validator_deposit,
+            unlock_period_of_delegator_deposit: old_version.unlock_period_of_delegator_deposit,
+            unlock_period_of_slashed_remainder_stake: old_version
+                .unlock_period_of_validator_deposit,
+            maximum_era_count_of_unwithdrawn_reward: old_version
+                .maximum_era_count_of_unwithdrawn_reward,
+            maximum_era_count_of_valid_appchain_message: old_version
+                .maximum_era_count_of_valid_appchain_message,
+            validator_commission_percent: old_version.validator_commission_percent,
+            maximum_allowed_unprofitable_era_count: old_version
+                .maximum_allowed_unprofitable_era_count,
+            fast_unbond_penalty_percent: 10,
+            maximum_fast_unbond_amount_per_era: U128::from(0),
+            loyalty_tiers_of_delegator: Vec::new(),
+            maximum_size_of_encoded_appchain_messages: U64::from(1_048_576),
+            maximum_count_of_appchain_messages_per_batch: 1000,
+            minimum_era_count_for_compacting_staking_history: U64::from(28),
+            secondary_stake_token: None,
+            era_count_of_validator_retirement_notice: U64::from(7),
+            insurance_pool_reward_percent: 0,
+            relayer_reimbursement_per_era: U128::from(0),
+            time_weighted_reward: false,
+            withdrawal_allowlist_change_delay: U64::from(7),
+            minimum_bonded_era_count_for_validator: U64::from(0),
+            reward_rounding_policy: RewardRoundingPolicy::Floor,
+            maximum_delegation_lock_era_count: U64::from(0),
+            delegation_lock_voting_boost_percent_per_era: 0,
+            maximum_validator_count_per_operator: U64::from(0),
+            penalty_dispute_period: U64::from(7),
+            maximum_validator_admissions_per_day_in_booting: U64::from(0),
+            validator_reward_vesting_percent: 0,
+            validator_reward_vesting_era_count: U64::from(0),
+            minimum_relayer_bond: U128::from(0),
+            relayer_bond_slash_percent: 10,
+            unlock_period_of_relayer_bond: U64::from(21),
+            validator_slash_percent: 0,
+            delegator_slash_percent: 0,
+        }
+    }
+}
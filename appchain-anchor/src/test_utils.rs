@@ -0,0 +1,259 @@
+use crate::appchain_messages::{
+    AttestedBalance, BalanceAttestationPayload, BurnAssetPayload, EraPayoutPayload, LockNftPayload,
+    LockPayload, MessagePayload, NotificationAckPayload, Offender, PlanNewEraPayload, RawMessage,
+    SettingChangeRequestPayload, SlashPayload,
+};
+use crate::*;
+use codec::{Decode, Encode};
+
+/// The builder of SCALE-encoded appchain messages, which can be passed to
+/// `verify_and_stage_appchain_messages` directly in witness mode of beefy light client.
+/// The nonce of messages starts from the given nonce and increases automatically.
+pub struct AppchainMessagesBuilder {
+    next_nonce: u32,
+    raw_messages: Vec<RawMessage>,
+}
+
+impl AppchainMessagesBuilder {
+    ///
+    pub fn new(start_nonce: u32) -> Self {
+        Self {
+            next_nonce: start_nonce,
+            raw_messages: Vec::new(),
+        }
+    }
+    /// The nonce of the next message to be added.
+    pub fn next_nonce(&self) -> u32 {
+        self.next_nonce
+    }
+    ///
+    pub fn plan_new_era(self, new_era: u32) -> Self {
+        self.push(
+            PayloadType::PlanNewEra,
+            PlanNewEraPayload { new_era }.try_to_vec(),
+        )
+    }
+    ///
+    pub fn era_payout(
+        self,
+        end_era: u32,
+        excluded_validators: Vec<String>,
+        offenders: Vec<Offender>,
+    ) -> Self {
+        self.push(
+            PayloadType::EraPayout,
+            EraPayoutPayload {
+                end_era,
+                excluded_validators,
+                offenders,
+            }
+            .try_to_vec(),
+        )
+    }
+    /// The hash of the validator set of the era is appended to the payload.
+    pub fn era_payout_with_validator_set_hash(
+        self,
+        end_era: u32,
+        excluded_validators: Vec<String>,
+        offenders: Vec<Offender>,
+        validator_set_hash: [u8; 32],
+    ) -> Self {
+        let mut payload = EraPayoutPayload {
+            end_era,
+            excluded_validators,
+            offenders,
+        }
+        .try_to_vec()
+        .unwrap();
+        payload.extend(validator_set_hash.try_to_vec().unwrap());
+        self.push(PayloadType::EraPayout, Ok(payload))
+    }
+    ///
+    pub fn lock(self, sender: String, receiver_id: AccountId, amount: u128) -> Self {
+        self.push(
+            PayloadType::Lock,
+            LockPayload {
+                sender,
+                receiver_id,
+                amount,
+            }
+            .try_to_vec(),
+        )
+    }
+    ///
+    pub fn burn_asset(
+        self,
+        token_id: String,
+        sender: String,
+        receiver_id: AccountId,
+        amount: u128,
+    ) -> Self {
+        self.push(
+            PayloadType::BurnAsset,
+            BurnAssetPayload {
+                token_id,
+                sender,
+                receiver_id,
+                amount,
+            }
+            .try_to_vec(),
+        )
+    }
+    ///
+    pub fn lock_nft(
+        self,
+        sender: String,
+        receiver_id: AccountId,
+        class: u128,
+        instance: u128,
+        metadata: TokenMetadata,
+    ) -> Self {
+        self.push(
+            PayloadType::LockNft,
+            LockNftPayload {
+                sender,
+                receiver_id,
+                class,
+                instance,
+                metadata,
+            }
+            .try_to_vec(),
+        )
+    }
+    ///
+    pub fn balance_attestation(self, balances: Vec<(String, u128)>) -> Self {
+        self.push(
+            PayloadType::BalanceAttestation,
+            BalanceAttestationPayload {
+                balances: balances
+                    .into_iter()
+                    .map(|(token_id, amount)| AttestedBalance { token_id, amount })
+                    .collect(),
+            }
+            .try_to_vec(),
+        )
+    }
+    ///
+    pub fn setting_change_request(self, change: AnchorSettingChange) -> Self {
+        self.push(
+            PayloadType::SettingChangeRequest,
+            SettingChangeRequestPayload { change }.try_to_vec(),
+        )
+    }
+    ///
+    pub fn notification_ack(self, processed_index: u64) -> Self {
+        self.push(
+            PayloadType::NotificationAck,
+            NotificationAckPayload { processed_index }.try_to_vec(),
+        )
+    }
+    ///
+    pub fn slash(self, who: String, kind: String, era: u32) -> Self {
+        self.push(
+            PayloadType::Slash,
+            SlashPayload { who, kind, era }.try_to_vec(),
+        )
+    }
+    /// Get the SCALE-encoded messages.
+    pub fn build(&self) -> Vec<u8> {
+        self.raw_messages.encode()
+    }
+    //
+    fn push(mut self, payload_type: PayloadType, payload: std::io::Result<Vec<u8>>) -> Self {
+        self.raw_messages.push(RawMessage {
+            nonce: u64::from(self.next_nonce),
+            payload_type,
+            payload: payload.unwrap(),
+        });
+        self.next_nonce += 1;
+        self
+    }
+}
+
+/// An appchain message decoded in the same layering as this contract does, that is,
+/// a SCALE-encoded `Vec<RawMessage>` with the Borsh-encoded payload in each message.
+pub struct DecodedAppchainMessage {
+    pub nonce: u64,
+    pub payload: MessagePayload,
+    /// The hash of validator set appended to the payload of `EraPayout` message.
+    pub validator_set_hash: Option<String>,
+}
+
+/// Decode the encoded appchain messages, for relayer developers to validate their encoding.
+pub fn decode_appchain_messages(
+    encoded_messages: &[u8],
+) -> Result<Vec<DecodedAppchainMessage>, String> {
+    let raw_messages: Vec<RawMessage> = Decode::decode(&mut &encoded_messages[..])
+        .map_err(|err| format!("Failed to decode messages: {}", err))?;
+    raw_messages
+        .iter()
+        .map(|raw_message| {
+            let mut payload_data = &raw_message.payload[..];
+            let payload = match raw_message.payload_type {
+                PayloadType::Lock => {
+                    BorshDeserialize::deserialize(&mut payload_data).map(MessagePayload::Lock)
+                }
+                PayloadType::BurnAsset => {
+                    BorshDeserialize::deserialize(&mut payload_data).map(MessagePayload::BurnAsset)
+                }
+                PayloadType::PlanNewEra => {
+                    BorshDeserialize::deserialize(&mut payload_data).map(MessagePayload::PlanNewEra)
+                }
+                PayloadType::EraPayout => {
+                    BorshDeserialize::deserialize(&mut payload_data).map(MessagePayload::EraPayout)
+                }
+                PayloadType::LockNft => {
+                    BorshDeserialize::deserialize(&mut payload_data).map(MessagePayload::LockNft)
+                }
+                PayloadType::BalanceAttestation => BorshDeserialize::deserialize(&mut payload_data)
+                    .map(MessagePayload::BalanceAttestation),
+                PayloadType::SettingChangeRequest => {
+                    BorshDeserialize::deserialize(&mut payload_data)
+                        .map(MessagePayload::SettingChangeRequest)
+                }
+                PayloadType::NotificationAck => BorshDeserialize::deserialize(&mut payload_data)
+                    .map(MessagePayload::NotificationAck),
+                PayloadType::Slash => {
+                    BorshDeserialize::deserialize(&mut payload_data).map(MessagePayload::Slash)
+                }
+            }
+            .map_err(|err| {
+                format!(
+                    "Failed to deserialize payload of message '{}': {}",
+                    raw_message.nonce, err
+                )
+            })?;
+            let validator_set_hash = match (&payload, payload_data.is_empty()) {
+                (MessagePayload::EraPayout(_), false) => {
+                    let hash: [u8; 32] =
+                        BorshDeserialize::deserialize(&mut payload_data).map_err(|err| {
+                            format!("Failed to deserialize hash of validator set: {}", err)
+                        })?;
+                    Some(format!("0x{}", hex::encode(hash)))
+                }
+                _ => None,
+            };
+            Ok(DecodedAppchainMessage {
+                nonce: raw_message.nonce,
+                payload,
+                validator_set_hash,
+            })
+        })
+        .collect()
+}
+
+/// Get a deterministic batch of appchain messages, including all kinds of messages
+/// which are commonly relayed (`PlanNewEra`, `EraPayout`, `Lock` and `BurnAsset`).
+pub fn sample_appchain_messages(start_nonce: u32) -> AppchainMessagesBuilder {
+    let receiver_id: AccountId = "receiver.testnet".parse().unwrap();
+    let sender = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d".to_string();
+    AppchainMessagesBuilder::new(start_nonce)
+        .plan_new_era(1)
+        .era_payout(0, Vec::new(), Vec::new())
+        .lock(
+            sender.clone(),
+            receiver_id.clone(),
+            1_000_000_000_000_000_000,
+        )
+        .burn_asset("usdc.testnet".to_string(), sender, receiver_id, 1_000_000)
+}
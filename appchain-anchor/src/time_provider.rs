@@ -0,0 +1,26 @@
+use crate::*;
+
+/// The provider of the time units for the periods in days (e.g. the unlock periods of
+/// unbonded stakes). The scale of it can be compressed by `seconds_of_a_day` of anchor
+/// settings on testnets, so that these periods can be exercised within minutes.
+pub struct TimeProvider {
+    seconds_of_a_day: u64,
+}
+
+impl TimeProvider {
+    ///
+    pub fn new(seconds_of_a_day: u64) -> Self {
+        Self { seconds_of_a_day }
+    }
+    /// Get the duration (in nanoseconds) of a certain number of days.
+    pub fn nanoseconds_of_days(&self, days: u64) -> u64 {
+        days * self.seconds_of_a_day * NANO_SECONDS_MULTIPLE
+    }
+}
+
+impl AppchainAnchor {
+    //
+    pub fn time_provider(&self) -> TimeProvider {
+        TimeProvider::new(self.anchor_settings.get().unwrap().seconds_of_a_day.0)
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{interfaces::TreasuryManager, *};
+use near_contract_standards::fungible_token::core::ext_ft_core;
+use std::collections::HashSet;
+
+/// The maximum count of destinations of a treasury source.
+const MAXIMUM_COUNT_OF_TREASURY_ROUTES: usize = 8;
+
+impl AppchainAnchor {
+    // Transfer a certain amount of token of a treasury source to the destinations in
+    // the routes of the source by their percents, and the rest to the treasury account.
+    // The amount without a destination is burnt (kept in this contract forever).
+    // Return the count of the transfers sent.
+    pub fn internal_route_to_treasury(
+        &mut self,
+        source: TreasurySource,
+        token_contract: &AccountId,
+        amount: Balance,
+    ) -> u64 {
+        if amount == 0 {
+            return 0;
+        }
+        let mut shares = Vec::<(Option<AccountId>, Balance)>::new();
+        let mut routed_amount = 0;
+        for route in self.treasury_routes.get(&source).unwrap_or_default() {
+            let share = amount * u128::from(route.percent) / 100;
+            if share > 0 {
+                shares.push((Some(route.destination), share));
+                routed_amount += share;
+            }
+        }
+        if amount > routed_amount {
+            shares.push((
+                self.anchor_settings.get().unwrap().treasury_account,
+                amount - routed_amount,
+            ));
+        }
+        let mut transfer_count = 0;
+        for (destination, share) in shares {
+            if let Some(receiver_id) = destination.clone() {
+                ext_ft_core::ext(token_contract.clone())
+                    .with_attached_deposit(1)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+                    .with_unused_gas_weight(0)
+                    .ft_transfer(receiver_id, share.into(), None);
+                transfer_count += 1;
+            }
+            self.record_treasury_flow(&source, token_contract, destination, share);
+        }
+        transfer_count
+    }
+    // Whether the amounts of a treasury source have any destination.
+    pub fn treasury_source_has_destination(&self, source: &TreasurySource) -> bool {
+        self.anchor_settings
+            .get()
+            .unwrap()
+            .treasury_account
+            .is_some()
+            || self.treasury_routes.contains_key(source)
+    }
+    // Accumulate the amount transferred from a treasury source to a destination
+    // in the latest era.
+    fn record_treasury_flow(
+        &mut self,
+        source: &TreasurySource,
+        token_contract: &AccountId,
+        destination: Option<AccountId>,
+        amount: Balance,
+    ) {
+        let era_number = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .index_range()
+            .end_index
+            .0;
+        let mut flows = self
+            .treasury_flows_of_eras
+            .get(&era_number)
+            .unwrap_or_default();
+        match flows.iter_mut().find(|flow| {
+            flow.source == *source
+                && flow.token_contract == *token_contract
+                && flow.destination == destination
+        }) {
+            Some(flow) => flow.amount = U128::from(flow.amount.0 + amount),
+            None => flows.push(TreasuryFlow {
+                source: source.clone(),
+                token_contract: token_contract.clone(),
+                destination,
+                amount: U128::from(amount),
+            }),
+        }
+        self.treasury_flows_of_eras.insert(&era_number, &flows);
+    }
+}
+
+#[near_bindgen]
+impl TreasuryManager for AppchainAnchor {
+    //
+    fn set_treasury_routes(&mut self, source: TreasurySource, routes: Vec<TreasuryRoute>) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(
+            routes.len() <= MAXIMUM_COUNT_OF_TREASURY_ROUTES,
+            "Too many treasury routes. The maximum count is {}.",
+            MAXIMUM_COUNT_OF_TREASURY_ROUTES
+        );
+        let mut destinations = HashSet::new();
+        for route in &routes {
+            assert!(
+                route.percent > 0,
+                "The percent of a treasury route should not be zero."
+            );
+            assert!(
+                destinations.insert(route.destination.clone()),
+                "Duplicated destination '{}' of treasury routes.",
+                route.destination
+            );
+        }
+        assert!(
+            routes
+                .iter()
+                .map(|route| u32::from(route.percent))
+                .sum::<u32>()
+                <= 100,
+            "The sum of percents of treasury routes should not exceed 100."
+        );
+        match routes.is_empty() {
+            true => self.treasury_routes.remove(&source),
+            false => self.treasury_routes.insert(&source, &routes),
+        };
+    }
+}
@@ -13,6 +13,16 @@ pub enum AppchainTemplateType {
     BarnacleEvm,
 }
 
+/// The way of assigning the block production slots to validators in appchain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum DutyWeighting {
+    /// The slots are assigned in proportion to the weights of validators.
+    ByWeight,
+    /// The slots are assigned equally to all validators.
+    Equal,
+}
+
 pub struct AccountIdInAppchain {
     appchain_template_type: AppchainTemplateType,
     origin: Option<String>,
@@ -25,32 +35,23 @@ impl AccountIdInAppchain {
         id_in_appchain: Option<String>,
         appchain_template_type: &AppchainTemplateType,
     ) -> Self {
-        let mut value = String::new();
-        if let Some(id_in_appchain) = id_in_appchain.clone() {
-            if !id_in_appchain.to_lowercase().starts_with("0x") {
-                value.push_str("0x");
-            }
-            value.push_str(&id_in_appchain);
-        }
+        let raw_string = match &id_in_appchain {
+            Some(id_in_appchain) => appchain_template_type
+                .template()
+                .normalize_account_id(id_in_appchain),
+            None => String::new(),
+        };
         Self {
             appchain_template_type: appchain_template_type.clone(),
             origin: id_in_appchain,
-            raw_string: value.to_lowercase(),
+            raw_string,
         }
     }
     ///
     pub fn is_valid(&self) -> bool {
-        if self.raw_string.len() > 2 {
-            match hex::decode(&self.raw_string.as_str()[2..self.raw_string.len()]) {
-                Ok(bytes) => match self.appchain_template_type {
-                    AppchainTemplateType::Barnacle => bytes.len() == 32,
-                    AppchainTemplateType::BarnacleEvm => bytes.len() == 20,
-                },
-                Err(_) => false,
-            }
-        } else {
-            false
-        }
+        self.appchain_template_type
+            .template()
+            .is_valid_account_id(&self.raw_string)
     }
     ///
     pub fn assert_valid(&self) {
@@ -100,6 +101,9 @@ pub enum AppchainState {
     Broken,
     /// The state which the lifecycle of an appchain is end.
     Dead,
+    /// The state while an appchain is winding down. No deposit or appchain message is accepted,
+    /// and all stakes and rewards can be withdrawn immediately.
+    Terminating,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -109,6 +113,16 @@ pub struct AppchainSettings {
     pub subql_endpoint: String,
     pub era_reward: U128,
     pub bonus_for_new_validator: U128,
+    /// The expected duration (in seconds) of an era of appchain,
+    /// the checking of era duration drift is disabled if it is 0.
+    pub expected_era_duration_in_secs: U64,
+    /// The tolerance (in percent of the expected duration) of the drift of era duration.
+    pub era_duration_drift_tolerance_percent: u16,
+    /// The total amount of appchain-native token which can be allocated to
+    /// validators in booting state.
+    pub bootstrap_budget: U128,
+    /// The boot nodes (in multiaddr format) of appchain.
+    pub boot_nodes: Vec<String>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -117,6 +131,31 @@ pub struct AnchorSettings {
     pub token_price_maintainer_account: Option<AccountId>,
     pub relayer_account: Option<AccountId>,
     pub beefy_light_client_witness_mode: bool,
+    pub guardian_account: Option<AccountId>,
+    pub treasury_account: Option<AccountId>,
+    /// The minimum interval (in seconds) between two price updates of a token,
+    /// 0 for no limit.
+    pub minimum_interval_of_price_updates: U64,
+    /// The maximum percentage of the change of price of a token in a single update,
+    /// 0 for no limit.
+    pub maximum_price_change_percent_per_update: u16,
+    /// Whether to pause asset transfer automatically when a price update violates the limits.
+    pub pause_asset_transfer_on_price_update_violation: bool,
+    /// The seconds of a day used in the periods in days (e.g. the unlock periods),
+    /// which can be compressed on testnets for testing.
+    pub seconds_of_a_day: U64,
+    /// The maximum percentage of the change of the era reward to be minted from the reward
+    /// paid in the previous era, beyond which the distribution is held, 0 for no limit.
+    pub maximum_era_reward_change_percent: u16,
+    /// The maximum percentage of the unprofitable (excluded) validators in the validator set
+    /// of an era, beyond which the reward distribution is held, 0 for no limit.
+    pub maximum_unprofitable_validator_percent: u16,
+    /// The account which can submit the attested exports of stake balances
+    /// from other staking contracts.
+    pub stake_migration_attester_account: Option<AccountId>,
+    /// The minimum interval (in seconds) between two reconciliations of the balance
+    /// of OCT token of this contract.
+    pub minimum_interval_of_oct_balance_reconciliations: U64,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -158,6 +197,9 @@ pub struct ProtocolSettings {
     /// The unlock period (in days) for delegator(s) can withdraw their deposit after
     /// they no longer delegates their stake to a certain validator on the corresponding appchain.
     pub unlock_period_of_delegator_deposit: U64,
+    /// The unlock period (in days) for validator(s) or delegator(s) can withdraw the remainder
+    /// of their stake after it is slashed.
+    pub unlock_period_of_slashed_remainder_stake: U64,
     /// The maximum number of historical eras that the validators or delegators are allowed to
     /// withdraw their reward
     pub maximum_era_count_of_unwithdrawn_reward: U64,
@@ -169,6 +211,173 @@ pub struct ProtocolSettings {
     pub validator_commission_percent: u16,
     /// The maximum unprofitable era count for auto-unbonding a validator
     pub maximum_allowed_unprofitable_era_count: u16,
+    /// The percent of the locked unbonded stake which is forfeited for withdrawing it
+    /// instantly (skipping the unlock period)
+    pub fast_unbond_penalty_percent: u16,
+    /// The maximum amount of locked unbonded stake which can be withdrawn instantly in an era.
+    /// If this value is zero, the fast unbonding is disabled.
+    pub maximum_fast_unbond_amount_per_era: U128,
+    /// The loyalty tiers for delegators who continuously bond their delegation for
+    /// a certain number of eras, which give them a rebate on the validator commission
+    pub loyalty_tiers_of_delegator: Vec<DelegatorLoyaltyTier>,
+    /// The maximum size (in bytes) of the encoded appchain messages in a submission.
+    pub maximum_size_of_encoded_appchain_messages: U64,
+    /// The maximum count of appchain messages in a submission.
+    pub maximum_count_of_appchain_messages_per_batch: u32,
+    /// The staking histories which take effect more than this number of eras ago
+    /// can be compacted.
+    pub minimum_era_count_for_compacting_staking_history: U64,
+    /// (Experimental) The secondary token which can also be staked by validators, and
+    /// contributes to their total stake at a configured conversion ratio.
+    pub secondary_stake_token: Option<SecondaryStakeToken>,
+    /// The number of eras between the announcement of the retirement of a validator
+    /// and the era in which the retirement can be completed.
+    pub era_count_of_validator_retirement_notice: U64,
+    /// The percent of the era reward which is put into the insurance pool,
+    /// for compensating the delegators affected by slashing.
+    pub insurance_pool_reward_percent: u16,
+    /// The amount of NEAR allocated from the relayer reimbursement pool to each era, which is
+    /// shared by the relayers in proportion to the gas they consumed in the era.
+    pub relayer_reimbursement_per_era: U128,
+    /// Whether the reward of the stake increased in the previous era of an era is pro-rated
+    /// by the time the stake was bonded in the previous era.
+    pub time_weighted_reward: bool,
+    /// The delay (in days) for a change of the withdrawal allowlist of an account to take effect.
+    pub withdrawal_allowlist_change_delay: U64,
+    /// The minimum number of consecutive eras in which a validator is bonded
+    /// before it can unbond its stake.
+    pub minimum_bonded_era_count_for_validator: U64,
+    /// The rounding policy in the pro-rata splitting of the reward of an era.
+    pub reward_rounding_policy: RewardRoundingPolicy,
+    /// The maximum number of eras for which a delegator can lock its delegation.
+    /// Zero means the delegation lock is disabled.
+    pub maximum_delegation_lock_era_count: U64,
+    /// The boost (in percent) of the voting weight of a locked delegation
+    /// for each era it is locked.
+    pub delegation_lock_voting_boost_percent_per_era: u16,
+    /// The maximum number of validators of a same operator entity (declared by the key
+    /// `operator` in validator profile) in the validator list of an era, the excess validators
+    /// are pushed to standby. Zero means no limit.
+    pub maximum_validator_count_per_operator: U64,
+    /// The period (in days) in which the slashed stake is held in escrow and can be appealed,
+    /// before it is transferred to the treasury account (or burnt).
+    pub penalty_dispute_period: U64,
+    /// The maximum count of validator registrations admitted per day while the appchain
+    /// is booting, the registrations beyond it are queued and admitted in order.
+    /// Zero means no validator can register while the appchain is booting.
+    pub maximum_validator_admissions_per_day_in_booting: U64,
+    /// The percent of the reward of a validator in an era which vests linearly over
+    /// `validator_reward_vesting_era_count` eras since the era. Zero means no vesting.
+    pub validator_reward_vesting_percent: u16,
+    /// The count of eras over which the vesting part of the reward of a validator vests.
+    pub validator_reward_vesting_era_count: U64,
+    /// The minimum amount of OCT token which a relayer needs to bond to be whitelisted.
+    /// Zero means the bonding is not required, and only the relayer account
+    /// in anchor settings can relay appchain messages.
+    pub minimum_relayer_bond: U128,
+    /// The percent of the bond of a relayer which is slashed by a misbehavior finding.
+    pub relayer_bond_slash_percent: u16,
+    /// The unlock period (in days) of the bond of a relayer after it is unbonded.
+    pub unlock_period_of_relayer_bond: U64,
+    /// The percent of the slashable deposit of a validator which is slashed by
+    /// an appchain message of payload type `Slash` reporting an offence of the validator.
+    pub validator_slash_percent: u16,
+    /// The percent of the delegation of each delegator of the offending validator
+    /// which is slashed by an appchain message of payload type `Slash`.
+    pub delegator_slash_percent: u16,
+}
+
+/// The versioned container of `ProtocolSettings` stored in the snapshots of eras.
+///
+/// The snapshots are not rewritten in state migrations. So, when the fields of
+/// `ProtocolSettings` are changed, the old layout should be kept as a separate struct
+/// in the latest variant, and a new variant should be added for the new layout, with
+/// the conversion from the old one implemented in `into_current`.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum VersionedProtocolSettings {
+    V1(ProtocolSettings),
+}
+
+impl VersionedProtocolSettings {
+    ///
+    pub fn new(protocol_settings: ProtocolSettings) -> Self {
+        VersionedProtocolSettings::V1(protocol_settings)
+    }
+    /// Get the protocol settings in the current layout.
+    pub fn into_current(self) -> ProtocolSettings {
+        match self {
+            VersionedProtocolSettings::V1(protocol_settings) => protocol_settings,
+        }
+    }
+}
+
+/// The rounding policies in the pro-rata splitting of the reward of an era. In both policies,
+/// the remainder of the reward which is not distributed is transferred to the treasury.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RewardRoundingPolicy {
+    /// All shares are rounded down.
+    Floor,
+    /// The shares of validators are rounded by the largest remainder method, so that they
+    /// sum up to the era reward exactly. The shares in a validator are rounded down.
+    LargestRemainder,
+}
+
+/// The rounding of the reward distribution of an era.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardRoundingOfEra {
+    pub policy: RewardRoundingPolicy,
+    /// The total stake (in whole OCT) which is the denominator of the shares of validators.
+    pub total_stake_units: U128,
+    /// The validators whose shares are rounded up, in `LargestRemainder` policy.
+    pub rounded_up_validator_ids: Vec<AccountId>,
+    /// The reward accounted in the distribution, including the distributed reward,
+    /// the withheld reward and the shares of jailed validators.
+    pub accounted_reward: U128,
+    /// The remainder transferred to the treasury, which is set when
+    /// the distribution is completed.
+    pub remainder: Option<U128>,
+}
+
+/// The classes of unbonded stake, which determine the unlock period of the stake.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum StakeClass {
+    ValidatorPrincipal,
+    DelegatorPrincipal,
+    VestingLocked,
+    SlashedRemainder,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegatorLoyaltyTier {
+    /// The minimum number of eras that the delegation is continuously bonded for
+    pub minimum_bonded_era_count: U64,
+    /// The percent of the validator commission (for the delegation) rebated to the delegator
+    pub commission_rebate_percent: u16,
+}
+
+/// The secondary token which can be staked by validators (experimental).
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SecondaryStakeToken {
+    /// The account of the NEP-141 contract of the token.
+    pub contract_account: AccountId,
+    /// The decimals of the token.
+    pub decimals: u8,
+    /// The stake (in OCT, with OCT decimals) which is contributed by one whole token.
+    /// If this value is zero, the deposit of the token is disabled.
+    pub price_in_oct: U128,
+}
+
+impl SecondaryStakeToken {
+    /// Convert the amount of the token to the stake it contributes.
+    pub fn convert_to_stake(&self, amount: Balance) -> Balance {
+        let unit = 10u128.pow(u32::from(self.decimals));
+        amount / unit * self.price_in_oct.0 + amount % unit * self.price_in_oct.0 / unit
+    }
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -211,6 +420,90 @@ pub struct NearFungibleToken {
     pub bridging_state: BridgingState,
 }
 
+/// The status of the acknowledgment of appchain notifications by appchain.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainNotificationAcknowledgment {
+    /// The index of the latest appchain notification which the appchain confirms processing.
+    pub latest_acknowledged_index: Option<U64>,
+    /// The index of the latest appchain notification.
+    pub latest_notification_index: Option<U64>,
+    /// The count of appchain notifications which are not acknowledged yet.
+    pub lag: U64,
+}
+
+/// The metadata of a NEP-141 token fetched from its contract by `ft_metadata`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FungibleTokenMetadataMirror {
+    pub metadata: FungibleTokenMetadata,
+    pub fetched_timestamp: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WrappedAssetBalance {
+    /// The contract account of the NEP-141 token.
+    pub contract_account: String,
+    /// The total balance of the wrapped token issued in appchain, attested by appchain.
+    pub attested_balance: U128,
+    /// The balance of the token locked in this contract.
+    /// If the token is not registered in this contract, the value will be `None`.
+    pub locked_balance: Option<U128>,
+    /// Whether the attested balance diverges from the locked balance.
+    pub is_divergent: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WrappedAssetBalanceAttestation {
+    /// The nonce of the appchain message which carries the attestation.
+    pub nonce: u32,
+    /// The timestamp when the attestation is applied.
+    pub timestamp: U64,
+    pub balances: Vec<WrappedAssetBalance>,
+    /// Whether any of the attested balances diverges from the locked balance.
+    pub has_divergence: bool,
+}
+
+/// The accumulated issuance of wrapped appchain token by this contract, by sources.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WrappedAppchainTokenIssuance {
+    /// The total amount minted for the reward of eras.
+    pub minted_by_era_payouts: U128,
+    /// The total amount minted for the native token locked in appchain.
+    pub minted_by_bridge_locks: U128,
+    /// The total amount burnt for the transfers back to appchain.
+    pub burnt_by_bridge_transfers: U128,
+    /// The latest issuance attested by appchain.
+    pub latest_attested_issuance: Option<AttestedIssuance>,
+}
+
+impl Default for WrappedAppchainTokenIssuance {
+    fn default() -> Self {
+        Self {
+            minted_by_era_payouts: U128(0),
+            minted_by_bridge_locks: U128(0),
+            burnt_by_bridge_transfers: U128(0),
+            latest_attested_issuance: None,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AttestedIssuance {
+    /// The nonce of the appchain message which carries the attestation.
+    pub nonce: u32,
+    /// The timestamp when the attestation is applied.
+    pub timestamp: U64,
+    pub attested_issuance: U128,
+    /// The issuance tracked by this contract when the attestation is applied.
+    pub tracked_issuance: U128,
+    pub is_divergent: bool,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub enum StakingFact {
@@ -280,198 +573,824 @@ pub enum StakingFact {
         validator_id: AccountId,
         validator_id_in_appchain: String,
     },
-}
-
-#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
-#[serde(crate = "near_sdk::serde")]
-pub struct StakingHistory {
-    pub staking_fact: StakingFact,
-    pub block_height: U64,
-    pub timestamp: U64,
-    pub index: U64,
-}
-
-#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
-#[serde(crate = "near_sdk::serde")]
-pub enum AnchorEvent {
-    /// The event that a certain amount of a NEAR fungible token has been locked
-    /// in appchain anchor.
-    NearFungibleTokenLocked {
-        symbol: String,
-        sender_id_in_near: AccountId,
-        receiver_id_in_appchain: String,
+    /// A validator's account id in appchain for receiving appchain-native benefits changed
+    ValidatorRewardAddressInAppchainChanged {
+        validator_id: AccountId,
+        reward_address_in_appchain: String,
+    },
+    /// A certain amount of vesting-locked stake is deposited to a validator by a depositor
+    VestingLockedStakeIncreased {
+        validator_id: AccountId,
+        depositor_id: AccountId,
         amount: U128,
     },
-    /// The event that a certain amount of a NEAR fungible token has been unlocked and
-    /// transfered to a receiver in NEAR protocol.
-    NearFungibleTokenUnlocked {
-        symbol: String,
-        sender_id_in_appchain: String,
-        receiver_id_in_near: AccountId,
+    /// The vesting-locked stake of a validator is unbonded,
+    /// which can only be withdrawn to the original depositor
+    VestingLockedStakeUnbonded {
+        validator_id: AccountId,
+        depositor_id: AccountId,
         amount: U128,
-        /// The nonce of the appchain message
-        appchain_message_nonce: u32,
     },
-    /// The event that the action for unlocking a certain amount of a NEAR fungible token
-    /// had failed due to some reasons.
-    FailedToUnlockNearFungibleToken {
-        symbol: String,
-        sender_id_in_appchain: String,
-        receiver_id_in_near: AccountId,
+    /// A certain amount of the deposit of a validator is slashed by the owner
+    ValidatorSlashed {
+        validator_id: AccountId,
         amount: U128,
-        /// The nonce of the appchain message
-        appchain_message_nonce: u32,
-        reason: String,
     },
-    /// The event that a certain amount of wrapped appchain token is burnt in its contract
-    /// in NEAR protocol.
-    WrappedAppchainTokenBurnt {
-        sender_id_in_near: AccountId,
-        receiver_id_in_appchain: String,
+    /// A certain amount of the delegation of a delegator is slashed by the owner
+    DelegatorSlashed {
+        delegator_id: AccountId,
+        validator_id: AccountId,
         amount: U128,
     },
-    /// The event that the action for burning a certain amount of wrapped appchain token
-    /// had failed due to some reasons.
-    FailedToBurnWrappedAppchainToken {
-        sender_id_in_near: AccountId,
-        receiver_id_in_appchain: String,
+    /// A validator is forced to be unbonded by the owner,
+    /// the unbonding will be applied as `ValidatorAutoUnbonded` in next era switching
+    ValidatorForceUnbonded { validator_id: AccountId },
+    /// A validator is jailed by the owner
+    ValidatorJailed { validator_id: AccountId },
+    /// A validator is released from jail by the owner
+    ValidatorUnjailed { validator_id: AccountId },
+    /// The commission percent of a validator is changed by the owner,
+    /// `None` means the validator uses the default commission percent in protocol settings
+    ValidatorCommissionChanged {
+        validator_id: AccountId,
+        commission_percent: Option<u16>,
+    },
+    /// A validator increased its stake with the secondary token,
+    /// the `stake` is the converted value of the `amount` of the token.
+    SecondaryStakeIncreased {
+        validator_id: AccountId,
         amount: U128,
-        reason: String,
+        stake: U128,
     },
-    /// The event that a certain amount of wrapped appchain token had been minted in NEAR protocol
-    WrappedAppchainTokenMinted {
-        /// The id of sender on the appchain, or `None` in distributing era rewards case
-        sender_id_in_appchain: Option<String>,
-        receiver_id_in_near: AccountId,
+    /// A validator decreased its stake of the secondary token.
+    SecondaryStakeDecreased {
+        validator_id: AccountId,
         amount: U128,
-        /// The nonce of the appchain message
-        appchain_message_nonce: u32,
+        stake: U128,
     },
-    /// The event that the action for minting a certain amount of wrapped appchain token
-    /// had failed due to some reasons.
-    FailedToMintWrappedAppchainToken {
-        /// The id of sender on the appchain, or `None` in distributing era rewards case
-        sender_id_in_appchain: Option<String>,
-        receiver_id_in_near: AccountId,
+    /// The stake of the secondary token of a validator is unbonded,
+    /// along with the unbonding of the validator.
+    SecondaryStakeUnbonded {
+        validator_id: AccountId,
         amount: U128,
-        /// The nonce of the appchain message
-        appchain_message_nonce: u32,
-        reason: String,
+        stake: U128,
+    },
+    /// The delegation of a delegator is moved from a validator to another one without unbonding,
+    /// by the delegator itself or on the retirement of the validator (to its fallback validator).
+    DelegationRedelegated {
+        delegator_id: AccountId,
+        from_validator_id: AccountId,
+        to_validator_id: AccountId,
+        amount: U128,
+    },
+    /// A delegator locks its delegation for a validator until a certain era,
+    /// the delegation cannot be decreased or unbonded by the delegator before the era
+    DelegationLocked {
+        delegator_id: AccountId,
+        validator_id: AccountId,
+        unlock_era: U64,
     },
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
-pub struct AppchainValidator {
-    pub validator_id: AccountId,
-    pub validator_id_in_appchain: String,
-    pub deposit_amount: U128,
-    pub total_stake: U128,
-    pub delegators_count: U64,
-    pub can_be_delegated_to: bool,
-    pub is_unbonding: bool,
+pub struct StakingHistory {
+    pub staking_fact: StakingFact,
+    pub block_height: U64,
+    pub timestamp: U64,
+    pub index: U64,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
-pub struct AppchainDelegator {
-    pub delegator_id: AccountId,
-    pub validator_id: AccountId,
-    pub delegation_amount: U128,
+pub struct CompactedStakingHistories {
+    /// The indexes of the original staking histories which are merged.
+    pub indexes: Vec<U64>,
+    /// The keccak256 hash (in hex format) of the borsh-encoded original staking histories.
+    pub hash: String,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
-pub struct UnbondedStake {
-    /// The number of era in appchain.
-    pub era_number: U64,
-    /// The account id of the owner of unbonded stake.
-    pub account_id: AccountId,
-    /// The amount of unbonded stake.
-    pub amount: U128,
-    /// The unlock time of the stake.
-    /// If the unlock time is not determined at the time, the value will be `None`.
-    pub unlock_time: U64,
+pub struct AppchainRuntimeUpgradeNotice {
+    /// The block number in appchain at which the runtime upgrade will be applied.
+    pub target_block_number: U64,
+    /// The spec version of the new runtime.
+    pub spec_version: u32,
+    /// The notes of the runtime upgrade, e.g. the release url.
+    pub notes: String,
+    /// The timestamp when the notice is recorded.
+    pub recorded_timestamp: U64,
+    pub index: U64,
 }
 
-/// The actual processing order is:
-/// `CopyingFromLastEra` -> `UnbondingValidator`-> `AutoUnbondingValidator`
-/// -> `ApplyingStakingHistory` -> `ReadyForDistributingReward` -> `DistributingReward`
-/// -> `CheckingForAutoUnbondingValidator` -> `Completed`
-#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// The commitment of the validator set of a certain era of an appchain,
+/// which is exchanged between peer anchors.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
-pub enum ValidatorSetProcessingStatus {
-    CopyingFromLastEra {
-        copying_validator_index: U64,
-        copying_delegator_index: U64,
-    },
-    ApplyingStakingHistory {
-        applying_index: U64,
-    },
-    ReadyForDistributingReward,
-    DistributingReward {
-        appchain_message_nonce: u32,
-        distributing_validator_index: U64,
-        distributing_delegator_index: U64,
-    },
-    Completed,
-    UnbondingValidator {
-        unbonding_validator_index: U64,
-        unbonding_delegator_index: U64,
-    },
-    AutoUnbondingValidator {
-        unbonding_validator_index: U64,
-        unbonding_delegator_index: U64,
-    },
-    CheckingForAutoUnbondingValidator {
-        unprofitable_validator_index: U64,
-    },
+pub struct ValidatorSetCommitment {
+    pub appchain_id: AppchainId,
+    pub era_number: U64,
+    /// The hash of the validator set, the same as `validator_set_hash` in `ValidatorSetInfo`.
+    pub validator_set_hash: String,
+    pub validator_count: U64,
+    pub total_stake: U128,
+    pub start_timestamp: U64,
 }
 
+/// The anchor of another appchain, which is registered in this contract.
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
-pub struct PermissionlessActionsStatus {
-    /// The era number that is switching by permissionless actions
-    pub switching_era_number: Option<U64>,
-    /// The era number that is distributing reward by permissionless actions
-    pub distributing_reward_era_number: Option<U64>,
-    ///
-    pub processing_appchain_message_nonce: Option<u32>,
-    ///
-    pub max_nonce_of_staged_appchain_messages: u32,
-    ///
-    pub latest_applied_appchain_message_nonce: u32,
+pub struct PeerAnchor {
+    pub anchor_account: AccountId,
+    pub appchain_id: AppchainId,
+    pub registered_timestamp: U64,
+    /// The latest validator set commitment submitted by the peer anchor.
+    pub latest_commitment: Option<ValidatorSetCommitment>,
+    /// The timestamp when the latest commitment is submitted.
+    pub latest_commitment_timestamp: Option<U64>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+/// The token whose price (in USD) is set by the token price maintainer.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(crate = "near_sdk::serde")]
-pub struct IndexRange {
-    pub start_index: U64,
-    pub end_index: U64,
+pub enum PricedToken {
+    OctToken,
+    WrappedAppchainToken,
+    NearFungibleToken { symbol: String },
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
 #[serde(crate = "near_sdk::serde")]
-pub struct RewardHistory {
-    pub era_number: U64,
-    pub total_reward: U128,
-    pub unwithdrawn_reward: U128,
+pub enum PriceUpdateViolationReason {
+    /// The price is updated again before the minimum interval passed.
+    TooFrequent { last_update_timestamp: U64 },
+    /// The change of the price is beyond the maximum percentage.
+    ChangeTooLarge { change_percent: U64 },
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+/// A price update rejected for violating the limits in anchor settings.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
-pub struct AnchorStatus {
-    pub total_stake_in_next_era: U128,
-    pub validator_count_in_next_era: U64,
-    pub delegator_count_in_next_era: U64,
-    pub index_range_of_appchain_notification_history: IndexRange,
-    pub index_range_of_validator_set_history: IndexRange,
-    pub index_range_of_staking_history: IndexRange,
-    pub nonce_range_of_appchain_messages: IndexRange,
-    pub index_range_of_appchain_challenges: IndexRange,
-    pub permissionless_actions_status: PermissionlessActionsStatus,
-    pub asset_transfer_is_paused: bool,
-    pub rewards_withdrawal_is_paused: bool,
+pub struct PriceUpdateViolation {
+    pub token: PricedToken,
+    pub current_price: U128,
+    pub rejected_price: U128,
+    pub reason: PriceUpdateViolationReason,
+    pub block_height: U64,
+    pub timestamp: U64,
+    pub index: U64,
+}
+
+/// An event for the delegators who are slashed to claim compensation from the insurance pool.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InsuranceClaimEvent {
+    /// The indexes of the staking histories of `DelegatorSlashed` covered by this event.
+    pub slashing_history_indexes: Vec<U64>,
+    /// The total slashed amount (of OCT token) of the covered staking histories.
+    pub total_slashed_amount: U128,
+    /// The percent of the balance of the insurance pool which is used for the compensation.
+    pub coverage_percent: u16,
+    /// The total compensation (of wrapped appchain token) of this event.
+    pub compensation_amount: U128,
+    /// The compensation which has been claimed by the delegators.
+    pub claimed_amount: U128,
+    /// The deadline (timestamp) of claiming compensation.
+    pub claim_deadline: U64,
+    /// Whether the event is closed, the unclaimed compensation is returned to the pool
+    /// when the event is closed.
+    pub is_closed: bool,
+    pub index: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ValidatorSetOverrideStatus {
+    /// The proposal is waiting for the approvals of validators.
+    Proposed,
+    /// The quorum of validators is reached, the override will be applied
+    /// to the validator set of the next era.
+    Approved,
+    /// The override is applied to the validator set of a certain era.
+    Applied { era_number: U64 },
+    /// The proposal is cancelled by the owner.
+    Cancelled,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ValidatorSetOverrideApproval {
+    pub validator_id: AccountId,
+    /// The total stake of the validator in the validator set of the base era.
+    pub stake: U128,
+    pub timestamp: U64,
+}
+
+/// A proposal of the owner to override the validator set of the next era with an explicit
+/// validator list, for recovering from the catastrophic consensus failures of appchain.
+/// It should be approved by the validators of the base era (the latest era when the
+/// proposal is made) with more than 2/3 of the total stake before taking effect.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ValidatorSetOverrideProposal {
+    /// The validators which remain active in the overridden validator set,
+    /// all other validators are pushed to standby.
+    pub validator_ids: Vec<AccountId>,
+    pub base_era_number: U64,
+    /// The total stake of the validator set of the base era.
+    pub total_stake: U128,
+    pub approvals: Vec<ValidatorSetOverrideApproval>,
+    pub approved_stake: U128,
+    pub status: ValidatorSetOverrideStatus,
+    pub proposed_timestamp: U64,
+    pub index: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum PenaltyEscrowStatus {
+    /// The slashed stake is held in escrow.
+    Escrowed,
+    /// The slashed account appeals against the penalty, waiting for the decision of the owner.
+    Appealed,
+    /// The appeal succeeds, the slashed stake is returned to the slashed account.
+    Returned,
+    /// The dispute window is over, the slashed stake is transferred to the treasury
+    /// (the treasury account and the destinations in treasury routes).
+    TransferredToTreasury,
+    /// The dispute window is over and the treasury has no destination,
+    /// the slashed stake is kept in this contract forever.
+    Burnt,
+}
+
+/// The escrow of the stake slashed by a staking history of `ValidatorSlashed`
+/// or `DelegatorSlashed`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PenaltyEscrow {
+    /// The account whose stake is slashed.
+    pub account_id: AccountId,
+    pub amount: U128,
+    pub staking_history_index: U64,
+    pub escrowed_timestamp: U64,
+    /// The end of the dispute window, after which the escrow can be settled.
+    pub dispute_end_timestamp: U64,
+    pub status: PenaltyEscrowStatus,
+    pub index: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum QueuedValidatorRegistrationStatus {
+    /// Waiting for admission in order.
+    Queued,
+    /// The validator is registered in the next validator set.
+    Admitted,
+    /// The registration cannot be admitted, the deposit is returned to the validator.
+    Dropped { reason: String },
+    /// The registration is cancelled by the validator, the deposit is returned to it.
+    Cancelled,
+}
+
+/// A validator registration which is queued while the appchain is booting,
+/// as the registrations admitted in the day exceed the limit in protocol settings.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct QueuedValidatorRegistration {
+    pub validator_id: AccountId,
+    pub validator_id_in_appchain: String,
+    pub profile: HashMap<String, String>,
+    pub deposit_amount: U128,
+    pub can_be_delegated_to: bool,
+    pub queued_timestamp: U64,
+    pub status: QueuedValidatorRegistrationStatus,
+    pub index: U64,
+}
+
+/// The count of validator registrations admitted in a certain day.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DailyValidatorAdmissions {
+    /// The number of the day since unix epoch, in the days of anchor settings.
+    pub day: U64,
+    pub count: U64,
+}
+
+impl Default for DailyValidatorAdmissions {
+    fn default() -> Self {
+        Self {
+            day: U64(0),
+            count: U64(0),
+        }
+    }
+}
+
+/// The staking contract which the stake balances are exported from.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum StakeMigrationSource {
+    /// An anchor contract of previous generation, exported at the end of a certain era.
+    PreviousAnchor {
+        contract_id: AccountId,
+        era_number: U64,
+    },
+    /// A NEAR staking pool, exported in a certain epoch.
+    StakingPool {
+        contract_id: AccountId,
+        epoch_height: U64,
+    },
+}
+
+/// A stake balance in an export of a stake migration source.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakeMigrationEntry {
+    pub account_id: AccountId,
+    /// The validator which the stake is delegated to,
+    /// `None` means the stake is the deposit of the account (validator) itself.
+    pub validator_id: Option<AccountId>,
+    pub amount: U128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum StakeMigrationExportStatus {
+    /// Waiting for the decision of the owner.
+    Pending,
+    /// The entries which can be credited are applied as staking histories,
+    /// the others are skipped.
+    Approved {
+        credited_amount: U128,
+        skipped_entry_count: u32,
+    },
+    Rejected,
+}
+
+/// An export of stake balances from another staking contract, attested by
+/// the stake migration attester account.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakeMigrationExport {
+    pub source: StakeMigrationSource,
+    pub entries: Vec<StakeMigrationEntry>,
+    /// The sum of the amounts of all entries.
+    pub total_amount: U128,
+    pub attester_id: AccountId,
+    pub submitted_timestamp: U64,
+    pub status: StakeMigrationExportStatus,
+    pub index: U64,
+}
+
+/// The obligations (in OCT token) of this contract, which should be covered by
+/// the balance of OCT token of this contract.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OctObligations {
+    /// The stakes bonded in the next validator set, excluding the secondary stakes.
+    pub bonded_stake: U128,
+    /// The unbonded stakes which are not withdrawn yet.
+    pub unbonded_stake: U128,
+    pub penalty_escrow_balance: U128,
+    pub stake_migration_allocation: U128,
+    pub relayer_bonds: U128,
+    /// The deposits of the pending validator registrations queued while appchain is booting.
+    pub queued_registration_deposits: U128,
+    pub total: U128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OctBalanceReconciliation {
+    pub obligations: OctObligations,
+    /// The balance of this contract queried from OCT token contract.
+    pub actual_balance: U128,
+    /// The actual balance minus the total obligations.
+    pub drift: I128,
+    pub keeper_id: AccountId,
+    pub timestamp: U64,
+    pub index: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RelayerBondStatus {
+    Bonded,
+    Unbonding { unlock_timestamp: U64 },
+}
+
+/// The OCT token bonded by a relayer to be whitelisted.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RelayerBond {
+    pub relayer_id: AccountId,
+    /// The remaining amount of the bond, excluding the slashed amount.
+    pub amount: U128,
+    pub slashed_amount: U128,
+    pub bonded_timestamp: U64,
+    pub status: RelayerBondStatus,
+}
+
+/// The finding of a misbehavior of a relayer, by which its bond is slashed.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RelayerMisbehaviorFinding {
+    /// A committed appchain challenge against the messages relayed by the relayer.
+    AppchainChallenge {
+        challenge_index: U64,
+    },
+    OwnerRuling {
+        reason: String,
+    },
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RelayerBondSlash {
+    pub relayer_id: AccountId,
+    pub amount: U128,
+    pub finding: RelayerMisbehaviorFinding,
+    pub slashed_timestamp: U64,
+    pub index: U64,
+}
+
+/// The part of the reward of a validator in a certain era which vests linearly
+/// over a number of eras since the era.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ValidatorRewardVesting {
+    pub era_number: U64,
+    pub vesting_era_count: U64,
+    pub total_amount: U128,
+    /// The amount which has been withdrawn by the validator.
+    pub released_amount: U128,
+}
+
+impl ValidatorRewardVesting {
+    /// Get the amount which is vested at a certain era.
+    pub fn vested_amount_at(&self, era_number: u64) -> Balance {
+        let vested_era_count = era_number
+            .saturating_sub(self.era_number.0)
+            .min(self.vesting_era_count.0);
+        self.total_amount.0 * u128::from(vested_era_count) / u128::from(self.vesting_era_count.0)
+    }
+}
+
+/// The config of the handler of the appchain messages of a certain payload type.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainMessageHandlerConfig {
+    /// The processing of appchain messages pauses at a message whose handler is disabled,
+    /// until the handler is enabled again.
+    pub is_enabled: bool,
+    /// The gas (in TGas) reserved for applying a message of the payload type. If the remaining
+    /// gas is not enough, the message is deferred to the next call of processing.
+    pub gas_budget: Option<U64>,
+}
+
+/// The change of an anchor-side setting which can be requested by the governance of appchain.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AnchorSettingChange {
+    EraReward { value: U128 },
+    BonusForNewValidator { value: U128 },
+    ValidatorCommissionPercent { value: u16 },
+    MaximumValidatorCount { value: U64 },
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AppchainSettingChangeProposalStatus {
+    /// The proposal is waiting for the decision of the owner.
+    Pending,
+    /// The change is approved by the owner and applied.
+    Approved,
+    /// The change is rejected by the owner.
+    Rejected,
+    /// A later request of the appchain changes the same setting before any decision.
+    Superseded { by_index: U64 },
+}
+
+/// A request of the governance of appchain to change an anchor-side setting, which is
+/// staged for the approval of the owner rather than being applied directly.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainSettingChangeProposal {
+    pub change: AnchorSettingChange,
+    /// The nonce of the appchain message which carries the request.
+    pub appchain_message_nonce: u32,
+    pub status: AppchainSettingChangeProposalStatus,
+    pub staged_timestamp: U64,
+    pub index: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AnchorEvent {
+    /// The event that a certain amount of a NEAR fungible token has been locked
+    /// in appchain anchor.
+    NearFungibleTokenLocked {
+        symbol: String,
+        sender_id_in_near: AccountId,
+        receiver_id_in_appchain: String,
+        amount: U128,
+    },
+    /// The event that a certain amount of a NEAR fungible token has been unlocked and
+    /// transfered to a receiver in NEAR protocol.
+    NearFungibleTokenUnlocked {
+        symbol: String,
+        sender_id_in_appchain: String,
+        receiver_id_in_near: AccountId,
+        amount: U128,
+        /// The nonce of the appchain message
+        appchain_message_nonce: u32,
+    },
+    /// The event that the action for unlocking a certain amount of a NEAR fungible token
+    /// had failed due to some reasons.
+    FailedToUnlockNearFungibleToken {
+        symbol: String,
+        sender_id_in_appchain: String,
+        receiver_id_in_near: AccountId,
+        amount: U128,
+        /// The nonce of the appchain message
+        appchain_message_nonce: u32,
+        reason: String,
+    },
+    /// The event that a certain amount of wrapped appchain token is burnt in its contract
+    /// in NEAR protocol.
+    WrappedAppchainTokenBurnt {
+        sender_id_in_near: AccountId,
+        receiver_id_in_appchain: String,
+        amount: U128,
+    },
+    /// The event that the action for burning a certain amount of wrapped appchain token
+    /// had failed due to some reasons.
+    FailedToBurnWrappedAppchainToken {
+        sender_id_in_near: AccountId,
+        receiver_id_in_appchain: String,
+        amount: U128,
+        reason: String,
+    },
+    /// The event that a certain amount of wrapped appchain token had been minted in NEAR protocol
+    WrappedAppchainTokenMinted {
+        /// The id of sender on the appchain, or `None` in distributing era rewards case
+        sender_id_in_appchain: Option<String>,
+        receiver_id_in_near: AccountId,
+        amount: U128,
+        /// The nonce of the appchain message
+        appchain_message_nonce: u32,
+    },
+    /// The event that the action for minting a certain amount of wrapped appchain token
+    /// had failed due to some reasons.
+    FailedToMintWrappedAppchainToken {
+        /// The id of sender on the appchain, or `None` in distributing era rewards case
+        sender_id_in_appchain: Option<String>,
+        receiver_id_in_near: AccountId,
+        amount: U128,
+        /// The nonce of the appchain message
+        appchain_message_nonce: u32,
+        reason: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainValidator {
+    pub validator_id: AccountId,
+    pub validator_id_in_appchain: String,
+    pub deposit_amount: U128,
+    pub total_stake: U128,
+    pub delegators_count: U64,
+    pub can_be_delegated_to: bool,
+    pub is_unbonding: bool,
+    pub reward_address_in_appchain: Option<String>,
+    pub vesting_locked_deposit_amount: U128,
+    pub is_jailed: bool,
+    pub commission_percent: Option<u16>,
+    pub secondary_deposit_amount: U128,
+    pub secondary_stake: U128,
+    /// The weight of the validator in the consensus of appchain, which is the part of
+    /// `total_stake` that has been bonded for at least one full era.
+    pub weight: U128,
+    /// Whether the validator is pushed to standby by the diversity constraints,
+    /// a standby validator has zero weight.
+    pub is_standby: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainDelegator {
+    pub delegator_id: AccountId,
+    pub validator_id: AccountId,
+    pub delegation_amount: U128,
+    pub label: Option<String>,
+}
+
+/// The unwithdrawn reward of an account in a certain era.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UnwithdrawnReward {
+    /// The number of era in appchain.
+    pub era_number: U64,
+    /// The validator of the delegation which the reward belongs to,
+    /// or `None` if the reward belongs to the account as a validator.
+    pub validator_id: Option<AccountId>,
+    pub reward: U128,
+    /// The commission of the account as a validator, which is zero for the reward of delegation.
+    pub commission: U128,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UnbondedStake {
+    /// The number of era in appchain.
+    pub era_number: U64,
+    /// The account id of the owner of unbonded stake.
+    pub account_id: AccountId,
+    /// The amount of unbonded stake.
+    pub amount: U128,
+    /// The unlock time of the stake.
+    /// If the unlock time is not determined at the time, the value will be `None`.
+    pub unlock_time: U64,
+}
+
+/// The actual processing order is:
+/// `CopyingFromLastEra` -> `UnbondingValidator`-> `AutoUnbondingValidator`
+/// -> `ApplyingStakingHistory` -> `ReadyForDistributingReward` -> `DistributingReward`
+/// -> `CheckingForAutoUnbondingValidator` -> `Completed`
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ValidatorSetProcessingStatus {
+    CopyingFromLastEra {
+        copying_validator_index: U64,
+        copying_delegator_index: U64,
+    },
+    ApplyingStakingHistory {
+        applying_index: U64,
+    },
+    ReadyForDistributingReward,
+    DistributingReward {
+        appchain_message_nonce: u32,
+        distributing_validator_index: U64,
+        distributing_delegator_index: U64,
+    },
+    Completed,
+    UnbondingValidator {
+        unbonding_validator_index: U64,
+        unbonding_delegator_index: U64,
+    },
+    AutoUnbondingValidator {
+        unbonding_validator_index: U64,
+        unbonding_delegator_index: U64,
+    },
+    CheckingForAutoUnbondingValidator {
+        unprofitable_validator_index: U64,
+    },
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PermissionlessActionsStatus {
+    /// The era number that is switching by permissionless actions
+    pub switching_era_number: Option<U64>,
+    /// The era number that is distributing reward by permissionless actions
+    pub distributing_reward_era_number: Option<U64>,
+    ///
+    pub processing_appchain_message_nonce: Option<u32>,
+    ///
+    pub max_nonce_of_staged_appchain_messages: u32,
+    ///
+    pub latest_applied_appchain_message_nonce: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct IndexRange {
+    pub start_index: U64,
+    pub end_index: U64,
+}
+
+/// The kinds of history-like data stored in anchor, which can be paged by index.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum HistoryKind {
+    StakingHistory,
+    ValidatorSetHistory,
+    /// The appchain notification histories, which replace the anchor event histories.
+    AppchainNotificationHistory,
+    /// The queue of staged appchain messages, the indexes are the nonces of the messages.
+    AppchainMessageQueue,
+    AppchainChallenge,
+    AppchainReferendum,
+    AppchainRuntimeUpgradeNotice,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardHistory {
+    pub era_number: U64,
+    pub total_reward: U128,
+    pub unwithdrawn_reward: U128,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorStatus {
+    pub total_stake_in_next_era: U128,
+    pub validator_count_in_next_era: U64,
+    pub delegator_count_in_next_era: U64,
+    pub index_range_of_appchain_notification_history: IndexRange,
+    pub index_range_of_validator_set_history: IndexRange,
+    pub index_range_of_staking_history: IndexRange,
+    pub nonce_range_of_appchain_messages: IndexRange,
+    pub index_range_of_appchain_challenges: IndexRange,
+    pub permissionless_actions_status: PermissionlessActionsStatus,
+    pub asset_transfer_is_paused: bool,
+    pub rewards_withdrawal_is_paused: bool,
+    pub maintenance_mode_is_on: bool,
+    pub staking_status: StakingStatus,
+}
+
+/// The status of staking in this contract, which is controlled by the owner
+/// (e.g. while the appchain is migrating or under incident response).
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum StakingStatus {
+    /// All staking actions are allowed.
+    Active,
+    /// The staking actions which change the stakes (including the deposits of OCT token)
+    /// are rejected, while the unbonded stakes and rewards can still be withdrawn.
+    StakingPaused,
+    /// All staking actions are rejected, including the withdrawals.
+    Frozen,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakingStatusChange {
+    pub status: StakingStatus,
+    pub reason: String,
+    pub timestamp: U64,
+    pub index: U64,
+}
+
+/// The initialization status of the sub-structures of this contract. A partially initialized
+/// deployment can be detected by the flags which are false.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorInitializationStatus {
+    /// Whether the settings of appchain, anchor and protocol, and the status of
+    /// permissionless actions are created.
+    pub settings_are_created: bool,
+    /// Whether the validator set histories, staking histories, appchain notification
+    /// histories, appchain messages, appchain challenges and reward distribution records
+    /// are created.
+    pub histories_are_created: bool,
+    /// Whether the price of OCT token is set.
+    pub oct_token_price_is_set: bool,
+    /// Whether the contract account of wrapped appchain token is set.
+    pub wrapped_appchain_token_contract_is_set: bool,
+    /// Whether the metadata and premined balance of wrapped appchain token are synced
+    /// by the wrapped appchain token contract.
+    pub wrapped_appchain_token_basedata_is_synced: bool,
+    /// Whether the rpc endpoint and subql endpoint of appchain are set.
+    pub appchain_endpoints_are_set: bool,
+    /// Whether the era reward of appchain is set.
+    pub era_reward_is_set: bool,
+    /// Whether the token price maintainer account is set.
+    pub token_price_maintainer_account_is_set: bool,
+    /// Whether the relayer account is set, or the relayers are whitelisted by bonds.
+    pub relayer_is_set: bool,
+    /// Whether the beefy light client is initialized.
+    pub beefy_light_client_is_initialized: bool,
+    /// Whether all of the above are done.
+    pub is_fully_initialized: bool,
+}
+
+/// The checksum of the state of anchor, for the clients with cached state (e.g. relayers
+/// and indexers) to detect whether they have missed any change cheaply.
+/// All hashes are keccak256 hashes (in hex format) of the borsh-encoded values.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorStateChecksum {
+    /// The hash of `(total_stake, validator_count, delegator_count)` of the next validator set.
+    pub stake_aggregates_hash: String,
+    /// The hash of `Vec<u64>` of the end indices of staking histories, validator set histories,
+    /// appchain notification histories and appchain challenges, the max nonce of staged
+    /// appchain messages and the nonce of the latest applied appchain message, in this order.
+    pub history_end_indices_hash: String,
+    /// The hash of the concatenation of the above hashes (in bytes).
+    pub checksum: String,
+}
+
+/// The configuration of anchor in a canonical form, for comparing the configurations of
+/// multiple anchors. The hash is the keccak256 hash (in hex format) of the JSON string.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorConfigurationDigest {
+    /// The JSON string of all settings of anchor, in which the keys of objects are sorted.
+    pub canonical_json: String,
+    pub hash: String,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -495,6 +1414,94 @@ pub struct ValidatorSetInfo {
     pub valid_total_stake: U128,
     /// The status of creation of this set
     pub processing_status: ValidatorSetProcessingStatus,
+    /// The deterministic random seed of this era (in hex format),
+    /// for the ordering/shuffling of validators in appchain.
+    /// It is `None` for the eras started before the seed is introduced.
+    pub shuffle_seed: Option<String>,
+    /// The total gas used (in unit of gas) for processing this set.
+    pub used_gas_of_processing: U64,
+    /// The hash of this set (in hex format), which can be used by the appchain
+    /// to prove the validator set it believes was active in this era.
+    pub validator_set_hash: String,
+    /// The nonce of the first appchain message applied in this era.
+    pub first_applied_message_nonce: Option<u32>,
+    /// The nonce of the last appchain message applied in this era.
+    pub last_applied_message_nonce: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegatorRewardPreview {
+    pub delegator_id: AccountId,
+    /// The reward of the delegator, including the commission rebate.
+    pub reward: U128,
+    pub commission_rebate: U128,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ValidatorRewardPreview {
+    pub validator_id: AccountId,
+    pub total_stake: U128,
+    /// Whether the validator (and its delegators) is excluded from the distribution,
+    /// as the validator is unprofitable or jailed.
+    pub is_excluded: bool,
+    /// The total reward of the validator, including the rewards of its delegators.
+    pub total_reward: U128,
+    pub commission_reward: U128,
+    /// The reward of the validator, excluding the commission rebates for its delegators.
+    pub validator_reward: U128,
+    pub delegator_rewards: Vec<DelegatorRewardPreview>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardDivergence {
+    pub validator_id: AccountId,
+    /// The delegator whose reward diverges, or `None` for the reward of the validator.
+    pub delegator_id: Option<AccountId>,
+    pub expected_reward: U128,
+    /// The reward stored in the validator set of the era.
+    pub stored_reward: U128,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EraDistributionAudit {
+    pub era_number: U64,
+    /// The count of validators audited in the query.
+    pub audited_validator_count: U64,
+    pub divergences: Vec<RewardDivergence>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegationExposure {
+    pub validator_id: AccountId,
+    pub delegated_amount: U128,
+    pub validator_total_stake: U128,
+    /// The share (in basis points) of the delegation in the total stake of the validator.
+    pub share_of_validator_stake: u16,
+    /// The effective commission percent of the validator.
+    pub commission_percent: u16,
+    /// The expected reward of the delegation in an era, including the commission rebate.
+    pub expected_reward: U128,
+    /// The amount which can be slashed if the validator misbehaves,
+    /// which is the whole delegation.
+    pub slash_exposure: U128,
+    pub is_jailed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegatorExposure {
+    pub delegator_id: AccountId,
+    /// The era whose data is used for the calculation.
+    pub era_number: U64,
+    pub total_delegated_amount: U128,
+    /// The total expected reward of all delegations in an era.
+    pub expected_reward: U128,
+    pub delegations: Vec<DelegationExposure>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -539,6 +1546,47 @@ pub enum AppchainNotification {
         owner_id_in_near: AccountId,
         receiver_id_in_appchain: String,
     },
+    /// The shuffle seed (in hex format) of the validator set of a new era is generated,
+    /// for the ordering/shuffling of validators in appchain.
+    ShuffleSeedGenerated {
+        era_number: U64,
+        shuffle_seed: String,
+    },
+    /// A certain referendum for appchain governance signaling is concluded in appchain anchor.
+    ReferendumConcluded {
+        referendum_index: U64,
+        title: String,
+        approved: bool,
+        aye_stake: U128,
+        nay_stake: U128,
+        total_stake: U128,
+    },
+    /// A runtime upgrade of appchain is noticed by the owner of appchain anchor.
+    RuntimeUpgradeNoticed {
+        notice_index: U64,
+        target_block_number: U64,
+        spec_version: u32,
+    },
+    /// A validator announced its retirement, the delegations to it will be moved to
+    /// the fallback validators of the delegators (or be unbonded) in the retiring era.
+    ValidatorRetirementAnnounced {
+        validator_id: AccountId,
+        retiring_era_number: U64,
+    },
+    /// A validator cancelled its retirement.
+    ValidatorRetirementCancelled { validator_id: AccountId },
+    /// The observed duration of an era drifts from the expected one beyond the tolerance.
+    EraDurationDrifted {
+        era_number: U64,
+        expected_duration_in_secs: U64,
+        observed_duration_in_secs: U64,
+    },
+    /// A certain amount of appchain-native token is allocated to a validator in booting state.
+    BootstrapBalanceAllocated {
+        validator_id: AccountId,
+        receiver_id_in_appchain: String,
+        amount: U128,
+    },
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -550,6 +1598,25 @@ pub struct AppchainNotificationHistory {
     pub index: U64,
 }
 
+/// The classes of anchor events (appchain notifications), for off-chain consumers
+/// to filter the events they care about.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AnchorEventClass {
+    Staking,
+    Bridging,
+    Governance,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorEventsPage {
+    /// The events matching the filters in the scanned range of histories.
+    pub events: Vec<AppchainNotificationHistory>,
+    /// The index to continue the query from.
+    pub next_index: U64,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub enum AppchainMessageProcessingResult {
@@ -557,12 +1624,202 @@ pub enum AppchainMessageProcessingResult {
     Error { nonce: u32, message: String },
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-#[serde(crate = "near_sdk::serde")]
-pub enum MultiTxsOperationProcessingResult {
-    NeedMoreGas,
-    Ok,
-    Error(String),
+/// The appchain messages which are applied (processed) during a certain era.
+/// As the messages are applied in order of their nonce, they are recorded as a range of nonce.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppliedMessagesOfEra {
+    pub era_number: U64,
+    pub start_nonce: u32,
+    pub end_nonce: u32,
+    /// The count of applied messages, including the messages failed in processing.
+    pub message_count: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum MultiTxsOperationProcessingResult {
+    NeedMoreGas,
+    Ok,
+    Error(String),
+}
+
+impl StakingFact {
+    /// Get the validators and delegators whose stake or status may be changed by this fact,
+    /// in the form of `(validator_id, delegator_id)`.
+    pub fn affected_stake_holders(&self) -> Vec<(AccountId, Option<AccountId>)> {
+        match self {
+            StakingFact::DelegatorRegistered {
+                delegator_id,
+                validator_id,
+                ..
+            }
+            | StakingFact::DelegationIncreased {
+                delegator_id,
+                validator_id,
+                ..
+            }
+            | StakingFact::DelegationDecreased {
+                delegator_id,
+                validator_id,
+                ..
+            }
+            | StakingFact::DelegatorUnbonded {
+                delegator_id,
+                validator_id,
+                ..
+            }
+            | StakingFact::DelegatorAutoUnbonded {
+                delegator_id,
+                validator_id,
+                ..
+            }
+            | StakingFact::DelegatorSlashed {
+                delegator_id,
+                validator_id,
+                ..
+            }
+            | StakingFact::DelegationLocked {
+                delegator_id,
+                validator_id,
+                ..
+            } => vec![(validator_id.clone(), Some(delegator_id.clone()))],
+            StakingFact::DelegationRedelegated {
+                delegator_id,
+                from_validator_id,
+                to_validator_id,
+                ..
+            } => vec![
+                (from_validator_id.clone(), Some(delegator_id.clone())),
+                (to_validator_id.clone(), Some(delegator_id.clone())),
+            ],
+            StakingFact::ValidatorRegistered { validator_id, .. }
+            | StakingFact::StakeIncreased { validator_id, .. }
+            | StakingFact::StakeDecreased { validator_id, .. }
+            | StakingFact::ValidatorUnbonded { validator_id, .. }
+            | StakingFact::ValidatorDelegationEnabled { validator_id }
+            | StakingFact::ValidatorDelegationDisabled { validator_id }
+            | StakingFact::ValidatorAutoUnbonded { validator_id, .. }
+            | StakingFact::ValidatorIdInAppchainChanged { validator_id, .. }
+            | StakingFact::ValidatorRewardAddressInAppchainChanged { validator_id, .. }
+            | StakingFact::VestingLockedStakeIncreased { validator_id, .. }
+            | StakingFact::VestingLockedStakeUnbonded { validator_id, .. }
+            | StakingFact::ValidatorSlashed { validator_id, .. }
+            | StakingFact::ValidatorForceUnbonded { validator_id }
+            | StakingFact::ValidatorJailed { validator_id }
+            | StakingFact::ValidatorUnjailed { validator_id }
+            | StakingFact::ValidatorCommissionChanged { validator_id, .. }
+            | StakingFact::SecondaryStakeIncreased { validator_id, .. }
+            | StakingFact::SecondaryStakeDecreased { validator_id, .. }
+            | StakingFact::SecondaryStakeUnbonded { validator_id, .. } => {
+                vec![(validator_id.clone(), None)]
+            }
+        }
+    }
+    /// Get the owner, the class and the amount of the stake which is unbonded by this fact.
+    /// If this fact doesn't unbond any stake, `None` will be returned.
+    pub fn unbonded_stake(&self) -> Option<(AccountId, StakeClass, U128)> {
+        match self {
+            StakingFact::StakeDecreased {
+                validator_id,
+                amount,
+            }
+            | StakingFact::ValidatorUnbonded {
+                validator_id,
+                amount,
+            }
+            | StakingFact::ValidatorAutoUnbonded {
+                validator_id,
+                amount,
+            } => Some((
+                validator_id.clone(),
+                StakeClass::ValidatorPrincipal,
+                *amount,
+            )),
+            StakingFact::DelegationDecreased {
+                delegator_id,
+                validator_id: _,
+                amount,
+            }
+            | StakingFact::DelegatorUnbonded {
+                delegator_id,
+                validator_id: _,
+                amount,
+            }
+            | StakingFact::DelegatorAutoUnbonded {
+                delegator_id,
+                validator_id: _,
+                amount,
+            } => Some((
+                delegator_id.clone(),
+                StakeClass::DelegatorPrincipal,
+                *amount,
+            )),
+            StakingFact::VestingLockedStakeUnbonded {
+                validator_id: _,
+                depositor_id,
+                amount,
+            } => Some((depositor_id.clone(), StakeClass::VestingLocked, *amount)),
+            _ => None,
+        }
+    }
+    /// Get the owner and the amount (of the secondary token) of the stake
+    /// which is unbonded by this fact.
+    pub fn unbonded_secondary_stake(&self) -> Option<(AccountId, U128)> {
+        match self {
+            StakingFact::SecondaryStakeDecreased {
+                validator_id,
+                amount,
+                stake: _,
+            }
+            | StakingFact::SecondaryStakeUnbonded {
+                validator_id,
+                amount,
+                stake: _,
+            } => Some((validator_id.clone(), *amount)),
+            _ => None,
+        }
+    }
+}
+
+impl AppchainNotification {
+    ///
+    pub fn event_class(&self) -> AnchorEventClass {
+        match self {
+            AppchainNotification::NearFungibleTokenLocked { .. }
+            | AppchainNotification::WrappedAppchainTokenBurnt { .. }
+            | AppchainNotification::WrappedNonFungibleTokenBurnt { .. }
+            | AppchainNotification::WrappedAppchainNFTLocked { .. }
+            | AppchainNotification::BootstrapBalanceAllocated { .. } => AnchorEventClass::Bridging,
+            AppchainNotification::ReferendumConcluded { .. }
+            | AppchainNotification::RuntimeUpgradeNoticed { .. } => AnchorEventClass::Governance,
+            AppchainNotification::ValidatorRetirementAnnounced { .. }
+            | AppchainNotification::ValidatorRetirementCancelled { .. }
+            | AppchainNotification::EraDurationDrifted { .. }
+            | AppchainNotification::ShuffleSeedGenerated { .. } => AnchorEventClass::Staking,
+        }
+    }
+    /// The account in NEAR protocol which performed (or received) the bridging operation.
+    pub fn bridging_account_id(&self) -> Option<&AccountId> {
+        match self {
+            AppchainNotification::NearFungibleTokenLocked {
+                sender_id_in_near, ..
+            }
+            | AppchainNotification::WrappedAppchainTokenBurnt {
+                sender_id_in_near, ..
+            }
+            | AppchainNotification::WrappedNonFungibleTokenBurnt {
+                sender_id_in_near, ..
+            }
+            | AppchainNotification::WrappedAppchainNFTLocked {
+                sender_id_in_near, ..
+            } => Some(sender_id_in_near),
+            AppchainNotification::BootstrapBalanceAllocated { validator_id, .. } => {
+                Some(validator_id)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl MultiTxsOperationProcessingResult {
@@ -632,6 +1889,65 @@ pub struct UserStakingHistory {
     pub has_taken_effect: bool,
 }
 
+/// The reward distribution of an era which is held by the circuit breaker on payout anomalies,
+/// until it is confirmed or rejected by the owner.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HeldEraRewardDistribution {
+    pub era_number: U64,
+    /// The nonce of the appchain message which concludes the reward of the era.
+    pub appchain_message_nonce: u32,
+    /// The era reward to be minted.
+    pub era_reward: U128,
+    pub unprofitable_validator_count: U64,
+    pub validator_count: U64,
+    /// The descriptions of the detected anomalies.
+    pub anomalies: Vec<String>,
+    pub held_timestamp: U64,
+    pub is_confirmed: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RewardWithdrawalHistory {
+    /// The validator which the reward is earned from.
+    pub validator_id: AccountId,
+    pub amount: U128,
+    /// The account which received the reward.
+    pub destination: AccountId,
+    pub block_height: U64,
+    pub timestamp: U64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AccountActivity {
+    /// A staking fact related to the account.
+    Staking {
+        staking_fact: StakingFact,
+        has_taken_effect: bool,
+    },
+    /// The account withdrew its reward earned from a validator.
+    RewardWithdrawn {
+        validator_id: AccountId,
+        amount: U128,
+        destination: AccountId,
+    },
+    /// A bridging appchain notification sent by (or allocated to) the account.
+    Bridging {
+        appchain_notification: AppchainNotification,
+        notification_index: U64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccountActivityRecord {
+    pub activity: AccountActivity,
+    pub block_height: U64,
+    pub timestamp: U64,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub enum FTDepositMessage {
@@ -647,17 +1963,61 @@ pub enum FTDepositMessage {
     IncreaseDelegation {
         validator_id: AccountId,
     },
+    /// Increase the stake of a certain validator on behalf of it.
+    /// The increased stake belongs to the validator.
+    IncreaseStakeFor {
+        validator_id: AccountId,
+    },
+    /// Increase the delegation of a certain delegator to a certain validator on behalf of it.
+    /// The increased delegation belongs to the delegator.
+    IncreaseDelegationFor {
+        delegator_id: AccountId,
+        validator_id: AccountId,
+    },
+    /// Increase the stake of a certain validator with vesting-locked stake.
+    /// The vesting-locked stake earns rewards for the validator normally, but the principal
+    /// can only be withdrawn to the depositor.
+    IncreaseVestingLockedStakeFor {
+        validator_id: AccountId,
+    },
+    /// Increase the stake of the sender (validator) with the secondary token.
+    IncreaseSecondaryStake,
+    /// Fund the allocation for crediting the migrated stakes.
+    /// Only the owner can fund the allocation.
+    FundStakeMigrationAllocation,
+    /// Bond OCT token to be (or to stay) a whitelisted relayer.
+    BondRelayer,
+    /// Bridge the deposited NEAR fungible token (which is locked in this contract)
+    /// or wrapped appchain token (which is burnt) to the appchain.
     BridgeToAppchain {
         receiver_id_in_appchain: String,
     },
 }
 
+/// The names of variants of `FTDepositMessage`, which should be updated along with the enum.
+pub const FT_DEPOSIT_MESSAGE_VARIANTS: [&str; 11] = [
+    "RegisterValidator",
+    "IncreaseStake",
+    "RegisterDelegator",
+    "IncreaseDelegation",
+    "IncreaseStakeFor",
+    "IncreaseDelegationFor",
+    "IncreaseVestingLockedStakeFor",
+    "IncreaseSecondaryStake",
+    "FundStakeMigrationAllocation",
+    "BondRelayer",
+    "BridgeToAppchain",
+];
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub enum NFTTransferMessage {
     BridgeToAppchain { receiver_id_in_appchain: String },
 }
 
+/// The names of variants of `NFTTransferMessage`, which should be updated along with the enum.
+pub const NFT_TRANSFER_MESSAGE_VARIANTS: [&str; 1] = ["BridgeToAppchain"];
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub struct WrappedAppchainNFT {
@@ -667,3 +2027,569 @@ pub struct WrappedAppchainNFT {
     pub bridging_state: BridgingState,
     pub count_of_locked_tokens: U64,
 }
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ReferendumVoteOption {
+    Aye,
+    Nay,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ReferendumVote {
+    /// The option of the vote.
+    pub option: ReferendumVoteOption,
+    /// The stake which is counted for the vote.
+    pub stake: U128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AppchainReferendumStatus {
+    Voting,
+    Approved,
+    Rejected,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainReferendumInfo {
+    /// The index of the referendum.
+    pub index: U64,
+    /// The title of the referendum.
+    pub title: String,
+    /// The description of the referendum.
+    pub description: String,
+    /// The era number of the validator set which is used for voting.
+    pub era_number: U64,
+    /// The timestamp when the voting starts.
+    pub start_timestamp: U64,
+    /// The timestamp when the voting ends.
+    pub end_timestamp: U64,
+    /// Total stake of the validator set which is used for voting, including the boosts
+    /// of voting weight of the delegators who have voted.
+    pub total_stake: U128,
+    /// Total stake of voters who vote `Aye`.
+    pub aye_stake: U128,
+    /// Total stake of voters who vote `Nay`.
+    pub nay_stake: U128,
+    /// The count of validators who voted.
+    pub voter_count: U64,
+    /// The count of delegators who voted by themselves.
+    pub delegator_voter_count: U64,
+    /// The status of the referendum.
+    pub status: AppchainReferendumStatus,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccountFreezing {
+    /// The reason of the freezing, e.g. the pending offence report or investigation.
+    pub reason: String,
+    /// The timestamp when the account is frozen.
+    pub frozen_timestamp: U64,
+    /// The timestamp when the freezing expires automatically.
+    pub expiry_timestamp: U64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EraSummary {
+    pub era_number: U64,
+    /// The block height when the era starts.
+    pub start_block_height: U64,
+    /// The timestamp when the era starts.
+    pub start_timestamp: U64,
+    /// The duration (in nanoseconds) of the era, which is set when the era is closed.
+    pub duration: Option<U64>,
+    /// The count of validators in the era, which is set when the era is closed.
+    pub validator_count: U64,
+    /// The total stake of the era, which is set when the era is closed.
+    pub total_stake: U128,
+    /// The total reward minted and distributed for the era.
+    pub reward_paid: U128,
+    /// The nonce of the appchain message which starts the era.
+    pub start_message_nonce: Option<U64>,
+    /// The nonce of the last appchain message in the era, which is set when the era is closed.
+    pub end_message_nonce: Option<U64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorFeatureFlags {
+    /// Whether the beefy light client is in witness mode, in which the appchain messages
+    /// are submitted by the relayer account without proofs.
+    pub beefy_light_client_witness_mode: bool,
+    /// Whether the secondary stake token is set, which can be staked by validators.
+    pub secondary_stake: bool,
+    /// Whether any wrapped appchain NFT is registered for bridging.
+    pub nft_bridge: bool,
+    /// Whether the contract is in maintenance mode.
+    pub maintenance_mode: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AnchorContractMetadata {
+    /// The version of this contract.
+    pub version: String,
+    /// The version of the JSON API of view functions.
+    pub view_api_version: u32,
+    /// The supported variants of the message of `ft_transfer_call` to this contract.
+    pub ft_deposit_messages: Vec<String>,
+    /// The supported variants of the message of `nft_transfer_call` to this contract.
+    pub nft_transfer_messages: Vec<String>,
+    /// The supported payload types of appchain messages.
+    pub payload_types: Vec<String>,
+    /// The supported compression algorithms of appchain messages submitted by relayers.
+    pub payload_compressions: Vec<String>,
+    pub feature_flags: AnchorFeatureFlags,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EraDurationDrift {
+    pub era_number: U64,
+    pub expected_duration_in_secs: U64,
+    pub observed_duration_in_secs: U64,
+    /// The drift (in percent of the expected duration) of the observed duration.
+    pub drift_percent: U64,
+    pub is_beyond_tolerance: bool,
+}
+
+/// The proof of membership of a validator in the validator set of an era.
+///
+/// The leaves of the merkle tree are the keccak256 hashes of borsh-encoded
+/// `(validator_id: String, total_stake: u128)` of all validators in the set,
+/// sorted by `validator_id`. A parent node is the keccak256 hash of the concatenation of
+/// its two children in ascending order, and a node without sibling is promoted unchanged.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ValidatorMembershipProof {
+    pub era_number: U64,
+    pub validator_id: AccountId,
+    pub total_stake: U128,
+    /// The sibling hashes (in hex format) on the path from the leaf of the validator to the root.
+    pub proof: Vec<String>,
+    /// The merkle root (in hex format) of the validator set of the era.
+    pub validator_set_merkle_root: String,
+}
+
+impl ValidatorMembershipProof {
+    ///
+    pub fn leaf_hash(validator_id: &AccountId, total_stake: Balance) -> [u8; 32] {
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&env::keccak256(
+            &(validator_id.to_string(), total_stake)
+                .try_to_vec()
+                .unwrap(),
+        ));
+        hash
+    }
+    ///
+    pub fn hash_of_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let input = match left <= right {
+            true => [left.as_ref(), right.as_ref()].concat(),
+            false => [right.as_ref(), left.as_ref()].concat(),
+        };
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&env::keccak256(&input));
+        hash
+    }
+    /// Verify that the proof leads to the merkle root in it. The verifier should
+    /// check the merkle root against the one of the era it trusts.
+    pub fn verify(&self) -> bool {
+        let mut hash = Self::leaf_hash(&self.validator_id, self.total_stake.0);
+        for sibling in &self.proof {
+            match decode_hash_in_hex(sibling) {
+                Some(sibling) => hash = Self::hash_of_pair(&hash, &sibling),
+                None => return false,
+            }
+        }
+        match decode_hash_in_hex(&self.validator_set_merkle_root) {
+            Some(root) => root == hash,
+            None => false,
+        }
+    }
+}
+
+fn decode_hash_in_hex(hash_in_hex: &str) -> Option<[u8; 32]> {
+    let bytes = hex::decode(hash_in_hex.trim_start_matches("0x")).ok()?;
+    bytes.try_into().ok()
+}
+
+/// A pending change of the withdrawal allowlist of an account.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingWithdrawalAllowlistChange {
+    pub destinations: Vec<AccountId>,
+    /// The timestamp when the change takes effect.
+    pub effective_timestamp: U64,
+}
+
+/// The allowlist of the destinations which the stake and rewards of an account
+/// can be withdrawn to.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WithdrawalAllowlist {
+    pub destinations: Vec<AccountId>,
+    pub pending_change: Option<PendingWithdrawalAllowlistChange>,
+}
+
+impl WithdrawalAllowlist {
+    /// Get the destinations which are effective at current block.
+    pub fn effective_destinations(&self) -> Vec<AccountId> {
+        match &self.pending_change {
+            Some(change) if change.effective_timestamp.0 <= env::block_timestamp() => {
+                change.destinations.clone()
+            }
+            _ => self.destinations.clone(),
+        }
+    }
+    /// Apply the pending change if it has taken effect.
+    pub fn settle_pending_change(&mut self) {
+        self.destinations = self.effective_destinations();
+        if let Some(change) = &self.pending_change {
+            if change.effective_timestamp.0 <= env::block_timestamp() {
+                self.pending_change = None;
+            }
+        }
+    }
+}
+
+/// The hint of the duties of a validator in an era, for estimating the infrastructure needs.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ValidatorDutyHint {
+    pub validator_id: AccountId,
+    pub validator_id_in_appchain: String,
+    /// The share (in basis points, 1/10000) of block production slots of the validator.
+    pub block_production_share_bps: U64,
+    /// The expected number of blocks produced by the validator in the era,
+    /// which is available only if the expected era duration is set in appchain settings.
+    pub expected_block_count: Option<U64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DutyScheduleOfEra {
+    pub era_number: U64,
+    pub duty_weighting: DutyWeighting,
+    pub validator_duties: Vec<ValidatorDutyHint>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ListSortKey {
+    Stake,
+    RegistrationTime,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// The position in a sorted list, after which the next page starts.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ListCursor {
+    /// The value of the sort key, the stake or the registration timestamp.
+    pub sort_value: U128,
+    pub account_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ListPage<T> {
+    pub items: Vec<T>,
+    /// The cursor for querying the next page, `None` if there are no more items.
+    pub next_cursor: Option<ListCursor>,
+}
+
+/// The position in the delegations of the next validator set, at which the next chunk
+/// of the dump of delegations starts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegationDumpCursor {
+    /// The index of the validator in the next validator set.
+    pub validator_index: U64,
+    /// The index of the delegator in the delegators of the validator.
+    pub delegator_index: U64,
+}
+
+/// A chunk of the dump of a certain kind of the current state, for indexers to bootstrap from.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StateDumpChunk<T, C> {
+    pub items: Vec<T>,
+    /// The cursor for querying the next chunk, `None` if there are no more items.
+    pub next_cursor: Option<C>,
+    /// The index of the latest staking history when the chunk is taken. The changes after it
+    /// can be followed by querying the staking histories.
+    pub latest_staking_history_index: U64,
+}
+
+/// The public endpoints of the appchain node of a validator.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ValidatorEndpoints {
+    /// The P2P addresses (in multiaddr format) of the node.
+    pub p2p_multiaddrs: Vec<String>,
+    /// The RPC endpoints of the node.
+    pub rpc_endpoints: Vec<String>,
+    /// Whether the endpoints are hidden by the owner of this contract,
+    /// the hidden endpoints are not included in the peers of appchain.
+    pub is_hidden: bool,
+    pub updated_timestamp: U64,
+}
+
+/// The peers of appchain for newly joining validators.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainPeers {
+    pub boot_nodes: Vec<String>,
+    /// The visible endpoints of the validators in the next validator set.
+    pub validator_endpoints: Vec<(AccountId, ValidatorEndpoints)>,
+}
+
+/// The condition of a stake change intent, which is checked against the next validator set.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum StakeChangeCondition {
+    /// The commission percent of the validator exceeds a certain percent.
+    CommissionExceeds { percent: u16 },
+    /// The validator is jailed.
+    ValidatorIsJailed,
+    /// The validator has announced its retirement.
+    ValidatorIsRetiring,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum StakeChangeAction {
+    UnbondDelegation,
+    DecreaseDelegation { amount: U128 },
+}
+
+/// The lock of a delegation, which is made by the delegator voluntarily for
+/// boosted voting weight in appchain referenda.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DelegationLock {
+    /// The era in which the lock is made.
+    pub lock_era: U64,
+    /// The delegation is locked before this era.
+    pub unlock_era: U64,
+    /// The boost (in percent) of the voting weight of the delegation while it is locked.
+    pub voting_boost_percent: u16,
+}
+
+impl DelegationLock {
+    /// Whether the delegation is locked in a certain era.
+    pub fn is_locked_in(&self, era_number: u64) -> bool {
+        self.lock_era.0 <= era_number && era_number < self.unlock_era.0
+    }
+}
+
+/// The intent of a delegator to change its delegation to a validator, which can be
+/// executed by anyone (e.g. a keeper) once the condition becomes true, before it expires.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StakeChangeIntent {
+    pub delegator_id: AccountId,
+    pub validator_id: AccountId,
+    pub action: StakeChangeAction,
+    pub condition: StakeChangeCondition,
+    pub submitted_timestamp: U64,
+    pub expiry_timestamp: U64,
+}
+
+/// The kinds of outbound transfers of withdrawals.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum WithdrawalKind {
+    /// The unbonded stake (in OCT token) of a validator or delegator.
+    Stake,
+    /// The unbonded stake (in OCT token) withdrawn instantly, with penalty.
+    FastUnbondedStake,
+    /// The unbonded stake in secondary stake token of a validator.
+    SecondaryStake,
+    /// The reward (in wrapped appchain token) of a validator, including vested reward.
+    ValidatorReward,
+    /// The commission (in wrapped appchain token) of a validator paid to its payout account.
+    Commission,
+    /// The reward (in wrapped appchain token) of a delegator.
+    DelegatorReward,
+    /// The tip (in wrapped appchain token) to the third party who withdraws the reward
+    /// of a delegator.
+    RewardClaimTip,
+    /// The NEAR fungible token unlocked by an appchain message.
+    BridgeUnlock,
+}
+
+/// The status of an outbound transfer of withdrawal.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum WithdrawalReceiptStatus {
+    /// The transfer is sent, and the result is not resolved yet.
+    Pending,
+    /// The transfer succeeded.
+    Succeeded,
+    /// The transfer failed, and the amount is held by this contract.
+    Failed,
+    /// The amount of a failed transfer has been refunded to the account.
+    Refunded,
+}
+
+/// The receipt of an outbound transfer of withdrawal, the index of it is the receipt id.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WithdrawalReceipt {
+    pub kind: WithdrawalKind,
+    /// The account whose asset is withdrawn.
+    pub account_id: AccountId,
+    /// The account which receives the transfer.
+    pub receiver_id: AccountId,
+    /// The contract account of the transferred token.
+    pub token_contract: AccountId,
+    pub amount: U128,
+    pub status: WithdrawalReceiptStatus,
+    pub created_timestamp: U64,
+    /// The timestamp of the latest change of the status.
+    pub updated_timestamp: U64,
+    pub index: U64,
+}
+
+/// The fact of a slash applied for an offence of a validator reported by the appchain.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SlashFact {
+    pub validator_id: AccountId,
+    pub validator_id_in_appchain: String,
+    /// The kind of the offence, e.g. `equivocation`.
+    pub offence_kind: String,
+    /// The era in which the offence is committed.
+    pub era_number: U64,
+    /// The nonce of the appchain message which reports the offence.
+    pub appchain_message_nonce: u32,
+    /// The amount slashed from the deposit of the validator.
+    pub validator_slashed_amount: U128,
+    /// The total amount slashed from the delegations of the delegators of the validator.
+    pub delegator_slashed_amount: U128,
+    pub slashed_delegator_count: U64,
+}
+
+/// The status of the application of a slash.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum SlashStatus {
+    /// The delegators of the validator at the time of the slash are being slashed,
+    /// which may take several function calls.
+    SlashingDelegators {
+        pending_delegator_ids: Vec<AccountId>,
+    },
+    Completed,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SlashHistory {
+    pub slash_fact: SlashFact,
+    pub status: SlashStatus,
+    pub block_height: U64,
+    pub timestamp: U64,
+    pub index: U64,
+}
+
+/// The sources of the amounts (of fees and penalties) which are transferred to the treasury.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TreasurySource {
+    /// The penalty (in OCT token) of the fast withdrawal of unbonded stake.
+    FastUnbondPenalty,
+    /// The slashed stake (in OCT token) of a settled penalty escrow.
+    SettledPenaltyEscrow,
+    /// The slashed bond (in OCT token) of a relayer.
+    RelayerBondSlash,
+    /// The remainder (in wrapped appchain token) of the reward of an era
+    /// which is not distributed.
+    RewardRemainder,
+}
+
+/// A destination of the amounts of a treasury source, with the percent of them.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TreasuryRoute {
+    pub destination: AccountId,
+    pub percent: u16,
+}
+
+/// The accumulated amount of a token transferred from a treasury source to a destination
+/// in an era. The destination `None` means the amount is burnt (kept in this contract).
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TreasuryFlow {
+    pub source: TreasurySource,
+    pub token_contract: AccountId,
+    pub destination: Option<AccountId>,
+    pub amount: U128,
+}
+
+/// The projection of the stake which a validator will carry into the next era.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProjectedStakeOfValidator {
+    pub validator_id: AccountId,
+    /// The total stake of the validator in the next validator set at present.
+    pub total_stake: U128,
+    /// Whether the validator is unbonding (or auto-unbonding), in which case
+    /// the projected stake is zero.
+    pub is_unbonding: bool,
+    /// The count of the reported offences of the validator whose slashes
+    /// are not applied (or not completely applied) yet.
+    pub pending_slash_count: u32,
+    /// The estimated amount of the pending slashes, with the current slash percents.
+    pub pending_slash_amount: U128,
+    /// The amount of the delegations to be decreased by the stake change intents
+    /// whose conditions are met.
+    pub pending_intent_decrease_amount: U128,
+    pub projected_total_stake: U128,
+}
+
+/// The progress of winding down a terminating appchain.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AppchainTerminationStatus {
+    /// Recording the unbonded stakes of the staking histories in range
+    /// `[applying_index, end_index]`, which are recorded after the start of the final era.
+    ApplyingStakingHistory { applying_index: U64, end_index: U64 },
+    /// Unbonding all validators and delegators in the next validator set.
+    UnbondingStakes,
+    /// All stakes are unbonded and can be withdrawn.
+    WoundDown,
+}
+
+/// The record of the termination of an appchain, including the summary of the final state
+/// of the appchain at the time of termination.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AppchainTermination {
+    pub reason: String,
+    /// The latest era at the time of termination, no era is switched after it.
+    pub final_era_number: U64,
+    /// The staged appchain messages after this nonce are dropped.
+    pub latest_applied_appchain_message_nonce: u32,
+    pub validator_count: U64,
+    pub delegator_count: U64,
+    /// The total stake in the next validator set.
+    pub total_stake: U128,
+    /// The total amount of the unbonded stakes which are not withdrawn yet.
+    pub total_unbonded_stake: U128,
+    pub terminated_block_height: U64,
+    pub terminated_timestamp: U64,
+    pub status: AppchainTerminationStatus,
+}
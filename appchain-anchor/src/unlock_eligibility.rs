@@ -0,0 +1,380 @@
+use crate::*;
+
+/// An unbonded stake of an account, along with whether it is unlocked (can be withdrawn)
+/// at the current block time.
+pub struct UnbondedStakeEntry {
+    pub reference: UnbondedStakeReference,
+    pub unbonded_stake: UnbondedStake,
+    pub is_unlocked: bool,
+}
+
+/// The unbonded stakes of an account, in the order of their references.
+pub struct UnbondedStakesEligibility {
+    pub entries: Vec<UnbondedStakeEntry>,
+}
+
+impl UnbondedStakesEligibility {
+    ///
+    pub fn unlocked_amount(&self) -> Balance {
+        self.entries
+            .iter()
+            .filter(|entry| entry.is_unlocked)
+            .map(|entry| entry.unbonded_stake.amount.0)
+            .sum()
+    }
+    ///
+    pub fn locked_amount(&self) -> Balance {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.is_unlocked)
+            .map(|entry| entry.unbonded_stake.amount.0)
+            .sum()
+    }
+    ///
+    pub fn locked_references(&self) -> Vec<UnbondedStakeReference> {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.is_unlocked)
+            .map(|entry| entry.reference.clone())
+            .collect()
+    }
+    ///
+    pub fn into_unbonded_stakes(self) -> Vec<UnbondedStake> {
+        self.entries
+            .into_iter()
+            .map(|entry| entry.unbonded_stake)
+            .collect()
+    }
+}
+
+impl AppchainAnchor {
+    // Get the unlock time of the stake of a certain class which is unbonded in a certain era,
+    // with the protocol settings which are effective in that era.
+    pub fn get_unlock_time_of_unbonded_stake(
+        &self,
+        era_number: u64,
+        stake_class: &StakeClass,
+    ) -> Timestamp {
+        let validator_set = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .get(&era_number)
+            .unwrap();
+        validator_set.start_timestamp()
+            + self.time_provider().nanoseconds_of_days(
+                self.get_effective_protocol_settings_of(era_number)
+                    .unlock_period_of(stake_class),
+            )
+    }
+    // Evaluate the unbonded stakes (in OCT token) of an account at the current block time.
+    pub fn evaluate_unbonded_stakes_of(&self, account_id: &AccountId) -> UnbondedStakesEligibility {
+        self.evaluate_unbonded_stake_references(
+            &self.unbonded_stakes.get(account_id).unwrap_or_default(),
+            |staking_fact| staking_fact.unbonded_stake(),
+        )
+    }
+    // Evaluate the unbonded stakes (in secondary stake token) of an account at the
+    // current block time. They have the same unlock period as the validator principal.
+    pub fn evaluate_unbonded_secondary_stakes_of(
+        &self,
+        account_id: &AccountId,
+    ) -> UnbondedStakesEligibility {
+        self.evaluate_unbonded_stake_references(
+            &self
+                .unbonded_secondary_stakes
+                .get(account_id)
+                .unwrap_or_default(),
+            |staking_fact| {
+                staking_fact
+                    .unbonded_secondary_stake()
+                    .map(|(account_id, amount)| {
+                        (account_id, StakeClass::ValidatorPrincipal, amount)
+                    })
+            },
+        )
+    }
+    //
+    fn evaluate_unbonded_stake_references<F>(
+        &self,
+        references: &Vec<UnbondedStakeReference>,
+        unbonded_stake_of: F,
+    ) -> UnbondedStakesEligibility
+    where
+        F: Fn(&StakingFact) -> Option<(AccountId, StakeClass, U128)>,
+    {
+        let staking_histories = self.staking_histories.get().unwrap();
+        let mut eligibility = UnbondedStakesEligibility {
+            entries: Vec::new(),
+        };
+        references.iter().for_each(|reference| {
+            let staking_history = staking_histories
+                .get(&reference.staking_history_index)
+                .unwrap();
+            if let Some((account_id, stake_class, amount)) =
+                unbonded_stake_of(&staking_history.staking_fact)
+            {
+                let unlock_time =
+                    self.get_unlock_time_of_unbonded_stake(reference.era_number, &stake_class);
+                let unbonded_stake = UnbondedStake {
+                    era_number: U64::from(reference.era_number),
+                    account_id,
+                    amount,
+                    unlock_time: U64::from(unlock_time),
+                };
+                eligibility.entries.push(UnbondedStakeEntry {
+                    reference: reference.clone(),
+                    unbonded_stake,
+                    is_unlocked: self.appchain_state == AppchainState::Terminating
+                        || unlock_time < env::block_timestamp(),
+                });
+            }
+        });
+        eligibility
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    const START_TIMESTAMP: Timestamp = 1_000_000_000_000;
+
+    fn set_block_timestamp(timestamp: Timestamp) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(accounts(0))
+            .block_timestamp(timestamp)
+            .build());
+    }
+
+    fn days(count: u64) -> Timestamp {
+        count * SECONDS_OF_A_DAY * NANO_SECONDS_MULTIPLE
+    }
+
+    // The unlock periods of validator principal, delegator principal and slashed remainder
+    // are 3, 1 and 5 days, and era 0 starts at `START_TIMESTAMP`.
+    fn setup_anchor() -> AppchainAnchor {
+        set_block_timestamp(START_TIMESTAMP);
+        let mut anchor = AppchainAnchor::new(
+            "test-appchain".to_string(),
+            AppchainTemplateType::Barnacle,
+            accounts(1),
+            accounts(2),
+        );
+        let mut protocol_settings = anchor.protocol_settings.get().unwrap();
+        protocol_settings.unlock_period_of_validator_deposit = U64::from(3);
+        protocol_settings.unlock_period_of_delegator_deposit = U64::from(1);
+        protocol_settings.unlock_period_of_slashed_remainder_stake = U64::from(5);
+        anchor.protocol_settings.set(&protocol_settings);
+        start_era(&mut anchor, 0);
+        anchor
+    }
+
+    fn start_era(anchor: &mut AppchainAnchor, era_number: u64) {
+        let mut validator_set_histories = anchor.validator_set_histories.get().unwrap();
+        validator_set_histories.insert(&era_number, &ValidatorSetOfEra::new(era_number, 0));
+        anchor.validator_set_histories.set(&validator_set_histories);
+    }
+
+    fn unbond(
+        anchor: &mut AppchainAnchor,
+        era_number: u64,
+        account_id: &AccountId,
+        staking_fact: StakingFact,
+    ) -> UnbondedStakeReference {
+        let staking_history = anchor.record_staking_fact(staking_fact);
+        let reference = UnbondedStakeReference {
+            era_number,
+            staking_history_index: staking_history.index.0,
+        };
+        let mut references = anchor.unbonded_stakes.get(account_id).unwrap_or_default();
+        references.push(reference.clone());
+        anchor.unbonded_stakes.insert(account_id, &references);
+        reference
+    }
+
+    #[test]
+    fn test_unlock_time_of_each_stake_class() {
+        let anchor = setup_anchor();
+        for (stake_class, unlock_period) in [
+            (StakeClass::ValidatorPrincipal, 3),
+            (StakeClass::DelegatorPrincipal, 1),
+            (StakeClass::VestingLocked, 3),
+            (StakeClass::SlashedRemainder, 5),
+        ] {
+            assert_eq!(
+                anchor.get_unlock_time_of_unbonded_stake(0, &stake_class),
+                START_TIMESTAMP + days(unlock_period)
+            );
+        }
+    }
+
+    #[test]
+    fn test_unbonded_stakes_of_each_stake_class() {
+        let mut anchor = setup_anchor();
+        let (validator_id, delegator_id, depositor_id) = (accounts(3), accounts(4), accounts(5));
+        unbond(
+            &mut anchor,
+            0,
+            &validator_id,
+            StakingFact::StakeDecreased {
+                validator_id: validator_id.clone(),
+                amount: U128::from(100),
+            },
+        );
+        unbond(
+            &mut anchor,
+            0,
+            &delegator_id,
+            StakingFact::DelegationDecreased {
+                delegator_id: delegator_id.clone(),
+                validator_id: validator_id.clone(),
+                amount: U128::from(200),
+            },
+        );
+        unbond(
+            &mut anchor,
+            0,
+            &depositor_id,
+            StakingFact::VestingLockedStakeUnbonded {
+                validator_id: validator_id.clone(),
+                depositor_id: depositor_id.clone(),
+                amount: U128::from(300),
+            },
+        );
+        // Only the delegator principal is unlocked after 1 day.
+        set_block_timestamp(START_TIMESTAMP + days(1) + 1);
+        for (account_id, unlocked_amount, locked_amount) in [
+            (&validator_id, 0, 100),
+            (&delegator_id, 200, 0),
+            (&depositor_id, 0, 300),
+        ] {
+            let eligibility = anchor.evaluate_unbonded_stakes_of(account_id);
+            assert_eq!(eligibility.entries.len(), 1);
+            assert_eq!(eligibility.unlocked_amount(), unlocked_amount);
+            assert_eq!(eligibility.locked_amount(), locked_amount);
+        }
+        // The validator principal and the vesting-locked stake are unlocked after 3 days.
+        set_block_timestamp(START_TIMESTAMP + days(3) + 1);
+        for (account_id, amount) in [
+            (&validator_id, 100),
+            (&delegator_id, 200),
+            (&depositor_id, 300),
+        ] {
+            let eligibility = anchor.evaluate_unbonded_stakes_of(account_id);
+            assert_eq!(eligibility.unlocked_amount(), amount);
+            assert!(eligibility.locked_references().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_unbonded_stake_at_boundary_of_unlock_period() {
+        let mut anchor = setup_anchor();
+        let validator_id = accounts(3);
+        unbond(
+            &mut anchor,
+            0,
+            &validator_id,
+            StakingFact::ValidatorUnbonded {
+                validator_id: validator_id.clone(),
+                amount: U128::from(100),
+            },
+        );
+        // The stake is still locked at the exact unlock time.
+        set_block_timestamp(START_TIMESTAMP + days(3));
+        let eligibility = anchor.evaluate_unbonded_stakes_of(&validator_id);
+        assert!(!eligibility.entries[0].is_unlocked);
+        assert_eq!(
+            eligibility.entries[0].unbonded_stake.unlock_time.0,
+            START_TIMESTAMP + days(3)
+        );
+        set_block_timestamp(START_TIMESTAMP + days(3) + 1);
+        let eligibility = anchor.evaluate_unbonded_stakes_of(&validator_id);
+        assert!(eligibility.entries[0].is_unlocked);
+    }
+
+    #[test]
+    fn test_mixed_locked_and_unlocked_unbonded_stakes() {
+        let mut anchor = setup_anchor();
+        let (account_id, validator_id) = (accounts(3), accounts(4));
+        // In era 0, the account unbonds a delegation (1 day) and its own stake (3 days).
+        unbond(
+            &mut anchor,
+            0,
+            &account_id,
+            StakingFact::DelegatorUnbonded {
+                delegator_id: account_id.clone(),
+                validator_id: validator_id.clone(),
+                amount: U128::from(100),
+            },
+        );
+        let validator_reference = unbond(
+            &mut anchor,
+            0,
+            &account_id,
+            StakingFact::StakeDecreased {
+                validator_id: account_id.clone(),
+                amount: U128::from(200),
+            },
+        );
+        // In era 1, which starts 1 day later, the account unbonds another delegation.
+        set_block_timestamp(START_TIMESTAMP + days(1));
+        start_era(&mut anchor, 1);
+        let era_1_reference = unbond(
+            &mut anchor,
+            1,
+            &account_id,
+            StakingFact::DelegationDecreased {
+                delegator_id: account_id.clone(),
+                validator_id: validator_id.clone(),
+                amount: U128::from(400),
+            },
+        );
+        set_block_timestamp(START_TIMESTAMP + days(2));
+        let eligibility = anchor.evaluate_unbonded_stakes_of(&account_id);
+        assert_eq!(
+            eligibility
+                .entries
+                .iter()
+                .map(|entry| entry.is_unlocked)
+                .collect::<Vec<bool>>(),
+            vec![true, false, false]
+        );
+        assert_eq!(eligibility.unlocked_amount(), 100);
+        assert_eq!(eligibility.locked_amount(), 600);
+        let locked_references = eligibility.locked_references();
+        assert_eq!(locked_references.len(), 2);
+        for (locked_reference, expected_reference) in locked_references
+            .iter()
+            .zip([validator_reference, era_1_reference])
+        {
+            assert_eq!(locked_reference.era_number, expected_reference.era_number);
+            assert_eq!(
+                locked_reference.staking_history_index,
+                expected_reference.staking_history_index
+            );
+        }
+    }
+
+    #[test]
+    fn test_unbonded_stakes_of_terminating_appchain() {
+        let mut anchor = setup_anchor();
+        let validator_id = accounts(3);
+        unbond(
+            &mut anchor,
+            0,
+            &validator_id,
+            StakingFact::StakeDecreased {
+                validator_id: validator_id.clone(),
+                amount: U128::from(100),
+            },
+        );
+        // The unbonded stakes are unlocked at once when the appchain is terminating.
+        anchor.appchain_state = AppchainState::Terminating;
+        let eligibility = anchor.evaluate_unbonded_stakes_of(&validator_id);
+        assert_eq!(eligibility.unlocked_amount(), 100);
+        assert!(eligibility.locked_references().is_empty());
+    }
+}
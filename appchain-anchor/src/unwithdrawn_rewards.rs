@@ -0,0 +1,139 @@
+use crate::*;
+
+impl AppchainAnchor {
+    // Add the reference to the unwithdrawn reward of an account in a certain era,
+    // if it is not referenced yet.
+    pub fn add_unwithdrawn_reward_reference(
+        &mut self,
+        account_id: &AccountId,
+        reference: UnwithdrawnRewardReference,
+    ) {
+        let mut references = self
+            .unwithdrawn_reward_references
+            .get(account_id)
+            .unwrap_or_default();
+        if !references.contains(&reference) {
+            references.push(reference);
+            self.unwithdrawn_reward_references
+                .insert(account_id, &references);
+        }
+    }
+    // Take the eras in range `[start_era, end_era)` in which an account has unwithdrawn reward
+    // (as a validator if `validator_id` is `None`, or as a delegator of `validator_id`).
+    // The references before `start_era` are dropped, as the rewards are expired. The eras before
+    // `first_era_of_unwithdrawn_reward_references` are always included, as they are not referenced.
+    pub fn take_unwithdrawn_reward_eras_of(
+        &mut self,
+        account_id: &AccountId,
+        validator_id: Option<&AccountId>,
+        start_era: u64,
+        end_era: u64,
+    ) -> Vec<u64> {
+        let mut era_numbers: Vec<u64> =
+            (start_era..end_era.min(self.first_era_of_unwithdrawn_reward_references)).collect();
+        let mut references = self
+            .unwithdrawn_reward_references
+            .get(account_id)
+            .unwrap_or_default();
+        references.retain(|reference| {
+            if reference.validator_id.as_ref() != validator_id || reference.era_number >= end_era {
+                return true;
+            }
+            if reference.era_number >= start_era && !era_numbers.contains(&reference.era_number) {
+                era_numbers.push(reference.era_number);
+            }
+            false
+        });
+        if references.is_empty() {
+            self.unwithdrawn_reward_references.remove(account_id);
+        } else {
+            self.unwithdrawn_reward_references
+                .insert(account_id, &references);
+        }
+        era_numbers
+    }
+    // Get the unwithdrawn rewards of an account which are not expired, including those in
+    // the eras which are not referenced.
+    pub fn calculate_unwithdrawn_rewards_of(
+        &self,
+        account_id: &AccountId,
+    ) -> Vec<UnwithdrawnReward> {
+        let end_era = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .index_range()
+            .end_index
+            .0;
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let start_era = match end_era > protocol_settings.maximum_era_count_of_unwithdrawn_reward.0
+        {
+            true => end_era - protocol_settings.maximum_era_count_of_unwithdrawn_reward.0 + 1,
+            false => 0,
+        };
+        // The eras before `first_era_of_unwithdrawn_reward_references` are not referenced,
+        // so the rewards in them are looked up by the validator set of each era.
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let mut references = Vec::new();
+        for era_number in
+            start_era..(end_era + 1).min(self.first_era_of_unwithdrawn_reward_references)
+        {
+            if let Some(validator_set) = validator_set_histories.get(&era_number) {
+                references.push(UnwithdrawnRewardReference {
+                    era_number,
+                    validator_id: None,
+                });
+                validator_set
+                    .get_validator_ids_of(account_id)
+                    .into_iter()
+                    .for_each(|validator_id| {
+                        references.push(UnwithdrawnRewardReference {
+                            era_number,
+                            validator_id: Some(validator_id),
+                        })
+                    });
+            }
+        }
+        self.unwithdrawn_reward_references
+            .get(account_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|reference| reference.era_number >= start_era)
+            .for_each(|reference| {
+                if !references.contains(&reference) {
+                    references.push(reference);
+                }
+            });
+        references
+            .into_iter()
+            .map(|reference| {
+                let (reward, commission) = match &reference.validator_id {
+                    Some(validator_id) => (
+                        self.unwithdrawn_delegator_rewards
+                            .get(&(
+                                reference.era_number,
+                                account_id.clone(),
+                                validator_id.clone(),
+                            ))
+                            .unwrap_or(0),
+                        0,
+                    ),
+                    None => {
+                        let key = (reference.era_number, account_id.clone());
+                        (
+                            self.unwithdrawn_validator_rewards.get(&key).unwrap_or(0),
+                            self.unwithdrawn_commission_rewards.get(&key).unwrap_or(0),
+                        )
+                    }
+                };
+                UnwithdrawnReward {
+                    era_number: U64::from(reference.era_number),
+                    validator_id: reference.validator_id,
+                    reward: U128::from(reward),
+                    commission: U128::from(commission),
+                }
+            })
+            .filter(|reward| reward.reward.0 > 0 || reward.commission.0 > 0)
+            .collect()
+    }
+}
@@ -36,8 +36,11 @@ pub extern "C" fn store_wasm_of_self() {
 #[no_mangle]
 pub fn update_self() {
     env::setup_panic_hook();
-    let contract: AppchainAnchor = env::state_read().expect("ERR_CONTRACT_IS_NOT_INITIALIZED");
+    let mut contract: AppchainAnchor = env::state_read().expect("ERR_CONTRACT_IS_NOT_INITIALIZED");
     contract.assert_owner();
+    // Reject all state-mutating actions until the migration is completed.
+    contract.maintenance_mode_is_on = true;
+    env::state_write(&contract);
     let current_id = env::current_account_id();
     let input = env::storage_read(&StorageKey::AnchorContractWasm.into_bytes())
         .expect("Wasm file for deployment is not staged yet.");
@@ -7,6 +7,7 @@ use crate::{
 impl AppchainLifecycleManager for AppchainAnchor {
     //
     fn go_booting(&mut self) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert_eq!(
             self.appchain_state,
@@ -30,7 +31,12 @@ impl AppchainLifecycleManager for AppchainAnchor {
             self.permissionless_actions_status.get().unwrap(),
         );
         let mut validator_set_histories = self.validator_set_histories.get().unwrap();
-        self.internal_start_switching_era(&mut processing_context, &mut validator_set_histories, 0);
+        self.internal_start_switching_era(
+            &mut processing_context,
+            &mut validator_set_histories,
+            0,
+            0,
+        );
         loop {
             match self.complete_switching_era(
                 &mut processing_context,
@@ -49,6 +55,7 @@ impl AppchainLifecycleManager for AppchainAnchor {
     }
     //
     fn go_live(&mut self) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert_eq!(
             self.appchain_state,
@@ -76,6 +83,7 @@ impl AppchainLifecycleManager for AppchainAnchor {
     }
     //
     fn initialize_beefy_light_client(&mut self, initial_public_keys: Vec<String>) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert_eq!(
             self.appchain_state,
@@ -89,4 +97,50 @@ impl AppchainLifecycleManager for AppchainAnchor {
         self.beefy_light_client_state
             .set(&beefy_light_client::new(initial_public_keys));
     }
+    //
+    fn allocate_bootstrap_balances(&mut self, allocations: Vec<(AccountId, U128)>) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert_eq!(
+            self.appchain_state,
+            AppchainState::Booting,
+            "Appchain state must be 'booting'."
+        );
+        let appchain_settings = self.appchain_settings.get().unwrap();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        for (validator_id, amount) in allocations {
+            self.assert_validator_id(&validator_id, &next_validator_set);
+            assert!(
+                !self.bootstrap_allocations.contains_key(&validator_id),
+                "Bootstrap balance has already been allocated to validator '{}'.",
+                &validator_id
+            );
+            assert!(
+                amount.0 > 0,
+                "The amount of allocation must be greater than 0."
+            );
+            assert!(
+                self.total_bootstrap_allocation + amount.0 <= appchain_settings.bootstrap_budget.0,
+                "Bootstrap budget exceeded."
+            );
+            let validator = next_validator_set.get_validator(&validator_id).unwrap();
+            self.bootstrap_allocations.insert(&validator_id, &amount.0);
+            self.total_bootstrap_allocation += amount.0;
+            self.internal_append_appchain_notification(
+                AppchainNotification::BootstrapBalanceAllocated {
+                    validator_id,
+                    receiver_id_in_appchain: validator
+                        .reward_address_in_appchain
+                        .unwrap_or(validator.validator_id_in_appchain),
+                    amount,
+                },
+            );
+        }
+    }
+    //
+    fn go_terminating(&mut self, reason: String) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        self.internal_go_terminating(reason);
+    }
 }
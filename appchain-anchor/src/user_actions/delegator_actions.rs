@@ -0,0 +1,229 @@
+use crate::{interfaces::DelegatorActions, *};
+use near_contract_standards::fungible_token::core::ext_ft_core;
+
+/// The maximum length (in bytes) of the label of a delegation.
+const MAXIMUM_LENGTH_OF_DELEGATION_LABEL: usize = 64;
+/// The maximum duration (in days) before a stake change intent expires.
+const MAXIMUM_DAYS_OF_STAKE_CHANGE_INTENT: u64 = 180;
+/// The maximum percent of the rewards which is tipped to the third party
+/// who withdraws the rewards of a delegator.
+const MAXIMUM_PERCENT_OF_REWARD_CLAIM_TIP: u16 = 5;
+
+#[near_bindgen]
+impl DelegatorActions for AppchainAnchor {
+    //
+    fn set_delegation_label(&mut self, validator_id: AccountId, label: Option<String>) {
+        self.assert_not_in_maintenance_mode();
+        let delegator_id = env::predecessor_account_id();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_delegator_id(&delegator_id, &validator_id, &next_validator_set);
+        match label {
+            Some(label) => {
+                assert!(
+                    !label.trim().is_empty(),
+                    "The label of delegation should not be empty."
+                );
+                assert!(
+                    label.len() <= MAXIMUM_LENGTH_OF_DELEGATION_LABEL,
+                    "The label of delegation should not be longer than {} bytes.",
+                    MAXIMUM_LENGTH_OF_DELEGATION_LABEL
+                );
+                self.delegation_labels
+                    .insert(&(delegator_id, validator_id), &label);
+            }
+            None => {
+                self.delegation_labels.remove(&(delegator_id, validator_id));
+            }
+        }
+    }
+    //
+    fn set_fallback_validator(&mut self, validator_id: Option<AccountId>) {
+        self.assert_not_in_maintenance_mode();
+        let delegator_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&delegator_id);
+        match validator_id {
+            Some(validator_id) => {
+                let next_validator_set = self.next_validator_set.get().unwrap();
+                self.assert_validator_id(&validator_id, &next_validator_set);
+                assert!(
+                    next_validator_set
+                        .get_validator(&validator_id)
+                        .unwrap()
+                        .can_be_delegated_to,
+                    "Validator '{}' cannot be delegated to.",
+                    &validator_id
+                );
+                assert!(
+                    !self.validator_retirements.contains_key(&validator_id),
+                    "Validator '{}' is retiring.",
+                    &validator_id
+                );
+                self.delegator_fallback_validators
+                    .insert(&delegator_id, &validator_id);
+            }
+            None => {
+                self.delegator_fallback_validators.remove(&delegator_id);
+            }
+        }
+    }
+    //
+    fn claim_insurance_compensation(&mut self, event_index: U64) {
+        self.assert_not_in_maintenance_mode();
+        let delegator_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&delegator_id);
+        self.assert_asset_transfer_is_not_paused();
+        self.assert_contract_account_of_wrapped_appchain_token_is_set();
+        let mut insurance_claim_events = self.insurance_claim_events.get().unwrap();
+        let mut event = insurance_claim_events
+            .get(&event_index.0)
+            .expect("Insurance claim event is not existed.");
+        assert!(
+            !event.is_closed && env::block_timestamp() <= event.claim_deadline.0,
+            "The claim window of the event is over."
+        );
+        let compensation = self
+            .insurance_compensations
+            .remove(&(event_index.0, delegator_id.clone()))
+            .expect("No compensation to claim.");
+        event.claimed_amount = U128::from(event.claimed_amount.0 + compensation);
+        insurance_claim_events.insert(&event_index.0, &event);
+        self.insurance_claim_events.set(&insurance_claim_events);
+        //
+        ext_ft_core::ext(
+            self.wrapped_appchain_token
+                .get()
+                .unwrap()
+                .contract_account
+                .unwrap(),
+        )
+        .with_attached_deposit(1)
+        .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+        .with_unused_gas_weight(0)
+        .ft_transfer(delegator_id, compensation.into(), None);
+    }
+    //
+    fn submit_stake_change_intent(
+        &mut self,
+        validator_id: AccountId,
+        action: StakeChangeAction,
+        condition: StakeChangeCondition,
+        expiry_timestamp: U64,
+    ) {
+        self.assert_not_in_maintenance_mode();
+        let delegator_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&delegator_id);
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_delegator_id(&delegator_id, &validator_id, &next_validator_set);
+        assert!(
+            expiry_timestamp.0 > env::block_timestamp(),
+            "The expiry timestamp should be in the future."
+        );
+        let maximum_duration = self
+            .time_provider()
+            .nanoseconds_of_days(MAXIMUM_DAYS_OF_STAKE_CHANGE_INTENT);
+        assert!(
+            expiry_timestamp.0 <= env::block_timestamp() + maximum_duration,
+            "The stake change intent should not last for more than {} days.",
+            MAXIMUM_DAYS_OF_STAKE_CHANGE_INTENT
+        );
+        if let StakeChangeCondition::CommissionExceeds { percent } = condition {
+            assert!(percent < 100, "Invalid commission percent in condition.");
+        }
+        if let StakeChangeAction::DecreaseDelegation { amount } = action {
+            assert!(amount.0 > 0, "The amount to decrease should not be zero.");
+        }
+        self.stake_change_intents.insert(
+            &(delegator_id.clone(), validator_id.clone()),
+            &StakeChangeIntent {
+                delegator_id,
+                validator_id,
+                action,
+                condition,
+                submitted_timestamp: U64::from(env::block_timestamp()),
+                expiry_timestamp,
+            },
+        );
+    }
+    //
+    fn cancel_stake_change_intent(&mut self, validator_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
+        let delegator_id = env::predecessor_account_id();
+        assert!(
+            self.stake_change_intents
+                .remove(&(delegator_id, validator_id.clone()))
+                .is_some(),
+            "No stake change intent for validator '{}'.",
+            validator_id
+        );
+    }
+    //
+    fn lock_delegation(&mut self, validator_id: AccountId, era_count: U64) {
+        self.assert_not_in_maintenance_mode();
+        let delegator_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&delegator_id);
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_delegator_id(&delegator_id, &validator_id, &next_validator_set);
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            protocol_settings.maximum_delegation_lock_era_count.0 > 0,
+            "Delegation lock is not enabled."
+        );
+        assert!(
+            era_count.0 > 0 && era_count.0 <= protocol_settings.maximum_delegation_lock_era_count.0,
+            "The era count of delegation lock should be between 1 and {}.",
+            protocol_settings.maximum_delegation_lock_era_count.0
+        );
+        let latest_era = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .index_range()
+            .end_index
+            .0;
+        let unlock_era = latest_era + era_count.0;
+        let key = (delegator_id.clone(), validator_id.clone());
+        if let Some(lock) = self.delegation_locks.get(&key) {
+            assert!(
+                unlock_era > lock.unlock_era.0,
+                "The delegation is already locked until era '{}'.",
+                lock.unlock_era.0
+            );
+        }
+        self.record_staking_fact(StakingFact::DelegationLocked {
+            delegator_id,
+            validator_id,
+            unlock_era: U64::from(unlock_era),
+        });
+        self.delegation_locks.insert(
+            &key,
+            &DelegationLock {
+                lock_era: U64::from(latest_era),
+                unlock_era: U64::from(unlock_era),
+                voting_boost_percent: u16::try_from(
+                    era_count.0
+                        * u64::from(protocol_settings.delegation_lock_voting_boost_percent_per_era),
+                )
+                .unwrap_or(u16::MAX),
+            },
+        );
+    }
+    //
+    fn set_reward_claim_tip_percent(&mut self, percent: Option<u16>) {
+        self.assert_not_in_maintenance_mode();
+        let delegator_id = env::predecessor_account_id();
+        match percent {
+            Some(percent) => {
+                assert!(
+                    percent > 0 && percent <= MAXIMUM_PERCENT_OF_REWARD_CLAIM_TIP,
+                    "The tip percent should be between 1 and {}.",
+                    MAXIMUM_PERCENT_OF_REWARD_CLAIM_TIP
+                );
+                self.reward_claim_tip_percents
+                    .insert(&delegator_id, &percent);
+            }
+            None => {
+                self.reward_claim_tip_percents.remove(&delegator_id);
+            }
+        }
+    }
+}
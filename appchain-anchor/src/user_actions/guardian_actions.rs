@@ -0,0 +1,48 @@
+use crate::interfaces::GuardianActions;
+use crate::*;
+
+#[near_bindgen]
+impl GuardianActions for AppchainAnchor {
+    //
+    fn freeze_account(&mut self, account_id: AccountId, reason: String, period_in_seconds: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_guardian();
+        assert!(
+            !reason.trim().is_empty(),
+            "The reason of freezing should not be empty."
+        );
+        assert!(
+            period_in_seconds.0 > 0,
+            "The freezing period should not be zero."
+        );
+        let frozen_timestamp = env::block_timestamp();
+        self.frozen_accounts.insert(
+            &account_id,
+            &AccountFreezing {
+                reason,
+                frozen_timestamp: U64::from(frozen_timestamp),
+                expiry_timestamp: U64::from(
+                    frozen_timestamp + period_in_seconds.0 * NANO_SECONDS_MULTIPLE,
+                ),
+            },
+        );
+    }
+    //
+    fn unfreeze_account(&mut self, account_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
+        let anchor_settings = self.anchor_settings.get().unwrap();
+        let predecessor_account_id = env::predecessor_account_id();
+        assert!(
+            predecessor_account_id.eq(&self.owner)
+                || anchor_settings
+                    .guardian_account
+                    .map_or(false, |account| predecessor_account_id.eq(&account)),
+            "Only the guardian or the owner can call this function."
+        );
+        assert!(
+            self.frozen_accounts.remove(&account_id).is_some(),
+            "Account '{}' is not frozen.",
+            account_id
+        );
+    }
+}
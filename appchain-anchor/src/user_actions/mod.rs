@@ -1,11 +1,18 @@
 use crate::*;
 
 mod appchain_lifecycle;
+mod delegator_actions;
+mod guardian_actions;
 mod owner_actions;
+mod peer_anchor_manager;
+mod relayer_bond_manager;
 mod settings_manager;
+mod stake_migration_manager;
 mod staking;
+mod staking_admin_actions;
 mod sudo_actions;
 mod validator_actions;
+mod withdrawal_allowlist;
 
 #[derive(BorshDeserialize, BorshSerialize, Clone)]
 pub struct UnbondedStakeReference {
@@ -14,3 +21,12 @@ pub struct UnbondedStakeReference {
     /// The index of corresponding `staking history`
     pub staking_history_index: u64,
 }
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, PartialEq)]
+pub struct UnwithdrawnRewardReference {
+    /// The number of era in appchain.
+    pub era_number: u64,
+    /// The validator of the delegation which the reward belongs to,
+    /// or `None` if the reward belongs to the account as a validator.
+    pub validator_id: Option<AccountId>,
+}
@@ -1,16 +1,23 @@
 use crate::{interfaces::OwnerActions, *};
+use borsh::maybestd::collections::HashMap;
 
 #[near_bindgen]
 impl OwnerActions for AppchainAnchor {
     //
     fn remove_validator_set_before(&mut self, era_number: U64) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut validator_set_histories = self.validator_set_histories.get().unwrap();
+        for index in validator_set_histories.index_range().start_index.0..era_number.0 {
+            self.protocol_settings_of_eras.remove(&index);
+            self.standby_validator_ids_of_eras.remove(&index);
+        }
         validator_set_histories.remove_before(&era_number.0);
         self.validator_set_histories.set(&validator_set_histories);
     }
     //
     fn remove_staking_history_before(&mut self, index: U64) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut staking_histories = self.staking_histories.get().unwrap();
         staking_histories.remove_before(&index.0);
@@ -18,11 +25,550 @@ impl OwnerActions for AppchainAnchor {
     }
     //
     fn remove_appchain_notification_history_before(&mut self, index: U64) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
+        assert!(
+            self.latest_acknowledged_appchain_notification_index
+                .map_or(false, |acknowledged_index| index.0
+                    <= acknowledged_index + 1),
+            "Only the appchain notifications acknowledged by appchain can be removed."
+        );
         let mut appchain_notification_histories =
             self.appchain_notification_histories.get().unwrap();
         appchain_notification_histories.remove_before(&index.0);
         self.appchain_notification_histories
             .set(&appchain_notification_histories);
     }
+    //
+    fn compact_staking_histories_of(
+        &mut self,
+        account_id: AccountId,
+    ) -> MultiTxsOperationProcessingResult {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let latest_era_number = validator_set_histories.index_range().end_index.0;
+        let era_count = protocol_settings
+            .minimum_era_count_for_compacting_staking_history
+            .0;
+        if latest_era_number < era_count {
+            return MultiTxsOperationProcessingResult::Ok;
+        }
+        let maximum_index = match validator_set_histories.get(&(latest_era_number - era_count)) {
+            Some(validator_set) => validator_set.staking_history_index(),
+            None => return MultiTxsOperationProcessingResult::Ok,
+        };
+        // The decreasing facts which are referenced by unbonded stakes can not be merged,
+        // as they are needed for withdrawing the unbonded stakes.
+        let referenced_indexes = self
+            .unbonded_stakes
+            .get(&account_id)
+            .unwrap_or_default()
+            .iter()
+            .map(|reference| reference.staking_history_index)
+            .collect::<Vec<u64>>();
+        let staking_history_indexes = self
+            .user_staking_histories
+            .get()
+            .unwrap()
+            .get_staking_history_indexes_of(&account_id);
+        let staking_histories = self.staking_histories.get().unwrap();
+        let mut run = Vec::<StakingHistory>::new();
+        for index in staking_history_indexes {
+            if index > maximum_index {
+                break;
+            }
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                return MultiTxsOperationProcessingResult::NeedMoreGas;
+            }
+            let staking_history = match staking_histories.get(&index) {
+                Some(staking_history) => staking_history,
+                None => continue,
+            };
+            let change = match referenced_indexes.contains(&index) {
+                true => None,
+                false => get_stake_change_of(&staking_history.staking_fact),
+            };
+            if let Some(last_staking_history) = run.last() {
+                let last_change = get_stake_change_of(&last_staking_history.staking_fact).unwrap();
+                if !change
+                    .as_ref()
+                    .map_or(false, |change| change.is_same_position_as(&last_change))
+                {
+                    self.merge_staking_histories(&account_id, &run);
+                    run.clear();
+                }
+            }
+            if change.is_some() {
+                run.push(staking_history);
+            }
+        }
+        self.merge_staking_histories(&account_id, &run);
+        MultiTxsOperationProcessingResult::Ok
+    }
+    //
+    fn record_appchain_runtime_upgrade_notice(
+        &mut self,
+        target_block_number: U64,
+        spec_version: u32,
+        notes: String,
+    ) -> U64 {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut appchain_runtime_upgrade_notices =
+            self.appchain_runtime_upgrade_notices.get().unwrap();
+        let index_range = appchain_runtime_upgrade_notices.index_range();
+        if let Some(latest_notice) = appchain_runtime_upgrade_notices.get(&index_range.end_index.0)
+        {
+            assert!(
+                spec_version > latest_notice.spec_version,
+                "The spec version should be bigger than '{}'.",
+                latest_notice.spec_version
+            );
+        }
+        let notice = appchain_runtime_upgrade_notices.append(&mut AppchainRuntimeUpgradeNotice {
+            target_block_number,
+            spec_version,
+            notes,
+            recorded_timestamp: U64::from(env::block_timestamp()),
+            index: U64::from(0),
+        });
+        self.appchain_runtime_upgrade_notices
+            .set(&appchain_runtime_upgrade_notices);
+        //
+        self.internal_append_appchain_notification(AppchainNotification::RuntimeUpgradeNoticed {
+            notice_index: notice.index,
+            target_block_number: notice.target_block_number,
+            spec_version: notice.spec_version,
+        });
+        notice.index
+    }
+    //
+    fn create_insurance_claim_event(
+        &mut self,
+        slashing_history_indexes: Vec<U64>,
+        coverage_percent: u16,
+        claim_window_in_days: U64,
+    ) -> U64 {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(
+            coverage_percent > 0 && coverage_percent <= 100,
+            "Invalid percent value."
+        );
+        assert!(
+            slashing_history_indexes.len() > 0,
+            "No slashing history is covered."
+        );
+        let mut insurance_claim_events = self.insurance_claim_events.get().unwrap();
+        let event_index = insurance_claim_events.next_index();
+        let staking_histories = self.staking_histories.get().unwrap();
+        let mut slashed_amounts = HashMap::<AccountId, Balance>::new();
+        let mut total_slashed_amount: Balance = 0;
+        for index in &slashing_history_indexes {
+            assert!(
+                !self.covered_slashing_histories.contains_key(&index.0),
+                "Staking history '{}' is already covered.",
+                index.0
+            );
+            let staking_history = staking_histories
+                .get(&index.0)
+                .expect("Staking history is not existed.");
+            match staking_history.staking_fact {
+                StakingFact::DelegatorSlashed {
+                    delegator_id,
+                    validator_id: _,
+                    amount,
+                } => {
+                    *slashed_amounts.entry(delegator_id).or_insert(0) += amount.0;
+                    total_slashed_amount += amount.0;
+                }
+                _ => panic!(
+                    "Staking history '{}' is not a slashing of delegator.",
+                    index.0
+                ),
+            }
+            self.covered_slashing_histories
+                .insert(&index.0, &event_index);
+        }
+        let coverage = self.insurance_pool_balance * u128::from(coverage_percent) / 100;
+        let mut compensation_amount: Balance = 0;
+        for (delegator_id, slashed_amount) in slashed_amounts {
+            let compensation = coverage * slashed_amount / total_slashed_amount;
+            if compensation > 0 {
+                self.insurance_compensations
+                    .insert(&(event_index, delegator_id), &compensation);
+                compensation_amount += compensation;
+            }
+        }
+        assert!(
+            compensation_amount > 0,
+            "The insurance pool is not enough for the compensation."
+        );
+        self.insurance_pool_balance -= compensation_amount;
+        let event = insurance_claim_events.append(&mut InsuranceClaimEvent {
+            slashing_history_indexes,
+            total_slashed_amount: U128::from(total_slashed_amount),
+            coverage_percent,
+            compensation_amount: U128::from(compensation_amount),
+            claimed_amount: U128::from(0),
+            claim_deadline: U64::from(
+                env::block_timestamp()
+                    + self
+                        .time_provider()
+                        .nanoseconds_of_days(claim_window_in_days.0),
+            ),
+            is_closed: false,
+            index: U64::from(0),
+        });
+        self.insurance_claim_events.set(&insurance_claim_events);
+        event.index
+    }
+    //
+    fn close_insurance_claim_event(&mut self, index: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut insurance_claim_events = self.insurance_claim_events.get().unwrap();
+        let mut event = insurance_claim_events
+            .get(&index.0)
+            .expect("Insurance claim event is not existed.");
+        assert!(!event.is_closed, "The event is already closed.");
+        assert!(
+            env::block_timestamp() > event.claim_deadline.0,
+            "The claim window of the event is not over."
+        );
+        self.insurance_pool_balance += event.compensation_amount.0 - event.claimed_amount.0;
+        event.is_closed = true;
+        insurance_claim_events.insert(&index.0, &event);
+        self.insurance_claim_events.set(&insurance_claim_events);
+    }
+    //
+    #[payable]
+    fn fund_relayer_reimbursement_pool(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let amount = env::attached_deposit();
+        assert!(amount > 0, "Attached deposit should not be 0.");
+        self.relayer_reimbursement_pool_balance += amount;
+    }
+    //
+    fn withdraw_from_relayer_reimbursement_pool(&mut self, amount: U128) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(
+            amount.0 > 0 && amount.0 <= self.relayer_reimbursement_pool_balance,
+            "Invalid amount to withdraw."
+        );
+        self.relayer_reimbursement_pool_balance -= amount.0;
+        Promise::new(env::predecessor_account_id()).transfer(amount.0);
+    }
+    //
+    fn set_accepted_payload_types_of_state(
+        &mut self,
+        appchain_state: AppchainState,
+        payload_types: Option<Vec<PayloadType>>,
+    ) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        match payload_types {
+            Some(payload_types) => {
+                self.accepted_payload_types_of_states
+                    .insert(&appchain_state, &payload_types);
+            }
+            None => {
+                self.accepted_payload_types_of_states
+                    .remove(&appchain_state);
+            }
+        }
+    }
+    //
+    fn set_appchain_message_handler_config(
+        &mut self,
+        payload_type: PayloadType,
+        is_enabled: bool,
+        gas_budget: Option<U64>,
+    ) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        if let Some(gas_budget) = gas_budget {
+            assert!(
+                gas_budget.0 <= T_GAS_CAP_FOR_PROCESSING_APPCHAIN_MESSAGES,
+                "The gas budget should not exceed {} TGas.",
+                T_GAS_CAP_FOR_PROCESSING_APPCHAIN_MESSAGES
+            );
+        }
+        self.appchain_message_handler_configs.insert(
+            &payload_type,
+            &AppchainMessageHandlerConfig {
+                is_enabled,
+                gas_budget,
+            },
+        );
+    }
+    //
+    fn set_validator_endpoints_visibility(&mut self, validator_id: AccountId, is_visible: bool) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut endpoints = self
+            .validator_endpoints
+            .get(&validator_id)
+            .expect("The validator has no endpoints.");
+        endpoints.is_hidden = !is_visible;
+        self.validator_endpoints.insert(&validator_id, &endpoints);
+    }
+    //
+    fn confirm_era_reward_distribution(&mut self, era_number: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut held_distribution = self.get_unconfirmed_era_reward_distribution(era_number.0);
+        held_distribution.is_confirmed = true;
+        self.held_era_reward_distributions
+            .insert(&era_number.0, &held_distribution);
+    }
+    //
+    fn reject_era_reward_distribution(&mut self, era_number: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let held_distribution = self.get_unconfirmed_era_reward_distribution(era_number.0);
+        self.held_era_reward_distributions.remove(&era_number.0);
+        self.record_appchain_message_processing_result(&AppchainMessageProcessingResult::Error {
+            nonce: held_distribution.appchain_message_nonce,
+            message: format!(
+                "Reward distribution of era '{}' is rejected by the owner.",
+                era_number.0
+            ),
+        });
+    }
+    //
+    fn propose_validator_set_override(&mut self, validator_ids: Vec<AccountId>) -> U64 {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(
+            self.get_pending_validator_set_override_proposal().is_none(),
+            "There is a pending proposal of validator set override."
+        );
+        assert!(
+            !validator_ids.is_empty(),
+            "The validator list should not be empty."
+        );
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        validator_ids
+            .iter()
+            .enumerate()
+            .for_each(|(index, validator_id)| {
+                assert!(
+                    !validator_ids[..index].contains(validator_id),
+                    "Duplicated validator '{}'.",
+                    validator_id
+                );
+                self.assert_validator_id(validator_id, &next_validator_set);
+            });
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let base_era_number = validator_set_histories.index_range().end_index.0;
+        let total_stake = validator_set_histories
+            .get(&base_era_number)
+            .map_or(0, |validator_set| validator_set.total_stake());
+        assert!(
+            total_stake > 0,
+            "The validator set of era '{}' is not ready for approving.",
+            base_era_number
+        );
+        let mut proposals = self.validator_set_override_proposals.get().unwrap();
+        let proposal = proposals.append(&mut ValidatorSetOverrideProposal {
+            validator_ids,
+            base_era_number: U64::from(base_era_number),
+            total_stake: U128::from(total_stake),
+            approvals: Vec::new(),
+            approved_stake: U128::from(0),
+            status: ValidatorSetOverrideStatus::Proposed,
+            proposed_timestamp: U64::from(env::block_timestamp()),
+            index: U64::from(0),
+        });
+        self.validator_set_override_proposals.set(&proposals);
+        proposal.index
+    }
+    //
+    fn cancel_validator_set_override(&mut self, proposal_index: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut proposal = self
+            .get_pending_validator_set_override_proposal()
+            .filter(|proposal| proposal.index.0 == proposal_index.0)
+            .expect("The proposal is not pending.");
+        proposal.status = ValidatorSetOverrideStatus::Cancelled;
+        let mut proposals = self.validator_set_override_proposals.get().unwrap();
+        proposals.insert(&proposal_index.0, &proposal);
+        self.validator_set_override_proposals.set(&proposals);
+    }
+    //
+    fn approve_appchain_setting_change(&mut self, proposal_index: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        self.internal_conclude_appchain_setting_change(proposal_index.0, true);
+    }
+    //
+    fn reject_appchain_setting_change(&mut self, proposal_index: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        self.internal_conclude_appchain_setting_change(proposal_index.0, false);
+    }
+}
+
+/// The change of stake (or delegation) of a staking fact.
+struct StakeChange {
+    delegator_id: Option<AccountId>,
+    validator_id: AccountId,
+    increased_amount: Balance,
+    decreased_amount: Balance,
+}
+
+impl StakeChange {
+    //
+    fn is_same_position_as(&self, other: &StakeChange) -> bool {
+        self.delegator_id.eq(&other.delegator_id) && self.validator_id.eq(&other.validator_id)
+    }
+}
+
+// Get the change of stake (or delegation) of a staking fact which can be merged.
+fn get_stake_change_of(staking_fact: &StakingFact) -> Option<StakeChange> {
+    match staking_fact {
+        StakingFact::StakeIncreased {
+            validator_id,
+            amount,
+        } => Some(StakeChange {
+            delegator_id: None,
+            validator_id: validator_id.clone(),
+            increased_amount: amount.0,
+            decreased_amount: 0,
+        }),
+        StakingFact::StakeDecreased {
+            validator_id,
+            amount,
+        } => Some(StakeChange {
+            delegator_id: None,
+            validator_id: validator_id.clone(),
+            increased_amount: 0,
+            decreased_amount: amount.0,
+        }),
+        StakingFact::DelegationIncreased {
+            delegator_id,
+            validator_id,
+            amount,
+        } => Some(StakeChange {
+            delegator_id: Some(delegator_id.clone()),
+            validator_id: validator_id.clone(),
+            increased_amount: amount.0,
+            decreased_amount: 0,
+        }),
+        StakingFact::DelegationDecreased {
+            delegator_id,
+            validator_id,
+            amount,
+        } => Some(StakeChange {
+            delegator_id: Some(delegator_id.clone()),
+            validator_id: validator_id.clone(),
+            increased_amount: 0,
+            decreased_amount: amount.0,
+        }),
+        _ => None,
+    }
+}
+
+impl AppchainAnchor {
+    //
+    fn get_unconfirmed_era_reward_distribution(
+        &self,
+        era_number: u64,
+    ) -> HeldEraRewardDistribution {
+        let held_distribution = self
+            .held_era_reward_distributions
+            .get(&era_number)
+            .expect("The reward distribution of the era is not held.");
+        assert!(
+            !held_distribution.is_confirmed,
+            "The reward distribution of the era is already confirmed."
+        );
+        held_distribution
+    }
+    // Merge a run of staking histories into the last one of them, and archive
+    // the hash of the original staking histories.
+    fn merge_staking_histories(&mut self, account_id: &AccountId, run: &Vec<StakingHistory>) {
+        if run.len() < 2 {
+            return;
+        }
+        let changes = run
+            .iter()
+            .map(|staking_history| get_stake_change_of(&staking_history.staking_fact).unwrap())
+            .collect::<Vec<StakeChange>>();
+        let increased_amount = changes
+            .iter()
+            .map(|change| change.increased_amount)
+            .sum::<Balance>();
+        let decreased_amount = changes
+            .iter()
+            .map(|change| change.decreased_amount)
+            .sum::<Balance>();
+        let last_staking_history = run.last().unwrap();
+        let validator_id = changes[0].validator_id.clone();
+        let staking_fact = match (
+            changes[0].delegator_id.clone(),
+            increased_amount >= decreased_amount,
+        ) {
+            (None, true) => StakingFact::StakeIncreased {
+                validator_id,
+                amount: U128::from(increased_amount - decreased_amount),
+            },
+            (None, false) => StakingFact::StakeDecreased {
+                validator_id,
+                amount: U128::from(decreased_amount - increased_amount),
+            },
+            (Some(delegator_id), true) => StakingFact::DelegationIncreased {
+                delegator_id,
+                validator_id,
+                amount: U128::from(increased_amount - decreased_amount),
+            },
+            (Some(delegator_id), false) => StakingFact::DelegationDecreased {
+                delegator_id,
+                validator_id,
+                amount: U128::from(decreased_amount - increased_amount),
+            },
+        };
+        let mut staking_histories = self.staking_histories.get().unwrap();
+        staking_histories.insert(
+            &last_staking_history.index.0,
+            &StakingHistory {
+                staking_fact,
+                block_height: last_staking_history.block_height,
+                timestamp: last_staking_history.timestamp,
+                index: last_staking_history.index,
+            },
+        );
+        let removed_indexes = run[..run.len() - 1]
+            .iter()
+            .map(|staking_history| staking_history.index.0)
+            .collect::<Vec<u64>>();
+        removed_indexes.iter().for_each(|index| {
+            staking_histories.remove_at(index);
+        });
+        self.staking_histories.set(&staking_histories);
+        //
+        let mut user_staking_histories = self.user_staking_histories.get().unwrap();
+        user_staking_histories.remove_staking_history_indexes_of(account_id, &removed_indexes);
+        self.user_staking_histories.set(&user_staking_histories);
+        //
+        self.compacted_staking_histories.insert(
+            &last_staking_history.index.0,
+            &CompactedStakingHistories {
+                indexes: run
+                    .iter()
+                    .map(|staking_history| staking_history.index)
+                    .collect(),
+                hash: format!(
+                    "0x{}",
+                    hex::encode(env::keccak256(&run.try_to_vec().unwrap()))
+                ),
+            },
+        );
+    }
 }
@@ -0,0 +1,129 @@
+use crate::{interfaces::PeerAnchorManager, *};
+
+/// The maximum number of peer anchors which can be registered.
+const MAXIMUM_COUNT_OF_PEER_ANCHORS: u64 = 16;
+
+#[near_bindgen]
+impl PeerAnchorManager for AppchainAnchor {
+    //
+    fn register_peer_anchor(&mut self, anchor_account: AccountId, appchain_id: AppchainId) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(
+            !anchor_account.eq(&env::current_account_id()),
+            "This contract can not be a peer anchor of itself."
+        );
+        assert!(
+            !appchain_id.eq(&self.appchain_id),
+            "The appchain id of peer anchor should be different with this appchain."
+        );
+        assert!(
+            self.peer_anchors.get(&anchor_account).is_none(),
+            "The peer anchor '{}' is already registered.",
+            anchor_account
+        );
+        assert!(
+            self.peer_anchors.len() < MAXIMUM_COUNT_OF_PEER_ANCHORS,
+            "Too many peer anchors."
+        );
+        self.peer_anchors.insert(
+            &anchor_account,
+            &PeerAnchor {
+                anchor_account: anchor_account.clone(),
+                appchain_id,
+                registered_timestamp: U64::from(env::block_timestamp()),
+                latest_commitment: None,
+                latest_commitment_timestamp: None,
+            },
+        );
+    }
+    //
+    fn remove_peer_anchor(&mut self, anchor_account: AccountId) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(
+            self.peer_anchors.remove(&anchor_account).is_some(),
+            "The peer anchor '{}' is not registered.",
+            anchor_account
+        );
+    }
+    //
+    fn publish_validator_set_commitment(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let validator_set = validator_set_histories
+            .get(&validator_set_histories.index_range().end_index.0)
+            .expect("Validator set is not existed.");
+        assert!(
+            validator_set
+                .processing_status()
+                .eq(&ValidatorSetProcessingStatus::Completed),
+            "The validator set of era '{}' is not completed.",
+            validator_set.era_number()
+        );
+        #[derive(near_sdk::serde::Serialize)]
+        #[serde(crate = "near_sdk::serde")]
+        struct Args {
+            commitment: ValidatorSetCommitment,
+        }
+        let args = near_sdk::serde_json::to_vec(&Args {
+            commitment: self.internal_get_validator_set_commitment(&validator_set),
+        })
+        .expect("Failed to serialize the cross contract args using JSON.");
+        for anchor_account in self.peer_anchors.keys() {
+            Promise::new(anchor_account).function_call(
+                "submit_validator_set_commitment".to_string(),
+                args.clone(),
+                0,
+                Gas::ONE_TERA.mul(T_GAS_FOR_SUBMIT_VALIDATOR_SET_COMMITMENT),
+            );
+        }
+    }
+    //
+    fn submit_validator_set_commitment(&mut self, commitment: ValidatorSetCommitment) {
+        self.assert_not_in_maintenance_mode();
+        let anchor_account = env::predecessor_account_id();
+        let mut peer_anchor = self
+            .peer_anchors
+            .get(&anchor_account)
+            .expect("Only registered peer anchor can submit validator set commitment.");
+        assert!(
+            commitment.appchain_id.eq(&peer_anchor.appchain_id),
+            "Invalid appchain id '{}' in commitment.",
+            commitment.appchain_id
+        );
+        if let Some(latest_commitment) = &peer_anchor.latest_commitment {
+            assert!(
+                commitment.era_number.0 > latest_commitment.era_number.0,
+                "The era number of commitment should be bigger than '{}'.",
+                latest_commitment.era_number.0
+            );
+        }
+        log!(
+            "Validator set commitment of era '{}' of appchain '{}' is received: '{}'.",
+            commitment.era_number.0,
+            commitment.appchain_id,
+            commitment.validator_set_hash
+        );
+        peer_anchor.latest_commitment = Some(commitment);
+        peer_anchor.latest_commitment_timestamp = Some(U64::from(env::block_timestamp()));
+        self.peer_anchors.insert(&anchor_account, &peer_anchor);
+    }
+}
+
+impl AppchainAnchor {
+    ///
+    pub fn internal_get_validator_set_commitment(
+        &self,
+        validator_set: &ValidatorSetOfEra,
+    ) -> ValidatorSetCommitment {
+        ValidatorSetCommitment {
+            appchain_id: self.appchain_id.clone(),
+            era_number: U64::from(validator_set.era_number()),
+            validator_set_hash: validator_set.validator_set_hash(),
+            validator_count: U64::from(validator_set.validator_count()),
+            total_stake: U128::from(validator_set.total_stake()),
+            start_timestamp: U64::from(validator_set.start_timestamp()),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::{interfaces::RelayerBondManager, *};
+
+#[near_bindgen]
+impl RelayerBondManager for AppchainAnchor {
+    //
+    fn unbond_relayer(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        self.internal_unbond_relayer(&env::predecessor_account_id());
+    }
+    //
+    fn withdraw_relayer_bond(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        let relayer_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&relayer_id);
+        self.internal_withdraw_relayer_bond(&relayer_id);
+    }
+    //
+    fn slash_relayer_bond(
+        &mut self,
+        relayer_id: AccountId,
+        finding: RelayerMisbehaviorFinding,
+    ) -> RelayerBondSlash {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        self.internal_slash_relayer_bond(&relayer_id, finding)
+    }
+}
@@ -20,10 +20,50 @@ impl Default for ProtocolSettings {
             maximum_validators_per_delegator: U64::from(16),
             unlock_period_of_validator_deposit: U64::from(21),
             unlock_period_of_delegator_deposit: U64::from(21),
+            unlock_period_of_slashed_remainder_stake: U64::from(21),
             maximum_era_count_of_unwithdrawn_reward: U64::from(84),
             maximum_era_count_of_valid_appchain_message: U64::from(7),
             validator_commission_percent: 20,
             maximum_allowed_unprofitable_era_count: 3,
+            fast_unbond_penalty_percent: 10,
+            maximum_fast_unbond_amount_per_era: U128::from(0),
+            loyalty_tiers_of_delegator: Vec::new(),
+            maximum_size_of_encoded_appchain_messages: U64::from(1_048_576),
+            maximum_count_of_appchain_messages_per_batch: 1000,
+            minimum_era_count_for_compacting_staking_history: U64::from(28),
+            secondary_stake_token: None,
+            era_count_of_validator_retirement_notice: U64::from(7),
+            insurance_pool_reward_percent: 0,
+            relayer_reimbursement_per_era: U128::from(0),
+            time_weighted_reward: false,
+            withdrawal_allowlist_change_delay: U64::from(7),
+            minimum_bonded_era_count_for_validator: U64::from(0),
+            reward_rounding_policy: RewardRoundingPolicy::Floor,
+            maximum_delegation_lock_era_count: U64::from(0),
+            delegation_lock_voting_boost_percent_per_era: 0,
+            maximum_validator_count_per_operator: U64::from(0),
+            penalty_dispute_period: U64::from(7),
+            maximum_validator_admissions_per_day_in_booting: U64::from(0),
+            validator_reward_vesting_percent: 0,
+            validator_reward_vesting_era_count: U64::from(0),
+            minimum_relayer_bond: U128::from(0),
+            relayer_bond_slash_percent: 10,
+            unlock_period_of_relayer_bond: U64::from(21),
+            validator_slash_percent: 0,
+            delegator_slash_percent: 0,
+        }
+    }
+}
+
+impl ProtocolSettings {
+    /// Get the unlock period (in days) of a certain class of unbonded stake.
+    pub fn unlock_period_of(&self, stake_class: &StakeClass) -> u64 {
+        match stake_class {
+            StakeClass::ValidatorPrincipal | StakeClass::VestingLocked => {
+                self.unlock_period_of_validator_deposit.0
+            }
+            StakeClass::DelegatorPrincipal => self.unlock_period_of_delegator_deposit.0,
+            StakeClass::SlashedRemainder => self.unlock_period_of_slashed_remainder_stake.0,
         }
     }
 }
@@ -34,6 +74,16 @@ impl Default for AnchorSettings {
             token_price_maintainer_account: None,
             relayer_account: None,
             beefy_light_client_witness_mode: false,
+            guardian_account: None,
+            treasury_account: None,
+            minimum_interval_of_price_updates: U64::from(0),
+            maximum_price_change_percent_per_update: 0,
+            pause_asset_transfer_on_price_update_violation: false,
+            seconds_of_a_day: U64::from(SECONDS_OF_A_DAY),
+            maximum_era_reward_change_percent: 0,
+            maximum_unprofitable_validator_percent: 0,
+            stake_migration_attester_account: None,
+            minimum_interval_of_oct_balance_reconciliations: U64::from(3600),
         }
     }
 }
@@ -45,6 +95,10 @@ impl Default for AppchainSettings {
             subql_endpoint: String::new(),
             era_reward: U128::from(0),
             bonus_for_new_validator: U128::from(0),
+            expected_era_duration_in_secs: U64::from(0),
+            era_duration_drift_tolerance_percent: 10,
+            bootstrap_budget: U128::from(0),
+            boot_nodes: Vec::new(),
         }
     }
 }
@@ -53,6 +107,7 @@ impl Default for AppchainSettings {
 impl ProtocolSettingsManager for AppchainAnchor {
     //
     fn change_minimum_validator_deposit(&mut self, value: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -67,10 +122,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value should be greater than `minimum_validator_deposit_changing_amount`."
         );
         protocol_settings.minimum_validator_deposit = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_minimum_validator_deposit_changing_amount(&mut self, value: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -85,10 +141,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value should be less than `minimum_validator_deposit`."
         );
         protocol_settings.minimum_validator_deposit_changing_amount = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_maximum_validator_stake_percent(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert!(value < 100, "Invalid percent value.");
         let mut protocol_settings = self.protocol_settings.get().unwrap();
@@ -97,10 +154,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value is not changed."
         );
         protocol_settings.maximum_validator_stake_percent = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_minimum_delegator_deposit(&mut self, value: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -115,10 +173,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value should be greater than `minimum_delegator_deposit_changing_amount`."
         );
         protocol_settings.minimum_delegator_deposit = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_minimum_delegator_deposit_changing_amount(&mut self, value: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -133,10 +192,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value should be less than `minimum_delegator_deposit`."
         );
         protocol_settings.minimum_delegator_deposit_changing_amount = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_minimum_total_stake_price_for_booting(&mut self, value: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -144,10 +204,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value is not changed."
         );
         protocol_settings.minimum_total_stake_price_for_booting = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_maximum_market_value_percent_of_near_fungible_tokens(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -155,10 +216,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value is not changed."
         );
         protocol_settings.maximum_market_value_percent_of_near_fungible_tokens = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_maximum_market_value_percent_of_wrapped_appchain_token(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -166,10 +228,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value is not changed."
         );
         protocol_settings.maximum_market_value_percent_of_wrapped_appchain_token = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_minimum_validator_count(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -181,10 +244,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value should be less than `maximum_validator_count`."
         );
         protocol_settings.minimum_validator_count = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_maximum_validator_count(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -196,10 +260,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value should be greater than `minimum_validator_count`."
         );
         protocol_settings.maximum_validator_count = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_maximum_validators_per_delegator(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -207,10 +272,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value is not changed."
         );
         protocol_settings.maximum_validators_per_delegator = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_unlock_period_of_validator_deposit(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -218,10 +284,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value is not changed."
         );
         protocol_settings.unlock_period_of_validator_deposit = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_unlock_period_of_delegator_deposit(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -229,10 +296,23 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value is not changed."
         );
         protocol_settings.unlock_period_of_delegator_deposit = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_unlock_period_of_slashed_remainder_stake(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.unlock_period_of_slashed_remainder_stake.0,
+            "The value is not changed."
+        );
+        protocol_settings.unlock_period_of_slashed_remainder_stake = value;
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_maximum_era_count_of_unwithdrawn_reward(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -240,10 +320,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value is not changed."
         );
         protocol_settings.maximum_era_count_of_unwithdrawn_reward = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_maximum_era_count_of_valid_appchain_message(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
@@ -254,10 +335,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value is not changed."
         );
         protocol_settings.maximum_era_count_of_valid_appchain_message = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_validator_commission_percent(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert!(value < 100, "Invalid percent value.");
         let mut protocol_settings = self.protocol_settings.get().unwrap();
@@ -266,10 +348,11 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value is not changed."
         );
         protocol_settings.validator_commission_percent = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
     }
     //
     fn change_maximum_allowed_unprofitable_era_count(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert!(
             value < 10,
@@ -281,7 +364,351 @@ impl ProtocolSettingsManager for AppchainAnchor {
             "The value is not changed."
         );
         protocol_settings.maximum_allowed_unprofitable_era_count = value;
-        self.protocol_settings.set(&protocol_settings);
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_fast_unbond_penalty_percent(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(value <= 100, "Invalid percent value.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.fast_unbond_penalty_percent,
+            "The value is not changed."
+        );
+        protocol_settings.fast_unbond_penalty_percent = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_maximum_fast_unbond_amount_per_era(&mut self, value: U128) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.maximum_fast_unbond_amount_per_era.0,
+            "The value is not changed."
+        );
+        protocol_settings.maximum_fast_unbond_amount_per_era = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_loyalty_tiers_of_delegator(&mut self, value: Vec<DelegatorLoyaltyTier>) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        value.iter().for_each(|tier| {
+            assert!(
+                tier.minimum_bonded_era_count.0 > 0,
+                "The minimum bonded era count of a loyalty tier should not be zero."
+            );
+            assert!(
+                tier.commission_rebate_percent <= 100,
+                "Invalid percent value."
+            );
+        });
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        protocol_settings.loyalty_tiers_of_delegator = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_maximum_size_of_encoded_appchain_messages(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(value.0 > 0, "The value should not be zero.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0
+                != protocol_settings
+                    .maximum_size_of_encoded_appchain_messages
+                    .0,
+            "The value is not changed."
+        );
+        protocol_settings.maximum_size_of_encoded_appchain_messages = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_maximum_count_of_appchain_messages_per_batch(&mut self, value: u32) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(value > 0, "The value should not be zero.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.maximum_count_of_appchain_messages_per_batch,
+            "The value is not changed."
+        );
+        protocol_settings.maximum_count_of_appchain_messages_per_batch = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_minimum_era_count_for_compacting_staking_history(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(value.0 > 0, "The value should not be zero.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0
+                != protocol_settings
+                    .minimum_era_count_for_compacting_staking_history
+                    .0,
+            "The value is not changed."
+        );
+        protocol_settings.minimum_era_count_for_compacting_staking_history = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_secondary_stake_token(&mut self, value: SecondaryStakeToken) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        if let Some(token) = &protocol_settings.secondary_stake_token {
+            assert!(
+                token.contract_account.eq(&value.contract_account)
+                    && token.decimals == value.decimals,
+                "Only the price of the secondary stake token can be changed."
+            );
+            assert!(
+                token.price_in_oct.0 != value.price_in_oct.0,
+                "The value is not changed."
+            );
+        }
+        protocol_settings.secondary_stake_token = Some(value);
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_era_count_of_validator_retirement_notice(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.era_count_of_validator_retirement_notice.0,
+            "The value is not changed."
+        );
+        protocol_settings.era_count_of_validator_retirement_notice = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_insurance_pool_reward_percent(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(value <= 100, "Invalid percent value.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.insurance_pool_reward_percent,
+            "The value is not changed."
+        );
+        protocol_settings.insurance_pool_reward_percent = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_relayer_reimbursement_per_era(&mut self, value: U128) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.relayer_reimbursement_per_era.0,
+            "The value is not changed."
+        );
+        protocol_settings.relayer_reimbursement_per_era = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_time_weighted_reward(&mut self, value: bool) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.time_weighted_reward,
+            "The value is not changed."
+        );
+        protocol_settings.time_weighted_reward = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_withdrawal_allowlist_change_delay(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.withdrawal_allowlist_change_delay.0,
+            "The value is not changed."
+        );
+        protocol_settings.withdrawal_allowlist_change_delay = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_minimum_bonded_era_count_for_validator(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.minimum_bonded_era_count_for_validator.0,
+            "The value is not changed."
+        );
+        protocol_settings.minimum_bonded_era_count_for_validator = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_reward_rounding_policy(&mut self, policy: RewardRoundingPolicy) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            policy != protocol_settings.reward_rounding_policy,
+            "The value is not changed."
+        );
+        protocol_settings.reward_rounding_policy = policy;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_maximum_delegation_lock_era_count(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.maximum_delegation_lock_era_count.0,
+            "The value is not changed."
+        );
+        protocol_settings.maximum_delegation_lock_era_count = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_delegation_lock_voting_boost_percent_per_era(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(value <= 100, "Invalid percent value.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.delegation_lock_voting_boost_percent_per_era,
+            "The value is not changed."
+        );
+        protocol_settings.delegation_lock_voting_boost_percent_per_era = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_maximum_validator_count_per_operator(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.maximum_validator_count_per_operator.0,
+            "The value is not changed."
+        );
+        protocol_settings.maximum_validator_count_per_operator = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_penalty_dispute_period(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.penalty_dispute_period.0,
+            "The value is not changed."
+        );
+        protocol_settings.penalty_dispute_period = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_maximum_validator_admissions_per_day_in_booting(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0
+                != protocol_settings
+                    .maximum_validator_admissions_per_day_in_booting
+                    .0,
+            "The value is not changed."
+        );
+        protocol_settings.maximum_validator_admissions_per_day_in_booting = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_validator_reward_vesting_percent(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(value <= 100, "The percent should be no more than 100.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.validator_reward_vesting_percent,
+            "The value is not changed."
+        );
+        protocol_settings.validator_reward_vesting_percent = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_validator_reward_vesting_era_count(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.validator_reward_vesting_era_count.0,
+            "The value is not changed."
+        );
+        protocol_settings.validator_reward_vesting_era_count = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_minimum_relayer_bond(&mut self, value: U128) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.minimum_relayer_bond.0,
+            "The value is not changed."
+        );
+        protocol_settings.minimum_relayer_bond = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_relayer_bond_slash_percent(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(value <= 100, "The percent should be no more than 100.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.relayer_bond_slash_percent,
+            "The value is not changed."
+        );
+        protocol_settings.relayer_bond_slash_percent = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_unlock_period_of_relayer_bond(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value.0 != protocol_settings.unlock_period_of_relayer_bond.0,
+            "The value is not changed."
+        );
+        protocol_settings.unlock_period_of_relayer_bond = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_validator_slash_percent(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(value <= 100, "The percent should be no more than 100.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.validator_slash_percent,
+            "The value is not changed."
+        );
+        protocol_settings.validator_slash_percent = value;
+        self.internal_set_protocol_settings(&protocol_settings);
+    }
+    //
+    fn change_delegator_slash_percent(&mut self, value: u16) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(value <= 100, "The percent should be no more than 100.");
+        let mut protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            value != protocol_settings.delegator_slash_percent,
+            "The value is not changed."
+        );
+        protocol_settings.delegator_slash_percent = value;
+        self.internal_set_protocol_settings(&protocol_settings);
     }
 }
 
@@ -289,31 +716,71 @@ impl ProtocolSettingsManager for AppchainAnchor {
 impl AppchainSettingsManager for AppchainAnchor {
     //
     fn set_rpc_endpoint(&mut self, rpc_endpoint: String) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut appchain_settings = self.appchain_settings.get().unwrap();
         appchain_settings.rpc_endpoint = rpc_endpoint;
-        self.appchain_settings.set(&appchain_settings);
+        self.internal_set_appchain_settings(&appchain_settings);
     }
     //
     fn set_subql_endpoint(&mut self, subql_endpoint: String) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut appchain_settings = self.appchain_settings.get().unwrap();
         appchain_settings.subql_endpoint = subql_endpoint;
-        self.appchain_settings.set(&appchain_settings);
+        self.internal_set_appchain_settings(&appchain_settings);
     }
     //
     fn set_era_reward(&mut self, era_reward: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut appchain_settings = self.appchain_settings.get().unwrap();
         appchain_settings.era_reward = era_reward;
-        self.appchain_settings.set(&appchain_settings);
+        self.internal_set_appchain_settings(&appchain_settings);
     }
     //
     fn set_bonus_for_new_validator(&mut self, bonus_amount: U128) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut appchain_settings = self.appchain_settings.get().unwrap();
         appchain_settings.bonus_for_new_validator = bonus_amount;
-        self.appchain_settings.set(&appchain_settings);
+        self.internal_set_appchain_settings(&appchain_settings);
+    }
+    //
+    fn set_expected_era_duration_in_secs(&mut self, duration: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut appchain_settings = self.appchain_settings.get().unwrap();
+        appchain_settings.expected_era_duration_in_secs = duration;
+        self.internal_set_appchain_settings(&appchain_settings);
+    }
+    //
+    fn set_era_duration_drift_tolerance_percent(&mut self, percent: u16) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut appchain_settings = self.appchain_settings.get().unwrap();
+        appchain_settings.era_duration_drift_tolerance_percent = percent;
+        self.internal_set_appchain_settings(&appchain_settings);
+    }
+    //
+    fn set_bootstrap_budget(&mut self, budget: U128) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut appchain_settings = self.appchain_settings.get().unwrap();
+        assert!(
+            budget.0 >= self.total_bootstrap_allocation,
+            "The budget can not be less than the amount already allocated."
+        );
+        appchain_settings.bootstrap_budget = budget;
+        self.internal_set_appchain_settings(&appchain_settings);
+    }
+    //
+    fn set_boot_nodes(&mut self, boot_nodes: Vec<String>) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut appchain_settings = self.appchain_settings.get().unwrap();
+        appchain_settings.boot_nodes = boot_nodes;
+        self.internal_set_appchain_settings(&appchain_settings);
     }
 }
 
@@ -321,6 +788,7 @@ impl AppchainSettingsManager for AppchainAnchor {
 impl AnchorSettingsManager for AppchainAnchor {
     //
     fn set_token_price_maintainer_account(&mut self, account_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert!(
             !account_id.eq(&self.owner),
@@ -328,10 +796,11 @@ impl AnchorSettingsManager for AppchainAnchor {
         );
         let mut anchor_settings = self.anchor_settings.get().unwrap();
         anchor_settings.token_price_maintainer_account = Some(account_id);
-        self.anchor_settings.set(&anchor_settings);
+        self.internal_set_anchor_settings(&anchor_settings);
     }
     //
     fn set_relayer_account(&mut self, account_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert!(
             !account_id.eq(&self.owner),
@@ -339,10 +808,31 @@ impl AnchorSettingsManager for AppchainAnchor {
         );
         let mut anchor_settings = self.anchor_settings.get().unwrap();
         anchor_settings.relayer_account = Some(account_id);
-        self.anchor_settings.set(&anchor_settings);
+        self.internal_set_anchor_settings(&anchor_settings);
+    }
+    //
+    fn set_guardian_account(&mut self, account_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(
+            !account_id.eq(&self.owner),
+            "This account should not be the same as the owner account."
+        );
+        let mut anchor_settings = self.anchor_settings.get().unwrap();
+        anchor_settings.guardian_account = Some(account_id);
+        self.internal_set_anchor_settings(&anchor_settings);
+    }
+    //
+    fn set_treasury_account(&mut self, account_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut anchor_settings = self.anchor_settings.get().unwrap();
+        anchor_settings.treasury_account = Some(account_id);
+        self.internal_set_anchor_settings(&anchor_settings);
     }
     //
     fn turn_on_beefy_light_client_witness_mode(&mut self) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut anchor_settings = self.anchor_settings.get().unwrap();
         assert!(
@@ -350,10 +840,11 @@ impl AnchorSettingsManager for AppchainAnchor {
             "Witness mode is already turned on."
         );
         anchor_settings.beefy_light_client_witness_mode = true;
-        self.anchor_settings.set(&anchor_settings);
+        self.internal_set_anchor_settings(&anchor_settings);
     }
     //
     fn turn_off_beefy_light_client_witness_mode(&mut self) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut anchor_settings = self.anchor_settings.get().unwrap();
         assert!(
@@ -361,6 +852,97 @@ impl AnchorSettingsManager for AppchainAnchor {
             "Witness mode is already turned off."
         );
         anchor_settings.beefy_light_client_witness_mode = false;
-        self.anchor_settings.set(&anchor_settings);
+        self.internal_set_anchor_settings(&anchor_settings);
+    }
+    //
+    fn set_minimum_interval_of_price_updates(&mut self, interval_in_secs: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut anchor_settings = self.anchor_settings.get().unwrap();
+        anchor_settings.minimum_interval_of_price_updates = interval_in_secs;
+        self.internal_set_anchor_settings(&anchor_settings);
+    }
+    //
+    fn set_maximum_price_change_percent_per_update(&mut self, percent: u16) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut anchor_settings = self.anchor_settings.get().unwrap();
+        anchor_settings.maximum_price_change_percent_per_update = percent;
+        self.internal_set_anchor_settings(&anchor_settings);
+    }
+    //
+    fn turn_on_pause_asset_transfer_on_price_update_violation(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut anchor_settings = self.anchor_settings.get().unwrap();
+        assert!(
+            !anchor_settings.pause_asset_transfer_on_price_update_violation,
+            "Auto pausing is already turned on."
+        );
+        anchor_settings.pause_asset_transfer_on_price_update_violation = true;
+        self.internal_set_anchor_settings(&anchor_settings);
+    }
+    //
+    fn turn_off_pause_asset_transfer_on_price_update_violation(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut anchor_settings = self.anchor_settings.get().unwrap();
+        assert!(
+            anchor_settings.pause_asset_transfer_on_price_update_violation,
+            "Auto pausing is already turned off."
+        );
+        anchor_settings.pause_asset_transfer_on_price_update_violation = false;
+        self.internal_set_anchor_settings(&anchor_settings);
+    }
+    //
+    fn set_seconds_of_a_day(&mut self, value: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(
+            value.0 >= MINIMUM_SECONDS_OF_A_DAY && value.0 <= SECONDS_OF_A_DAY,
+            "The seconds of a day should be between {} and {}.",
+            MINIMUM_SECONDS_OF_A_DAY,
+            SECONDS_OF_A_DAY
+        );
+        let mut anchor_settings = self.anchor_settings.get().unwrap();
+        anchor_settings.seconds_of_a_day = value;
+        self.internal_set_anchor_settings(&anchor_settings);
+    }
+    //
+    fn set_maximum_era_reward_change_percent(&mut self, percent: u16) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut anchor_settings = self.anchor_settings.get().unwrap();
+        anchor_settings.maximum_era_reward_change_percent = percent;
+        self.internal_set_anchor_settings(&anchor_settings);
+    }
+    //
+    fn set_maximum_unprofitable_validator_percent(&mut self, percent: u16) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(percent <= 100, "The percent should be no more than 100.");
+        let mut anchor_settings = self.anchor_settings.get().unwrap();
+        anchor_settings.maximum_unprofitable_validator_percent = percent;
+        self.internal_set_anchor_settings(&anchor_settings);
+    }
+    //
+    fn set_stake_migration_attester_account(&mut self, account_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(
+            !account_id.eq(&self.owner),
+            "This account should not be the same as the owner account."
+        );
+        let mut anchor_settings = self.anchor_settings.get().unwrap();
+        anchor_settings.stake_migration_attester_account = Some(account_id);
+        self.internal_set_anchor_settings(&anchor_settings);
+    }
+    //
+    fn set_minimum_interval_of_oct_balance_reconciliations(&mut self, interval_in_secs: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let mut anchor_settings = self.anchor_settings.get().unwrap();
+        anchor_settings.minimum_interval_of_oct_balance_reconciliations = interval_in_secs;
+        self.internal_set_anchor_settings(&anchor_settings);
     }
 }
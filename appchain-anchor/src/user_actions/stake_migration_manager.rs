@@ -0,0 +1,32 @@
+use crate::{interfaces::StakeMigrationManager, *};
+
+#[near_bindgen]
+impl StakeMigrationManager for AppchainAnchor {
+    //
+    fn submit_stake_migration_export(
+        &mut self,
+        source: StakeMigrationSource,
+        entries: Vec<StakeMigrationEntry>,
+    ) -> U64 {
+        self.assert_not_in_maintenance_mode();
+        U64::from(self.internal_submit_stake_migration_export(source, entries))
+    }
+    //
+    fn approve_stake_migration_export(&mut self, export_index: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        self.internal_conclude_stake_migration_export(export_index.0, true);
+    }
+    //
+    fn reject_stake_migration_export(&mut self, export_index: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        self.internal_conclude_stake_migration_export(export_index.0, false);
+    }
+    //
+    fn withdraw_stake_migration_allocation(&mut self, amount: U128) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        self.internal_withdraw_stake_migration_allocation(amount.0);
+    }
+}
@@ -1,6 +1,5 @@
-use crate::{interfaces::StakingManager, *};
+use crate::{interfaces::StakingManager, validator_profiles::assert_validator_profile_is_valid, *};
 use borsh::maybestd::collections::HashMap;
-use near_contract_standards::fungible_token::core::ext_ft_core;
 use near_sdk::serde_json;
 use std::str::FromStr;
 
@@ -14,6 +13,8 @@ impl AppchainAnchor {
         amount: U128,
         deposit_message: FTDepositMessage,
     ) -> PromiseOrValue<U128> {
+        self.assert_staking_is_active();
+        self.assert_account_is_not_frozen(&sender_id);
         match deposit_message {
             FTDepositMessage::RegisterValidator {
                 validator_id_in_appchain,
@@ -41,6 +42,43 @@ impl AppchainAnchor {
                 self.increase_delegation(sender_id, validator_id, amount);
                 PromiseOrValue::Value(0.into())
             }
+            FTDepositMessage::IncreaseStakeFor { validator_id } => {
+                self.assert_account_is_not_frozen(&validator_id);
+                log!(
+                    "Stake of validator '{}' is increased by '{}' on behalf of it.",
+                    validator_id,
+                    sender_id
+                );
+                self.increase_stake(validator_id, amount);
+                PromiseOrValue::Value(0.into())
+            }
+            FTDepositMessage::IncreaseDelegationFor {
+                delegator_id,
+                validator_id,
+            } => {
+                self.assert_account_is_not_frozen(&delegator_id);
+                log!(
+                    "Delegation of delegator '{}' to validator '{}' is increased by '{}' on behalf of it.",
+                    delegator_id,
+                    validator_id,
+                    sender_id
+                );
+                self.increase_delegation(delegator_id, validator_id, amount);
+                PromiseOrValue::Value(0.into())
+            }
+            FTDepositMessage::IncreaseVestingLockedStakeFor { validator_id } => {
+                self.assert_account_is_not_frozen(&validator_id);
+                self.increase_vesting_locked_stake(sender_id, validator_id, amount);
+                PromiseOrValue::Value(0.into())
+            }
+            FTDepositMessage::FundStakeMigrationAllocation => {
+                self.internal_fund_stake_migration_allocation(sender_id, amount.0);
+                PromiseOrValue::Value(0.into())
+            }
+            FTDepositMessage::BondRelayer => {
+                self.internal_bond_relayer(sender_id, amount.0);
+                PromiseOrValue::Value(0.into())
+            }
             _ => panic!(
                 "Internal error: misuse of internal function 'internal_process_oct_deposit'."
             ),
@@ -57,11 +95,46 @@ impl AppchainAnchor {
     ) {
         match self.appchain_state {
             AppchainState::Staging | AppchainState::Active => (),
+            AppchainState::Booting
+                if self
+                    .protocol_settings
+                    .get()
+                    .unwrap()
+                    .maximum_validator_admissions_per_day_in_booting
+                    .0
+                    > 0 =>
+            {
+                self.internal_register_validator_in_booting(
+                    validator_id,
+                    validator_id_in_appchain,
+                    profile,
+                    deposit_amount,
+                    can_be_delegated_to,
+                );
+                return;
+            }
             _ => panic!(
                 "Cannot register validator while appchain state is '{}'.",
                 serde_json::to_string(&self.appchain_state).unwrap()
             ),
         };
+        self.internal_admit_validator_registration(
+            validator_id,
+            validator_id_in_appchain,
+            profile,
+            deposit_amount,
+            can_be_delegated_to,
+        );
+    }
+    // Register a validator in the next validator set.
+    pub fn internal_admit_validator_registration(
+        &mut self,
+        validator_id: AccountId,
+        validator_id_in_appchain: String,
+        profile: HashMap<String, String>,
+        deposit_amount: U128,
+        can_be_delegated_to: bool,
+    ) {
         assert!(
             env::prepaid_gas() > Gas::ONE_TERA.mul(T_GAS_FOR_REGISTER_VALIDATOR),
             "Prepaid gas is not enough."
@@ -72,6 +145,7 @@ impl AppchainAnchor {
             "The account '{}' has already been registered.",
             &validator_id
         );
+        assert_validator_profile_is_valid(&profile);
         let mut validator_profiles = self.validator_profiles.get().unwrap();
         let formatted_validator_id_in_appchain = AccountIdInAppchain::new(
             Some(validator_id_in_appchain.clone()),
@@ -114,7 +188,10 @@ impl AppchainAnchor {
             amount: deposit_amount,
             can_be_delegated_to,
         });
-        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
         self.next_validator_set.set(&next_validator_set);
         //
         self.sync_state_to_registry();
@@ -190,7 +267,61 @@ impl AppchainAnchor {
             amount,
         });
         //
-        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
+        self.next_validator_set.set(&next_validator_set);
+        //
+        self.sync_state_to_registry();
+    }
+    //
+    fn increase_vesting_locked_stake(
+        &mut self,
+        depositor_id: AccountId,
+        validator_id: AccountId,
+        amount: U128,
+    ) {
+        match self.appchain_state {
+            AppchainState::Staging | AppchainState::Active => (),
+            _ => panic!(
+                "Cannot increase stake while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        assert!(
+            !depositor_id.eq(&validator_id),
+            "The depositor of vesting-locked stake should not be the validator itself."
+        );
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            amount.0
+                >= protocol_settings
+                    .minimum_validator_deposit_changing_amount
+                    .0,
+            "Too few amount to increase. It should not be less than {} OCT.",
+            protocol_settings
+                .minimum_validator_deposit_changing_amount
+                .0
+                / OCT_DECIMALS_VALUE
+        );
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        let validator = next_validator_set.get_validator(&validator_id).unwrap();
+        self.assert_validator_stake_is_valid(
+            validator.deposit_amount + amount.0,
+            Some(validator.total_stake + amount.0),
+        );
+        let staking_history = self.record_staking_fact(StakingFact::VestingLockedStakeIncreased {
+            validator_id,
+            depositor_id,
+            amount,
+        });
+        //
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
         self.next_validator_set.set(&next_validator_set);
         //
         self.sync_state_to_registry();
@@ -223,6 +354,11 @@ impl AppchainAnchor {
             "Validator '{}' cannot be delegated to.",
             &validator_id
         );
+        assert!(
+            !self.validator_retirements.contains_key(&validator_id),
+            "Validator '{}' is retiring.",
+            &validator_id
+        );
         let protocol_settings = self.protocol_settings.get().unwrap();
         assert!(
             next_validator_set.get_validator_count_of(&delegator_id)
@@ -243,7 +379,10 @@ impl AppchainAnchor {
             amount: U128::from(deposit_amount),
         });
         //
-        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
         self.next_validator_set.set(&next_validator_set);
         //
         self.sync_state_to_registry();
@@ -259,13 +398,61 @@ impl AppchainAnchor {
             index: U64::from(0),
         });
         self.staking_histories.set(&staking_histories);
+        if let Some((_, _, amount)) = staking_history.staking_fact.unbonded_stake() {
+            self.total_unbonded_stake += amount.0;
+        }
         //
         let mut user_staking_histories = self.user_staking_histories.get().unwrap();
         user_staking_histories.add_staking_history(&staking_history);
         self.user_staking_histories.set(&user_staking_histories);
         //
+        self.sync_delegation_lock_with_staking_fact(&staking_history.staking_fact);
+        //
+        Nep297Event::StakingFactRecorded(&staking_history).emit();
         staking_history
     }
+    // The lock of a delegation is removed along with the delegation,
+    // and is moved along with the redelegation.
+    fn sync_delegation_lock_with_staking_fact(&mut self, staking_fact: &StakingFact) {
+        match staking_fact {
+            StakingFact::DelegatorUnbonded {
+                delegator_id,
+                validator_id,
+                ..
+            }
+            | StakingFact::DelegatorAutoUnbonded {
+                delegator_id,
+                validator_id,
+                ..
+            } => {
+                self.delegation_locks
+                    .remove(&(delegator_id.clone(), validator_id.clone()));
+            }
+            StakingFact::DelegationRedelegated {
+                delegator_id,
+                from_validator_id,
+                to_validator_id,
+                ..
+            } => {
+                if let Some(lock) = self
+                    .delegation_locks
+                    .remove(&(delegator_id.clone(), from_validator_id.clone()))
+                {
+                    let key = (delegator_id.clone(), to_validator_id.clone());
+                    if self
+                        .delegation_locks
+                        .get(&key)
+                        .map_or(true, |existing_lock| {
+                            existing_lock.unlock_era.0 < lock.unlock_era.0
+                        })
+                    {
+                        self.delegation_locks.insert(&key, &lock);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
     //
     fn increase_delegation(
         &mut self,
@@ -294,6 +481,11 @@ impl AppchainAnchor {
         );
         let mut next_validator_set = self.next_validator_set.get().unwrap();
         self.assert_delegator_id(&delegator_id, &validator_id, &next_validator_set);
+        assert!(
+            !self.validator_retirements.contains_key(&validator_id),
+            "Validator '{}' is retiring.",
+            &validator_id
+        );
         let validator = next_validator_set.get_validator(&validator_id).unwrap();
         self.assert_validator_stake_is_valid(
             validator.deposit_amount,
@@ -305,17 +497,70 @@ impl AppchainAnchor {
             amount,
         });
         //
-        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
         self.next_validator_set.set(&next_validator_set);
         //
         self.sync_state_to_registry();
     }
+    //
+    pub fn internal_process_secondary_stake_deposit(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+    ) -> PromiseOrValue<U128> {
+        self.assert_staking_is_active();
+        self.assert_account_is_not_frozen(&sender_id);
+        match self.appchain_state {
+            AppchainState::Staging | AppchainState::Active => (),
+            _ => panic!(
+                "Cannot increase stake while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let secondary_stake_token = protocol_settings
+            .secondary_stake_token
+            .expect("The secondary stake token is not set.");
+        assert!(
+            secondary_stake_token.price_in_oct.0 > 0,
+            "The deposit of secondary stake token is disabled."
+        );
+        let stake = secondary_stake_token.convert_to_stake(amount.0);
+        assert!(stake > 0, "Too few amount to increase.");
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&sender_id, &next_validator_set);
+        let validator = next_validator_set.get_validator(&sender_id).unwrap();
+        self.assert_validator_stake_is_valid(
+            validator.deposit_amount,
+            Some(validator.total_stake + stake),
+        );
+        let staking_history = self.record_staking_fact(StakingFact::SecondaryStakeIncreased {
+            validator_id: sender_id,
+            amount,
+            stake: U128::from(stake),
+        });
+        //
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
+        self.next_validator_set.set(&next_validator_set);
+        //
+        self.sync_state_to_registry();
+        PromiseOrValue::Value(0.into())
+    }
 }
 
 #[near_bindgen]
 impl StakingManager for AppchainAnchor {
     //
     fn decrease_stake(&mut self, amount: U128) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_active();
+        self.assert_account_is_not_frozen(&env::predecessor_account_id());
         match self.appchain_state {
             AppchainState::Active => (),
             _ => panic!(
@@ -343,6 +588,10 @@ impl StakingManager for AppchainAnchor {
             validator.deposit_amount > amount.0,
             "Unable to decrease so much stake."
         );
+        assert!(
+            validator.deposit_amount - amount.0 >= validator.vesting_locked_deposit_amount(),
+            "Unable to decrease vesting-locked stake."
+        );
         self.assert_validator_stake_is_valid(validator.deposit_amount - amount.0, None);
         self.assert_total_stake_price(&protocol_settings, &next_validator_set, amount.0);
         let staking_history = self.record_staking_fact(StakingFact::StakeDecreased {
@@ -350,13 +599,19 @@ impl StakingManager for AppchainAnchor {
             amount,
         });
         //
-        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
         self.next_validator_set.set(&next_validator_set);
         //
         self.sync_state_to_registry();
     }
     //
     fn unbond_stake(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_active();
+        self.assert_account_is_not_frozen(&env::predecessor_account_id());
         match self.appchain_state {
             AppchainState::Active | AppchainState::Broken => (),
             _ => panic!(
@@ -372,6 +627,13 @@ impl StakingManager for AppchainAnchor {
         );
         let validator_id = env::predecessor_account_id();
         self.assert_validator_id(&validator_id, &next_validator_set);
+        let minimum_bonded_era_count = protocol_settings.minimum_bonded_era_count_for_validator.0;
+        assert!(
+            self.get_bonded_era_count_of(&validator_id, minimum_bonded_era_count)
+                >= minimum_bonded_era_count,
+            "The validator should be bonded for at least {} eras before unbonding.",
+            minimum_bonded_era_count
+        );
         self.record_unbonding_validator(
             &protocol_settings,
             &mut next_validator_set,
@@ -382,199 +644,169 @@ impl StakingManager for AppchainAnchor {
     }
     //
     fn enable_delegation(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_active();
+        self.assert_account_is_not_frozen(&env::predecessor_account_id());
         let mut next_validator_set = self.next_validator_set.get().unwrap();
         let validator_id = env::predecessor_account_id();
         self.assert_validator_id(&validator_id, &next_validator_set);
         let staking_history =
             self.record_staking_fact(StakingFact::ValidatorDelegationEnabled { validator_id });
         //
-        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
         self.next_validator_set.set(&next_validator_set);
         //
         self.sync_state_to_registry();
     }
     //
     fn disable_delegation(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_active();
+        self.assert_account_is_not_frozen(&env::predecessor_account_id());
         let mut next_validator_set = self.next_validator_set.get().unwrap();
         let validator_id = env::predecessor_account_id();
         self.assert_validator_id(&validator_id, &next_validator_set);
         let staking_history =
             self.record_staking_fact(StakingFact::ValidatorDelegationDisabled { validator_id });
         //
-        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
         self.next_validator_set.set(&next_validator_set);
         //
         self.sync_state_to_registry();
     }
     //
     fn decrease_delegation(&mut self, validator_id: AccountId, amount: U128) {
-        match self.appchain_state {
-            AppchainState::Active => (),
-            _ => panic!(
-                "Cannot decrease delegation while appchain state is '{}'.",
-                serde_json::to_string(&self.appchain_state).unwrap()
-            ),
-        };
-        let protocol_settings = self.protocol_settings.get().unwrap();
-        assert!(
-            amount.0
-                >= protocol_settings
-                    .minimum_delegator_deposit_changing_amount
-                    .0,
-            "Too few amount to decrease. It should not be less than {} OCT.",
-            protocol_settings
-                .minimum_delegator_deposit_changing_amount
-                .0
-                / OCT_DECIMALS_VALUE
-        );
-        let mut next_validator_set = self.next_validator_set.get().unwrap();
-        let delegator_id = env::predecessor_account_id();
-        self.assert_delegator_id(&delegator_id, &validator_id, &next_validator_set);
-        let protocol_settings = self.protocol_settings.get().unwrap();
-        assert!(
-            next_validator_set
-                .get_delegator(&delegator_id, &validator_id)
-                .unwrap()
-                .deposit_amount
-                >= protocol_settings.minimum_delegator_deposit.0 + amount.0,
-            "Unable to decrease so much stake."
-        );
-        self.assert_total_stake_price(&protocol_settings, &next_validator_set, amount.0);
-        let staking_history = self.record_staking_fact(StakingFact::DelegationDecreased {
-            delegator_id: delegator_id.clone(),
-            validator_id: validator_id.clone(),
-            amount,
-        });
-        //
-        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
-        self.next_validator_set.set(&next_validator_set);
-        //
-        self.sync_state_to_registry();
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_active();
+        self.internal_decrease_delegation(&env::predecessor_account_id(), &validator_id, amount);
     }
     //
     fn unbond_delegation(&mut self, validator_id: AccountId) {
-        match self.appchain_state {
-            AppchainState::Active | AppchainState::Broken => (),
-            _ => panic!(
-                "Cannot unbond delegation while appchain state is '{}'.",
-                serde_json::to_string(&self.appchain_state).unwrap()
-            ),
-        };
-        let mut next_validator_set = self.next_validator_set.get().unwrap();
-        let delegator_id = env::predecessor_account_id();
-        self.assert_delegator_id(&delegator_id, &validator_id, &next_validator_set);
-        let delegator = next_validator_set
-            .get_delegator(&delegator_id, &validator_id)
-            .unwrap();
-        let protocol_settings = self.protocol_settings.get().unwrap();
-        self.assert_total_stake_price(
-            &protocol_settings,
-            &next_validator_set,
-            delegator.deposit_amount,
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_active();
+        self.internal_unbond_delegation(&env::predecessor_account_id(), &validator_id);
+    }
+    //
+    fn change_delegated_validator(
+        &mut self,
+        old_validator_id: AccountId,
+        new_validator_id: AccountId,
+    ) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_active();
+        self.internal_change_delegated_validator(
+            &env::predecessor_account_id(),
+            &old_validator_id,
+            &new_validator_id,
         );
-        let staking_history = self.record_staking_fact(StakingFact::DelegatorUnbonded {
-            delegator_id: delegator_id.clone(),
-            validator_id: validator_id.clone(),
-            amount: U128::from(delegator.deposit_amount),
-        });
-        //
-        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
-        self.next_validator_set.set(&next_validator_set);
-        //
-        self.sync_state_to_registry();
     }
     //
-    fn withdraw_stake(&mut self, account_id: AccountId) {
+    fn withdraw_stake(&mut self, account_id: AccountId, destination: Option<AccountId>) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_not_frozen();
+        self.assert_account_is_not_frozen(&account_id);
         self.assert_asset_transfer_is_not_paused();
-        let protocol_settings = self.protocol_settings.get().unwrap();
-        let mut balance_to_withdraw: u128 = 0;
-        let mut remained_stakes = Vec::<UnbondedStakeReference>::new();
-        if let Some(unbonded_stake_references) = self.unbonded_stakes.get(&account_id) {
-            unbonded_stake_references.iter().for_each(|reference| {
-                let validator_set = self
-                    .validator_set_histories
-                    .get()
-                    .unwrap()
-                    .get(&reference.era_number)
-                    .unwrap();
-                let staking_history = self
-                    .staking_histories
-                    .get()
-                    .unwrap()
-                    .get(&reference.staking_history_index)
-                    .unwrap();
-                match staking_history.staking_fact {
-                    StakingFact::StakeDecreased {
-                        validator_id: _,
-                        amount,
-                    }
-                    | StakingFact::ValidatorUnbonded {
-                        validator_id: _,
-                        amount,
-                    }
-                    | StakingFact::ValidatorAutoUnbonded {
-                        validator_id: _,
-                        amount,
-                    } => {
-                        if validator_set.start_timestamp()
-                            + protocol_settings.unlock_period_of_validator_deposit.0
-                                * SECONDS_OF_A_DAY
-                                * NANO_SECONDS_MULTIPLE
-                            < env::block_timestamp()
-                        {
-                            balance_to_withdraw += amount.0;
-                        } else {
-                            remained_stakes.push(reference.clone());
-                        }
-                    }
-                    StakingFact::DelegationDecreased {
-                        delegator_id: _,
-                        validator_id: _,
-                        amount,
-                    }
-                    | StakingFact::DelegatorUnbonded {
-                        delegator_id: _,
-                        validator_id: _,
-                        amount,
-                    }
-                    | StakingFact::DelegatorAutoUnbonded {
-                        delegator_id: _,
-                        validator_id: _,
-                        amount,
-                    } => {
-                        if validator_set.start_timestamp()
-                            + protocol_settings.unlock_period_of_delegator_deposit.0
-                                * SECONDS_OF_A_DAY
-                                * NANO_SECONDS_MULTIPLE
-                            < env::block_timestamp()
-                        {
-                            balance_to_withdraw += amount.0;
-                        } else {
-                            remained_stakes.push(reference.clone());
-                        }
-                    }
-                    _ => (),
-                };
-            });
+        let destination = self.get_withdrawal_destination_of(&account_id, destination);
+        if self.unbonded_stakes.contains_key(&account_id) {
+            let eligibility = self.evaluate_unbonded_stakes_of(&account_id);
+            let balance_to_withdraw = eligibility.unlocked_amount();
+            let remained_stakes = eligibility.locked_references();
             if remained_stakes.len() > 0 {
                 self.unbonded_stakes.insert(&account_id, &remained_stakes);
             } else {
                 self.unbonded_stakes.remove(&account_id);
             }
+            self.decrease_total_unbonded_stake(balance_to_withdraw);
             if balance_to_withdraw > 0 {
-                ext_ft_core::ext(self.oct_token.get().unwrap().contract_account)
-                    .with_attached_deposit(1)
-                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
-                    .with_unused_gas_weight(0)
-                    .ft_transfer(account_id, balance_to_withdraw.into(), None);
+                let oct_token = self.oct_token.get().unwrap();
+                self.transfer_with_withdrawal_receipt(
+                    WithdrawalKind::Stake,
+                    &account_id,
+                    &oct_token.contract_account,
+                    &destination,
+                    balance_to_withdraw,
+                );
             }
         };
     }
     //
-    fn withdraw_validator_rewards(&mut self, validator_id: AccountId) {
+    fn fast_withdraw_stake(&mut self, destination: Option<AccountId>) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_not_frozen();
+        let account_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&account_id);
+        self.assert_asset_transfer_is_not_paused();
+        let destination = self.get_withdrawal_destination_of(&account_id, destination);
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            protocol_settings.maximum_fast_unbond_amount_per_era.0 > 0,
+            "Fast unbonding is not enabled."
+        );
+        assert!(
+            self.unbonded_stakes.contains_key(&account_id),
+            "No unbonded stake to withdraw."
+        );
+        let eligibility = self.evaluate_unbonded_stakes_of(&account_id);
+        let unlocked_balance = eligibility.unlocked_amount();
+        let locked_balance = eligibility.locked_amount();
+        let era_number = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .index_range()
+            .end_index
+            .0;
+        let fast_unbonded_amount = self.fast_unbonded_amounts.get(&era_number).unwrap_or(0);
+        assert!(
+            fast_unbonded_amount + locked_balance
+                <= protocol_settings.maximum_fast_unbond_amount_per_era.0,
+            "The amount of fast unbonding exceeds the limit of era '{}'. Please try again later.",
+            era_number
+        );
+        self.fast_unbonded_amounts
+            .insert(&era_number, &(fast_unbonded_amount + locked_balance));
+        self.unbonded_stakes.remove(&account_id);
+        self.decrease_total_unbonded_stake(unlocked_balance + locked_balance);
+        //
+        let penalty =
+            locked_balance * u128::from(protocol_settings.fast_unbond_penalty_percent) / 100;
+        let balance_to_withdraw = unlocked_balance + locked_balance - penalty;
+        let oct_token = self.oct_token.get().unwrap();
+        if balance_to_withdraw > 0 {
+            self.transfer_with_withdrawal_receipt(
+                WithdrawalKind::FastUnbondedStake,
+                &account_id,
+                &oct_token.contract_account,
+                &destination,
+                balance_to_withdraw,
+            );
+        }
+        self.transfer_oct_token_to_treasury(TreasurySource::FastUnbondPenalty, penalty);
+        log!(
+            "Unbonded stake of '{}' is withdrawn instantly, with penalty '{}'.",
+            account_id,
+            penalty
+        );
+    }
+    //
+    fn withdraw_validator_rewards(
+        &mut self,
+        validator_id: AccountId,
+        destination: Option<AccountId>,
+    ) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_not_frozen();
+        self.assert_account_is_not_frozen(&validator_id);
         self.assert_asset_transfer_is_not_paused();
         self.assert_rewards_withdrawal_is_not_paused();
         self.assert_contract_account_of_wrapped_appchain_token_is_set();
+        let destination = self.get_withdrawal_destination_of(&validator_id, destination);
         let end_era = self
             .validator_set_histories
             .get()
@@ -589,35 +821,93 @@ impl StakingManager for AppchainAnchor {
             false => 0,
         };
         let mut reward_to_withdraw: u128 = 0;
-        for era_number in start_era..end_era + 1 {
+        let mut commission_to_withdraw: u128 = 0;
+        for era_number in
+            self.take_unwithdrawn_reward_eras_of(&validator_id, None, start_era, end_era + 1)
+        {
             if let Some(reward) = self
                 .unwithdrawn_validator_rewards
                 .get(&(era_number, validator_id.clone()))
             {
-                reward_to_withdraw += reward;
+                reward_to_withdraw += self.internal_take_vesting_part_of_validator_reward(
+                    &validator_id,
+                    era_number,
+                    reward,
+                );
                 self.unwithdrawn_validator_rewards
                     .remove(&(era_number, validator_id.clone()));
             }
+            if let Some(commission) = self
+                .unwithdrawn_commission_rewards
+                .get(&(era_number, validator_id.clone()))
+            {
+                commission_to_withdraw += commission;
+                self.unwithdrawn_commission_rewards
+                    .remove(&(era_number, validator_id.clone()));
+            }
+        }
+        reward_to_withdraw += self.internal_release_vested_rewards_of(&validator_id, end_era);
+        // The commission is withdrawn to the payout account of the validator,
+        // or to the validator itself if the payout account is removed.
+        let payout_account = self
+            .commission_payout_accounts
+            .get(&validator_id)
+            .unwrap_or(validator_id.clone());
+        if payout_account.eq(&validator_id) {
+            reward_to_withdraw += commission_to_withdraw;
+            commission_to_withdraw = 0;
         }
+        let contract_account = self
+            .wrapped_appchain_token
+            .get()
+            .unwrap()
+            .contract_account
+            .unwrap();
         if reward_to_withdraw > 0 {
-            ext_ft_core::ext(
-                self.wrapped_appchain_token
-                    .get()
-                    .unwrap()
-                    .contract_account
-                    .unwrap(),
-            )
-            .with_attached_deposit(1)
-            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
-            .with_unused_gas_weight(0)
-            .ft_transfer(validator_id, reward_to_withdraw.into(), None);
+            self.record_reward_withdrawal(
+                &validator_id,
+                &validator_id,
+                reward_to_withdraw,
+                &destination,
+            );
+            self.transfer_with_withdrawal_receipt(
+                WithdrawalKind::ValidatorReward,
+                &validator_id,
+                &contract_account,
+                &destination,
+                reward_to_withdraw,
+            );
+        }
+        if commission_to_withdraw > 0 {
+            self.record_reward_withdrawal(
+                &validator_id,
+                &validator_id,
+                commission_to_withdraw,
+                &payout_account,
+            );
+            self.transfer_with_withdrawal_receipt(
+                WithdrawalKind::Commission,
+                &validator_id,
+                &contract_account,
+                &payout_account,
+                commission_to_withdraw,
+            );
         }
     }
     //
-    fn withdraw_delegator_rewards(&mut self, delegator_id: AccountId, validator_id: AccountId) {
+    fn withdraw_delegator_rewards(
+        &mut self,
+        delegator_id: AccountId,
+        validator_id: AccountId,
+        destination: Option<AccountId>,
+    ) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_not_frozen();
+        self.assert_account_is_not_frozen(&delegator_id);
         self.assert_asset_transfer_is_not_paused();
         self.assert_rewards_withdrawal_is_not_paused();
         self.assert_contract_account_of_wrapped_appchain_token_is_set();
+        let destination = self.get_withdrawal_destination_of(&delegator_id, destination);
         let end_era = self
             .validator_set_histories
             .get()
@@ -632,7 +922,12 @@ impl StakingManager for AppchainAnchor {
             false => 0,
         };
         let mut reward_to_withdraw: u128 = 0;
-        for era_number in start_era..end_era {
+        for era_number in self.take_unwithdrawn_reward_eras_of(
+            &delegator_id,
+            Some(&validator_id),
+            start_era,
+            end_era,
+        ) {
             if let Some(reward) = self.unwithdrawn_delegator_rewards.get(&(
                 era_number,
                 delegator_id.clone(),
@@ -647,22 +942,307 @@ impl StakingManager for AppchainAnchor {
             }
         }
         if reward_to_withdraw > 0 {
-            ext_ft_core::ext(
-                self.wrapped_appchain_token
-                    .get()
-                    .unwrap()
-                    .contract_account
-                    .unwrap(),
-            )
-            .with_attached_deposit(1)
-            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
-            .with_unused_gas_weight(0)
-            .ft_transfer(delegator_id, reward_to_withdraw.into(), None);
+            let contract_account = self
+                .wrapped_appchain_token
+                .get()
+                .unwrap()
+                .contract_account
+                .unwrap();
+            // The third party who withdraws the rewards for the delegator gets the tip
+            // which is opted in by the delegator.
+            let claimer_id = env::predecessor_account_id();
+            let tip = match claimer_id.eq(&delegator_id) {
+                true => 0,
+                false => {
+                    reward_to_withdraw
+                        * u128::from(
+                            self.reward_claim_tip_percents
+                                .get(&delegator_id)
+                                .unwrap_or(0),
+                        )
+                        / 100
+                }
+            };
+            if tip > 0 {
+                reward_to_withdraw -= tip;
+                self.record_reward_withdrawal(&delegator_id, &validator_id, tip, &claimer_id);
+                self.transfer_with_withdrawal_receipt(
+                    WithdrawalKind::RewardClaimTip,
+                    &claimer_id,
+                    &contract_account,
+                    &claimer_id,
+                    tip,
+                );
+            }
+            self.record_reward_withdrawal(
+                &delegator_id,
+                &validator_id,
+                reward_to_withdraw,
+                &destination,
+            );
+            self.transfer_with_withdrawal_receipt(
+                WithdrawalKind::DelegatorReward,
+                &delegator_id,
+                &contract_account,
+                &destination,
+                reward_to_withdraw,
+            );
         }
     }
+    //
+    fn decrease_secondary_stake(&mut self, amount: U128) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_active();
+        self.assert_account_is_not_frozen(&env::predecessor_account_id());
+        match self.appchain_state {
+            AppchainState::Active => (),
+            _ => panic!(
+                "Cannot decrease stake while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        let validator_id = env::predecessor_account_id();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        let validator = next_validator_set.get_validator(&validator_id).unwrap();
+        assert!(
+            amount.0 > 0 && validator.secondary_deposit_amount >= amount.0,
+            "Unable to decrease so much stake of secondary token."
+        );
+        // The stake is decreased in proportion, to keep the conversion ratio
+        // which is applied at the time of deposit.
+        let stake = match amount.0 == validator.secondary_deposit_amount {
+            true => validator.secondary_stake,
+            false => {
+                validator.secondary_stake / validator.secondary_deposit_amount * amount.0
+                    + validator.secondary_stake % validator.secondary_deposit_amount * amount.0
+                        / validator.secondary_deposit_amount
+            }
+        };
+        self.assert_total_stake_price(&protocol_settings, &next_validator_set, stake);
+        let staking_history = self.record_staking_fact(StakingFact::SecondaryStakeDecreased {
+            validator_id,
+            amount,
+            stake: U128::from(stake),
+        });
+        //
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
+        self.next_validator_set.set(&next_validator_set);
+        //
+        self.sync_state_to_registry();
+    }
+    //
+    fn withdraw_secondary_stake(&mut self, account_id: AccountId, destination: Option<AccountId>) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_not_frozen();
+        self.assert_account_is_not_frozen(&account_id);
+        self.assert_asset_transfer_is_not_paused();
+        let destination = self.get_withdrawal_destination_of(&account_id, destination);
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        if self.unbonded_secondary_stakes.contains_key(&account_id) {
+            let eligibility = self.evaluate_unbonded_secondary_stakes_of(&account_id);
+            let amount_to_withdraw = eligibility.unlocked_amount();
+            let remained_stakes = eligibility.locked_references();
+            if remained_stakes.len() > 0 {
+                self.unbonded_secondary_stakes
+                    .insert(&account_id, &remained_stakes);
+            } else {
+                self.unbonded_secondary_stakes.remove(&account_id);
+            }
+            if amount_to_withdraw > 0 {
+                self.transfer_with_withdrawal_receipt(
+                    WithdrawalKind::SecondaryStake,
+                    &account_id,
+                    &protocol_settings
+                        .secondary_stake_token
+                        .unwrap()
+                        .contract_account,
+                    &destination,
+                    amount_to_withdraw,
+                );
+            }
+        };
+    }
+    //
+    fn appeal_penalty(&mut self, escrow_index: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_not_frozen();
+        self.internal_appeal_penalty(&env::predecessor_account_id(), escrow_index.0);
+    }
+    //
+    fn cancel_queued_validator_registration(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_not_frozen();
+        self.internal_cancel_queued_validator_registration(&env::predecessor_account_id());
+    }
 }
 
 impl AppchainAnchor {
+    //
+    pub fn internal_decrease_delegation(
+        &mut self,
+        delegator_id: &AccountId,
+        validator_id: &AccountId,
+        amount: U128,
+    ) {
+        self.assert_account_is_not_frozen(delegator_id);
+        match self.appchain_state {
+            AppchainState::Active => (),
+            _ => panic!(
+                "Cannot decrease delegation while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            amount.0
+                >= protocol_settings
+                    .minimum_delegator_deposit_changing_amount
+                    .0,
+            "Too few amount to decrease. It should not be less than {} OCT.",
+            protocol_settings
+                .minimum_delegator_deposit_changing_amount
+                .0
+                / OCT_DECIMALS_VALUE
+        );
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_delegator_id(delegator_id, validator_id, &next_validator_set);
+        self.assert_delegation_is_not_locked(delegator_id, validator_id);
+        assert!(
+            next_validator_set
+                .get_delegator(delegator_id, validator_id)
+                .unwrap()
+                .deposit_amount
+                >= protocol_settings.minimum_delegator_deposit.0 + amount.0,
+            "Unable to decrease so much stake."
+        );
+        self.assert_total_stake_price(&protocol_settings, &next_validator_set, amount.0);
+        let staking_history = self.record_staking_fact(StakingFact::DelegationDecreased {
+            delegator_id: delegator_id.clone(),
+            validator_id: validator_id.clone(),
+            amount,
+        });
+        //
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
+        self.next_validator_set.set(&next_validator_set);
+        //
+        self.sync_state_to_registry();
+    }
+    //
+    pub fn internal_unbond_delegation(
+        &mut self,
+        delegator_id: &AccountId,
+        validator_id: &AccountId,
+    ) {
+        self.assert_account_is_not_frozen(delegator_id);
+        match self.appchain_state {
+            AppchainState::Active | AppchainState::Broken => (),
+            _ => panic!(
+                "Cannot unbond delegation while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_delegator_id(delegator_id, validator_id, &next_validator_set);
+        self.assert_delegation_is_not_locked(delegator_id, validator_id);
+        let delegator = next_validator_set
+            .get_delegator(delegator_id, validator_id)
+            .unwrap();
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        self.assert_total_stake_price(
+            &protocol_settings,
+            &next_validator_set,
+            delegator.deposit_amount,
+        );
+        let staking_history = self.record_staking_fact(StakingFact::DelegatorUnbonded {
+            delegator_id: delegator_id.clone(),
+            validator_id: validator_id.clone(),
+            amount: U128::from(delegator.deposit_amount),
+        });
+        //
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
+        self.next_validator_set.set(&next_validator_set);
+        //
+        self.sync_state_to_registry();
+    }
+    // The delegation is moved within the next validator set, the total stake is not changed
+    // and no OCT token is transferred. The lock of the delegation (if any) is moved along.
+    pub fn internal_change_delegated_validator(
+        &mut self,
+        delegator_id: &AccountId,
+        old_validator_id: &AccountId,
+        new_validator_id: &AccountId,
+    ) {
+        self.assert_account_is_not_frozen(delegator_id);
+        match self.appchain_state {
+            AppchainState::Staging | AppchainState::Active => (),
+            _ => panic!(
+                "Cannot change delegated validator while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        assert!(
+            !old_validator_id.eq(new_validator_id),
+            "The new validator should be different from the old one."
+        );
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_delegator_id(delegator_id, old_validator_id, &next_validator_set);
+        self.assert_validator_id(new_validator_id, &next_validator_set);
+        let new_validator = next_validator_set.get_validator(new_validator_id).unwrap();
+        assert!(
+            new_validator.can_be_delegated_to,
+            "Validator '{}' cannot be delegated to.",
+            new_validator_id
+        );
+        assert!(
+            !new_validator.is_jailed,
+            "Validator '{}' is jailed.",
+            new_validator_id
+        );
+        assert!(
+            !self.validator_retirements.contains_key(new_validator_id),
+            "Validator '{}' is retiring.",
+            new_validator_id
+        );
+        let delegator = next_validator_set
+            .get_delegator(delegator_id, old_validator_id)
+            .unwrap();
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        assert!(
+            self.is_total_stake_of_validator_allowed(
+                &protocol_settings,
+                new_validator.total_stake + delegator.deposit_amount,
+            ),
+            "The total stake of validator '{}' is too much.",
+            new_validator_id
+        );
+        // The count of validators delegated by the delegator is not increased,
+        // as the delegation to the old validator is removed.
+        let staking_history = self.record_staking_fact(StakingFact::DelegationRedelegated {
+            delegator_id: delegator_id.clone(),
+            from_validator_id: old_validator_id.clone(),
+            to_validator_id: new_validator_id.clone(),
+            amount: U128::from(delegator.deposit_amount),
+        });
+        //
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
+        self.next_validator_set.set(&next_validator_set);
+        //
+        self.sync_state_to_registry();
+    }
     //
     fn assert_total_stake_price(
         &self,
@@ -702,4 +1282,67 @@ impl AppchainAnchor {
             }
         }
     }
+    //
+    fn assert_delegation_is_not_locked(&self, delegator_id: &AccountId, validator_id: &AccountId) {
+        if let Some(lock) = self
+            .delegation_locks
+            .get(&(delegator_id.clone(), validator_id.clone()))
+        {
+            let latest_era = self
+                .validator_set_histories
+                .get()
+                .unwrap()
+                .index_range()
+                .end_index
+                .0;
+            assert!(
+                !lock.is_locked_in(latest_era),
+                "The delegation is locked until era '{}'.",
+                lock.unlock_era.0
+            );
+        }
+    }
+    // Get the number of consecutive eras (up to the latest one) in which the validator is
+    // included in the validator set, the counting stops once it reaches `maximum_count`.
+    fn get_bonded_era_count_of(&self, validator_id: &AccountId, maximum_count: u64) -> u64 {
+        let validator_set_histories = self.validator_set_histories.get().unwrap();
+        let latest_era = validator_set_histories.index_range().end_index.0;
+        let mut count: u64 = 0;
+        while count < maximum_count && count <= latest_era {
+            match validator_set_histories.get(&(latest_era - count)) {
+                Some(validator_set) if validator_set.contains_validator(validator_id) => count += 1,
+                _ => break,
+            }
+        }
+        count
+    }
+    //
+    fn record_reward_withdrawal(
+        &mut self,
+        account_id: &AccountId,
+        validator_id: &AccountId,
+        amount: Balance,
+        destination: &AccountId,
+    ) {
+        let mut histories = self
+            .reward_withdrawal_histories
+            .get(account_id)
+            .unwrap_or_default();
+        histories.push(RewardWithdrawalHistory {
+            validator_id: validator_id.clone(),
+            amount: U128::from(amount),
+            destination: destination.clone(),
+            block_height: U64::from(env::block_height()),
+            timestamp: U64::from(env::block_timestamp()),
+        });
+        self.reward_withdrawal_histories
+            .insert(account_id, &histories);
+    }
+    /// Transfer a certain amount of OCT token of a treasury source to the destinations
+    /// in the treasury routes of the source and the treasury account. The amount without
+    /// a destination is locked in this contract forever (burnt).
+    pub fn transfer_oct_token_to_treasury(&mut self, source: TreasurySource, amount: Balance) {
+        let oct_token = self.oct_token.get().unwrap();
+        self.internal_route_to_treasury(source, &oct_token.contract_account, amount);
+    }
 }
@@ -0,0 +1,147 @@
+use crate::interfaces::StakingAdminActions;
+use crate::*;
+
+#[near_bindgen]
+impl StakingAdminActions for AppchainAnchor {
+    //
+    fn slash_validator(&mut self, validator_id: AccountId, amount: U128) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, next_validator_set.validator_set());
+        let validator = next_validator_set
+            .validator_set()
+            .get_validator(&validator_id)
+            .unwrap();
+        assert!(amount.0 > 0, "The slashed amount should not be zero.");
+        assert!(
+            amount.0 <= validator.slashable_deposit_amount(),
+            "The slashed amount exceeds the slashable deposit of validator '{}'.",
+            validator_id
+        );
+        let staking_history =
+            self.apply_administrative_staking_fact(StakingFact::ValidatorSlashed {
+                validator_id: validator_id.clone(),
+                amount,
+            });
+        self.internal_escrow_penalty(&validator_id, amount.0, staking_history.index.0);
+    }
+    //
+    fn slash_delegator(&mut self, delegator_id: AccountId, validator_id: AccountId, amount: U128) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_delegator_id(
+            &delegator_id,
+            &validator_id,
+            next_validator_set.validator_set(),
+        );
+        let delegator = next_validator_set
+            .validator_set()
+            .get_delegator(&delegator_id, &validator_id)
+            .unwrap();
+        assert!(amount.0 > 0, "The slashed amount should not be zero.");
+        assert!(
+            amount.0 <= delegator.deposit_amount,
+            "The slashed amount exceeds the delegation of delegator '{}'.",
+            delegator_id
+        );
+        let staking_history =
+            self.apply_administrative_staking_fact(StakingFact::DelegatorSlashed {
+                delegator_id: delegator_id.clone(),
+                validator_id,
+                amount,
+            });
+        self.internal_escrow_penalty(&delegator_id, amount.0, staking_history.index.0);
+    }
+    //
+    fn force_unbond_validator(&mut self, validator_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        self.apply_administrative_staking_fact(StakingFact::ValidatorForceUnbonded {
+            validator_id: validator_id.clone(),
+        });
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        next_validator_set.add_auto_unbonding_validator(&validator_id);
+        self.next_validator_set.set(&next_validator_set);
+    }
+    //
+    fn jail_validator(&mut self, validator_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, next_validator_set.validator_set());
+        assert!(
+            !next_validator_set
+                .get_validator(&validator_id)
+                .unwrap()
+                .is_jailed,
+            "Validator '{}' is already jailed.",
+            validator_id
+        );
+        self.apply_administrative_staking_fact(StakingFact::ValidatorJailed { validator_id });
+    }
+    //
+    fn unjail_validator(&mut self, validator_id: AccountId) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, next_validator_set.validator_set());
+        assert!(
+            next_validator_set
+                .validator_set()
+                .get_validator(&validator_id)
+                .unwrap()
+                .is_jailed,
+            "Validator '{}' is not jailed.",
+            validator_id
+        );
+        self.apply_administrative_staking_fact(StakingFact::ValidatorUnjailed { validator_id });
+    }
+    //
+    fn conclude_penalty_appeal(&mut self, escrow_index: U64, is_upheld: bool) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        self.internal_conclude_penalty_appeal(escrow_index.0, is_upheld);
+    }
+    //
+    fn change_commission_percent_of_validator(
+        &mut self,
+        validator_id: AccountId,
+        commission_percent: Option<u16>,
+    ) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        if let Some(percent) = commission_percent {
+            assert!(
+                percent <= 100,
+                "The commission percent should not be greater than 100."
+            );
+        }
+        self.apply_administrative_staking_fact(StakingFact::ValidatorCommissionChanged {
+            validator_id,
+            commission_percent,
+        });
+    }
+}
+
+impl AppchainAnchor {
+    // Record an administrative staking fact and apply it to the next validator set,
+    // the fact will be applied to the validator set of next era in era switching.
+    fn apply_administrative_staking_fact(&mut self, staking_fact: StakingFact) -> StakingHistory {
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        let staking_history = self.record_staking_fact(staking_fact);
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
+        self.next_validator_set.set(&next_validator_set);
+        //
+        self.sync_state_to_registry();
+        staking_history
+    }
+}
@@ -7,11 +7,13 @@ use near_contract_standards::fungible_token::metadata::FungibleTokenMetadata;
 impl SudoActions for AppchainAnchor {
     //
     fn set_owner_pk(&mut self, public_key: PublicKey) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         self.owner_pk = public_key;
     }
     //
     fn set_metadata_of_wrapped_appchain_token(&mut self, metadata: FungibleTokenMetadata) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
         wrapped_appchain_token.metadata = metadata;
@@ -23,6 +25,7 @@ impl SudoActions for AppchainAnchor {
         premined_beneficiary: AccountId,
         premined_balance: U128,
     ) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut wrapped_appchain_token = self.wrapped_appchain_token.get().unwrap();
         wrapped_appchain_token.premined_beneficiary = Some(premined_beneficiary);
@@ -31,6 +34,7 @@ impl SudoActions for AppchainAnchor {
     }
     //
     fn regenerate_user_staking_histories(&mut self) -> MultiTxsOperationProcessingResult {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut user_staking_histories = self.user_staking_histories.get().unwrap();
         let staking_histories = self.staking_histories.get().unwrap();
@@ -49,12 +53,14 @@ impl SudoActions for AppchainAnchor {
     }
     //
     fn reset_beefy_light_client(&mut self, initial_public_keys: Vec<String>) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         self.beefy_light_client_state
             .set(&beefy_light_client::new(initial_public_keys));
     }
     //
     fn pause_asset_transfer(&mut self) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert!(
             !self.asset_transfer_is_paused,
@@ -64,6 +70,7 @@ impl SudoActions for AppchainAnchor {
     }
     //
     fn resume_asset_transfer(&mut self) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert!(
             self.asset_transfer_is_paused,
@@ -73,6 +80,7 @@ impl SudoActions for AppchainAnchor {
     }
     //
     fn pause_rewards_withdrawal(&mut self) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert!(
             !self.rewards_withdrawal_is_paused,
@@ -82,6 +90,7 @@ impl SudoActions for AppchainAnchor {
     }
     //
     fn resume_rewards_withdrawal(&mut self) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         assert!(
             self.rewards_withdrawal_is_paused,
@@ -90,11 +99,45 @@ impl SudoActions for AppchainAnchor {
         self.rewards_withdrawal_is_paused = false;
     }
     //
+    fn set_staking_status(&mut self, status: StakingStatus, reason: String) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(status != self.staking_status, "The status is not changed.");
+        self.staking_status = status.clone();
+        let mut staking_status_changes = self.staking_status_changes.get().unwrap();
+        staking_status_changes.append(&mut StakingStatusChange {
+            status,
+            reason,
+            timestamp: U64::from(env::block_timestamp()),
+            index: U64::from(0),
+        });
+        self.staking_status_changes.set(&staking_status_changes);
+    }
+    //
+    fn turn_on_maintenance_mode(&mut self) {
+        self.assert_owner();
+        assert!(
+            !self.maintenance_mode_is_on,
+            "Maintenance mode is already turned on."
+        );
+        self.maintenance_mode_is_on = true;
+    }
+    //
+    fn turn_off_maintenance_mode(&mut self) {
+        self.assert_owner();
+        assert!(
+            self.maintenance_mode_is_on,
+            "Maintenance mode is already turned off."
+        );
+        self.maintenance_mode_is_on = false;
+    }
+    //
     fn change_account_id_in_appchain_of_validator(
         &mut self,
         validator_id: AccountId,
         account_id_in_appchain: String,
     ) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         self.internal_change_account_id_in_appchain_of_validator(
             &validator_id,
@@ -103,6 +146,7 @@ impl SudoActions for AppchainAnchor {
     }
     //
     fn set_latest_applied_appchain_message_nonce(&mut self, nonce: u32) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         let mut permissionless_actions_status = self.permissionless_actions_status.get().unwrap();
         permissionless_actions_status.latest_applied_appchain_message_nonce = nonce;
@@ -117,6 +161,7 @@ impl SudoActions for AppchainAnchor {
         validator_id: AccountId,
         staking_history_index: U64,
     ) {
+        self.assert_not_in_maintenance_mode();
         self.assert_owner();
         self.assert_asset_transfer_is_not_paused();
         let unbonded_stake_references = match delegator_id.clone() {
@@ -144,6 +189,7 @@ impl SudoActions for AppchainAnchor {
                             .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
                             .with_unused_gas_weight(0)
                             .ft_transfer(validator_id.clone(), amount.clone(), None);
+                        self.decrease_total_unbonded_stake(amount.0);
                         found = true;
                         break;
                     }
@@ -167,6 +213,7 @@ impl SudoActions for AppchainAnchor {
                             .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
                             .with_unused_gas_weight(0)
                             .ft_transfer(delegator_id.clone(), amount.clone(), None);
+                        self.decrease_total_unbonded_stake(amount.0);
                         found = true;
                         break;
                     }
@@ -187,4 +234,49 @@ impl SudoActions for AppchainAnchor {
                 .remove(&delegator_id.unwrap_or(validator_id));
         }
     }
+    //
+    fn rebuild_next_validator_set_from_history(
+        &mut self,
+        start_index: U64,
+    ) -> MultiTxsOperationProcessingResult {
+        self.assert_not_in_maintenance_mode();
+        self.assert_owner();
+        assert!(
+            self.maintenance_mode_is_on,
+            "The next validator set can only be rebuilt in maintenance mode."
+        );
+        let staking_histories = self.staking_histories.get().unwrap();
+        let index_range = staking_histories.index_range();
+        assert!(
+            start_index.0 >= index_range.start_index.0
+                && start_index.0 <= index_range.end_index.0 + 1,
+            "Invalid start index of staking histories."
+        );
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        if start_index.0 == index_range.start_index.0 {
+            let result = next_validator_set.clear_validator_set();
+            self.next_validator_set.set(&next_validator_set);
+            if !result.is_ok() {
+                return result;
+            }
+        }
+        for index in start_index.0..index_range.end_index.0 + 1 {
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                self.next_validator_set.set(&next_validator_set);
+                log!(
+                    "Rebuilding of next validator set should be continued with start index '{}'.",
+                    index
+                );
+                return MultiTxsOperationProcessingResult::NeedMoreGas;
+            }
+            if let Some(staking_history) = staking_histories.get(&index) {
+                self.apply_staking_fact_to_next_validator_set(
+                    &mut next_validator_set,
+                    &staking_history.staking_fact,
+                );
+            }
+        }
+        self.next_validator_set.set(&next_validator_set);
+        MultiTxsOperationProcessingResult::Ok
+    }
 }
@@ -1,11 +1,20 @@
-use crate::{interfaces::ValidatorActions, *};
+use crate::{
+    interfaces::ValidatorActions, validator_profiles::assert_validator_profile_is_valid, *,
+};
 
 use borsh::maybestd::collections::HashMap;
+use near_sdk::serde_json;
+
+/// The maximum count of endpoints of each kind of a validator.
+const MAXIMUM_COUNT_OF_VALIDATOR_ENDPOINTS: usize = 4;
+/// The maximum length (in bytes) of an endpoint of a validator.
+const MAXIMUM_LENGTH_OF_VALIDATOR_ENDPOINT: usize = 256;
 
 #[near_bindgen]
 impl ValidatorActions for AppchainAnchor {
     //
     fn set_validator_id_in_appchain(&mut self, account_id_in_appchain: String) {
+        self.assert_not_in_maintenance_mode();
         let validator_id = env::predecessor_account_id();
         self.internal_change_account_id_in_appchain_of_validator(
             &validator_id,
@@ -14,15 +23,195 @@ impl ValidatorActions for AppchainAnchor {
     }
     //
     fn set_validator_profile(&mut self, profile: HashMap<String, String>) {
+        self.assert_not_in_maintenance_mode();
         let validator_id = env::predecessor_account_id();
         let next_validator_set = self.next_validator_set.get().unwrap();
         self.assert_validator_id(&validator_id, &next_validator_set);
+        assert_validator_profile_is_valid(&profile);
         let mut validator_profiles = self.validator_profiles.get().unwrap();
         let mut validator_profile = validator_profiles.get(&validator_id).unwrap();
         validator_profile.profile = profile;
         validator_profiles.insert(validator_profile);
         self.validator_profiles.set(&validator_profiles);
     }
+    //
+    fn set_reward_address_in_appchain(&mut self, reward_address_in_appchain: String) {
+        self.assert_not_in_maintenance_mode();
+        let validator_id = env::predecessor_account_id();
+        let mut next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        let reward_address = AccountIdInAppchain::new(
+            Some(reward_address_in_appchain.clone()),
+            &self.appchain_template_type,
+        );
+        reward_address.assert_valid();
+        //
+        let staking_history =
+            self.record_staking_fact(StakingFact::ValidatorRewardAddressInAppchainChanged {
+                validator_id,
+                reward_address_in_appchain: reward_address.to_string(),
+            });
+        //
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
+        self.next_validator_set.set(&next_validator_set);
+    }
+    //
+    fn set_commission_payout_account(&mut self, account_id: Option<AccountId>) {
+        self.assert_not_in_maintenance_mode();
+        let validator_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&validator_id);
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        match account_id {
+            Some(account_id) => {
+                assert!(
+                    !account_id.eq(&validator_id),
+                    "The payout account should not be the validator itself."
+                );
+                let destinations = self.get_effective_withdrawal_destinations_of(&validator_id);
+                assert!(
+                    destinations.is_empty() || destinations.contains(&account_id),
+                    "The payout account should be in the withdrawal allowlist of the validator."
+                );
+                self.commission_payout_accounts
+                    .insert(&validator_id, &account_id);
+            }
+            None => {
+                self.commission_payout_accounts.remove(&validator_id);
+            }
+        }
+    }
+    //
+    fn announce_retirement(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_staking_is_active();
+        let validator_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&validator_id);
+        match self.appchain_state {
+            AppchainState::Active => (),
+            _ => panic!(
+                "Cannot announce retirement while appchain state is '{}'.",
+                serde_json::to_string(&self.appchain_state).unwrap()
+            ),
+        };
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        assert!(
+            !self.validator_retirements.contains_key(&validator_id),
+            "Validator '{}' has already announced its retirement.",
+            &validator_id
+        );
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        let retiring_era_number = U64::from(
+            next_validator_set.era_number()
+                + protocol_settings.era_count_of_validator_retirement_notice.0,
+        );
+        self.validator_retirements
+            .insert(&validator_id, &retiring_era_number);
+        //
+        self.internal_append_appchain_notification(
+            AppchainNotification::ValidatorRetirementAnnounced {
+                validator_id: validator_id.clone(),
+                retiring_era_number,
+            },
+        );
+        log!(
+            "Validator '{}' will retire in era '{}'.",
+            &validator_id,
+            retiring_era_number.0
+        );
+    }
+    //
+    fn cancel_retirement(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        let validator_id = env::predecessor_account_id();
+        assert!(
+            self.validator_retirements.remove(&validator_id).is_some(),
+            "Validator '{}' has not announced its retirement.",
+            &validator_id
+        );
+        self.internal_append_appchain_notification(
+            AppchainNotification::ValidatorRetirementCancelled { validator_id },
+        );
+    }
+    //
+    fn set_validator_endpoints(&mut self, p2p_multiaddrs: Vec<String>, rpc_endpoints: Vec<String>) {
+        self.assert_not_in_maintenance_mode();
+        let validator_id = env::predecessor_account_id();
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        self.assert_validator_id(&validator_id, &next_validator_set);
+        assert_valid_endpoints(&p2p_multiaddrs, |endpoint| endpoint.starts_with('/'));
+        assert_valid_endpoints(&rpc_endpoints, |endpoint| {
+            ["http://", "https://", "ws://", "wss://"]
+                .iter()
+                .any(|scheme| endpoint.starts_with(scheme))
+        });
+        // The endpoints hidden by the owner are still hidden after being changed.
+        let is_hidden = self
+            .validator_endpoints
+            .get(&validator_id)
+            .map_or(false, |endpoints| endpoints.is_hidden);
+        self.validator_endpoints.insert(
+            &validator_id,
+            &ValidatorEndpoints {
+                p2p_multiaddrs,
+                rpc_endpoints,
+                is_hidden,
+                updated_timestamp: U64::from(env::block_timestamp()),
+            },
+        );
+    }
+    //
+    fn remove_validator_endpoints(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        let validator_id = env::predecessor_account_id();
+        let endpoints = self
+            .validator_endpoints
+            .get(&validator_id)
+            .expect("No endpoints to remove.");
+        // The hidden endpoints are kept, to keep them hidden if they are set again.
+        match endpoints.is_hidden {
+            true => {
+                self.validator_endpoints.insert(
+                    &validator_id,
+                    &ValidatorEndpoints {
+                        p2p_multiaddrs: Vec::new(),
+                        rpc_endpoints: Vec::new(),
+                        ..endpoints
+                    },
+                );
+            }
+            false => {
+                self.validator_endpoints.remove(&validator_id);
+            }
+        }
+    }
+    //
+    fn approve_validator_set_override(&mut self, proposal_index: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.internal_approve_validator_set_override(
+            &env::predecessor_account_id(),
+            proposal_index.0,
+        );
+    }
+}
+
+fn assert_valid_endpoints<F: Fn(&str) -> bool>(endpoints: &Vec<String>, is_valid: F) {
+    assert!(
+        endpoints.len() <= MAXIMUM_COUNT_OF_VALIDATOR_ENDPOINTS,
+        "Too many endpoints. The maximum count is {}.",
+        MAXIMUM_COUNT_OF_VALIDATOR_ENDPOINTS
+    );
+    endpoints.iter().for_each(|endpoint| {
+        assert!(
+            endpoint.len() <= MAXIMUM_LENGTH_OF_VALIDATOR_ENDPOINT && is_valid(endpoint),
+            "Invalid endpoint '{}'.",
+            endpoint
+        );
+    });
 }
 
 impl AppchainAnchor {
@@ -51,7 +240,10 @@ impl AppchainAnchor {
             validator_id_in_appchain: account_id_in_appchain.clone(),
         });
         //
-        next_validator_set.apply_staking_fact(&staking_history.staking_fact);
+        self.apply_staking_fact_to_next_validator_set(
+            &mut next_validator_set,
+            &staking_history.staking_fact,
+        );
         self.next_validator_set.set(&next_validator_set);
         //
         self.sync_state_to_registry();
@@ -0,0 +1,119 @@
+use crate::{interfaces::WithdrawalAllowlistManager, *};
+
+/// The maximum number of destinations in the withdrawal allowlist of an account.
+const MAXIMUM_WITHDRAWAL_DESTINATIONS: usize = 5;
+
+#[near_bindgen]
+impl WithdrawalAllowlistManager for AppchainAnchor {
+    //
+    fn set_withdrawal_allowlist(&mut self, destinations: Vec<AccountId>) {
+        self.assert_not_in_maintenance_mode();
+        let account_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&account_id);
+        assert!(
+            destinations.len() <= MAXIMUM_WITHDRAWAL_DESTINATIONS,
+            "Too many destinations. The maximum number is {}.",
+            MAXIMUM_WITHDRAWAL_DESTINATIONS
+        );
+        for (index, destination) in destinations.iter().enumerate() {
+            assert!(
+                !destinations[index + 1..].contains(destination),
+                "Duplicated destination '{}'.",
+                destination
+            );
+        }
+        match self.withdrawal_allowlists.get(&account_id) {
+            Some(mut allowlist) if !allowlist.effective_destinations().is_empty() => {
+                // The allowlist is locked in, the change takes effect after the delay.
+                allowlist.settle_pending_change();
+                let protocol_settings = self.protocol_settings.get().unwrap();
+                let effective_timestamp = env::block_timestamp()
+                    + self
+                        .time_provider()
+                        .nanoseconds_of_days(protocol_settings.withdrawal_allowlist_change_delay.0);
+                allowlist.pending_change = Some(PendingWithdrawalAllowlistChange {
+                    destinations,
+                    effective_timestamp: U64::from(effective_timestamp),
+                });
+                self.withdrawal_allowlists.insert(&account_id, &allowlist);
+                log!(
+                    "The withdrawal allowlist of '{}' will be changed at '{}'.",
+                    &account_id,
+                    effective_timestamp
+                );
+            }
+            _ => {
+                assert!(
+                    !destinations.is_empty(),
+                    "The withdrawal allowlist should not be empty."
+                );
+                self.withdrawal_allowlists.insert(
+                    &account_id,
+                    &WithdrawalAllowlist {
+                        destinations,
+                        pending_change: None,
+                    },
+                );
+            }
+        }
+    }
+    //
+    fn cancel_withdrawal_allowlist_change(&mut self) {
+        self.assert_not_in_maintenance_mode();
+        let account_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&account_id);
+        let mut allowlist = self
+            .withdrawal_allowlists
+            .get(&account_id)
+            .expect("No withdrawal allowlist is set.");
+        allowlist.settle_pending_change();
+        assert!(
+            allowlist.pending_change.is_some(),
+            "No pending change of withdrawal allowlist."
+        );
+        allowlist.pending_change = None;
+        self.withdrawal_allowlists.insert(&account_id, &allowlist);
+    }
+}
+
+impl AppchainAnchor {
+    // Get the effective destinations in the withdrawal allowlist of an account,
+    // an empty list means the account has no withdrawal allowlist.
+    pub fn get_effective_withdrawal_destinations_of(
+        &self,
+        account_id: &AccountId,
+    ) -> Vec<AccountId> {
+        self.withdrawal_allowlists
+            .get(account_id)
+            .map(|allowlist| allowlist.effective_destinations())
+            .unwrap_or_default()
+    }
+    // Get the destination of a withdrawal of an account. If the account has an effective
+    // withdrawal allowlist, the destination must be one of the allowlist, and defaults to
+    // the first one. Otherwise, the destination can only be the account itself.
+    pub fn get_withdrawal_destination_of(
+        &self,
+        account_id: &AccountId,
+        destination: Option<AccountId>,
+    ) -> AccountId {
+        let destinations = self.get_effective_withdrawal_destinations_of(account_id);
+        match destination {
+            Some(destination) => {
+                assert!(
+                    match destinations.is_empty() {
+                        true => destination.eq(account_id),
+                        false => destinations.contains(&destination),
+                    },
+                    "Destination '{}' is not allowed for the withdrawal of '{}'.",
+                    &destination,
+                    account_id
+                );
+                destination
+            }
+            None => destinations
+                .first()
+                .cloned()
+                .unwrap_or_else(|| account_id.clone()),
+        }
+    }
+}
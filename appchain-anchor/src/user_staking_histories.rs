@@ -27,12 +27,26 @@ impl UserStakingHistories {
             | StakingFact::ValidatorAutoUnbonded { validator_id, .. }
             | StakingFact::ValidatorDelegationEnabled { validator_id }
             | StakingFact::ValidatorDelegationDisabled { validator_id }
-            | StakingFact::ValidatorIdInAppchainChanged { validator_id, .. } => validator_id,
+            | StakingFact::ValidatorIdInAppchainChanged { validator_id, .. }
+            | StakingFact::ValidatorRewardAddressInAppchainChanged { validator_id, .. }
+            | StakingFact::ValidatorSlashed { validator_id, .. }
+            | StakingFact::ValidatorForceUnbonded { validator_id }
+            | StakingFact::ValidatorJailed { validator_id }
+            | StakingFact::ValidatorUnjailed { validator_id }
+            | StakingFact::ValidatorCommissionChanged { validator_id, .. }
+            | StakingFact::SecondaryStakeIncreased { validator_id, .. }
+            | StakingFact::SecondaryStakeDecreased { validator_id, .. }
+            | StakingFact::SecondaryStakeUnbonded { validator_id, .. } => validator_id,
             StakingFact::DelegatorRegistered { delegator_id, .. }
             | StakingFact::DelegationIncreased { delegator_id, .. }
             | StakingFact::DelegationDecreased { delegator_id, .. }
             | StakingFact::DelegatorUnbonded { delegator_id, .. }
-            | StakingFact::DelegatorAutoUnbonded { delegator_id, .. } => delegator_id,
+            | StakingFact::DelegatorAutoUnbonded { delegator_id, .. }
+            | StakingFact::DelegatorSlashed { delegator_id, .. }
+            | StakingFact::DelegationRedelegated { delegator_id, .. }
+            | StakingFact::DelegationLocked { delegator_id, .. } => delegator_id,
+            StakingFact::VestingLockedStakeIncreased { depositor_id, .. }
+            | StakingFact::VestingLockedStakeUnbonded { depositor_id, .. } => depositor_id,
         };
         self.account_id_set.insert(account_id);
         let mut staking_histories_indexes = match self.staking_histories_map.get(account_id) {
@@ -53,6 +67,18 @@ impl UserStakingHistories {
         }
     }
     ///
+    pub fn remove_staking_history_indexes_of(
+        &mut self,
+        account_id: &AccountId,
+        indexes: &Vec<u64>,
+    ) {
+        if let Some(mut staking_histories_indexes) = self.staking_histories_map.get(account_id) {
+            staking_histories_indexes.retain(|index| !indexes.contains(index));
+            self.staking_histories_map
+                .insert(account_id, &staking_histories_indexes);
+        }
+    }
+    ///
     pub fn clear(&mut self) -> MultiTxsOperationProcessingResult {
         for account_id in self.account_id_set.to_vec() {
             self.staking_histories_map.remove(&account_id);
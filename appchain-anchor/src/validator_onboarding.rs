@@ -0,0 +1,228 @@
+use crate::*;
+use borsh::maybestd::collections::HashMap;
+use near_contract_standards::fungible_token::core::ext_ft_core;
+
+impl AppchainAnchor {
+    // Register a validator while the appchain is booting. The registration is admitted
+    // directly if there is no queued registration and the admissions of the day do not
+    // reach the limit, otherwise it is queued.
+    pub fn internal_register_validator_in_booting(
+        &mut self,
+        validator_id: AccountId,
+        validator_id_in_appchain: String,
+        profile: HashMap<String, String>,
+        deposit_amount: U128,
+        can_be_delegated_to: bool,
+    ) {
+        let formatted_validator_id_in_appchain = AccountIdInAppchain::new(
+            Some(validator_id_in_appchain.clone()),
+            &self.appchain_template_type,
+        );
+        formatted_validator_id_in_appchain.assert_valid();
+        let queued_registrations = self.get_pending_queued_validator_registrations();
+        queued_registrations.iter().for_each(|registration| {
+            assert!(
+                !registration.validator_id.eq(&validator_id),
+                "The account '{}' has already been queued.",
+                &validator_id
+            );
+            assert!(
+                !registration
+                    .validator_id_in_appchain
+                    .eq(&formatted_validator_id_in_appchain.to_string()),
+                "The account '{}' in appchain is already used by a queued registration.",
+                &formatted_validator_id_in_appchain.origin_to_string()
+            );
+        });
+        if let Err(message) = self.check_validator_registration(
+            &validator_id,
+            &formatted_validator_id_in_appchain.to_string(),
+            deposit_amount.0,
+            queued_registrations.len() as u64,
+        ) {
+            panic!("{}", message);
+        }
+        if queued_registrations.is_empty() && self.take_daily_validator_admission() {
+            self.internal_admit_validator_registration(
+                validator_id,
+                validator_id_in_appchain,
+                profile,
+                deposit_amount,
+                can_be_delegated_to,
+            );
+            return;
+        }
+        let mut registrations = self.queued_validator_registrations.get().unwrap();
+        let registration = registrations.append(&mut QueuedValidatorRegistration {
+            validator_id,
+            validator_id_in_appchain: formatted_validator_id_in_appchain.to_string(),
+            profile,
+            deposit_amount,
+            can_be_delegated_to,
+            queued_timestamp: U64::from(env::block_timestamp()),
+            status: QueuedValidatorRegistrationStatus::Queued,
+            index: U64::from(0),
+        });
+        self.queued_validator_registrations.set(&registrations);
+        log!(
+            "Registration of validator '{}' is queued as '{}'.",
+            registration.validator_id,
+            registration.index.0
+        );
+    }
+    // Admit the queued validator registrations in order, until the admissions of the day
+    // reach the limit. If the appchain is not booting any more, the remaining registrations
+    // are dropped.
+    pub fn internal_admit_queued_validator_registrations(
+        &mut self,
+    ) -> MultiTxsOperationProcessingResult {
+        let mut registrations = self.queued_validator_registrations.get().unwrap();
+        let is_booting = self.appchain_state.eq(&AppchainState::Booting);
+        let mut result = MultiTxsOperationProcessingResult::Ok;
+        while let Some(mut registration) =
+            registrations.get(&self.next_queued_validator_registration_index)
+        {
+            if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
+                result = MultiTxsOperationProcessingResult::NeedMoreGas;
+                break;
+            }
+            if registration.status == QueuedValidatorRegistrationStatus::Queued {
+                let checking_result = match is_booting {
+                    true => self.check_validator_registration(
+                        &registration.validator_id,
+                        &registration.validator_id_in_appchain,
+                        registration.deposit_amount.0,
+                        0,
+                    ),
+                    false => Err("The appchain is not booting any more.".to_string()),
+                };
+                match checking_result {
+                    Ok(()) => {
+                        if !self.take_daily_validator_admission() {
+                            break;
+                        }
+                        self.internal_admit_validator_registration(
+                            registration.validator_id.clone(),
+                            registration.validator_id_in_appchain.clone(),
+                            registration.profile.clone(),
+                            registration.deposit_amount,
+                            registration.can_be_delegated_to,
+                        );
+                        registration.status = QueuedValidatorRegistrationStatus::Admitted;
+                    }
+                    Err(reason) => {
+                        self.return_deposit_of_queued_validator_registration(&registration);
+                        registration.status = QueuedValidatorRegistrationStatus::Dropped { reason };
+                    }
+                }
+                registrations.insert(&registration.index.0, &registration);
+            }
+            self.next_queued_validator_registration_index += 1;
+        }
+        self.queued_validator_registrations.set(&registrations);
+        result
+    }
+    //
+    pub fn internal_cancel_queued_validator_registration(&mut self, validator_id: &AccountId) {
+        let mut registration = self
+            .get_pending_queued_validator_registrations()
+            .into_iter()
+            .find(|registration| registration.validator_id.eq(validator_id))
+            .expect("No queued registration of the account.");
+        self.return_deposit_of_queued_validator_registration(&registration);
+        registration.status = QueuedValidatorRegistrationStatus::Cancelled;
+        let mut registrations = self.queued_validator_registrations.get().unwrap();
+        registrations.insert(&registration.index.0, &registration);
+        self.queued_validator_registrations.set(&registrations);
+    }
+    //
+    pub fn get_pending_queued_validator_registrations(&self) -> Vec<QueuedValidatorRegistration> {
+        let registrations = self.queued_validator_registrations.get().unwrap();
+        let mut index = self.next_queued_validator_registration_index;
+        let mut pending_registrations = Vec::new();
+        while let Some(registration) = registrations.get(&index) {
+            if registration.status == QueuedValidatorRegistrationStatus::Queued {
+                pending_registrations.push(registration);
+            }
+            index += 1;
+        }
+        pending_registrations
+    }
+    // Check whether a validator registration can be admitted in the next validator set,
+    // with the same conditions as a normal registration. The param `queued_count` is the
+    // count of the registrations queued before it.
+    fn check_validator_registration(
+        &self,
+        validator_id: &AccountId,
+        validator_id_in_appchain: &String,
+        deposit_amount: Balance,
+        queued_count: u64,
+    ) -> Result<(), String> {
+        let next_validator_set = self.next_validator_set.get().unwrap();
+        if next_validator_set.contains_validator(validator_id) {
+            return Err(format!(
+                "The account '{}' has already been registered.",
+                validator_id
+            ));
+        }
+        if let Some(validator_profile) = self
+            .validator_profiles
+            .get()
+            .unwrap()
+            .get_by_id_in_appchain(validator_id_in_appchain)
+        {
+            if next_validator_set.contains_validator(&validator_profile.validator_id) {
+                return Err(format!(
+                    "The account '{}' in appchain is already used by a validator in next era.",
+                    validator_id_in_appchain
+                ));
+            }
+        }
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        if deposit_amount < protocol_settings.minimum_validator_deposit.0 {
+            return Err("The deposit of the validator is too few.".to_string());
+        }
+        if next_validator_set.validator_count() + queued_count
+            >= protocol_settings.maximum_validator_count.0
+        {
+            return Err("The count of registered validators exceeds the upper limit.".to_string());
+        }
+        Ok(())
+    }
+    // Count an admission in the current day, return false if the admissions of the day
+    // have reached the limit.
+    fn take_daily_validator_admission(&mut self) -> bool {
+        let day = env::block_timestamp() / self.time_provider().nanoseconds_of_days(1);
+        if self.daily_validator_admissions.day.0 != day {
+            self.daily_validator_admissions = DailyValidatorAdmissions {
+                day: U64::from(day),
+                count: U64::from(0),
+            };
+        }
+        let protocol_settings = self.protocol_settings.get().unwrap();
+        if self.daily_validator_admissions.count.0
+            >= protocol_settings
+                .maximum_validator_admissions_per_day_in_booting
+                .0
+        {
+            return false;
+        }
+        self.daily_validator_admissions.count.0 += 1;
+        true
+    }
+    //
+    fn return_deposit_of_queued_validator_registration(
+        &self,
+        registration: &QueuedValidatorRegistration,
+    ) {
+        ext_ft_core::ext(self.oct_token.get().unwrap().contract_account)
+            .with_attached_deposit(1)
+            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+            .with_unused_gas_weight(0)
+            .ft_transfer(
+                registration.validator_id.clone(),
+                registration.deposit_amount,
+                None,
+            );
+    }
+}
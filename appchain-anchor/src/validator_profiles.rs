@@ -1,4 +1,10 @@
 use crate::*;
+use borsh::maybestd::collections::HashMap;
+
+/// The maximum count of entries in the profile of a validator.
+const MAXIMUM_COUNT_OF_VALIDATOR_PROFILE_ENTRIES: usize = 16;
+/// The maximum length (in bytes) of a key or a value in the profile of a validator.
+const MAXIMUM_LENGTH_OF_VALIDATOR_PROFILE_ENTRY: usize = 512;
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct ValidatorProfiles {
@@ -77,3 +83,46 @@ impl ValidatorProfiles {
             .remove_raw(&validator_id.try_to_vec().unwrap())
     }
 }
+
+/// Assert the profile of a validator is valid. The values of the well-known keys
+/// (`email`, `website` and `logo`) are checked by their formats, while the values
+/// of other keys are free-form.
+pub fn assert_validator_profile_is_valid(profile: &HashMap<String, String>) {
+    assert!(
+        profile.len() <= MAXIMUM_COUNT_OF_VALIDATOR_PROFILE_ENTRIES,
+        "Too many entries in validator profile. The maximum count is {}.",
+        MAXIMUM_COUNT_OF_VALIDATOR_PROFILE_ENTRIES
+    );
+    profile.iter().for_each(|(key, value)| {
+        assert!(
+            key.len() <= MAXIMUM_LENGTH_OF_VALIDATOR_PROFILE_ENTRY
+                && value.len() <= MAXIMUM_LENGTH_OF_VALIDATOR_PROFILE_ENTRY,
+            "The key or value of '{}' in validator profile is too long.",
+            key
+        );
+    });
+    if let Some(email) = profile.get(VALIDATOR_PROFILE_KEY_OF_EMAIL) {
+        let parts: Vec<&str> = email.split('@').collect();
+        assert!(
+            parts.len() == 2
+                && !parts[0].is_empty()
+                && parts[1].contains('.')
+                && !email.contains(char::is_whitespace),
+            "Invalid email '{}' in validator profile.",
+            email
+        );
+    }
+    for key in [
+        VALIDATOR_PROFILE_KEY_OF_WEBSITE,
+        VALIDATOR_PROFILE_KEY_OF_LOGO,
+    ] {
+        if let Some(url) = profile.get(key) {
+            assert!(
+                url.starts_with("https://") && !url.contains(char::is_whitespace),
+                "Invalid {} '{}' in validator profile. It must be a 'https' url.",
+                key,
+                url
+            );
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use near_sdk::borsh::maybestd::collections::HashMap;
 use near_sdk::BlockHeight;
 
+use crate::storage_migration::OldValidator;
 use crate::*;
 
 pub mod next_validator_set;
@@ -21,6 +23,22 @@ pub struct Validator {
     pub total_stake: Balance,
     /// Whether the validator accepts delegation from delegators.
     pub can_be_delegated_to: bool,
+    /// The account id in the appchain for receiving appchain-native benefits
+    /// (e.g. tx fee shares) of the validator.
+    pub reward_address_in_appchain: Option<String>,
+    /// The vesting-locked deposits of the validator, which are deposited by other accounts
+    /// and can only be withdrawn to the depositors. They are included in `deposit_amount`.
+    pub vesting_locked_deposits: HashMap<AccountId, Balance>,
+    /// Whether the validator is jailed. A jailed validator will not be profited.
+    pub is_jailed: bool,
+    /// The commission percent of the validator, which overrides the default
+    /// commission percent in protocol settings.
+    pub commission_percent: Option<u16>,
+    /// The deposited amount of the secondary stake token of the validator.
+    pub secondary_deposit_amount: Balance,
+    /// The stake converted from the deposit of the secondary stake token,
+    /// which is included in `total_stake` but not in `deposit_amount`.
+    pub secondary_stake: Balance,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -166,6 +184,12 @@ impl ValidatorSet {
                         deposit_amount: amount.0,
                         total_stake: amount.0,
                         can_be_delegated_to: *can_be_delegated_to,
+                        reward_address_in_appchain: None,
+                        vesting_locked_deposits: HashMap::new(),
+                        is_jailed: false,
+                        commission_percent: None,
+                        secondary_deposit_amount: 0,
+                        secondary_stake: 0,
                     },
                 );
                 self.total_stake += amount.0;
@@ -366,6 +390,149 @@ impl ValidatorSet {
                 validator.validator_id_in_appchain = validator_id_in_appchain.to_string();
                 self.validators.insert(validator_id, &validator);
             }
+            StakingFact::ValidatorRewardAddressInAppchainChanged {
+                validator_id,
+                reward_address_in_appchain,
+            } => {
+                let mut validator = self.validators.get(validator_id).unwrap();
+                validator.reward_address_in_appchain = Some(reward_address_in_appchain.to_string());
+                self.validators.insert(validator_id, &validator);
+            }
+            StakingFact::VestingLockedStakeIncreased {
+                validator_id,
+                depositor_id,
+                amount,
+            } => {
+                let mut validator = self.validators.get(validator_id).unwrap();
+                let deposit = validator
+                    .vesting_locked_deposits
+                    .get(depositor_id)
+                    .unwrap_or(&0)
+                    + amount.0;
+                validator
+                    .vesting_locked_deposits
+                    .insert(depositor_id.clone(), deposit);
+                validator.deposit_amount += amount.0;
+                validator.total_stake += amount.0;
+                self.validators.insert(validator_id, &validator);
+                self.total_stake += amount.0;
+            }
+            StakingFact::VestingLockedStakeUnbonded {
+                validator_id,
+                depositor_id,
+                amount,
+            } => {
+                let mut validator = self.validators.get(validator_id).unwrap();
+                validator.vesting_locked_deposits.remove(depositor_id);
+                validator.deposit_amount -= amount.0;
+                validator.total_stake -= amount.0;
+                self.validators.insert(validator_id, &validator);
+                self.total_stake -= amount.0;
+            }
+            StakingFact::ValidatorSlashed {
+                validator_id,
+                amount,
+            } => {
+                let mut validator = self.validators.get(validator_id).unwrap();
+                validator.deposit_amount -= amount.0;
+                validator.total_stake -= amount.0;
+                self.validators.insert(validator_id, &validator);
+                self.total_stake -= amount.0;
+            }
+            StakingFact::DelegatorSlashed {
+                delegator_id,
+                validator_id,
+                amount,
+            } => {
+                let mut delegator = self
+                    .delegators
+                    .get(&(delegator_id.clone(), validator_id.clone()))
+                    .unwrap();
+                delegator.deposit_amount -= amount.0;
+                self.delegators
+                    .insert(&(delegator_id.clone(), validator_id.clone()), &delegator);
+                let mut validator = self.validators.get(validator_id).unwrap();
+                validator.total_stake -= amount.0;
+                self.validators.insert(validator_id, &validator);
+                self.total_stake -= amount.0;
+            }
+            StakingFact::ValidatorForceUnbonded { validator_id: _ } => (),
+            StakingFact::DelegationLocked { .. } => (),
+            StakingFact::ValidatorJailed { validator_id } => {
+                let mut validator = self.validators.get(validator_id).unwrap();
+                validator.is_jailed = true;
+                self.validators.insert(validator_id, &validator);
+            }
+            StakingFact::ValidatorUnjailed { validator_id } => {
+                let mut validator = self.validators.get(validator_id).unwrap();
+                validator.is_jailed = false;
+                self.validators.insert(validator_id, &validator);
+            }
+            StakingFact::ValidatorCommissionChanged {
+                validator_id,
+                commission_percent,
+            } => {
+                let mut validator = self.validators.get(validator_id).unwrap();
+                validator.commission_percent = *commission_percent;
+                self.validators.insert(validator_id, &validator);
+            }
+            StakingFact::SecondaryStakeIncreased {
+                validator_id,
+                amount,
+                stake,
+            } => {
+                let mut validator = self.validators.get(validator_id).unwrap();
+                validator.secondary_deposit_amount += amount.0;
+                validator.secondary_stake += stake.0;
+                validator.total_stake += stake.0;
+                self.validators.insert(validator_id, &validator);
+                self.total_stake += stake.0;
+            }
+            StakingFact::SecondaryStakeDecreased {
+                validator_id,
+                amount,
+                stake,
+            }
+            | StakingFact::SecondaryStakeUnbonded {
+                validator_id,
+                amount,
+                stake,
+            } => {
+                let mut validator = self.validators.get(validator_id).unwrap();
+                validator.secondary_deposit_amount -= amount.0;
+                validator.secondary_stake -= stake.0;
+                validator.total_stake -= stake.0;
+                self.validators.insert(validator_id, &validator);
+                self.total_stake -= stake.0;
+            }
+            StakingFact::DelegationRedelegated {
+                delegator_id,
+                from_validator_id,
+                to_validator_id,
+                amount,
+            } => {
+                self.apply_staking_fact(&StakingFact::DelegatorUnbonded {
+                    delegator_id: delegator_id.clone(),
+                    validator_id: from_validator_id.clone(),
+                    amount: *amount,
+                });
+                if self
+                    .delegators
+                    .contains_key(&(delegator_id.clone(), to_validator_id.clone()))
+                {
+                    self.apply_staking_fact(&StakingFact::DelegationIncreased {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: to_validator_id.clone(),
+                        amount: *amount,
+                    });
+                } else {
+                    self.apply_staking_fact(&StakingFact::DelegatorRegistered {
+                        delegator_id: delegator_id.clone(),
+                        validator_id: to_validator_id.clone(),
+                        amount: *amount,
+                    });
+                }
+            }
         }
     }
 }
@@ -463,9 +630,40 @@ impl ValidatorSetViewer for ValidatorSet {
     }
 }
 
+impl Validator {
+    ///
+    pub fn from_old_version(old_version: OldValidator) -> Self {
+        Self {
+            validator_id: old_version.validator_id,
+            validator_id_in_appchain: old_version.validator_id_in_appchain,
+            registered_block_height: old_version.registered_block_height,
+            registered_timestamp: old_version.registered_timestamp,
+            deposit_amount: old_version.deposit_amount,
+            total_stake: old_version.total_stake,
+            can_be_delegated_to: old_version.can_be_delegated_to,
+            reward_address_in_appchain: None,
+            vesting_locked_deposits: HashMap::new(),
+            is_jailed: false,
+            commission_percent: None,
+            secondary_deposit_amount: 0,
+            secondary_stake: 0,
+        }
+    }
+    ///
+    pub fn vesting_locked_deposit_amount(&self) -> Balance {
+        self.vesting_locked_deposits.values().sum()
+    }
+    /// The deposit of the validator which can be slashed, excluding the vesting-locked
+    /// deposits which can only be withdrawn to their depositors.
+    pub fn slashable_deposit_amount(&self) -> Balance {
+        self.deposit_amount - self.vesting_locked_deposit_amount()
+    }
+}
+
 impl AppchainValidator {
     ///
     pub fn from_validator(validator: Validator, delegators_count: u64, is_unbonding: bool) -> Self {
+        let vesting_locked_deposit_amount = validator.vesting_locked_deposit_amount();
         Self {
             validator_id: validator.validator_id,
             validator_id_in_appchain: validator.validator_id_in_appchain,
@@ -474,6 +672,14 @@ impl AppchainValidator {
             delegators_count: U64::from(delegators_count),
             can_be_delegated_to: validator.can_be_delegated_to,
             is_unbonding,
+            reward_address_in_appchain: validator.reward_address_in_appchain,
+            vesting_locked_deposit_amount: U128::from(vesting_locked_deposit_amount),
+            is_jailed: validator.is_jailed,
+            commission_percent: validator.commission_percent,
+            secondary_deposit_amount: U128::from(validator.secondary_deposit_amount),
+            secondary_stake: U128::from(validator.secondary_stake),
+            weight: U128::from(validator.total_stake),
+            is_standby: false,
         }
     }
 }
@@ -42,6 +42,11 @@ impl NextValidatorSet {
         }
         result
     }
+    /// Clear the validator set only, the unbonding validator ids
+    /// and the auto-unbonding validator ids are kept.
+    pub fn clear_validator_set(&mut self) -> MultiTxsOperationProcessingResult {
+        self.validator_set.clear()
+    }
     ///
     pub fn apply_staking_fact(&mut self, staking_fact: &StakingFact) {
         self.validator_set.apply_staking_fact(staking_fact);
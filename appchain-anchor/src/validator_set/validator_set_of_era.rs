@@ -1,5 +1,6 @@
 use near_sdk::BlockHeight;
 
+use crate::storage_migration::OldValidatorSetOfEra;
 use crate::*;
 
 use super::{Delegator, Validator, ValidatorSet};
@@ -24,6 +25,17 @@ pub struct ValidatorSetOfEra {
     delegator_rewards: LookupMap<(AccountId, AccountId), Balance>,
     /// The status of creation of this set
     processing_status: ValidatorSetProcessingStatus,
+    /// The deterministic random seed generated when the era starts,
+    /// for the ordering/shuffling of validators in appchain.
+    shuffle_seed: Option<[u8; 32]>,
+    /// The total gas used (in unit of gas) for processing this set.
+    used_gas_of_processing: u64,
+    /// The total commission rebated to loyal delegators of validators in this era
+    commission_rebates: LookupMap<AccountId, Balance>,
+    /// The nonce of the first appchain message applied in this era.
+    first_applied_message_nonce: Option<u32>,
+    /// The nonce of the last appchain message applied in this era.
+    last_applied_message_nonce: Option<u32>,
 }
 
 impl ValidatorSetOfEra {
@@ -48,9 +60,42 @@ impl ValidatorSetOfEra {
                 copying_validator_index: U64::from(0),
                 copying_delegator_index: U64::from(0),
             },
+            shuffle_seed: Some(generate_shuffle_seed(era_number)),
+            used_gas_of_processing: 0,
+            commission_rebates: LookupMap::new(
+                StorageKey::CommissionRebatesOfEra(era_number).into_bytes(),
+            ),
+            first_applied_message_nonce: None,
+            last_applied_message_nonce: None,
+        }
+    }
+    ///
+    pub fn from_old_version(old_version: OldValidatorSetOfEra) -> Self {
+        let era_number = old_version.validator_set.era_number;
+        Self {
+            validator_set: old_version.validator_set,
+            start_block_height: old_version.start_block_height,
+            start_timestamp: old_version.start_timestamp,
+            staking_history_index: old_version.staking_history_index,
+            unprofitable_validator_id_set: old_version.unprofitable_validator_id_set,
+            valid_total_stake: old_version.valid_total_stake,
+            validator_rewards: old_version.validator_rewards,
+            delegator_rewards: old_version.delegator_rewards,
+            processing_status: old_version.processing_status,
+            shuffle_seed: None,
+            used_gas_of_processing: 0,
+            commission_rebates: LookupMap::new(
+                StorageKey::CommissionRebatesOfEra(era_number).into_bytes(),
+            ),
+            first_applied_message_nonce: None,
+            last_applied_message_nonce: None,
         }
     }
     ///
+    pub fn start_block_height(&self) -> BlockHeight {
+        self.start_block_height
+    }
+    ///
     pub fn start_timestamp(&self) -> Timestamp {
         self.start_timestamp
     }
@@ -83,6 +128,14 @@ impl ValidatorSetOfEra {
     pub fn valid_total_stake(&self) -> u128 {
         self.valid_total_stake
     }
+    ///
+    pub fn shuffle_seed(&self) -> Option<[u8; 32]> {
+        self.shuffle_seed
+    }
+    ///
+    pub fn used_gas_of_processing(&self) -> u64 {
+        self.used_gas_of_processing
+    }
     //
     fn all_staking_histories_are_applied(&self) -> bool {
         match self.processing_status {
@@ -102,6 +155,18 @@ impl ValidatorSetOfEra {
         self.validator_set.total_stake = total_stake;
     }
     ///
+    pub fn add_used_gas_of_processing(&mut self, used_gas: u64) {
+        self.used_gas_of_processing += used_gas;
+    }
+    /// Record the nonce of an appchain message applied in this era.
+    /// The messages are always applied in order of their nonce.
+    pub fn watermark_applied_message_nonce(&mut self, nonce: u32) {
+        if self.first_applied_message_nonce.is_none() {
+            self.first_applied_message_nonce = Some(nonce);
+        }
+        self.last_applied_message_nonce = Some(nonce);
+    }
+    ///
     pub fn set_processing_status(&mut self, process_status: ValidatorSetProcessingStatus) {
         self.processing_status = process_status
     }
@@ -180,6 +245,16 @@ impl ValidatorSetOfEra {
             .insert(delegator_id, &validator_id_set);
     }
     ///
+    pub fn get_commission_rebate_of(&self, validator_id: &AccountId) -> u128 {
+        self.commission_rebates.get(validator_id).unwrap_or(0)
+    }
+    ///
+    pub fn add_commission_rebate(&mut self, validator_id: &AccountId, amount: u128) {
+        let commission_rebate = self.get_commission_rebate_of(validator_id) + amount;
+        self.commission_rebates
+            .insert(validator_id, &commission_rebate);
+    }
+    ///
     pub fn set_validator_reward(&mut self, validator_id: &AccountId, amount: u128) {
         self.validator_rewards.insert(validator_id, &amount);
     }
@@ -193,11 +268,11 @@ impl ValidatorSetOfEra {
         self.delegator_rewards
             .insert(&(delegator_id.clone(), validator_id.clone()), &amount);
     }
-    ///
-    pub fn calculate_valid_total_stake(&mut self) {
-        let unprofitable_validator_ids = self.unprofitable_validator_id_set.to_vec();
+    /// Calculate the total stake excluding the stake of the validators in
+    /// `excluded_validator_ids`, which should include all unprofitable validators.
+    pub fn calculate_valid_total_stake(&mut self, excluded_validator_ids: &Vec<AccountId>) {
         self.valid_total_stake = self.validator_set.total_stake;
-        unprofitable_validator_ids.iter().for_each(|v_id| {
+        excluded_validator_ids.iter().for_each(|v_id| {
             let validator = self.validator_set.validators.get(v_id).unwrap();
             self.valid_total_stake -= validator.total_stake;
         });
@@ -214,7 +289,82 @@ impl ValidatorSetOfEra {
             unprofitable_validator_ids: self.unprofitable_validator_id_set.to_vec(),
             valid_total_stake: U128::from(self.valid_total_stake),
             processing_status: self.processing_status.clone(),
+            shuffle_seed: self
+                .shuffle_seed
+                .map(|shuffle_seed| format!("0x{}", hex::encode(shuffle_seed))),
+            used_gas_of_processing: U64::from(self.used_gas_of_processing),
+            validator_set_hash: self.validator_set_hash(),
+            first_applied_message_nonce: self.first_applied_message_nonce,
+            last_applied_message_nonce: self.last_applied_message_nonce,
+        }
+    }
+    /// Get the hash of this set (in hex format), which is the keccak256 hash of
+    /// borsh-encoded `Vec<(String, u128)>` of `(validator_id_in_appchain, total_stake)`
+    /// of all validators, sorted by `validator_id_in_appchain`.
+    pub fn validator_set_hash(&self) -> String {
+        let mut validators = self
+            .validator_set
+            .validator_id_set
+            .iter()
+            .map(|validator_id| {
+                let validator = self.validator_set.validators.get(&validator_id).unwrap();
+                (validator.validator_id_in_appchain, validator.total_stake)
+            })
+            .collect::<Vec<(String, Balance)>>();
+        validators.sort_by(|a, b| a.0.cmp(&b.0));
+        format!(
+            "0x{}",
+            hex::encode(env::keccak256(&validators.try_to_vec().unwrap()))
+        )
+    }
+    /// Get the merkle proof of membership of a validator in this set,
+    /// return `None` if the validator is not in this set.
+    pub fn validator_membership_proof_of(
+        &self,
+        validator_id: &AccountId,
+    ) -> Option<ValidatorMembershipProof> {
+        if !self.all_staking_histories_are_applied() {
+            return None;
+        }
+        let mut validators = self
+            .validator_set
+            .validator_id_set
+            .iter()
+            .map(|validator_id| {
+                let validator = self.validator_set.validators.get(&validator_id).unwrap();
+                (validator_id, validator.total_stake)
+            })
+            .collect::<Vec<(AccountId, Balance)>>();
+        validators.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut index = validators.iter().position(|v| v.0.eq(validator_id))?;
+        let total_stake = validators[index].1;
+        let mut nodes = validators
+            .iter()
+            .map(|(validator_id, total_stake)| {
+                ValidatorMembershipProof::leaf_hash(validator_id, *total_stake)
+            })
+            .collect::<Vec<[u8; 32]>>();
+        let mut proof = Vec::<String>::new();
+        while nodes.len() > 1 {
+            if index ^ 1 < nodes.len() {
+                proof.push(format!("0x{}", hex::encode(nodes[index ^ 1])));
+            }
+            nodes = nodes
+                .chunks(2)
+                .map(|pair| match pair.len() {
+                    2 => ValidatorMembershipProof::hash_of_pair(&pair[0], &pair[1]),
+                    _ => pair[0],
+                })
+                .collect();
+            index /= 2;
         }
+        Some(ValidatorMembershipProof {
+            era_number: U64::from(self.validator_set.era_number),
+            validator_id: validator_id.clone(),
+            total_stake: U128::from(total_stake),
+            proof,
+            validator_set_merkle_root: format!("0x{}", hex::encode(nodes[0])),
+        })
     }
     ///
     pub fn clear_reward_distribution_records(&mut self) -> MultiTxsOperationProcessingResult {
@@ -238,6 +388,7 @@ impl ValidatorSetOfEra {
                 }
             }
             self.validator_rewards.remove(&validator_id);
+            self.commission_rebates.remove(&validator_id);
             if env::used_gas() > Gas::ONE_TERA.mul(T_GAS_CAP_FOR_MULTI_TXS_PROCESSING) {
                 return MultiTxsOperationProcessingResult::NeedMoreGas;
             }
@@ -397,3 +548,17 @@ impl IndexedAndClearable for ValidatorSetOfEra {
         self.clear()
     }
 }
+
+/// Generate the shuffle seed of an era by the random seed of current block
+/// and the era number.
+fn generate_shuffle_seed(era_number: u64) -> [u8; 32] {
+    let input = [
+        env::random_seed(),
+        env::block_height().to_le_bytes().to_vec(),
+        era_number.to_le_bytes().to_vec(),
+    ]
+    .concat();
+    let mut shuffle_seed = [0u8; 32];
+    shuffle_seed.copy_from_slice(&env::sha256(&input));
+    shuffle_seed
+}
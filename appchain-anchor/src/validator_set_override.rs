@@ -0,0 +1,87 @@
+use crate::*;
+
+impl AppchainAnchor {
+    // Get the latest proposal of validator set override, if it is not concluded yet.
+    pub fn get_pending_validator_set_override_proposal(
+        &self,
+    ) -> Option<ValidatorSetOverrideProposal> {
+        let proposals = self.validator_set_override_proposals.get().unwrap();
+        proposals
+            .get(&proposals.index_range().end_index.0)
+            .filter(|proposal| match proposal.status {
+                ValidatorSetOverrideStatus::Proposed | ValidatorSetOverrideStatus::Approved => true,
+                _ => false,
+            })
+    }
+    // Record the approval of a validator in the validator set of the base era of the pending
+    // proposal, the proposal is approved once the approved stake exceeds 2/3 of total stake.
+    pub fn internal_approve_validator_set_override(
+        &mut self,
+        validator_id: &AccountId,
+        proposal_index: u64,
+    ) {
+        let mut proposal = self
+            .get_pending_validator_set_override_proposal()
+            .filter(|proposal| proposal.index.0 == proposal_index)
+            .expect("The proposal is not pending.");
+        assert!(
+            !proposal
+                .approvals
+                .iter()
+                .any(|approval| approval.validator_id.eq(validator_id)),
+            "The proposal is already approved by the sender."
+        );
+        let validator = self
+            .validator_set_histories
+            .get()
+            .unwrap()
+            .get(&proposal.base_era_number.0)
+            .and_then(|validator_set| validator_set.get_validator(validator_id))
+            .expect("Only validators in the validator set of the base era can approve.");
+        proposal.approvals.push(ValidatorSetOverrideApproval {
+            validator_id: validator_id.clone(),
+            stake: U128::from(validator.total_stake),
+            timestamp: U64::from(env::block_timestamp()),
+        });
+        proposal.approved_stake = U128::from(proposal.approved_stake.0 + validator.total_stake);
+        if proposal.status == ValidatorSetOverrideStatus::Proposed
+            && proposal.approved_stake.0 * 3 > proposal.total_stake.0 * 2
+        {
+            proposal.status = ValidatorSetOverrideStatus::Approved;
+            log!(
+                "Validator set override proposal '{}' is approved.",
+                proposal.index.0
+            );
+        }
+        let mut proposals = self.validator_set_override_proposals.get().unwrap();
+        proposals.insert(&proposal.index.0, &proposal);
+        self.validator_set_override_proposals.set(&proposals);
+    }
+    // Apply the approved proposal (if any) to the validator set of an era, return the
+    // validators in the set which are not in the list of the proposal.
+    pub fn apply_validator_set_override_to(
+        &mut self,
+        validator_set: &ValidatorSetOfEra,
+    ) -> Vec<AccountId> {
+        let mut proposal = match self.get_pending_validator_set_override_proposal() {
+            Some(proposal)
+                if proposal.status == ValidatorSetOverrideStatus::Approved
+                    && validator_set.era_number() > proposal.base_era_number.0 =>
+            {
+                proposal
+            }
+            _ => return Vec::new(),
+        };
+        proposal.status = ValidatorSetOverrideStatus::Applied {
+            era_number: U64::from(validator_set.era_number()),
+        };
+        let mut proposals = self.validator_set_override_proposals.get().unwrap();
+        proposals.insert(&proposal.index.0, &proposal);
+        self.validator_set_override_proposals.set(&proposals);
+        validator_set
+            .get_validator_ids()
+            .into_iter()
+            .filter(|validator_id| !proposal.validator_ids.contains(validator_id))
+            .collect()
+    }
+}
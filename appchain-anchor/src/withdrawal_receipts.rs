@@ -0,0 +1,144 @@
+use crate::{interfaces::WithdrawalReceiptManager, *};
+use near_contract_standards::fungible_token::core::ext_ft_core;
+
+pub trait WithdrawalReceiptResolver {
+    /// Resolver for the outbound transfer of a withdrawal
+    fn resolve_withdrawal_transfer(&mut self, receipt_id: U64, is_refund: bool);
+}
+
+impl AppchainAnchor {
+    // Transfer a certain amount of token to the receiver, along with a withdrawal receipt
+    // whose status is updated by the result of the transfer. Return the receipt id.
+    pub fn transfer_with_withdrawal_receipt(
+        &mut self,
+        kind: WithdrawalKind,
+        account_id: &AccountId,
+        token_contract: &AccountId,
+        receiver_id: &AccountId,
+        amount: Balance,
+    ) -> u64 {
+        let receipt =
+            self.create_withdrawal_receipt(kind, account_id, token_contract, receiver_id, amount);
+        self.send_transfer_of_withdrawal_receipt(&receipt, receiver_id.clone(), false);
+        receipt.index.0
+    }
+    //
+    pub fn create_withdrawal_receipt(
+        &mut self,
+        kind: WithdrawalKind,
+        account_id: &AccountId,
+        token_contract: &AccountId,
+        receiver_id: &AccountId,
+        amount: Balance,
+    ) -> WithdrawalReceipt {
+        let mut withdrawal_receipts = self.withdrawal_receipts.get().unwrap();
+        let receipt = withdrawal_receipts.append(&mut WithdrawalReceipt {
+            kind,
+            account_id: account_id.clone(),
+            receiver_id: receiver_id.clone(),
+            token_contract: token_contract.clone(),
+            amount: U128::from(amount),
+            status: WithdrawalReceiptStatus::Pending,
+            created_timestamp: U64::from(env::block_timestamp()),
+            updated_timestamp: U64::from(env::block_timestamp()),
+            index: U64::from(0),
+        });
+        self.withdrawal_receipts.set(&withdrawal_receipts);
+        log!(
+            "Withdrawal receipt '{}' is created for '{}' to '{}' with amount '{}'.",
+            receipt.index.0,
+            account_id,
+            receiver_id,
+            amount
+        );
+        receipt
+    }
+    //
+    fn send_transfer_of_withdrawal_receipt(
+        &self,
+        receipt: &WithdrawalReceipt,
+        receiver_id: AccountId,
+        is_refund: bool,
+    ) {
+        ext_ft_core::ext(receipt.token_contract.clone())
+            .with_attached_deposit(1)
+            .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_FT_TRANSFER))
+            .with_unused_gas_weight(0)
+            .ft_transfer(receiver_id, receipt.amount, None)
+            .then(
+                ext_self::ext(env::current_account_id())
+                    .with_attached_deposit(0)
+                    .with_static_gas(Gas::ONE_TERA.mul(T_GAS_FOR_RESOLVER_FUNCTION))
+                    .with_unused_gas_weight(0)
+                    .resolve_withdrawal_transfer(receipt.index, is_refund),
+            );
+    }
+    //
+    pub fn update_withdrawal_receipt_status(
+        &mut self,
+        receipt_id: u64,
+        status: WithdrawalReceiptStatus,
+    ) {
+        let mut withdrawal_receipts = self.withdrawal_receipts.get().unwrap();
+        if let Some(mut receipt) = withdrawal_receipts.get(&receipt_id) {
+            receipt.status = status;
+            receipt.updated_timestamp = U64::from(env::block_timestamp());
+            withdrawal_receipts.insert(&receipt_id, &receipt);
+            self.withdrawal_receipts.set(&withdrawal_receipts);
+        }
+    }
+}
+
+#[near_bindgen]
+impl WithdrawalReceiptManager for AppchainAnchor {
+    //
+    fn refund_failed_withdrawal(&mut self, receipt_id: U64) {
+        self.assert_not_in_maintenance_mode();
+        self.assert_asset_transfer_is_not_paused();
+        let account_id = env::predecessor_account_id();
+        self.assert_account_is_not_frozen(&account_id);
+        let receipt = self
+            .withdrawal_receipts
+            .get()
+            .unwrap()
+            .get(&receipt_id.0)
+            .expect("Withdrawal receipt is not found.");
+        assert_eq!(
+            receipt.account_id, account_id,
+            "Only '{}' can refund this withdrawal.",
+            receipt.account_id
+        );
+        assert_eq!(
+            receipt.status,
+            WithdrawalReceiptStatus::Failed,
+            "Only a failed withdrawal can be refunded."
+        );
+        // Mark the receipt as pending, to avoid refunding it again before the transfer
+        // is resolved.
+        self.update_withdrawal_receipt_status(receipt_id.0, WithdrawalReceiptStatus::Pending);
+        self.send_transfer_of_withdrawal_receipt(&receipt, account_id, true);
+    }
+}
+
+#[near_bindgen]
+impl WithdrawalReceiptResolver for AppchainAnchor {
+    //
+    fn resolve_withdrawal_transfer(&mut self, receipt_id: U64, is_refund: bool) {
+        assert_self();
+        let status = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => match is_refund {
+                true => WithdrawalReceiptStatus::Refunded,
+                false => WithdrawalReceiptStatus::Succeeded,
+            },
+            PromiseResult::Failed => {
+                log!(
+                    "Transfer of withdrawal receipt '{}' failed. It can be refunded later.",
+                    receipt_id.0
+                );
+                WithdrawalReceiptStatus::Failed
+            }
+        };
+        self.update_withdrawal_receipt_status(receipt_id.0, status);
+    }
+}
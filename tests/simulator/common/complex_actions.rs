@@ -10,13 +10,10 @@ use crate::{
     contract_interfaces::{anchor_viewer, permissionless_actions, staking_actions},
 };
 use appchain_anchor::{
-    appchain_messages::{EraPayoutPayload, RawMessage},
-    appchain_messages::{PayloadType, PlanNewEraPayload},
+    test_utils::AppchainMessagesBuilder,
     types::{AnchorStatus, MultiTxsOperationProcessingResult, ValidatorSetInfo},
 };
-use near_primitives::borsh::BorshSerialize;
 use near_sdk::{json_types::U64, serde_json};
-use parity_scale_codec::Encode;
 use workspaces::network::Sandbox;
 use workspaces::{Account, Contract, Worker};
 
@@ -54,21 +51,13 @@ pub async fn switch_era(
     to_confirm_view_result: bool,
 ) -> anyhow::Result<()> {
     if era_number > 0 {
-        let payload = PlanNewEraPayload {
-            new_era: era_number,
-        };
-        let raw_message = RawMessage {
-            nonce: appchain_message_nonce as u64,
-            payload_type: PayloadType::PlanNewEra,
-            payload: payload.try_to_vec().unwrap(),
-        };
-        let mut raw_messages = Vec::new();
-        raw_messages.push(raw_message);
         permissionless_actions::verify_and_stage_appchain_messages(
             worker,
             relayer,
             anchor,
-            raw_messages.encode(),
+            AppchainMessagesBuilder::new(appchain_message_nonce)
+                .plan_new_era(era_number)
+                .build(),
             Vec::new(),
             Vec::new(),
             Vec::new(),
@@ -112,23 +101,13 @@ pub async fn distribute_reward_of(
 ) -> anyhow::Result<()> {
     let anchor_balance_of_wat =
         common::get_ft_balance_of(worker, &anchor.as_account(), &wrapped_appchain_token).await?;
-    let payload = EraPayoutPayload {
-        end_era: era_number,
-        excluded_validators: unprofitable_validator_ids,
-        offenders: Vec::new(),
-    };
-    let raw_message = RawMessage {
-        nonce: nonce as u64,
-        payload_type: PayloadType::EraPayout,
-        payload: payload.try_to_vec().unwrap(),
-    };
-    let mut raw_messages = Vec::new();
-    raw_messages.push(raw_message);
     permissionless_actions::verify_and_stage_appchain_messages(
         worker,
         relayer,
         anchor,
-        raw_messages.encode(),
+        AppchainMessagesBuilder::new(nonce)
+            .era_payout(era_number, unprofitable_validator_ids, Vec::new())
+            .build(),
         Vec::new(),
         Vec::new(),
         Vec::new(),
@@ -240,9 +240,13 @@ pub async fn print_delegator_list_of(
     era_number: u64,
     validator: &Account,
 ) -> anyhow::Result<()> {
-    let delegator_list =
-        anchor_viewer::get_delegators_of_validator_in_era(worker, &anchor, era_number, validator)
-            .await?;
+    let delegator_list = anchor_viewer::get_delegators_of_validator_in_era(
+        worker,
+        &anchor,
+        Some(era_number),
+        validator,
+    )
+    .await?;
     let mut index = 0;
     for delegator in delegator_list {
         println!(
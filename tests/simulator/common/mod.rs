@@ -1,6 +1,7 @@
 pub mod basic_actions;
 pub mod complex_actions;
 pub mod complex_viewer;
+pub mod test_env;
 
 use crate::contract_interfaces::{
     anchor_viewer, lifecycle_actions, settings_manager, staking_actions, validator_actions,
@@ -77,6 +78,17 @@ pub async fn get_ft_balance_of(
         .json::<U128>()
 }
 
+pub async fn get_ft_total_supply(
+    worker: &Worker<Sandbox>,
+    ft_contract: &Contract,
+) -> anyhow::Result<U128> {
+    ft_contract
+        .call(worker, "ft_total_supply")
+        .view()
+        .await?
+        .json::<U128>()
+}
+
 pub fn to_actual_amount(amount: u128, decimals: u32) -> u128 {
     let bt_decimals_base = (10 as u128).pow(decimals);
     amount * bt_decimals_base
@@ -0,0 +1,124 @@
+use crate::{
+    common::{self, complex_actions},
+    contract_interfaces::{anchor_viewer, permissionless_actions},
+};
+use appchain_anchor::test_utils::AppchainMessagesBuilder;
+use appchain_anchor::types::{AppchainValidator, StakingHistory};
+use workspaces::network::Sandbox;
+use workspaces::{Account, Contract, Worker};
+
+/// The environment for integration tests in sandbox, with the anchor contract, the mock OCT
+/// token contract, the wrapped appchain token contract and a set of validators and delegators
+/// preloaded (which is the same as the result of `common::test_normal_actions`).
+pub struct TestEnv {
+    pub worker: Worker<Sandbox>,
+    pub root: Account,
+    pub oct_token: Contract,
+    pub wrapped_appchain_token: Contract,
+    pub registry: Contract,
+    pub anchor: Contract,
+    pub wat_faucet: Contract,
+    pub users: Vec<Account>,
+    /// The nonce of the latest appchain message staged in the anchor contract.
+    pub appchain_message_nonce: u32,
+}
+
+impl TestEnv {
+    ///
+    pub async fn new() -> anyhow::Result<Self> {
+        let worker = workspaces::sandbox().await?;
+        let (
+            root,
+            oct_token,
+            wrapped_appchain_token,
+            registry,
+            anchor,
+            wat_faucet,
+            users,
+            appchain_message_nonce,
+        ) = common::test_normal_actions(&worker, false, false, vec!["0x00".to_string()]).await?;
+        Ok(Self {
+            worker,
+            root,
+            oct_token,
+            wrapped_appchain_token,
+            registry,
+            anchor,
+            wat_faucet,
+            users,
+            appchain_message_nonce,
+        })
+    }
+    /// The account which acts as the relayer in tests.
+    pub fn relayer(&self) -> &Account {
+        &self.users[5]
+    }
+    /// Get a builder of appchain messages starting from the next nonce.
+    pub fn appchain_messages(&self) -> AppchainMessagesBuilder {
+        AppchainMessagesBuilder::new(self.appchain_message_nonce + 1)
+    }
+    /// Stage the messages in the builder to the anchor contract and process them.
+    pub async fn stage_and_process(
+        &mut self,
+        appchain_messages: AppchainMessagesBuilder,
+    ) -> anyhow::Result<()> {
+        permissionless_actions::verify_and_stage_appchain_messages(
+            &self.worker,
+            self.relayer(),
+            &self.anchor,
+            appchain_messages.build(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        )
+        .await?;
+        self.appchain_message_nonce = appchain_messages.next_nonce() - 1;
+        complex_actions::process_appchain_messages(&self.worker, self.relayer(), &self.anchor).await
+    }
+    /// Switch to a certain era by a `PlanNewEra` message.
+    pub async fn switch_era(&mut self, era_number: u32) -> anyhow::Result<()> {
+        let appchain_messages = self.appchain_messages().plan_new_era(era_number);
+        self.stage_and_process(appchain_messages).await
+    }
+    /// Get the validator of a certain user in the next era.
+    pub async fn validator_in_next_era(
+        &self,
+        validator: &Account,
+    ) -> anyhow::Result<AppchainValidator> {
+        let validator_list =
+            anchor_viewer::get_validator_list_of(&self.worker, &self.anchor, None).await?;
+        Ok(validator_list
+            .into_iter()
+            .find(|v| v.validator_id.as_str() == validator.id().as_str())
+            .expect("The validator is not in the next validator set."))
+    }
+    /// Get the index which the next staking history will be recorded at.
+    pub async fn next_staking_history_index(&self) -> anyhow::Result<u64> {
+        let index_range =
+            anchor_viewer::get_index_range_of_staking_history(&self.worker, &self.anchor).await?;
+        let latest_staking_history =
+            anchor_viewer::get_staking_history(&self.worker, &self.anchor, index_range.end_index.0)
+                .await?;
+        Ok(match latest_staking_history {
+            Some(_) => index_range.end_index.0 + 1,
+            None => index_range.end_index.0,
+        })
+    }
+    /// Get the staking histories recorded since a certain index.
+    pub async fn staking_histories_since(
+        &self,
+        start_index: u64,
+    ) -> anyhow::Result<Vec<StakingHistory>> {
+        let index_range =
+            anchor_viewer::get_index_range_of_staking_history(&self.worker, &self.anchor).await?;
+        let mut staking_histories = Vec::new();
+        for index in start_index..=index_range.end_index.0 {
+            if let Some(staking_history) =
+                anchor_viewer::get_staking_history(&self.worker, &self.anchor, index).await?
+            {
+                staking_histories.push(staking_history);
+            }
+        }
+        Ok(staking_histories)
+    }
+}
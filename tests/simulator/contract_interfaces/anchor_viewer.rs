@@ -2,11 +2,13 @@ use appchain_anchor::appchain_challenge::AppchainChallenge;
 use appchain_anchor::types::{
     AnchorSettings, AnchorStatus, AppchainCommitment, AppchainDelegator,
     AppchainMessageProcessingResult, AppchainNotificationHistory, AppchainSettings, AppchainState,
-    AppchainValidator, IndexRange, NearFungibleToken, RewardHistory, StakingHistory, UnbondedStake,
-    UserStakingHistory, ValidatorProfile, ValidatorSetInfo, WrappedAppchainToken,
+    AppchainValidator, EraDistributionAudit, IndexRange, NearFungibleToken, OctToken,
+    PenaltyEscrow, PriceUpdateViolation, RewardHistory, RewardRoundingOfEra, SlashHistory,
+    StakingHistory, UnbondedStake, UserStakingHistory, ValidatorProfile, ValidatorRewardPreview,
+    ValidatorSetInfo, WrappedAppchainToken, WrappedAppchainTokenIssuance,
 };
 use appchain_anchor::AppchainMessage;
-use near_sdk::json_types::U64;
+use near_sdk::json_types::{U128, U64};
 use near_sdk::serde_json::json;
 use near_sdk::AccountId;
 use workspaces::{network::Sandbox, Account, Contract, Worker};
@@ -44,6 +46,17 @@ pub async fn get_wrapped_appchain_token(
         .json::<WrappedAppchainToken>()
 }
 
+pub async fn get_wrapped_appchain_token_issuance(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<WrappedAppchainTokenIssuance> {
+    anchor
+        .call(worker, "get_wrapped_appchain_token_issuance")
+        .view()
+        .await?
+        .json::<WrappedAppchainTokenIssuance>()
+}
+
 pub async fn get_near_fungible_tokens(
     worker: &Worker<Sandbox>,
     anchor: &Contract,
@@ -55,6 +68,17 @@ pub async fn get_near_fungible_tokens(
         .json::<Vec<NearFungibleToken>>()
 }
 
+pub async fn get_oct_token(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<OctToken> {
+    anchor
+        .call(worker, "get_oct_token")
+        .view()
+        .await?
+        .json::<OctToken>()
+}
+
 pub async fn get_appchain_state(
     worker: &Worker<Sandbox>,
     anchor: &Contract,
@@ -77,6 +101,19 @@ pub async fn get_anchor_status(
         .json::<AnchorStatus>()
 }
 
+pub async fn get_price_update_violations(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    start_index: u64,
+) -> anyhow::Result<Vec<PriceUpdateViolation>> {
+    anchor
+        .call(worker, "get_price_update_violations")
+        .args_json(json!({ "start_index": U64::from(start_index) }))?
+        .view()
+        .await?
+        .json::<Vec<PriceUpdateViolation>>()
+}
+
 pub async fn get_validator_set_info_of(
     worker: &Worker<Sandbox>,
     anchor: &Contract,
@@ -202,13 +239,13 @@ pub async fn get_validator_profile_by_id_in_appchain(
 pub async fn get_delegators_of_validator_in_era(
     worker: &Worker<Sandbox>,
     anchor: &Contract,
-    index: u64,
+    index: Option<u64>,
     validator: &Account,
 ) -> anyhow::Result<Vec<AppchainDelegator>> {
     anchor
         .call(worker, "get_delegators_of_validator_in_era")
         .args_json(json!({
-            "era_number": Some(U64::from(index)),
+            "era_number": index.map(U64::from),
             "validator_id": validator.id()
         }))?
         .view()
@@ -358,3 +395,98 @@ pub async fn get_appchain_challenges(
         .await?
         .json::<Vec<AppchainChallenge>>()
 }
+
+pub async fn get_reward_rounding_of_era(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    era_number: u64,
+) -> anyhow::Result<Option<RewardRoundingOfEra>> {
+    anchor
+        .call(worker, "get_reward_rounding_of_era")
+        .args_json(json!({ "era_number": U64::from(era_number) }))?
+        .view()
+        .await?
+        .json::<Option<RewardRoundingOfEra>>()
+}
+
+pub async fn preview_reward_distribution(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    era_number: u64,
+) -> anyhow::Result<Vec<ValidatorRewardPreview>> {
+    anchor
+        .call(worker, "preview_reward_distribution")
+        .args_json(json!({
+            "era_number": U64::from(era_number),
+            "start_index": U64::from(0)
+        }))?
+        .view()
+        .await?
+        .json::<Vec<ValidatorRewardPreview>>()
+}
+
+pub async fn audit_era_distribution(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    era_number: u64,
+) -> anyhow::Result<Option<EraDistributionAudit>> {
+    anchor
+        .call(worker, "audit_era_distribution")
+        .args_json(json!({
+            "era_number": U64::from(era_number),
+            "start_index": U64::from(0)
+        }))?
+        .view()
+        .await?
+        .json::<Option<EraDistributionAudit>>()
+}
+
+pub async fn get_penalty_escrow_balance(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+) -> anyhow::Result<U128> {
+    anchor
+        .call(worker, "get_penalty_escrow_balance")
+        .view()
+        .await?
+        .json::<U128>()
+}
+
+pub async fn get_penalty_escrows(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    start_index: u64,
+) -> anyhow::Result<Vec<PenaltyEscrow>> {
+    anchor
+        .call(worker, "get_penalty_escrows")
+        .args_json(json!({ "start_index": U64::from(start_index) }))?
+        .view()
+        .await?
+        .json::<Vec<PenaltyEscrow>>()
+}
+
+pub async fn get_slash_histories(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    start_index: u64,
+) -> anyhow::Result<Vec<SlashHistory>> {
+    anchor
+        .call(worker, "get_slash_histories")
+        .args_json(json!({ "start_index": U64::from(start_index) }))?
+        .view()
+        .await?
+        .json::<Vec<SlashHistory>>()
+}
+
+pub async fn get_slash_histories_of_validator(
+    worker: &Worker<Sandbox>,
+    anchor: &Contract,
+    validator: &Account,
+) -> anyhow::Result<Vec<SlashHistory>> {
+    anchor
+        .call(worker, "get_slash_histories_of_validator")
+        .args_json(json!({ "validator_id": validator.id() }))?
+        .view()
+        .await?
+        .json::<Vec<SlashHistory>>()
+}
@@ -4,6 +4,7 @@ pub mod near_fungible_token_manager;
 pub mod permissionless_actions;
 pub mod settings_manager;
 pub mod staking_actions;
+pub mod staking_admin_actions;
 pub mod validator_actions;
 pub mod wrapped_appchain_nft_manager;
 pub mod wrapped_appchain_token_manager;
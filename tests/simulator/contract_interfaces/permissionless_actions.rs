@@ -1,5 +1,5 @@
 use appchain_anchor::types::{MultiTxsOperationProcessingResult, ValidatorMerkleProof};
-use near_sdk::serde_json::json;
+use near_sdk::{json_types::U64, serde_json::json};
 use workspaces::{network::Sandbox, result::CallExecutionDetails, Account, Contract, Worker};
 
 pub async fn verify_and_stage_appchain_messages(
@@ -87,3 +87,31 @@ pub async fn try_complete_updating_state_of_beefy_light_client(
     println!("{:?}", result);
     result.json::<MultiTxsOperationProcessingResult>()
 }
+
+pub async fn settle_penalty_escrow(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    escrow_index: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "settle_penalty_escrow")
+        .args_json(json!({ "escrow_index": U64::from(escrow_index) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn try_complete_switching_era(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+) -> anyhow::Result<MultiTxsOperationProcessingResult> {
+    let result = signer
+        .call(worker, anchor.id(), "try_complete_switching_era")
+        .gas(300_000_000_000_000)
+        .transact()
+        .await?;
+    println!("{:?}", result);
+    result.json::<MultiTxsOperationProcessingResult>()
+}
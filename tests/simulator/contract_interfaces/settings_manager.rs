@@ -1,3 +1,7 @@
+use appchain_anchor::{
+    types::{DelegatorLoyaltyTier, RewardRoundingPolicy},
+    PayloadType,
+};
 use near_sdk::{
     json_types::{U128, U64},
     serde_json::json,
@@ -187,3 +191,173 @@ pub async fn turn_off_beefy_light_client_witness_mode(
         .transact()
         .await
 }
+
+pub async fn set_minimum_interval_of_price_updates(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_minimum_interval_of_price_updates")
+        .args_json(json!({ "interval_in_secs": U64::from(value) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_maximum_price_change_percent_per_update(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u16,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(
+            worker,
+            anchor.id(),
+            "set_maximum_price_change_percent_per_update",
+        )
+        .args_json(json!({ "percent": value }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn turn_on_pause_asset_transfer_on_price_update_violation(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(
+            worker,
+            anchor.id(),
+            "turn_on_pause_asset_transfer_on_price_update_violation",
+        )
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_maximum_fast_unbond_amount_per_era(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u128,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(
+            worker,
+            anchor.id(),
+            "change_maximum_fast_unbond_amount_per_era",
+        )
+        .args_json(json!({ "value": U128::from(value) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_reward_rounding_policy(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    policy: RewardRoundingPolicy,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "change_reward_rounding_policy")
+        .args_json(json!({ "policy": policy }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_loyalty_tiers_of_delegator(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: Vec<DelegatorLoyaltyTier>,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "change_loyalty_tiers_of_delegator")
+        .args_json(json!({ "value": value }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_penalty_dispute_period(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "change_penalty_dispute_period")
+        .args_json(json!({ "value": U64::from(value) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_seconds_of_a_day(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_seconds_of_a_day")
+        .args_json(json!({ "value": U64::from(value) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_validator_slash_percent(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u16,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "change_validator_slash_percent")
+        .args_json(json!({ "value": value }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_delegator_slash_percent(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    value: u16,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "change_delegator_slash_percent")
+        .args_json(json!({ "value": value }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn set_appchain_message_handler_config(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    payload_type: PayloadType,
+    is_enabled: bool,
+    gas_budget: Option<u64>,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "set_appchain_message_handler_config")
+        .args_json(json!({
+            "payload_type": payload_type,
+            "is_enabled": is_enabled,
+            "gas_budget": gas_budget.map(U64::from),
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
@@ -1,5 +1,9 @@
 use crate::common;
-use near_sdk::{json_types::U128, serde_json::json, AccountId};
+use near_sdk::{
+    json_types::{U128, U64},
+    serde_json::json,
+    AccountId,
+};
 use std::collections::HashMap;
 use workspaces::{network::Sandbox, result::CallExecutionDetails, Account, Contract, Worker};
 
@@ -203,3 +207,47 @@ pub async fn withdraw_delegator_rewards(
         .transact()
         .await
 }
+
+pub async fn appeal_penalty(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    escrow_index: u64,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "appeal_penalty")
+        .args_json(json!({ "escrow_index": U64::from(escrow_index) }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn fast_withdraw_stake(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "fast_withdraw_stake")
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn change_delegated_validator(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    old_validator_id: &AccountId,
+    new_validator_id: &AccountId,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "change_delegated_validator")
+        .args_json(json!({
+            "old_validator_id": old_validator_id,
+            "new_validator_id": new_validator_id
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
@@ -0,0 +1,62 @@
+use near_sdk::{
+    json_types::{U128, U64},
+    serde_json::json,
+    AccountId,
+};
+use workspaces::{network::Sandbox, result::CallExecutionDetails, Account, Contract, Worker};
+
+pub async fn slash_validator(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    validator_id: &AccountId,
+    amount: u128,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "slash_validator")
+        .args_json(json!({
+            "validator_id": validator_id,
+            "amount": U128::from(amount)
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn slash_delegator(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    delegator_id: &AccountId,
+    validator_id: &AccountId,
+    amount: u128,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "slash_delegator")
+        .args_json(json!({
+            "delegator_id": delegator_id,
+            "validator_id": validator_id,
+            "amount": U128::from(amount)
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
+
+pub async fn conclude_penalty_appeal(
+    worker: &Worker<Sandbox>,
+    signer: &Account,
+    anchor: &Contract,
+    escrow_index: u64,
+    is_upheld: bool,
+) -> anyhow::Result<CallExecutionDetails> {
+    signer
+        .call(worker, anchor.id(), "conclude_penalty_appeal")
+        .args_json(json!({
+            "escrow_index": U64::from(escrow_index),
+            "is_upheld": is_upheld
+        }))?
+        .gas(200_000_000_000_000)
+        .transact()
+        .await
+}
@@ -2,10 +2,19 @@ mod common;
 mod contract_interfaces;
 
 mod test_anchor_actions;
+mod test_appchain_message_fixtures;
 mod test_beefy_light_client;
 mod test_beefy_light_client_2;
+mod test_bridge_to_appchain;
 mod test_equivocation_challenge;
+mod test_era_switching;
+mod test_fast_unbond;
 mod test_migration;
+mod test_penalty_escrow;
+mod test_redelegation;
+mod test_reward_distribution;
+mod test_slashing;
+mod test_token_price_updates;
 mod test_transfer_nft;
 mod test_transfer_oct_to_appchain;
 mod test_wrapped_appchain_token;
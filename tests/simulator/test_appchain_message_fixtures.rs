@@ -0,0 +1,41 @@
+use appchain_anchor::{
+    appchain_messages::MessagePayload,
+    test_utils::{decode_appchain_messages, sample_appchain_messages},
+};
+
+#[test]
+fn test_appchain_message_fixtures() {
+    let appchain_messages = sample_appchain_messages(5);
+    assert_eq!(appchain_messages.next_nonce(), 9);
+    let encoded_messages = appchain_messages.build();
+    assert_eq!(encoded_messages, sample_appchain_messages(5).build());
+    let decoded_messages = decode_appchain_messages(&encoded_messages).unwrap();
+    assert_eq!(decoded_messages.len(), 4);
+    assert_eq!(decoded_messages[0].nonce, 5);
+    assert!(matches!(
+        decoded_messages[0].payload,
+        MessagePayload::PlanNewEra(ref payload) if payload.new_era == 1
+    ));
+    assert!(matches!(
+        decoded_messages[1].payload,
+        MessagePayload::EraPayout(_)
+    ));
+    assert!(decoded_messages[1].validator_set_hash.is_none());
+    assert!(matches!(
+        decoded_messages[2].payload,
+        MessagePayload::Lock(_)
+    ));
+    assert!(matches!(
+        decoded_messages[3].payload,
+        MessagePayload::BurnAsset(_)
+    ));
+    //
+    let encoded_messages = sample_appchain_messages(0)
+        .era_payout_with_validator_set_hash(1, Vec::new(), Vec::new(), [1; 32])
+        .build();
+    let decoded_messages = decode_appchain_messages(&encoded_messages).unwrap();
+    assert_eq!(
+        decoded_messages[4].validator_set_hash,
+        Some(format!("0x{}", "01".repeat(32)))
+    );
+}
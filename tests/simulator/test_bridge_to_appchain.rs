@@ -0,0 +1,128 @@
+use crate::{
+    common::{self, test_env::TestEnv},
+    contract_interfaces::anchor_viewer,
+};
+use appchain_anchor::types::AppchainNotification;
+use near_sdk::serde_json::json;
+
+#[tokio::test]
+async fn test_bridging_wrapped_appchain_token_to_appchain() -> anyhow::Result<()> {
+    let env = TestEnv::new().await?;
+    let receiver_id_in_appchain =
+        "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d".to_string();
+    let amount = common::to_actual_amount(100, 18);
+    let user0_wat_balance =
+        common::get_ft_balance_of(&env.worker, &env.users[0], &env.wrapped_appchain_token).await?;
+    let anchor_wat_balance = common::get_ft_balance_of(
+        &env.worker,
+        env.anchor.as_account(),
+        &env.wrapped_appchain_token,
+    )
+    .await?;
+    let total_supply =
+        common::get_ft_total_supply(&env.worker, &env.wrapped_appchain_token).await?;
+    let issuance =
+        anchor_viewer::get_wrapped_appchain_token_issuance(&env.worker, &env.anchor).await?;
+    let notification_index_range =
+        anchor_viewer::get_index_range_of_appchain_notification_history(&env.worker, &env.anchor)
+            .await?;
+    let start_index = env.next_staking_history_index().await?;
+    //
+    // The deposit with an invalid receiver in appchain is refunded.
+    //
+    common::call_ft_transfer_call(
+        &env.worker,
+        &env.users[0],
+        env.anchor.as_account(),
+        amount,
+        json!({
+            "BridgeToAppchain": {
+                "receiver_id_in_appchain": "invalid_receiver"
+            }
+        })
+        .to_string(),
+        &env.wrapped_appchain_token,
+    )
+    .await?;
+    assert_eq!(
+        common::get_ft_balance_of(&env.worker, &env.users[0], &env.wrapped_appchain_token)
+            .await?
+            .0,
+        user0_wat_balance.0
+    );
+    assert_eq!(
+        anchor_viewer::get_wrapped_appchain_token_issuance(&env.worker, &env.anchor)
+            .await?
+            .burnt_by_bridge_transfers
+            .0,
+        issuance.burnt_by_bridge_transfers.0
+    );
+    //
+    // The deposit is burnt, and a notification is generated for the appchain.
+    //
+    common::call_ft_transfer_call(
+        &env.worker,
+        &env.users[0],
+        env.anchor.as_account(),
+        amount,
+        json!({
+            "BridgeToAppchain": {
+                "receiver_id_in_appchain": receiver_id_in_appchain
+            }
+        })
+        .to_string(),
+        &env.wrapped_appchain_token,
+    )
+    .await?;
+    assert_eq!(
+        common::get_ft_balance_of(&env.worker, &env.users[0], &env.wrapped_appchain_token)
+            .await?
+            .0,
+        user0_wat_balance.0 - amount
+    );
+    assert_eq!(
+        common::get_ft_balance_of(
+            &env.worker,
+            env.anchor.as_account(),
+            &env.wrapped_appchain_token
+        )
+        .await?
+        .0,
+        anchor_wat_balance.0
+    );
+    assert_eq!(
+        common::get_ft_total_supply(&env.worker, &env.wrapped_appchain_token)
+            .await?
+            .0,
+        total_supply.0 - amount
+    );
+    let new_issuance =
+        anchor_viewer::get_wrapped_appchain_token_issuance(&env.worker, &env.anchor).await?;
+    assert_eq!(
+        new_issuance.burnt_by_bridge_transfers.0,
+        issuance.burnt_by_bridge_transfers.0 + amount
+    );
+    assert_eq!(
+        new_issuance.minted_by_bridge_locks.0,
+        issuance.minted_by_bridge_locks.0
+    );
+    let notification_histories = anchor_viewer::get_appchain_notification_histories(
+        &env.worker,
+        &env.anchor,
+        notification_index_range.end_index.0,
+        None,
+    )
+    .await?;
+    assert!(matches!(
+        &notification_histories.last().unwrap().appchain_notification,
+        AppchainNotification::WrappedAppchainTokenBurnt {
+            sender_id_in_near,
+            receiver_id_in_appchain: receiver,
+            amount: burnt_amount,
+        } if sender_id_in_near.as_str() == env.users[0].id().as_str()
+            && receiver.eq(&receiver_id_in_appchain)
+            && burnt_amount.0 == amount
+    ));
+    assert!(env.staking_histories_since(start_index).await?.is_empty());
+    Ok(())
+}
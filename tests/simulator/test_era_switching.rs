@@ -0,0 +1,165 @@
+use crate::{
+    common::test_env::TestEnv,
+    contract_interfaces::{anchor_viewer, permissionless_actions, settings_manager},
+};
+use appchain_anchor::{
+    types::{MultiTxsOperationProcessingResult, ValidatorSetProcessingStatus},
+    PayloadType,
+};
+use near_sdk::json_types::U64;
+
+#[tokio::test]
+async fn test_era_switching() -> anyhow::Result<()> {
+    let mut env = TestEnv::new().await?;
+    env.switch_era(1).await?;
+    let anchor_status = anchor_viewer::get_anchor_status(&env.worker, &env.anchor).await?;
+    assert_eq!(
+        anchor_status
+            .index_range_of_validator_set_history
+            .end_index
+            .0,
+        1
+    );
+    env.switch_era(2).await?;
+    let anchor_status = anchor_viewer::get_anchor_status(&env.worker, &env.anchor).await?;
+    assert_eq!(
+        anchor_status
+            .index_range_of_validator_set_history
+            .end_index
+            .0,
+        2
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resuming_era_switching_across_calls() -> anyhow::Result<()> {
+    let mut env = TestEnv::new().await?;
+    //
+    // With the whole gas cap as the budget of `PlanNewEra`, each call of processing
+    // applies only one stage of switching era.
+    //
+    let result = settings_manager::set_appchain_message_handler_config(
+        &env.worker,
+        &env.root,
+        &env.anchor,
+        PayloadType::PlanNewEra,
+        true,
+        Some(240),
+    )
+    .await?;
+    assert!(result.is_success());
+    let appchain_messages = env.appchain_messages().plan_new_era(1);
+    permissionless_actions::verify_and_stage_appchain_messages(
+        &env.worker,
+        env.relayer(),
+        &env.anchor,
+        appchain_messages.build(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+    )
+    .await?;
+    env.appchain_message_nonce = appchain_messages.next_nonce() - 1;
+    let result =
+        permissionless_actions::process_appchain_messages(&env.worker, env.relayer(), &env.anchor)
+            .await?;
+    assert!(matches!(
+        result,
+        MultiTxsOperationProcessingResult::NeedMoreGas
+    ));
+    let anchor_status = anchor_viewer::get_anchor_status(&env.worker, &env.anchor).await?;
+    assert_eq!(
+        anchor_status
+            .permissionless_actions_status
+            .switching_era_number,
+        Some(U64::from(1))
+    );
+    //
+    // The switching can not be continued while the handler is disabled.
+    //
+    let result = settings_manager::set_appchain_message_handler_config(
+        &env.worker,
+        &env.root,
+        &env.anchor,
+        PayloadType::PlanNewEra,
+        false,
+        Some(240),
+    )
+    .await?;
+    assert!(result.is_success());
+    let result =
+        permissionless_actions::try_complete_switching_era(&env.worker, env.relayer(), &env.anchor)
+            .await?;
+    assert!(matches!(
+        result,
+        MultiTxsOperationProcessingResult::Error(..)
+    ));
+    let result = settings_manager::set_appchain_message_handler_config(
+        &env.worker,
+        &env.root,
+        &env.anchor,
+        PayloadType::PlanNewEra,
+        true,
+        Some(240),
+    )
+    .await?;
+    assert!(result.is_success());
+    //
+    // The switching is resumed from the persisted stage until it is completed.
+    //
+    let mut count_of_calls = 0;
+    loop {
+        count_of_calls += 1;
+        assert!(
+            count_of_calls <= 10,
+            "Too many calls to complete switching era."
+        );
+        match permissionless_actions::try_complete_switching_era(
+            &env.worker,
+            env.relayer(),
+            &env.anchor,
+        )
+        .await?
+        {
+            MultiTxsOperationProcessingResult::Ok => break,
+            MultiTxsOperationProcessingResult::NeedMoreGas => (),
+            MultiTxsOperationProcessingResult::Error(message) => {
+                panic!("Failed to complete switching era: {}", message)
+            }
+        }
+    }
+    let anchor_status = anchor_viewer::get_anchor_status(&env.worker, &env.anchor).await?;
+    assert!(anchor_status
+        .permissionless_actions_status
+        .switching_era_number
+        .is_none());
+    //
+    // The message is then marked as applied by the next call of processing.
+    //
+    let result =
+        permissionless_actions::process_appchain_messages(&env.worker, env.relayer(), &env.anchor)
+            .await?;
+    assert!(matches!(result, MultiTxsOperationProcessingResult::Ok));
+    let anchor_status = anchor_viewer::get_anchor_status(&env.worker, &env.anchor).await?;
+    assert_eq!(
+        anchor_status
+            .index_range_of_validator_set_history
+            .end_index
+            .0,
+        1
+    );
+    assert_eq!(
+        anchor_status
+            .permissionless_actions_status
+            .latest_applied_appchain_message_nonce,
+        env.appchain_message_nonce
+    );
+    let validator_set_info =
+        anchor_viewer::get_validator_set_info_of(&env.worker, &env.anchor, U64::from(1)).await?;
+    assert_eq!(
+        validator_set_info.processing_status,
+        ValidatorSetProcessingStatus::Completed
+    );
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use crate::{
+    common,
+    contract_interfaces::{anchor_viewer, settings_manager, staking_actions},
+};
+use appchain_anchor::types::StakingFact;
+use near_sdk::AccountId;
+
+#[tokio::test]
+async fn test_fast_unbond() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await?;
+    let (
+        root,
+        oct_token,
+        _wrapped_appchain_token,
+        _registry,
+        anchor,
+        _wat_faucet,
+        users,
+        mut appchain_message_nonce,
+    ) = common::test_normal_actions(&worker, false, false, vec!["0x00".to_string()]).await?;
+    let user0_id: AccountId = users[0].id().to_string().parse().unwrap();
+    let decreased_amount = common::to_actual_amount(500, 18);
+    //
+    // The decreased delegation becomes an unbonded stake (locked for the unlock period)
+    // in the next era.
+    //
+    let next_index = anchor_viewer::get_index_range_of_staking_history(&worker, &anchor)
+        .await?
+        .end_index
+        .0
+        + 1;
+    staking_actions::decrease_delegation(&worker, &users[3], &anchor, &user0_id, decreased_amount)
+        .await
+        .expect("Failed in calling 'decrease_delegation'");
+    let staking_history = anchor_viewer::get_staking_history(&worker, &anchor, next_index)
+        .await?
+        .expect("Staking history should exist");
+    assert!(matches!(
+        &staking_history.staking_fact,
+        StakingFact::DelegationDecreased { validator_id, amount, .. }
+            if validator_id.eq(&user0_id) && amount.0 == decreased_amount
+    ));
+    appchain_message_nonce += 1;
+    common::complex_actions::switch_era(
+        &worker,
+        &users[5],
+        &anchor,
+        1,
+        appchain_message_nonce,
+        false,
+    )
+    .await
+    .expect("Failed to switch era");
+    let unbonded_stakes =
+        anchor_viewer::get_unbonded_stakes_of(&worker, &anchor, &users[3]).await?;
+    assert_eq!(unbonded_stakes.len(), 1);
+    assert_eq!(unbonded_stakes[0].amount.0, decreased_amount);
+    //
+    // Fast unbonding is disabled by default, and is limited by the cap of an era.
+    //
+    let user3_oct_balance = common::get_ft_balance_of(&worker, &users[3], &oct_token).await?;
+    let anchor_oct_balance =
+        common::get_ft_balance_of(&worker, anchor.as_account(), &oct_token).await?;
+    let end_index = anchor_viewer::get_index_range_of_staking_history(&worker, &anchor)
+        .await?
+        .end_index
+        .0;
+    staking_actions::fast_withdraw_stake(&worker, &users[3], &anchor)
+        .await
+        .expect_err("Should fail");
+    settings_manager::change_maximum_fast_unbond_amount_per_era(
+        &worker,
+        &root,
+        &anchor,
+        decreased_amount / 2,
+    )
+    .await
+    .expect("Failed in calling 'change_maximum_fast_unbond_amount_per_era'");
+    staking_actions::fast_withdraw_stake(&worker, &users[3], &anchor)
+        .await
+        .expect_err("Should fail");
+    settings_manager::change_maximum_fast_unbond_amount_per_era(
+        &worker,
+        &root,
+        &anchor,
+        decreased_amount,
+    )
+    .await
+    .expect("Failed in calling 'change_maximum_fast_unbond_amount_per_era'");
+    staking_actions::fast_withdraw_stake(&worker, &users[3], &anchor)
+        .await
+        .expect("Failed in calling 'fast_withdraw_stake'");
+    //
+    // 10% (by default) of the locked stake is forfeited, which is kept in the anchor
+    // as the treasury has no destination.
+    //
+    let penalty = decreased_amount * 10 / 100;
+    assert_eq!(
+        common::get_ft_balance_of(&worker, &users[3], &oct_token)
+            .await?
+            .0,
+        user3_oct_balance.0 + decreased_amount - penalty
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&worker, anchor.as_account(), &oct_token)
+            .await?
+            .0,
+        anchor_oct_balance.0 - decreased_amount + penalty
+    );
+    assert!(
+        anchor_viewer::get_unbonded_stakes_of(&worker, &anchor, &users[3])
+            .await?
+            .is_empty()
+    );
+    assert_eq!(
+        anchor_viewer::get_index_range_of_staking_history(&worker, &anchor)
+            .await?
+            .end_index
+            .0,
+        end_index
+    );
+    //
+    // Nothing is left to withdraw.
+    //
+    staking_actions::fast_withdraw_stake(&worker, &users[3], &anchor)
+        .await
+        .expect_err("Should fail");
+    Ok(())
+}
@@ -0,0 +1,210 @@
+use crate::{
+    common::{self, test_env::TestEnv},
+    contract_interfaces::{
+        anchor_viewer, permissionless_actions, settings_manager, staking_actions,
+        staking_admin_actions,
+    },
+};
+use appchain_anchor::types::{PenaltyEscrowStatus, StakingFact};
+use near_sdk::AccountId;
+
+#[tokio::test]
+async fn test_penalty_escrow() -> anyhow::Result<()> {
+    let env = TestEnv::new().await?;
+    //
+    // Shorten the dispute window to one (shortest) day, which is 60 seconds.
+    //
+    settings_manager::set_seconds_of_a_day(&env.worker, &env.root, &env.anchor, 60).await?;
+    settings_manager::change_penalty_dispute_period(&env.worker, &env.root, &env.anchor, 1).await?;
+    let user0_id: AccountId = env.users[0].id().to_string().parse().unwrap();
+    let user2_id: AccountId = env.users[2].id().to_string().parse().unwrap();
+    let validator = env.validator_in_next_era(&env.users[0]).await?;
+    let user0_oct_balance =
+        common::get_ft_balance_of(&env.worker, &env.users[0], &env.oct_token).await?;
+    let user2_oct_balance =
+        common::get_ft_balance_of(&env.worker, &env.users[2], &env.oct_token).await?;
+    let anchor_oct_balance =
+        common::get_ft_balance_of(&env.worker, env.anchor.as_account(), &env.oct_token).await?;
+    let escrow_balance =
+        anchor_viewer::get_penalty_escrow_balance(&env.worker, &env.anchor).await?;
+    let start_index = env.next_staking_history_index().await?;
+    //
+    // Slash the validator and one of its delegators.
+    //
+    let validator_slashed_amount = common::to_actual_amount(100, 18);
+    let delegator_slashed_amount = common::to_actual_amount(50, 18);
+    staking_admin_actions::slash_validator(
+        &env.worker,
+        &env.root,
+        &env.anchor,
+        &user0_id,
+        validator_slashed_amount,
+    )
+    .await?;
+    staking_admin_actions::slash_delegator(
+        &env.worker,
+        &env.root,
+        &env.anchor,
+        &user2_id,
+        &user0_id,
+        delegator_slashed_amount,
+    )
+    .await?;
+    let staking_histories = env.staking_histories_since(start_index).await?;
+    assert_eq!(staking_histories.len(), 2);
+    assert!(matches!(
+        &staking_histories[0].staking_fact,
+        StakingFact::ValidatorSlashed { validator_id, amount }
+            if validator_id.eq(&user0_id) && amount.0 == validator_slashed_amount
+    ));
+    assert!(matches!(
+        &staking_histories[1].staking_fact,
+        StakingFact::DelegatorSlashed { delegator_id, validator_id, amount }
+            if delegator_id.eq(&user2_id)
+                && validator_id.eq(&user0_id)
+                && amount.0 == delegator_slashed_amount
+    ));
+    assert_eq!(
+        env.validator_in_next_era(&env.users[0])
+            .await?
+            .total_stake
+            .0,
+        validator.total_stake.0 - validator_slashed_amount - delegator_slashed_amount
+    );
+    let escrows = anchor_viewer::get_penalty_escrows(&env.worker, &env.anchor, 0).await?;
+    assert_eq!(escrows.len(), 2);
+    let (validator_escrow, delegator_escrow) = (&escrows[0], &escrows[1]);
+    assert!(validator_escrow.account_id.eq(&user0_id));
+    assert_eq!(validator_escrow.amount.0, validator_slashed_amount);
+    assert_eq!(
+        validator_escrow.staking_history_index,
+        staking_histories[0].index
+    );
+    assert!(delegator_escrow.account_id.eq(&user2_id));
+    assert_eq!(delegator_escrow.amount.0, delegator_slashed_amount);
+    assert_eq!(
+        delegator_escrow.staking_history_index,
+        staking_histories[1].index
+    );
+    assert!(escrows
+        .iter()
+        .all(|escrow| escrow.status == PenaltyEscrowStatus::Escrowed));
+    assert_eq!(
+        anchor_viewer::get_penalty_escrow_balance(&env.worker, &env.anchor)
+            .await?
+            .0,
+        escrow_balance.0 + validator_slashed_amount + delegator_slashed_amount
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&env.worker, env.anchor.as_account(), &env.oct_token)
+            .await?
+            .0,
+        anchor_oct_balance.0
+    );
+    //
+    // Only the slashed account can appeal, and an upheld appeal returns the slashed stake.
+    //
+    staking_actions::appeal_penalty(
+        &env.worker,
+        &env.users[2],
+        &env.anchor,
+        validator_escrow.index.0,
+    )
+    .await
+    .expect_err("Should fail");
+    staking_actions::appeal_penalty(
+        &env.worker,
+        &env.users[0],
+        &env.anchor,
+        validator_escrow.index.0,
+    )
+    .await?;
+    staking_admin_actions::conclude_penalty_appeal(
+        &env.worker,
+        &env.root,
+        &env.anchor,
+        validator_escrow.index.0,
+        true,
+    )
+    .await?;
+    assert_eq!(
+        common::get_ft_balance_of(&env.worker, &env.users[0], &env.oct_token)
+            .await?
+            .0,
+        user0_oct_balance.0 + validator_slashed_amount
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&env.worker, env.anchor.as_account(), &env.oct_token)
+            .await?
+            .0,
+        anchor_oct_balance.0 - validator_slashed_amount
+    );
+    //
+    // A rejected appeal puts the penalty back into escrow.
+    //
+    staking_actions::appeal_penalty(
+        &env.worker,
+        &env.users[2],
+        &env.anchor,
+        delegator_escrow.index.0,
+    )
+    .await?;
+    staking_admin_actions::conclude_penalty_appeal(
+        &env.worker,
+        &env.root,
+        &env.anchor,
+        delegator_escrow.index.0,
+        false,
+    )
+    .await?;
+    let escrows = anchor_viewer::get_penalty_escrows(&env.worker, &env.anchor, 0).await?;
+    assert_eq!(escrows[0].status, PenaltyEscrowStatus::Returned);
+    assert_eq!(escrows[1].status, PenaltyEscrowStatus::Escrowed);
+    assert_eq!(
+        anchor_viewer::get_penalty_escrow_balance(&env.worker, &env.anchor)
+            .await?
+            .0,
+        escrow_balance.0 + delegator_slashed_amount
+    );
+    //
+    // The escrow can only be settled after the dispute window. As the treasury has
+    // no destination, the slashed stake is burnt (kept in the anchor).
+    //
+    permissionless_actions::settle_penalty_escrow(
+        &env.worker,
+        env.relayer(),
+        &env.anchor,
+        delegator_escrow.index.0,
+    )
+    .await
+    .expect_err("Should fail");
+    env.worker.fast_forward(200).await?;
+    permissionless_actions::settle_penalty_escrow(
+        &env.worker,
+        env.relayer(),
+        &env.anchor,
+        delegator_escrow.index.0,
+    )
+    .await?;
+    let escrows = anchor_viewer::get_penalty_escrows(&env.worker, &env.anchor, 0).await?;
+    assert_eq!(escrows[1].status, PenaltyEscrowStatus::Burnt);
+    assert_eq!(
+        anchor_viewer::get_penalty_escrow_balance(&env.worker, &env.anchor)
+            .await?
+            .0,
+        escrow_balance.0
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&env.worker, &env.users[2], &env.oct_token)
+            .await?
+            .0,
+        user2_oct_balance.0
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&env.worker, env.anchor.as_account(), &env.oct_token)
+            .await?
+            .0,
+        anchor_oct_balance.0 - validator_slashed_amount
+    );
+    Ok(())
+}
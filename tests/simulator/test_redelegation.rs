@@ -0,0 +1,139 @@
+use crate::{
+    common::{self, test_env::TestEnv},
+    contract_interfaces::{anchor_viewer, staking_actions},
+};
+use appchain_anchor::types::StakingFact;
+use near_sdk::AccountId;
+
+#[tokio::test]
+async fn test_redelegation() -> anyhow::Result<()> {
+    let mut env = TestEnv::new().await?;
+    let user0_id: AccountId = env.users[0].id().to_string().parse().unwrap();
+    let user1_id: AccountId = env.users[1].id().to_string().parse().unwrap();
+    let user3_id: AccountId = env.users[3].id().to_string().parse().unwrap();
+    let user4_id: AccountId = env.users[4].id().to_string().parse().unwrap();
+    let old_validator = env.validator_in_next_era(&env.users[0]).await?;
+    let new_validator = env.validator_in_next_era(&env.users[4]).await?;
+    let delegation_amount = anchor_viewer::get_delegators_of_validator_in_era(
+        &env.worker,
+        &env.anchor,
+        None,
+        &env.users[0],
+    )
+    .await?
+    .iter()
+    .find(|delegator| delegator.delegator_id.eq(&user3_id))
+    .expect("User3 should delegate to user0.")
+    .delegation_amount
+    .0;
+    let user3_oct_balance =
+        common::get_ft_balance_of(&env.worker, &env.users[3], &env.oct_token).await?;
+    let anchor_oct_balance =
+        common::get_ft_balance_of(&env.worker, env.anchor.as_account(), &env.oct_token).await?;
+    let start_index = env.next_staking_history_index().await?;
+    //
+    // The new validator should accept delegations and be different from the old one.
+    //
+    staking_actions::change_delegated_validator(
+        &env.worker,
+        &env.users[3],
+        &env.anchor,
+        &user0_id,
+        &user1_id,
+    )
+    .await
+    .expect_err("Should fail");
+    staking_actions::change_delegated_validator(
+        &env.worker,
+        &env.users[3],
+        &env.anchor,
+        &user0_id,
+        &user0_id,
+    )
+    .await
+    .expect_err("Should fail");
+    staking_actions::change_delegated_validator(
+        &env.worker,
+        &env.users[3],
+        &env.anchor,
+        &user0_id,
+        &user4_id,
+    )
+    .await?;
+    //
+    let staking_histories = env.staking_histories_since(start_index).await?;
+    assert_eq!(staking_histories.len(), 1);
+    assert!(matches!(
+        &staking_histories[0].staking_fact,
+        StakingFact::DelegationRedelegated {
+            delegator_id,
+            from_validator_id,
+            to_validator_id,
+            amount,
+        } if delegator_id.eq(&user3_id)
+            && from_validator_id.eq(&user0_id)
+            && to_validator_id.eq(&user4_id)
+            && amount.0 == delegation_amount
+    ));
+    assert_eq!(
+        env.validator_in_next_era(&env.users[0])
+            .await?
+            .total_stake
+            .0,
+        old_validator.total_stake.0 - delegation_amount
+    );
+    assert_eq!(
+        env.validator_in_next_era(&env.users[4])
+            .await?
+            .total_stake
+            .0,
+        new_validator.total_stake.0 + delegation_amount
+    );
+    //
+    // No token is moved.
+    //
+    assert_eq!(
+        common::get_ft_balance_of(&env.worker, &env.users[3], &env.oct_token)
+            .await?
+            .0,
+        user3_oct_balance.0
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&env.worker, env.anchor.as_account(), &env.oct_token)
+            .await?
+            .0,
+        anchor_oct_balance.0
+    );
+    //
+    // The redelegation is applied to the validator set of the next era.
+    //
+    env.switch_era(1).await?;
+    assert!(anchor_viewer::get_delegators_of_validator_in_era(
+        &env.worker,
+        &env.anchor,
+        Some(1),
+        &env.users[0],
+    )
+    .await?
+    .iter()
+    .all(|delegator| !delegator.delegator_id.eq(&user3_id)));
+    let delegators_of_user4 = anchor_viewer::get_delegators_of_validator_in_era(
+        &env.worker,
+        &env.anchor,
+        Some(1),
+        &env.users[4],
+    )
+    .await?;
+    assert_eq!(delegators_of_user4.len(), 1);
+    assert!(delegators_of_user4[0].delegator_id.eq(&user3_id));
+    assert_eq!(
+        delegators_of_user4[0].delegation_amount.0,
+        delegation_amount
+    );
+    assert!(
+        anchor_viewer::get_unbonded_stakes_of(&env.worker, &env.anchor, &env.users[3])
+            .await?
+            .is_empty()
+    );
+    Ok(())
+}
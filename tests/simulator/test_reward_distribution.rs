@@ -0,0 +1,148 @@
+use crate::{
+    common::{self, test_env::TestEnv},
+    contract_interfaces::{anchor_viewer, settings_manager},
+};
+use appchain_anchor::types::{DelegatorLoyaltyTier, RewardRoundingPolicy};
+use near_sdk::json_types::U64;
+
+#[tokio::test]
+async fn test_reward_rounding_and_commission_rebates() -> anyhow::Result<()> {
+    let mut env = TestEnv::new().await?;
+    settings_manager::change_reward_rounding_policy(
+        &env.worker,
+        &env.root,
+        &env.anchor,
+        RewardRoundingPolicy::LargestRemainder,
+    )
+    .await?;
+    settings_manager::change_loyalty_tiers_of_delegator(
+        &env.worker,
+        &env.root,
+        &env.anchor,
+        vec![DelegatorLoyaltyTier {
+            minimum_bonded_era_count: U64::from(1),
+            commission_rebate_percent: 50,
+        }],
+    )
+    .await?;
+    //
+    // The settings are applied to the eras started after the change.
+    //
+    env.switch_era(1).await?;
+    env.switch_era(2).await?;
+    let validator_ids: Vec<String> =
+        anchor_viewer::get_validator_list_of(&env.worker, &env.anchor, Some(1))
+            .await?
+            .iter()
+            .map(|validator| validator.validator_id.to_string())
+            .collect();
+    let validators: Vec<_> = env
+        .users
+        .iter()
+        .filter(|user| validator_ids.contains(&user.id().to_string()))
+        .collect();
+    assert_eq!(validators.len(), validator_ids.len());
+    let anchor_wat_balance = common::get_ft_balance_of(
+        &env.worker,
+        env.anchor.as_account(),
+        &env.wrapped_appchain_token,
+    )
+    .await?;
+    let start_index = env.next_staking_history_index().await?;
+    //
+    let appchain_messages = env
+        .appchain_messages()
+        .era_payout(1, Vec::new(), Vec::new());
+    env.stage_and_process(appchain_messages).await?;
+    //
+    // The whole era reward is minted to the anchor, and the remainder of the rounding
+    // stays in the anchor as no treasury account is set.
+    //
+    let era_reward = common::to_actual_amount(10, 18);
+    assert_eq!(
+        common::get_ft_balance_of(
+            &env.worker,
+            env.anchor.as_account(),
+            &env.wrapped_appchain_token
+        )
+        .await?
+        .0,
+        anchor_wat_balance.0 + era_reward
+    );
+    assert!(env.staking_histories_since(start_index).await?.is_empty());
+    //
+    // The shares of validators sum up to the era reward exactly.
+    //
+    let previews = anchor_viewer::preview_reward_distribution(&env.worker, &env.anchor, 1).await?;
+    assert_eq!(
+        previews
+            .iter()
+            .map(|preview| preview.total_reward.0)
+            .sum::<u128>(),
+        era_reward
+    );
+    let reward_rounding = anchor_viewer::get_reward_rounding_of_era(&env.worker, &env.anchor, 1)
+        .await?
+        .expect("Reward rounding of era 1 should be recorded.");
+    assert_eq!(
+        reward_rounding.policy,
+        RewardRoundingPolicy::LargestRemainder
+    );
+    let remainder = reward_rounding
+        .remainder
+        .expect("Remainder of era 1 should be settled.");
+    assert_eq!(reward_rounding.accounted_reward.0 + remainder.0, era_reward);
+    //
+    // The loyal delegators get half of the commission for their delegations back.
+    //
+    let preview_of_user0 = previews
+        .iter()
+        .find(|preview| preview.validator_id.as_str() == env.users[0].id().as_str())
+        .unwrap();
+    assert_eq!(preview_of_user0.delegator_rewards.len(), 2);
+    let mut distributed_reward = 0;
+    for delegator in [&env.users[2], &env.users[3]] {
+        let delegator_reward = preview_of_user0
+            .delegator_rewards
+            .iter()
+            .find(|reward| reward.delegator_id.as_str() == delegator.id().as_str())
+            .unwrap();
+        assert!(delegator_reward.commission_rebate.0 > 0);
+        let reward_histories = anchor_viewer::get_delegator_rewards_of(
+            &env.worker,
+            &env.anchor,
+            1,
+            1,
+            delegator,
+            &env.users[0],
+        )
+        .await?;
+        assert_eq!(reward_histories.len(), 1);
+        assert_eq!(
+            reward_histories[0].total_reward.0,
+            delegator_reward.reward.0
+        );
+        distributed_reward += reward_histories[0].total_reward.0;
+    }
+    for validator in validators {
+        let preview = previews
+            .iter()
+            .find(|preview| preview.validator_id.as_str() == validator.id().as_str())
+            .unwrap();
+        let reward_histories =
+            anchor_viewer::get_validator_rewards_of(&env.worker, &env.anchor, 1, 1, validator)
+                .await?;
+        assert_eq!(reward_histories.len(), 1);
+        assert_eq!(
+            reward_histories[0].total_reward.0,
+            preview.validator_reward.0
+        );
+        distributed_reward += reward_histories[0].total_reward.0;
+    }
+    assert_eq!(distributed_reward, reward_rounding.accounted_reward.0);
+    let audit = anchor_viewer::audit_era_distribution(&env.worker, &env.anchor, 1)
+        .await?
+        .expect("Distribution of era 1 should be completed.");
+    assert!(audit.divergences.is_empty());
+    Ok(())
+}
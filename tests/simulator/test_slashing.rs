@@ -0,0 +1,130 @@
+use crate::{
+    common::{self, test_env::TestEnv},
+    contract_interfaces::{anchor_viewer, settings_manager},
+};
+use appchain_anchor::types::{SlashStatus, StakingFact};
+
+#[tokio::test]
+async fn test_slashing_for_reported_offence() -> anyhow::Result<()> {
+    let mut env = TestEnv::new().await?;
+    settings_manager::change_validator_slash_percent(&env.worker, &env.root, &env.anchor, 10)
+        .await?;
+    settings_manager::change_delegator_slash_percent(&env.worker, &env.root, &env.anchor, 5)
+        .await?;
+    //
+    let validator = env.validator_in_next_era(&env.users[0]).await?;
+    let delegators = anchor_viewer::get_delegators_of_validator_in_era(
+        &env.worker,
+        &env.anchor,
+        None,
+        &env.users[0],
+    )
+    .await?;
+    assert_eq!(delegators.len(), 2);
+    let anchor_oct_balance =
+        common::get_ft_balance_of(&env.worker, env.anchor.as_account(), &env.oct_token).await?;
+    let escrow_balance =
+        anchor_viewer::get_penalty_escrow_balance(&env.worker, &env.anchor).await?;
+    let start_index = env.next_staking_history_index().await?;
+    //
+    let appchain_messages = env.appchain_messages().slash(
+        validator.validator_id_in_appchain.clone(),
+        "equivocation".to_string(),
+        0,
+    );
+    env.stage_and_process(appchain_messages).await?;
+    //
+    let validator_slashed_amount = validator.deposit_amount.0 / 10;
+    let delegator_slashed_amount: u128 = delegators
+        .iter()
+        .map(|delegator| delegator.delegation_amount.0 * 5 / 100)
+        .sum();
+    let slashed_validator = env.validator_in_next_era(&env.users[0]).await?;
+    assert_eq!(
+        slashed_validator.deposit_amount.0,
+        validator.deposit_amount.0 - validator_slashed_amount
+    );
+    assert_eq!(
+        slashed_validator.total_stake.0,
+        validator.total_stake.0 - validator_slashed_amount - delegator_slashed_amount
+    );
+    let slashed_delegators = anchor_viewer::get_delegators_of_validator_in_era(
+        &env.worker,
+        &env.anchor,
+        None,
+        &env.users[0],
+    )
+    .await?;
+    for delegator in &delegators {
+        let slashed_delegator = slashed_delegators
+            .iter()
+            .find(|d| d.delegator_id == delegator.delegator_id)
+            .unwrap();
+        assert_eq!(
+            slashed_delegator.delegation_amount.0,
+            delegator.delegation_amount.0 - delegator.delegation_amount.0 * 5 / 100
+        );
+    }
+    //
+    let slash_histories = anchor_viewer::get_slash_histories(&env.worker, &env.anchor, 0).await?;
+    assert_eq!(slash_histories.len(), 1);
+    assert_eq!(slash_histories[0].status, SlashStatus::Completed);
+    let slash_fact = &slash_histories[0].slash_fact;
+    assert_eq!(slash_fact.offence_kind, "equivocation");
+    assert_eq!(
+        slash_fact.validator_slashed_amount.0,
+        validator_slashed_amount
+    );
+    assert_eq!(
+        slash_fact.delegator_slashed_amount.0,
+        delegator_slashed_amount
+    );
+    assert_eq!(slash_fact.slashed_delegator_count.0, 2);
+    let slash_histories_of_validator =
+        anchor_viewer::get_slash_histories_of_validator(&env.worker, &env.anchor, &env.users[0])
+            .await?;
+    assert_eq!(slash_histories_of_validator.len(), 1);
+    assert_eq!(
+        slash_histories_of_validator[0].index,
+        slash_histories[0].index
+    );
+    assert!(anchor_viewer::get_slash_histories_of_validator(
+        &env.worker,
+        &env.anchor,
+        &env.users[1]
+    )
+    .await?
+    .is_empty());
+    //
+    let staking_histories = env.staking_histories_since(start_index).await?;
+    assert_eq!(staking_histories.len(), 3);
+    assert!(matches!(
+        &staking_histories[0].staking_fact,
+        StakingFact::ValidatorSlashed { validator_id, amount }
+            if validator_id.as_str() == env.users[0].id().as_str()
+                && amount.0 == validator_slashed_amount
+    ));
+    assert!(staking_histories[1..]
+        .iter()
+        .all(|staking_history| matches!(
+            &staking_history.staking_fact,
+            StakingFact::DelegatorSlashed { validator_id, .. }
+                if validator_id.as_str() == env.users[0].id().as_str()
+        )));
+    //
+    // The slashed stake is held in escrow, so no token leaves the anchor.
+    //
+    assert_eq!(
+        anchor_viewer::get_penalty_escrow_balance(&env.worker, &env.anchor)
+            .await?
+            .0,
+        escrow_balance.0 + validator_slashed_amount + delegator_slashed_amount
+    );
+    assert_eq!(
+        common::get_ft_balance_of(&env.worker, env.anchor.as_account(), &env.oct_token)
+            .await?
+            .0,
+        anchor_oct_balance.0
+    );
+    Ok(())
+}
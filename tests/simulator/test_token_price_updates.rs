@@ -0,0 +1,95 @@
+use crate::{
+    common,
+    contract_interfaces::{anchor_viewer, settings_manager},
+};
+use appchain_anchor::types::{PriceUpdateViolationReason, PricedToken};
+use near_sdk::{json_types::U128, serde_json::json};
+
+#[tokio::test]
+async fn test_token_price_updates() -> anyhow::Result<()> {
+    let worker = workspaces::sandbox().await?;
+    let (root, _, _, _, anchor, _, users, _) =
+        common::test_normal_actions(&worker, false, false, vec!["0x00".to_string()]).await?;
+    let price_maintainer = &users[4];
+    settings_manager::set_minimum_interval_of_price_updates(&worker, &root, &anchor, 60).await?;
+    settings_manager::set_maximum_price_change_percent_per_update(&worker, &root, &anchor, 10)
+        .await?;
+    settings_manager::turn_on_pause_asset_transfer_on_price_update_violation(
+        &worker, &root, &anchor,
+    )
+    .await?;
+    worker.fast_forward(200).await?;
+    let oct_price = anchor_viewer::get_oct_token(&worker, &anchor)
+        .await?
+        .price_in_usd
+        .0;
+    //
+    // The check of price updates is not a contract method, so an outsider can neither
+    // record a violation (which pauses asset transfer) nor refresh the last update time.
+    //
+    for new_price in [1, oct_price + oct_price / 20] {
+        users[0]
+            .call(&worker, anchor.id(), "check_price_update")
+            .args_json(json!({
+                "token": PricedToken::OctToken,
+                "current_price": U128::from(oct_price),
+                "new_price": U128::from(new_price),
+            }))?
+            .gas(200_000_000_000_000)
+            .transact()
+            .await
+            .expect_err("Should fail");
+    }
+    assert!(
+        !anchor_viewer::get_anchor_status(&worker, &anchor)
+            .await?
+            .asset_transfer_is_paused
+    );
+    assert!(
+        anchor_viewer::get_price_update_violations(&worker, &anchor, 0)
+            .await?
+            .is_empty()
+    );
+    //
+    // The update of the price maintainer is accepted.
+    //
+    let new_price = oct_price + oct_price / 20;
+    settings_manager::set_price_of_oct_token(&worker, price_maintainer, &anchor, new_price).await?;
+    assert_eq!(
+        anchor_viewer::get_oct_token(&worker, &anchor)
+            .await?
+            .price_in_usd
+            .0,
+        new_price
+    );
+    assert!(
+        anchor_viewer::get_price_update_violations(&worker, &anchor, 0)
+            .await?
+            .is_empty()
+    );
+    //
+    // An update within the minimum interval is rejected and pauses asset transfer.
+    //
+    settings_manager::set_price_of_oct_token(&worker, price_maintainer, &anchor, oct_price).await?;
+    assert_eq!(
+        anchor_viewer::get_oct_token(&worker, &anchor)
+            .await?
+            .price_in_usd
+            .0,
+        new_price
+    );
+    let violations = anchor_viewer::get_price_update_violations(&worker, &anchor, 0).await?;
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].token, PricedToken::OctToken);
+    assert_eq!(violations[0].rejected_price.0, oct_price);
+    assert!(matches!(
+        violations[0].reason,
+        PriceUpdateViolationReason::TooFrequent { .. }
+    ));
+    assert!(
+        anchor_viewer::get_anchor_status(&worker, &anchor)
+            .await?
+            .asset_transfer_is_paused
+    );
+    Ok(())
+}